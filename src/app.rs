@@ -198,22 +198,27 @@ pub fn wgpu_options() -> egui_wgpu::WgpuConfiguration {
                 },
                 ..egui_wgpu::WgpuSetupCreateNew::without_display_handle().instance_descriptor
             },
-            device_descriptor: Arc::new(|adapter| {
-                const OPTIONAL_FEATURES: wgpu::Features = wgpu::Features::POLYGON_MODE_LINE
-                    .union(wgpu::Features::POLYGON_MODE_POINT)
-                    .union(wgpu::Features::FLOAT32_FILTERABLE);
-
-                wgpu::DeviceDescriptor {
-                    label: Some("rau device"),
-                    required_features: adapter.features() & OPTIONAL_FEATURES,
-                    experimental_features: wgpu::ExperimentalFeatures::disabled(),
-                    required_limits: adapter.limits(),
-                    memory_hints: Default::default(),
-                    trace: wgpu::Trace::Off,
-                }
-            }),
+            device_descriptor: Arc::new(device_descriptor),
             ..egui_wgpu::WgpuSetupCreateNew::without_display_handle()
         }),
         ..Default::default()
     }
 }
+
+/// The device the app requests from `adapter`, with the optional features it
+/// supports enabled. Shared with headless rendering, so projects behave alike.
+pub fn device_descriptor(adapter: &wgpu::Adapter) -> wgpu::DeviceDescriptor<'static> {
+    const OPTIONAL_FEATURES: wgpu::Features = wgpu::Features::POLYGON_MODE_LINE
+        .union(wgpu::Features::POLYGON_MODE_POINT)
+        .union(wgpu::Features::FLOAT32_FILTERABLE)
+        .union(wgpu::Features::IMMEDIATES);
+
+    wgpu::DeviceDescriptor {
+        label: Some("rau device"),
+        required_features: adapter.features() & OPTIONAL_FEATURES,
+        experimental_features: wgpu::ExperimentalFeatures::disabled(),
+        required_limits: adapter.limits(),
+        memory_hints: Default::default(),
+        trace: wgpu::Trace::Off,
+    }
+}
@@ -1,7 +1,14 @@
 #[cfg(not(target_arch = "wasm32"))]
 use std::path::PathBuf;
 
-use crate::project::{ResourceId, paths::FilePath};
+use crate::project::{
+    ModelId, ResourceId, ResourceKind, migration,
+    paths::FilePath,
+    resource::{
+        model::vertex_buffer::VertexBufferSpec, presentation::PassStep,
+        uniform::UniformFieldDataKind,
+    },
+};
 
 pub type AppResult<T> = std::result::Result<T, AppError>;
 
@@ -24,6 +31,190 @@ pub enum AppError {
         expected_material_index: usize,
         material_index: usize,
     },
+    /// A shader uses a binding that the pipeline's bind groups don't provide.
+    #[error(
+        "Group {group}, binding {binding}: the shader expects {expected}, but no bind group entry is bound there."
+    )]
+    MissingShaderBinding {
+        group: u32,
+        binding: u32,
+        expected: String,
+    },
+    /// A bind group entry doesn't match the binding the shader declares.
+    #[error(
+        "Group {group}, binding {binding}: the shader expects {expected}, but the bind group provides {actual}."
+    )]
+    ShaderBindingMismatch {
+        group: u32,
+        binding: u32,
+        expected: String,
+        actual: String,
+    },
+    /// A bind group entry isn't visible to a shader stage that uses it.
+    #[error(
+        "Group {group}, binding {binding} is used by the {stage} shader, but the bind group entry isn't visible to it."
+    )]
+    ShaderBindingVisibility {
+        group: u32,
+        binding: u32,
+        stage: String,
+    },
+    /// A vertex shader input doesn't match the model's vertex buffer attribute
+    /// at the same location.
+    #[error(
+        "Vertex input @location({location}): the shader expects {expected}, but the model's vertex buffer provides {actual}."
+    )]
+    VertexInputMismatch {
+        location: u32,
+        expected: String,
+        actual: String,
+        model_id: ModelId,
+        /// A reordered vertex buffer spec that matches the shader, if one exists.
+        suggested_spec: Option<VertexBufferSpec>,
+    },
+    /// A pass samples a texture it also writes to.
+    #[error("Samples texture “{texture}” while also writing to it.")]
+    RenderGraphFeedbackLoop { texture: String },
+    /// A pass reads a texture before the pass that writes it this frame.
+    #[error(
+        "Reads texture “{texture}” before “{writer}” writes it, so it sees the previous frame's contents."
+    )]
+    RenderGraphReadBeforeWrite { texture: String, writer: String },
+    /// A pass writes a texture that nothing reads.
+    #[error("Writes texture “{texture}”, but no pass or viewport reads it.")]
+    RenderGraphUnusedOutput { texture: String },
+    /// The schedule doesn't follow its data dependencies.
+    #[error(
+        "The schedule runs passes before the passes they read from. Suggested order: {}.",
+        suggested.join(" → ")
+    )]
+    RenderGraphOrder {
+        steps: Vec<PassStep>,
+        suggested: Vec<String>,
+    },
+    /// A resource refers to one that no longer exists.
+    #[error("Refers to a {kind} that doesn't exist.")]
+    DanglingReference { kind: ResourceKind },
+    /// No other resource refers to a resource.
+    #[error("Nothing uses this {kind}.")]
+    UnusedResource { kind: ResourceKind },
+    /// No resource reads a file in the project folder.
+    #[error("No resource uses “{0}”.")]
+    UnusedFile(FilePath),
+    /// A texture lacks the usage a resource uses it with.
+    #[error("Texture “{texture}” needs the {usage} usage to be {purpose}.")]
+    TextureUsage {
+        texture: String,
+        usage: &'static str,
+        purpose: &'static str,
+    },
+    /// A render pipeline's attachment format differs from its pass's target.
+    #[error(
+        "Pipeline “{pipeline}” renders {attachment} as {expected}, but the pass's {attachment} target is {actual}."
+    )]
+    AttachmentFormatMismatch {
+        pipeline: String,
+        attachment: &'static str,
+        expected: String,
+        actual: String,
+    },
+    /// A shader struct member with no uniform field type, which would leave
+    /// the fields after it misaligned.
+    #[error(
+        "Member “{member}” is a {type_label}, which no uniform field type matches, so the fields can't follow the struct's layout."
+    )]
+    UnmappableStructMember { member: String, type_label: String },
+    /// A shader no longer declares the struct a uniform is made from.
+    #[error("The shader no longer declares a struct named “{0}”.")]
+    StructNotFound(String),
+    /// An animated uniform field mixes keyframes of different types.
+    #[error(
+        "Animation keyframe {index} is a {actual}, but the animation's first keyframe is a {expected}."
+    )]
+    AnimationKeyframeTypeMismatch {
+        index: usize,
+        expected: UniformFieldDataKind,
+        actual: UniformFieldDataKind,
+    },
+    /// A texture a copy pass reads or writes lacks the usage the copy needs.
+    #[error("The {role} texture needs the {usage} usage for this copy.")]
+    CopyTextureUsage {
+        role: &'static str,
+        usage: &'static str,
+    },
+    /// A whole-texture copy between textures of different sizes.
+    #[error("Can't copy a {from} texture into a {to} texture; both must be the same size.")]
+    CopySizeMismatch { from: String, to: String },
+    /// A copy between textures whose formats aren't copy-compatible.
+    #[error("Can't copy {from} texels into a {to} texture; the formats must match, up to sRGB.")]
+    CopyFormatMismatch { from: String, to: String },
+    /// A copy region reaches past the edge of a texture.
+    #[error("The copied region reaches {end} on the {role} texture, which is only {size}.")]
+    CopyRegionOutOfBounds {
+        role: &'static str,
+        end: String,
+        size: String,
+    },
+    /// A buffer upload into a texture format with no fixed texel size.
+    #[error("Can't upload a buffer into a {format} texture.")]
+    CopyBufferFormat { format: String },
+    /// A buffer upload reads past the end of the buffer.
+    #[error("The copy reads {required} bytes from the buffer, but it only holds {actual}.")]
+    CopyBufferTooSmall { required: u64, actual: u64 },
+    /// A resource reads more double-buffered textures than it can prebuild
+    /// variants for.
+    #[error("Depends on {count} double-buffered textures, but at most {max} can be used together.")]
+    SwappedTextureLimitExceeded { count: usize, max: usize },
+    /// A texture view picks the back of a texture that isn't double-buffered.
+    #[error("Texture “{texture}” isn't double-buffered, so it has no back.")]
+    NotDoubleBuffered { texture: String },
+    /// A compute pass reads its iteration count from a field that isn't a number.
+    #[error("The iteration count must come from a UInt32 or Float field, not a {actual}.")]
+    IterationCountType { actual: UniformFieldDataKind },
+    /// A compute pass runs more iterations than a frame allows.
+    #[error("Runs {count} iterations per frame, but at most {max} are allowed.")]
+    IterationLimitExceeded { count: u32, max: u32 },
+    /// An alternate bind group doesn't share the layout of the one it replaces.
+    #[error("Alternate bind group {group} doesn't match the layout of bind group {group}.")]
+    AlternateBindGroupMismatch { group: usize },
+    /// A compute shader's immediate data, which holds the iteration index,
+    /// isn't a `u32`.
+    #[error(
+        "The shader's immediate data holds the iteration index, so it must be a u32, not {actual}."
+    )]
+    IterationIndexType { actual: String },
+    /// A texture format whose texels can't be decoded into a readback table.
+    #[error("Can't read back {format} texels.")]
+    ReadbackFormat { format: String },
+    /// A readback layout uses a type that can't be shown as numbers.
+    #[error("Can't show {ty} values in a readback table.")]
+    ReadbackType { ty: String },
+    /// A readback layout splits each element into too many values.
+    #[error("Each element splits into more than {max} values; pick a smaller type.")]
+    ReadbackColumnLimit { max: usize },
+    /// A texture can only be read back if it can be copied from.
+    #[error("The texture needs the Copy Source usage to be read back.")]
+    ReadbackTextureUsage,
+
+    #[error("The texture needs the Copy Source usage to be captured.")]
+    CaptureTextureUsage,
+
+    #[error("Only 2D textures can be captured.")]
+    CaptureTextureDimension,
+
+    #[error("Can't capture {format} textures; their depth or stencil can't be copied.")]
+    CaptureTextureFormat { format: String },
+
+    #[error("The texture needs the Texture Binding usage to be analyzed.")]
+    AnalysisTextureUsage,
+
+    #[error("Can't analyze {format} texels; only float and depth textures can be.")]
+    AnalysisFormat { format: String },
+
+    // Kept free of counts and locations, which change from one analysis to the
+    // next and would reopen the error panel each time.
+    #[error("Holds NaN texels; its statistics show where.")]
+    TextureNan,
     /// The current renderer does not support a feature required by the resource.
     #[error("The “{0}” feature isn't supported by the current renderer.")]
     UnsupportedRendererFeature(&'static str),
@@ -97,6 +288,8 @@ pub enum AppError {
     InvalidUrlParameters(String),
     #[error("Failed to capture viewport: {0}")]
     CaptureError(String),
+    #[error("The image is {image} but the reference is {reference}.")]
+    CompareSizeMismatch { image: String, reference: String },
     #[cfg(not(target_arch = "wasm32"))]
     #[error("Config directory is unavailable.")]
     ConfigDirectoryUnavailable,
@@ -105,6 +298,23 @@ pub enum AppError {
     InvalidProjectPath(PathBuf),
     #[error("Missing project.json.")]
     MissingProjectJson,
+    /// `project.json` was saved by a build with a newer format.
+    #[error(
+        "This project was created with a newer Rau (format version {version}, this one reads up to {}). Update Rau to open it.",
+        migration::VERSION
+    )]
+    ProjectFromNewerVersion { version: u32 },
+    #[error("Invalid project.json version: {0}")]
+    InvalidProjectVersion(String),
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("The project has no main viewport.")]
+    MissingMainViewport,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("The main viewport's texture view isn't built.")]
+    MainViewportNotReady,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("The project's resources didn't finish building within {seconds} seconds.")]
+    BuildTimeout { seconds: u64 },
     #[error("Failed to serialize/deserialize JSON: {0}")]
     SerializationError(#[from] serde_json::Error),
     #[cfg(not(target_arch = "wasm32"))]
@@ -0,0 +1,210 @@
+//! Renders a project without a window: builds its resources, then runs the
+//! schedule on a fixed clock and saves or records the main viewport after
+//! every frame.
+
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    app,
+    error::{AppError, AppResult},
+    file::{
+        file_system::AppFileSystem,
+        identifier::{ProjectIdentifier, ProjectSource},
+    },
+    ui::size::Size2d,
+    utils::{
+        recording::{Recorder, RecordingFormat},
+        texture_capture::{ImageFileFormat, TextureReadback, texture_image},
+        wgpu_error_scope::WgpuErrorScope,
+    },
+    workspace::{AppContext, Workspace},
+};
+
+/// How long the project's resources may take to build before giving up.
+const BUILD_TIMEOUT: Duration = Duration::from_secs(60);
+/// Texture views register with egui's renderer for their previews, but
+/// nothing is ever drawn with it, so its target format doesn't matter.
+const EGUI_TARGET_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+pub struct HeadlessOptions {
+    pub project_folder: PathBuf,
+    pub frames: u32,
+    pub fps: f32,
+    /// Render with the software adapter, on machines without a GPU.
+    pub software: bool,
+    /// Size the main viewport is resized to.
+    pub size: Size2d,
+}
+
+/// Saves the main viewport after every frame into `output`, as
+/// `frame_00000.png` and so on. `format` defaults to the one that suits the
+/// main viewport's texture.
+pub fn render(
+    options: HeadlessOptions,
+    output: PathBuf,
+    format: Option<ImageFileFormat>,
+) -> AppResult<()> {
+    pollster::block_on(async {
+        let mut headless = Headless::open(&options).await?;
+
+        let texture_format = headless.workspace.main_viewport_texture()?.format();
+        let format = format.unwrap_or_else(|| ImageFileFormat::for_texture(texture_format));
+        format.check(texture_format)?;
+
+        std::fs::create_dir_all(&output)?;
+        for frame in 0..options.frames {
+            headless.render_frame(frame).await?;
+
+            let texture = headless.workspace.main_viewport_texture()?;
+            let readback = TextureReadback::copy(&headless.device, &headless.queue, texture)?;
+            let [width, height] = readback.size();
+            let texture_format = readback.format();
+            let pixels = readback.read(&headless.device).await?;
+            let image = format.encode(pixels, width, height, texture_format)?;
+
+            let path = output.join(format.frame_file_name(frame));
+            std::fs::write(&path, image)?;
+            log::info!("Wrote {}", path.display());
+        }
+
+        Ok(())
+    })
+}
+
+/// Records the main viewport of every frame into `output`.
+pub fn record(options: HeadlessOptions, output: PathBuf, format: RecordingFormat) -> AppResult<()> {
+    pollster::block_on(async {
+        let mut headless = Headless::open(&options).await?;
+        let mut recorder = Recorder::create(format, options.fps, output)?;
+
+        for frame in 0..options.frames {
+            headless.render_frame(frame).await?;
+            let texture = headless.workspace.main_viewport_texture()?;
+            recorder.capture(&headless.device, &headless.queue, texture)?;
+        }
+
+        let output = recorder.output().display().to_string();
+        let frames = recorder.finish()?;
+        log::info!("Recorded {frames} frames to {output}");
+        Ok(())
+    })
+}
+
+/// Renders every frame and returns the main viewport of the last one.
+pub fn render_image(options: HeadlessOptions) -> AppResult<image::DynamicImage> {
+    pollster::block_on(async {
+        let mut headless = Headless::open(&options).await?;
+        for frame in 0..options.frames.max(1) {
+            headless.render_frame(frame).await?;
+        }
+
+        let texture = headless.workspace.main_viewport_texture()?;
+        let readback = TextureReadback::copy(&headless.device, &headless.queue, texture)?;
+        let [width, height] = readback.size();
+        let texture_format = readback.format();
+        let pixels = readback.read(&headless.device).await?;
+        texture_image(pixels, width, height, texture_format)
+    })
+}
+
+struct Headless {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    egui_renderer: egui::mutex::RwLock<egui_wgpu::Renderer>,
+    downlevel_flags: wgpu::DownlevelFlags,
+    workspace: Workspace,
+    frame_time: Duration,
+}
+
+impl Headless {
+    /// Opens the project on a new device and builds its resources.
+    async fn open(options: &HeadlessOptions) -> AppResult<Self> {
+        let instance = wgpu::Instance::default();
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                force_fallback_adapter: options.software,
+                ..Default::default()
+            })
+            .await?;
+        log::info!(
+            "Selected renderer backend: {:?}",
+            adapter.get_info().backend
+        );
+        let (device, queue) = adapter
+            .request_device(&app::device_descriptor(&adapter))
+            .await?;
+        let egui_renderer = egui::mutex::RwLock::new(egui_wgpu::Renderer::new(
+            &device,
+            EGUI_TARGET_FORMAT,
+            egui_wgpu::RendererOptions::default(),
+        ));
+
+        let app_file_system = AppFileSystem::open().await?;
+        let project_id = ProjectIdentifier::extract_identifier(options.project_folder.clone())?;
+        let source = ProjectSource::Persistent(project_id);
+        let mut workspace = Workspace::open_headless(&app_file_system, source).await?;
+        workspace.resize_main_viewport(options.size)?;
+
+        let mut headless = Self {
+            device,
+            queue,
+            egui_renderer,
+            downlevel_flags: adapter.get_downlevel_capabilities().flags,
+            workspace,
+            frame_time: Duration::from_secs_f64(1.0 / options.fps as f64),
+        };
+        headless.build_resources()?;
+        for (label, error) in headless.workspace.errors() {
+            log::warn!("{label}: {error}");
+        }
+        Ok(headless)
+    }
+
+    /// Builds the project's resources until none is pending, without running
+    /// the schedule, so the first frame renders like any other.
+    fn build_resources(&mut self) -> AppResult<()> {
+        let deadline = Instant::now() + BUILD_TIMEOUT;
+        loop {
+            let mut ctx = AppContext {
+                device: &self.device,
+                queue: &self.queue,
+                egui_renderer: &self.egui_renderer,
+                downlevel_flags: self.downlevel_flags,
+                dt: Duration::ZERO,
+            };
+            self.workspace.build_resources(&mut ctx);
+            if !self.workspace.is_rebuilding() {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(AppError::BuildTimeout {
+                    seconds: BUILD_TIMEOUT.as_secs(),
+                });
+            }
+            // Resources load and compile on background threads.
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Renders frame number `frame`. The first frame is at time zero, and
+    /// every later one a frame time after the previous.
+    async fn render_frame(&mut self, frame: u32) -> AppResult<()> {
+        let dt = match frame {
+            0 => Duration::ZERO,
+            _ => self.frame_time,
+        };
+        let scope = WgpuErrorScope::push(&self.device);
+        let mut ctx = AppContext {
+            device: &self.device,
+            queue: &self.queue,
+            egui_renderer: &self.egui_renderer,
+            downlevel_flags: self.downlevel_flags,
+            dt,
+        };
+        self.workspace.render(&mut ctx);
+        scope.pop().await
+    }
+}
@@ -23,6 +23,8 @@ pub mod built_info {
 mod app;
 mod error;
 mod file;
+#[cfg(not(target_arch = "wasm32"))]
+mod headless;
 mod main_menu;
 mod project;
 #[cfg(not(target_arch = "wasm32"))]
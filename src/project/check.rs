@@ -0,0 +1,712 @@
+//! Static validation of a project, without a GPU.
+//!
+//! Catches what would otherwise only show up once the project is opened:
+//! references to deleted resources, missing files, shaders that don't parse
+//! or validate, bind groups that don't match their shaders, and textures
+//! used in ways their format or usage doesn't allow. The schedule's
+//! [`RenderGraph`] hazards and the unused resources and files [`lint`] finds
+//! are reported as warnings.
+
+use serde::Serialize;
+use slotmap::SecondaryMap;
+
+use crate::{
+    error::{AppError, AppResult},
+    file::file_system::{ProjectFileSystem, ProjectFileSystemTrait},
+    project::{
+        BindGroupId, Project, ProjectResource, ResourceId, ShaderId, TextureId, TextureViewId,
+        lint::{self, Lint},
+        paths::FilePath,
+        render_graph::RenderGraph,
+        resource::{
+            bindgroup::BindGroupResource,
+            copy_pass::CopyMode,
+            model::Material,
+            texture::{Texture, TextureSource},
+            texture_view::TextureViewFormat,
+        },
+    },
+    utils::{
+        obj, shader_reflection,
+        wgpu_utils::{self, ShaderSourceKind},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, strum::Display)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Severity {
+    /// The project won't run as it is.
+    Error,
+    /// The project runs, but likely not as intended.
+    Warning,
+}
+
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// The resource it's about, or `None` when it's about the whole project.
+    pub resource: Option<ResourceId>,
+    pub error: AppError,
+}
+
+/// Reads and parses `project.json`.
+pub async fn load_project(file_system: &ProjectFileSystem) -> AppResult<Project> {
+    let path = FilePath::project_json();
+    if !file_system.exists(&path).await? {
+        return Err(AppError::MissingProjectJson);
+    }
+    Project::deserialize(&file_system.read(&path).await?)
+}
+
+/// Checks everything about `project` that doesn't need a GPU, reading the
+/// files it refers to from `file_system`. Errors come before warnings.
+pub async fn check(project: &Project, file_system: &ProjectFileSystem) -> Vec<Diagnostic> {
+    let mut checker = Checker {
+        project,
+        shaders: SecondaryMap::new(),
+        diagnostics: vec![],
+    };
+
+    checker.check_lints(file_system).await;
+    checker.check_files(file_system).await;
+    checker.check_shaders(file_system).await;
+    checker.check_bind_groups();
+    checker.check_pipelines();
+    checker.check_compute_passes();
+    checker.check_render_passes();
+    checker.check_copy_passes();
+
+    for (resource, error) in RenderGraph::build(project).diagnostics(project) {
+        checker.warn(resource, error);
+    }
+
+    let mut diagnostics = checker.diagnostics;
+    diagnostics.sort_by_key(|diagnostic| diagnostic.severity == Severity::Warning);
+    diagnostics
+}
+
+struct Checker<'a> {
+    project: &'a Project,
+    /// The shaders that parsed and validated.
+    shaders: SecondaryMap<ShaderId, (naga::Module, naga::valid::ModuleInfo)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker<'_> {
+    fn error(&mut self, resource: impl Into<ResourceId>, error: AppError) {
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            resource: Some(resource.into()),
+            error,
+        });
+    }
+
+    fn warn(&mut self, resource: impl Into<ResourceId>, error: AppError) {
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            resource: Some(resource.into()),
+            error,
+        });
+    }
+
+    fn report(&mut self, resource: impl Into<ResourceId>, result: AppResult<()>) {
+        if let Err(error) = result {
+            self.error(resource, error);
+        }
+    }
+
+    fn exists(&self, id: ResourceId) -> bool {
+        self.project.label(id).is_some()
+    }
+
+    /// Dangling references are errors, unused resources and files warnings.
+    async fn check_lints(&mut self, file_system: &ProjectFileSystem) {
+        let files = match file_system.list_entries().await {
+            Ok(entries) => entries.files,
+            Err(error) => {
+                self.diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    resource: None,
+                    error,
+                });
+                vec![]
+            }
+        };
+        let indirect_files = self.model_files(file_system).await;
+        for Lint {
+            resource, error, ..
+        } in lint::lint(self.project, &files, &indirect_files)
+        {
+            let severity = match error {
+                AppError::DanglingReference { .. } => Severity::Error,
+                _ => Severity::Warning,
+            };
+            self.diagnostics.push(Diagnostic {
+                severity,
+                resource,
+                error,
+            });
+        }
+    }
+
+    /// The files models read through their OBJ files: material libraries and
+    /// the textures they name.
+    async fn model_files(&mut self, file_system: &ProjectFileSystem) -> Vec<FilePath> {
+        let mut files = vec![];
+        for (id, model) in self.project.models.list() {
+            let Some(source) = model.source() else {
+                continue;
+            };
+            // Missing files are reported by `check_files`.
+            if !file_system.exists(source).await.unwrap_or(false) {
+                continue;
+            }
+            match obj::load_obj(source.clone(), file_system.clone()).await {
+                Ok(loaded) => {
+                    files.extend(loaded.mtl_dependencies);
+                    for material in loaded.materials {
+                        match Material::from_obj_material(material, source.parent().as_ref()) {
+                            Ok(material) => files.extend(
+                                material
+                                    .texture_paths()
+                                    .iter()
+                                    .map(|(_, path)| path.clone()),
+                            ),
+                            Err(error) => self.error(id, error),
+                        }
+                    }
+                }
+                Err(error) => self.error(id, error),
+            }
+        }
+        files
+    }
+
+    async fn check_files(&mut self, file_system: &ProjectFileSystem) {
+        for (id, path) in lint::referenced_files(self.project) {
+            match file_system.exists(path).await {
+                Ok(true) => {}
+                Ok(false) => self.error(id, AppError::FileNotFound(path.clone())),
+                Err(error) => self.error(id, error),
+            }
+        }
+    }
+
+    async fn check_shaders(&mut self, file_system: &ProjectFileSystem) {
+        for (id, shader) in self.project.shaders.list() {
+            let Some(path) = shader.source() else {
+                continue;
+            };
+            // Missing files were reported already.
+            if !file_system.exists(path).await.unwrap_or(false) {
+                continue;
+            }
+
+            let extension = path.extension().unwrap_or_default();
+            let parsed = match ShaderSourceKind::from_extension(extension) {
+                Some(kind) => match file_system.read_to_string(path).await {
+                    Ok(source) => wgpu_utils::parse_shader(&source, kind),
+                    Err(error) => Err(error),
+                },
+                None => Err(AppError::UnsupportedShaderExtension(extension.to_string())),
+            };
+            match parsed {
+                Ok(module) => {
+                    self.shaders.insert(id, module);
+                }
+                Err(error) => self.error(id, error),
+            }
+        }
+    }
+
+    /// The texture a texture view shows, if both exist.
+    fn view_texture(&self, id: TextureViewId) -> Option<(TextureId, &'a Texture)> {
+        let texture_id = self.project.texture_views.get(id).ok()?.texture_id()?;
+        Some((texture_id, self.project.textures.get(texture_id).ok()?))
+    }
+
+    /// The format a texture view is created with.
+    fn view_format(&self, id: TextureViewId) -> Option<wgpu::TextureFormat> {
+        let view = self.project.texture_views.get(id).ok()?;
+        let (_, texture) = self.view_texture(id)?;
+        let format = texture.format().to_wgpu();
+        Some(match view.format() {
+            Some(TextureViewFormat::Srgb) => format.add_srgb_suffix(),
+            Some(TextureViewFormat::Linear) => format.remove_srgb_suffix(),
+            None => format,
+        })
+    }
+
+    fn check_texture_usage(
+        &mut self,
+        resource: impl Into<ResourceId>,
+        texture_id: TextureId,
+        usage: (wgpu::TextureUsages, &'static str),
+        purpose: &'static str,
+    ) {
+        let Ok(texture) = self.project.textures.get(texture_id) else {
+            return;
+        };
+        if !texture.usage().contains(usage.0) {
+            let error = AppError::TextureUsage {
+                texture: texture.label().to_string(),
+                usage: usage.1,
+                purpose,
+            };
+            self.error(resource, error);
+        }
+    }
+
+    fn check_bind_groups(&mut self) {
+        for (id, texture) in self.project.textures.list() {
+            if let TextureSource::Image(_) = texture.source() {
+                self.check_texture_usage(id, id, COPY_DST, "loaded from an image");
+            }
+        }
+
+        for (id, bind_group) in self.project.bind_groups.list() {
+            for entry in bind_group.entries() {
+                let (texture_view_id, usage, purpose) = match entry.resource {
+                    BindGroupResource::Texture {
+                        texture_view_id: Some(texture_view_id),
+                        ..
+                    } => (texture_view_id, TEXTURE_BINDING, "sampled"),
+                    BindGroupResource::StorageTexture {
+                        texture_view_id: Some(texture_view_id),
+                        ..
+                    } => (
+                        texture_view_id,
+                        STORAGE_BINDING,
+                        "bound as a storage texture",
+                    ),
+                    _ => continue,
+                };
+                if let Some((texture_id, _)) = self.view_texture(texture_view_id) {
+                    self.check_texture_usage(id, texture_id, usage, purpose);
+                }
+            }
+        }
+    }
+
+    /// The layout entries of a bind group, as its runtime would create them.
+    /// `None` when one of its storage textures can't be resolved.
+    fn layout_entries(&self, id: BindGroupId) -> Option<Vec<wgpu::BindGroupLayoutEntry>> {
+        let bind_group = self.project.bind_groups.get(id).ok()?;
+        bind_group
+            .entries()
+            .iter()
+            .enumerate()
+            .map(|(binding, entry)| {
+                let storage_format = match entry.resource {
+                    BindGroupResource::StorageTexture {
+                        texture_view_id: Some(texture_view_id),
+                        ..
+                    } => Some(self.view_texture(texture_view_id)?.1.format().to_wgpu()),
+                    _ => None,
+                };
+                entry.layout_entry(binding as u32, storage_format)
+            })
+            .collect()
+    }
+
+    /// Checks a shader's bindings against the bind groups in each slot, when
+    /// the shader parsed and every bind group resolves.
+    fn check_bindings(
+        &mut self,
+        resource: ResourceId,
+        shader: Option<ShaderId>,
+        stage: naga::ShaderStage,
+        bind_groups: &[Option<BindGroupId>],
+    ) {
+        let Some((module, info)) = shader.and_then(|id| self.shaders.get(id)) else {
+            return;
+        };
+        let layouts: Option<Vec<_>> = bind_groups
+            .iter()
+            .map(|id| match id {
+                Some(id) => self.layout_entries(*id).map(Some),
+                None => Some(None),
+            })
+            .collect();
+        let Some(layouts) = layouts else {
+            return;
+        };
+        let layouts: Vec<_> = layouts.iter().map(|entries| entries.as_deref()).collect();
+        let result = shader_reflection::validate_bindings(module, info, stage, &layouts);
+        self.report(resource, result);
+    }
+
+    fn check_pipelines(&mut self) {
+        for (id, pipeline) in self.project.render_pipelines.list() {
+            let Ok(bind_groups) = pipeline.collect_bind_group_ids(&self.project.models) else {
+                continue;
+            };
+            let bind_groups: Vec<_> = bind_groups
+                .into_iter()
+                .map(|id| id.filter(|id| self.exists((*id).into())))
+                .collect();
+            let stages = [
+                (pipeline.vertex_shader(), naga::ShaderStage::Vertex),
+                (pipeline.fragment_shader(), naga::ShaderStage::Fragment),
+            ];
+            for (shader, stage) in stages {
+                self.check_bindings(id.into(), shader, stage, &bind_groups);
+            }
+        }
+    }
+
+    fn check_compute_passes(&mut self) {
+        for (id, compute_pass) in self.project.compute_passes.list() {
+            let bind_groups: Vec<_> = compute_pass
+                .bind_groups()
+                .iter()
+                .map(|id| Some(*id).filter(|id| self.exists((*id).into())))
+                .collect();
+            self.check_bindings(
+                id.into(),
+                compute_pass.shader(),
+                naga::ShaderStage::Compute,
+                &bind_groups,
+            );
+        }
+    }
+
+    fn check_render_passes(&mut self) {
+        for (id, render_pass) in self.project.render_passes.list() {
+            let color_view = render_pass.target().texture_view_id();
+            let depth_view = render_pass
+                .depth_target()
+                .and_then(|target| target.texture_view_id());
+            for view in [color_view, depth_view].into_iter().flatten() {
+                if let Some((texture_id, _)) = self.view_texture(view) {
+                    self.check_texture_usage(id, texture_id, RENDER_ATTACHMENT, "rendered to");
+                }
+            }
+
+            let color_format = color_view.and_then(|view| self.view_format(view));
+            let depth_format = depth_view.and_then(|view| self.view_format(view));
+            for pipeline_id in render_pass.pipelines() {
+                let Ok(pipeline) = self.project.render_pipelines.get(*pipeline_id) else {
+                    continue;
+                };
+                let expected = pipeline.color_format().to_wgpu();
+                if let Some(actual) = color_format
+                    && actual != expected
+                {
+                    let error = AppError::AttachmentFormatMismatch {
+                        pipeline: pipeline.label().to_string(),
+                        attachment: "color",
+                        expected: format!("{expected:?}"),
+                        actual: format!("{actual:?}"),
+                    };
+                    self.error(id, error);
+                }
+
+                let expected = pipeline.depth_format().map(|format| format.to_wgpu());
+                let actual = match depth_view {
+                    // Leave an unresolved depth view to the other checks.
+                    Some(_) if depth_format.is_none() => continue,
+                    Some(_) => depth_format,
+                    None => None,
+                };
+                if actual != expected {
+                    let name = |format: Option<wgpu::TextureFormat>| match format {
+                        Some(format) => format!("{format:?}"),
+                        None => "none".to_string(),
+                    };
+                    let error = AppError::AttachmentFormatMismatch {
+                        pipeline: pipeline.label().to_string(),
+                        attachment: "depth",
+                        expected: name(expected),
+                        actual: name(actual),
+                    };
+                    self.error(id, error);
+                }
+            }
+        }
+    }
+
+    fn check_copy_passes(&mut self) {
+        for (id, copy_pass) in self.project.copy_passes.list() {
+            let (source, destination) = match copy_pass.mode() {
+                CopyMode::Texture {
+                    source,
+                    destination,
+                }
+                | CopyMode::Region {
+                    source,
+                    destination,
+                    ..
+                } => (source, destination),
+                CopyMode::Buffer { destination, .. } => (None, destination),
+                CopyMode::Blit {
+                    source,
+                    destination,
+                    ..
+                } => {
+                    let source = source.and_then(|view| self.view_texture(view));
+                    let destination = destination.and_then(|view| self.view_texture(view));
+                    if let Some((texture_id, _)) = source {
+                        self.check_texture_usage(id, texture_id, TEXTURE_BINDING, "blitted from");
+                    }
+                    if let Some((texture_id, _)) = destination {
+                        self.check_texture_usage(id, texture_id, RENDER_ATTACHMENT, "blitted to");
+                    }
+                    continue;
+                }
+            };
+
+            if let Some(source) = source {
+                self.check_texture_usage(id, source, COPY_SRC, "copied from");
+            }
+            if let Some(destination) = destination {
+                self.check_texture_usage(id, destination, COPY_DST, "copied to");
+            }
+
+            let source = source.and_then(|id| self.project.textures.get(id).ok());
+            let destination = destination.and_then(|id| self.project.textures.get(id).ok());
+            if let CopyMode::Texture { .. } = copy_pass.mode()
+                && let (Some(source), Some(destination)) = (source, destination)
+            {
+                let (from, to) = (source.format().to_wgpu(), destination.format().to_wgpu());
+                if from.remove_srgb_suffix() != to.remove_srgb_suffix() {
+                    let error = AppError::CopyFormatMismatch {
+                        from: format!("{from:?}"),
+                        to: format!("{to:?}"),
+                    };
+                    self.error(id, error);
+                }
+            }
+        }
+    }
+}
+
+const COPY_SRC: (wgpu::TextureUsages, &str) = (wgpu::TextureUsages::COPY_SRC, "Copy Source");
+const COPY_DST: (wgpu::TextureUsages, &str) = (wgpu::TextureUsages::COPY_DST, "Copy Destination");
+const TEXTURE_BINDING: (wgpu::TextureUsages, &str) =
+    (wgpu::TextureUsages::TEXTURE_BINDING, "Texture Binding");
+const STORAGE_BINDING: (wgpu::TextureUsages, &str) =
+    (wgpu::TextureUsages::STORAGE_BINDING, "Storage Binding");
+const RENDER_ATTACHMENT: (wgpu::TextureUsages, &str) =
+    (wgpu::TextureUsages::RENDER_ATTACHMENT, "Render Attachment");
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{
+        file::{file_system::AppFileSystem, identifier::ProjectSource},
+        project::{
+            ResourceKind,
+            resource::{
+                bindgroup::{BindGroup, BindGroupEntry},
+                compute_pass::{ComputePass, DispatchSize, DispatchUnit},
+                dispatch_policy::DispatchPolicy,
+                shader::Shader,
+                texture_view::TextureView,
+            },
+        },
+        utils::wgpu_utils::TextureFormat,
+    };
+
+    fn path(path: &str) -> FilePath {
+        FilePath::from_str(path).unwrap()
+    }
+
+    /// Checks `project` against an in-memory folder holding `files`.
+    fn run_check(project: &Project, files: Vec<(FilePath, Vec<u8>)>) -> Vec<Diagnostic> {
+        futures_lite::future::block_on(async {
+            let source = ProjectSource::Ephemeral {
+                project_name: "Check".to_string(),
+            };
+            let app_file_system = AppFileSystem::open().await.unwrap();
+            let (file_system, _) = app_file_system.mount_project(source).await.unwrap();
+            for (path, bytes) in files {
+                file_system.write(&path, bytes).await.unwrap();
+            }
+            check(project, &file_system).await
+        })
+    }
+
+    fn errors(diagnostics: &[Diagnostic]) -> Vec<&Diagnostic> {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .collect()
+    }
+
+    fn read_folder(root: &Path, folder: &Path, files: &mut Vec<(FilePath, Vec<u8>)>) {
+        for entry in std::fs::read_dir(folder).unwrap() {
+            let entry = entry.unwrap().path();
+            if entry.is_dir() {
+                read_folder(root, &entry, files);
+            } else {
+                let path = FilePath::from_relative_path(entry.strip_prefix(root).unwrap()).unwrap();
+                files.push((path, std::fs::read(&entry).unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn bundled_projects_have_no_errors() {
+        let projects = Path::new(env!("CARGO_MANIFEST_DIR")).join("projects");
+        let mut checked = 0;
+        for entry in std::fs::read_dir(projects).unwrap() {
+            let folder = entry.unwrap().path();
+            let Ok(bytes) = std::fs::read(folder.join("project.json")) else {
+                continue;
+            };
+            let project = Project::deserialize(&bytes).unwrap();
+            let mut files = vec![];
+            read_folder(&folder, &folder, &mut files);
+
+            let diagnostics = run_check(&project, files);
+            let errors: Vec<String> = errors(&diagnostics)
+                .into_iter()
+                // Some models aren't bundled and are dropped in by hand, like
+                // the backpack of `ssao`.
+                .filter(|diagnostic| match &diagnostic.error {
+                    AppError::FileNotFound(path) => path.extension() != Some("obj"),
+                    _ => true,
+                })
+                .map(|diagnostic| diagnostic.error.to_string())
+                .collect();
+            assert!(errors.is_empty(), "{}: {errors:#?}", folder.display());
+            checked += 1;
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn reports_dangling_ids_missing_files_and_invalid_shaders() {
+        let mut project = Project::default();
+        let camera = project.cameras.create("Camera".to_string());
+        let dimension = project.dimensions.create("Old".to_string());
+        project
+            .cameras
+            .get_mut(camera)
+            .unwrap()
+            .set_dimension_id(Some(dimension));
+        project.unregister(dimension.into());
+        let missing = project
+            .shaders
+            .register(Shader::new("Missing", path("missing.wgsl")));
+        let broken = project
+            .shaders
+            .register(Shader::new("Broken", path("broken.wgsl")));
+
+        let files = vec![(path("broken.wgsl"), b"fn main( {".to_vec())];
+        let diagnostics = run_check(&project, files);
+        let errors = errors(&diagnostics);
+        assert_eq!(errors.len(), 3);
+
+        let error = |id: ResourceId| {
+            errors
+                .iter()
+                .find(|diagnostic| diagnostic.resource == Some(id))
+                .map(|diagnostic| &diagnostic.error)
+        };
+        assert!(matches!(
+            error(camera.into()),
+            Some(AppError::DanglingReference {
+                kind: ResourceKind::Dimension
+            })
+        ));
+        assert!(matches!(
+            error(missing.into()),
+            Some(AppError::FileNotFound(file)) if *file == path("missing.wgsl")
+        ));
+        assert!(matches!(
+            error(broken.into()),
+            Some(AppError::ShaderParseError(_))
+        ));
+    }
+
+    const STORAGE_SHADER: &str = "
+        @group(0) @binding(0) var output: texture_storage_2d<rgba8unorm, write>;
+
+        @compute @workgroup_size(8, 8)
+        fn main(@builtin(global_invocation_id) id: vec3u) {
+            textureStore(output, id.xy, vec4f(1.0));
+        }
+    ";
+
+    /// Checks a compute pass writing a texture of `format` and `usage`
+    /// through a shader that declares an `rgba8unorm` storage texture.
+    /// Returns its errors, with the ids of the bind group and the pass.
+    fn check_storage_write(
+        format: TextureFormat,
+        usage: wgpu::TextureUsages,
+    ) -> (Vec<Diagnostic>, BindGroupId, ResourceId) {
+        let mut project = Project::default();
+        let shader = project
+            .shaders
+            .register(Shader::new("Write", path("write.wgsl")));
+        let dimension = project.dimensions.create("Size".to_string());
+        let texture = project.textures.create("Output".to_string());
+        let output = project.textures.get_mut(texture).unwrap();
+        output.set_format(format);
+        output.set_usage(usage);
+        output.set_source(TextureSource::dimension(dimension));
+        let view =
+            project
+                .texture_views
+                .register(TextureView::new("Output", Some(texture), None, None));
+        let bind_group = project.bind_groups.register(BindGroup::new(
+            "Output",
+            vec![BindGroupEntry::new_compute(
+                BindGroupResource::StorageTexture {
+                    texture_view_id: Some(view),
+                    access: wgpu::StorageTextureAccess::WriteOnly,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                },
+            )],
+        ));
+        let compute_pass = project.compute_passes.register(ComputePass::new(
+            "Write",
+            vec![bind_group],
+            Some(shader),
+            DispatchSize::new_fixed(1, 1, 1, DispatchUnit::WorkGroups),
+            DispatchPolicy::default(),
+        ));
+
+        let files = vec![(path("write.wgsl"), STORAGE_SHADER.as_bytes().to_vec())];
+        let errors = run_check(&project, files)
+            .into_iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .collect();
+        (errors, bind_group, compute_pass.into())
+    }
+
+    #[test]
+    fn reports_storage_format_mismatches_and_missing_usages() {
+        let usage = wgpu::TextureUsages::STORAGE_BINDING;
+        let (errors, ..) = check_storage_write(TextureFormat::Rgba8Unorm, usage);
+        assert!(errors.is_empty());
+
+        let (errors, _, compute_pass) = check_storage_write(TextureFormat::Rgba16Float, usage);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].resource, Some(compute_pass));
+        assert!(matches!(
+            errors[0].error,
+            AppError::ShaderBindingMismatch {
+                group: 0,
+                binding: 0,
+                ..
+            }
+        ));
+
+        let usage = wgpu::TextureUsages::TEXTURE_BINDING;
+        let (errors, bind_group, _) = check_storage_write(TextureFormat::Rgba8Unorm, usage);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].resource, Some(bind_group.into()));
+        assert!(matches!(
+            errors[0].error,
+            AppError::TextureUsage {
+                usage: "Storage Binding",
+                ..
+            }
+        ));
+    }
+}
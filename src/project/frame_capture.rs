@@ -0,0 +1,482 @@
+//! Frame capture: one frame of the schedule, recorded step by step for the
+//! frame debugger.
+//!
+//! While a capture is armed, [`Presentation::execute`] hands every enabled
+//! step to a [`CaptureRecorder`]. It copies the textures the step reads right
+//! before it runs and the ones it writes right after, into the frame's own
+//! encoder, so each snapshot holds the texture exactly as that step saw or
+//! left it. Snapshots stay on the GPU and are shown through egui; formats
+//! egui can't show go through the same proxy as a texture view's
+//! [`PreviewRemap`], with its default settings.
+//!
+//! [`Presentation::execute`]: crate::project::resource::presentation::Presentation
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    error::{AppError, AppResult},
+    project::{
+        Project, UniformId,
+        render_graph::{AccessKind, GraphTexture, RenderGraph, StepAccess},
+        resource::{
+            bindgroup::BindGroupResource,
+            presentation::PassStep,
+            render_pass::DrawCommand,
+            texture::Texture,
+            texture_view::{
+                is_previewable,
+                preview::{PreviewPipeline, PreviewRemap},
+            },
+            uniform::{Uniform, UniformFieldData, animation},
+        },
+        storage::RuntimeStorage,
+    },
+    utils::wgpu_utils::TextureFormat,
+};
+
+/// The last captured frame, and whether the next one should be captured.
+#[derive(Default)]
+pub struct FrameCapture {
+    requested: bool,
+    frame: Option<CapturedFrame>,
+    /// The step the debugger shows, as an index into the frame's steps.
+    pub selected: usize,
+}
+
+/// Every enabled step of one frame, in schedule order.
+pub struct CapturedFrame {
+    pub steps: Vec<CapturedStep>,
+    /// The elapsed time the frame was rendered at.
+    pub elapsed: instant::Duration,
+}
+
+/// What one step saw and did during the captured frame.
+pub struct CapturedStep {
+    pub step: PassStep,
+    /// Whether it ran that frame. Steps that didn't run have no snapshots.
+    pub ran: bool,
+    /// The textures it reads, as they were right before it ran.
+    pub inputs: Vec<Snapshot>,
+    /// The textures it writes, as it left them.
+    pub outputs: Vec<Snapshot>,
+    /// The values of the uniforms its bind groups hold that frame.
+    pub uniforms: Vec<CapturedUniform>,
+    /// The commands recorded into a render pass's bundle.
+    pub commands: Vec<DrawCommand>,
+}
+
+/// A copy of one texture at one point of the frame.
+pub struct Snapshot {
+    pub texture: GraphTexture,
+    pub access: AccessKind,
+    pub image: AppResult<SnapshotImage>,
+}
+
+pub struct SnapshotImage {
+    texture: wgpu::Texture,
+    /// What egui shows: the copy itself, or its preview proxy.
+    shown: Option<wgpu::TextureView>,
+    egui_id: Option<egui::TextureId>,
+}
+
+pub struct CapturedUniform {
+    pub id: UniformId,
+    /// Each field's label and value.
+    pub fields: Vec<(String, String)>,
+}
+
+impl FrameCapture {
+    /// Captures the next frame.
+    pub fn request(&mut self) {
+        self.requested = true;
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.requested
+    }
+
+    pub fn frame(&self) -> Option<&CapturedFrame> {
+        self.frame.as_ref()
+    }
+
+    /// Clears the request, returning whether this frame should be captured.
+    pub fn take_request(&mut self) -> bool {
+        std::mem::take(&mut self.requested)
+    }
+
+    /// Replaces the last capture with `frame`, handing its snapshots to egui.
+    pub fn store(
+        &mut self,
+        mut frame: CapturedFrame,
+        device: &wgpu::Device,
+        egui_renderer: &egui::mutex::RwLock<egui_wgpu::Renderer>,
+    ) {
+        let mut renderer = egui_renderer.write();
+        if let Some(previous) = self.frame.take() {
+            for image in previous.images() {
+                if let Some(egui_id) = image.egui_id {
+                    renderer.free_texture(&egui_id);
+                }
+            }
+        }
+
+        for step in &mut frame.steps {
+            for snapshot in step.inputs.iter_mut().chain(&mut step.outputs) {
+                if let Ok(image) = &mut snapshot.image
+                    && let Some(view) = &image.shown
+                {
+                    image.egui_id = Some(renderer.register_native_texture(
+                        device,
+                        view,
+                        wgpu::FilterMode::Nearest,
+                    ));
+                }
+            }
+        }
+
+        self.selected = self.selected.min(frame.steps.len().saturating_sub(1));
+        self.frame = Some(frame);
+    }
+}
+
+impl CapturedFrame {
+    fn images(&self) -> impl Iterator<Item = &SnapshotImage> {
+        self.steps
+            .iter()
+            .flat_map(|step| step.inputs.iter().chain(&step.outputs))
+            .filter_map(|snapshot| snapshot.image.as_ref().ok())
+    }
+}
+
+impl SnapshotImage {
+    pub fn size(&self) -> [u32; 2] {
+        [self.texture.width(), self.texture.height()]
+    }
+
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.texture.format()
+    }
+
+    /// `None` for formats that can't be shown at all, such as integer ones.
+    pub fn egui_id(&self) -> Option<egui::TextureId> {
+        self.egui_id
+    }
+}
+
+/// Records one frame while [`Presentation::execute`] encodes it.
+///
+/// [`Presentation::execute`]: crate::project::resource::presentation::Presentation
+pub struct CaptureRecorder<'a> {
+    project: &'a Project,
+    runtime_textures: &'a RuntimeStorage<Texture>,
+    runtime_uniforms: &'a RuntimeStorage<Uniform>,
+    device: &'a wgpu::Device,
+    queue: &'a wgpu::Queue,
+    graph: RenderGraph,
+    /// Preview pipelines for formats egui can't show, for color and depth.
+    previews: HashMap<bool, PreviewPipeline>,
+    /// The inputs of the step being encoded.
+    inputs: Vec<Snapshot>,
+    steps: Vec<CapturedStep>,
+}
+
+impl<'a> CaptureRecorder<'a> {
+    pub fn new(
+        project: &'a Project,
+        runtime_textures: &'a RuntimeStorage<Texture>,
+        runtime_uniforms: &'a RuntimeStorage<Uniform>,
+        device: &'a wgpu::Device,
+        queue: &'a wgpu::Queue,
+    ) -> Self {
+        Self {
+            project,
+            runtime_textures,
+            runtime_uniforms,
+            device,
+            queue,
+            graph: RenderGraph::build(project),
+            previews: HashMap::new(),
+            inputs: vec![],
+            steps: vec![],
+        }
+    }
+
+    /// Snapshots the textures `step` reads. Call it right before encoding it.
+    pub fn begin_step(&mut self, encoder: &mut wgpu::CommandEncoder, step: PassStep) {
+        let reads = self
+            .access(step)
+            .map(|access| access.reads.clone())
+            .unwrap_or_default();
+        self.inputs = self.snapshot_all(encoder, reads);
+    }
+
+    /// Snapshots the textures `step` wrote, if it `ran`, and records the step
+    /// with `commands`, the bundle's commands of a render pass.
+    pub fn end_step(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        step: PassStep,
+        ran: bool,
+        commands: &[DrawCommand],
+    ) {
+        let inputs = std::mem::take(&mut self.inputs);
+        let Some(access) = self.access(step).cloned() else {
+            return;
+        };
+
+        let captured = match ran {
+            true => CapturedStep {
+                step,
+                ran,
+                inputs,
+                outputs: self.snapshot_all(encoder, access.writes.clone()),
+                uniforms: self.uniforms(&access),
+                commands: commands.to_vec(),
+            },
+            // Its input copies are still encoded, but nothing shows them.
+            false => CapturedStep {
+                step,
+                ran,
+                inputs: vec![],
+                outputs: vec![],
+                uniforms: vec![],
+                commands: vec![],
+            },
+        };
+        self.steps.push(captured);
+    }
+
+    pub fn finish(self, elapsed: instant::Duration) -> CapturedFrame {
+        CapturedFrame {
+            steps: self.steps,
+            elapsed,
+        }
+    }
+
+    fn access(&self, step: PassStep) -> Option<&StepAccess> {
+        self.graph.steps().iter().find(|access| access.step == step)
+    }
+
+    fn snapshot_all(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        accesses: Vec<(GraphTexture, AccessKind)>,
+    ) -> Vec<Snapshot> {
+        let mut seen = HashSet::new();
+        accesses
+            .into_iter()
+            .filter(|(texture, _)| seen.insert(*texture))
+            .filter_map(|(texture, access)| {
+                let image = self.snapshot(encoder, texture).transpose()?;
+                Some(Snapshot {
+                    texture,
+                    access,
+                    image,
+                })
+            })
+            .collect()
+    }
+
+    /// Copies the first layer and mip level of `texture`, or returns `None`
+    /// if it isn't created yet.
+    fn snapshot(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: GraphTexture,
+    ) -> AppResult<Option<SnapshotImage>> {
+        let Some(runtime) = self.runtime_textures.get_init(texture.id)? else {
+            return Ok(None);
+        };
+        let source = runtime
+            .side(texture.side)
+            .ok_or_else(|| AppError::NotDoubleBuffered {
+                texture: self
+                    .project
+                    .label(texture.id)
+                    .unwrap_or_default()
+                    .to_string(),
+            })?;
+
+        check_copyable(source.usage(), source.dimension(), source.format())?;
+
+        // Copies may drop the sRGB suffix, which keeps egui from decoding it,
+        // like texture view previews.
+        let format = source.format().remove_srgb_suffix();
+        let size = wgpu::Extent3d {
+            width: source.width(),
+            height: source.height(),
+            depth_or_array_layers: 1,
+        };
+        let copy = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Frame Capture Snapshot"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        encoder.copy_texture_to_texture(source.as_image_copy(), copy.as_image_copy(), size);
+
+        let shown = self.shown_view(encoder, &copy);
+        Ok(Some(SnapshotImage {
+            texture: copy,
+            shown,
+            egui_id: None,
+        }))
+    }
+
+    /// The view egui can show for `copy`, converting it through a preview
+    /// proxy if needed.
+    fn shown_view(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        copy: &wgpu::Texture,
+    ) -> Option<wgpu::TextureView> {
+        let format = copy.format();
+        if TextureFormat::from_wgpu(format).is_some_and(is_previewable) {
+            return Some(copy.create_view(&wgpu::TextureViewDescriptor::default()));
+        }
+
+        let depth = format.is_depth_stencil_format();
+        let convertible = matches!(
+            format.sample_type(None, None),
+            Some(wgpu::TextureSampleType::Float { .. } | wgpu::TextureSampleType::Depth)
+        );
+        if !convertible {
+            return None;
+        }
+        let preview = match self.previews.entry(depth) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(PreviewPipeline::new(self.device, "Frame Capture", copy)?)
+            }
+        };
+        let target = preview.create_target(self.device, "Frame Capture", copy);
+        preview.encode(
+            self.queue,
+            encoder,
+            &PreviewRemap::default(),
+            std::iter::once(&target),
+        );
+        Some(target.view().clone())
+    }
+
+    /// The uniforms the step's bind groups hold. They're written once before
+    /// the frame, so every step sees the same values.
+    fn uniforms(&self, access: &StepAccess) -> Vec<CapturedUniform> {
+        let mut seen = HashSet::new();
+        access
+            .bind_groups
+            .iter()
+            .filter_map(|id| self.project.bind_groups.get(*id).ok())
+            .flat_map(|bind_group| bind_group.entries())
+            .filter_map(|entry| match entry.resource {
+                BindGroupResource::Uniform(Some(id)) => Some(id),
+                _ => None,
+            })
+            .filter(|id| seen.insert(*id))
+            .filter_map(|id| {
+                let uniform = self.project.uniforms.get(id).ok()?;
+                let runtime = self.runtime_uniforms.get_init(id).ok().flatten()?;
+                let fields = uniform
+                    .fields()
+                    .iter()
+                    .zip(runtime.fields())
+                    .map(|(field, value)| (field.label().to_string(), format_data(value.data())))
+                    .collect();
+                Some(CapturedUniform { id, fields })
+            })
+            .collect()
+    }
+}
+
+/// Whether a texture with these properties can be snapshotted.
+fn check_copyable(
+    usage: wgpu::TextureUsages,
+    dimension: wgpu::TextureDimension,
+    format: wgpu::TextureFormat,
+) -> AppResult<()> {
+    if !usage.contains(wgpu::TextureUsages::COPY_SRC) {
+        return Err(AppError::CaptureTextureUsage);
+    }
+    if dimension != wgpu::TextureDimension::D2 {
+        return Err(AppError::CaptureTextureDimension);
+    }
+    // Only depth formats with a fixed layout can be copied, one aspect at most.
+    if format.is_combined_depth_stencil_format()
+        || matches!(
+            format,
+            wgpu::TextureFormat::Depth24Plus | wgpu::TextureFormat::Stencil8
+        )
+    {
+        return Err(AppError::CaptureTextureFormat {
+            format: format!("{format:?}"),
+        });
+    }
+    Ok(())
+}
+
+fn format_data(data: &UniformFieldData) -> String {
+    let components: Vec<String> = animation::components(data)
+        .iter()
+        .map(|value| value.to_string())
+        .collect();
+    match components.as_slice() {
+        [single] => single.clone(),
+        _ => format!("({})", components.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshots_need_copyable_2d_textures() {
+        use wgpu::{TextureDimension as D, TextureFormat as F, TextureUsages as U};
+
+        let copyable = U::COPY_SRC | U::TEXTURE_BINDING;
+        assert!(check_copyable(copyable, D::D2, F::Rgba8UnormSrgb).is_ok());
+        assert!(check_copyable(copyable, D::D2, F::Depth32Float).is_ok());
+
+        assert!(matches!(
+            check_copyable(U::TEXTURE_BINDING, D::D2, F::Rgba8Unorm),
+            Err(AppError::CaptureTextureUsage)
+        ));
+        assert!(matches!(
+            check_copyable(copyable, D::D3, F::Rgba8Unorm),
+            Err(AppError::CaptureTextureDimension)
+        ));
+        for format in [
+            F::Depth24Plus,
+            F::Depth24PlusStencil8,
+            F::Depth32FloatStencil8,
+        ] {
+            assert!(matches!(
+                check_copyable(copyable, D::D2, format),
+                Err(AppError::CaptureTextureFormat { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn uniform_values_format_as_scalars_or_tuples() {
+        assert_eq!(format_data(&UniformFieldData::UInt32(7)), "7");
+        assert_eq!(format_data(&UniformFieldData::Float(0.5)), "0.5");
+        assert_eq!(format_data(&UniformFieldData::Vec2u([1, 2])), "(1, 2)");
+        assert_eq!(
+            format_data(&UniformFieldData::Rgba([1.0, 0.5, 0.25, 0.0])),
+            "(1, 0.5, 0.25, 0)"
+        );
+        let identity =
+            std::array::from_fn(|i| std::array::from_fn(|j| if i == j { 1.0 } else { 0.0 }));
+        assert_eq!(
+            format_data(&UniformFieldData::Mat4x4f(identity)),
+            "(1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1)"
+        );
+    }
+}
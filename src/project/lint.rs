@@ -0,0 +1,706 @@
+//! Lints for what a project accumulates as it's edited: resources nothing
+//! uses, references to resources that were deleted, and files no resource
+//! reads. Every finding comes with a [`LintFix`] that resolves it.
+
+use std::collections::HashSet;
+
+use crate::{
+    error::AppError,
+    project::{
+        Project, ResourceId, ResourceKind,
+        paths::{FilePath, is_image_file},
+        resource::{
+            bindgroup::BindGroupResource,
+            compute_pass::{IterationCount, WorkSize},
+            copy_pass::CopyMode,
+            presentation::PassStep,
+            render_pass::RenderPassTarget,
+            render_pipeline::{BindGroupTarget, RenderDrawStrategy},
+            texture::{SwapTrigger, TextureSource},
+            uniform::{UniformField, UniformFieldSource},
+        },
+    },
+    utils::wgpu_utils::ShaderSourceKind,
+};
+
+pub struct Lint {
+    /// The resource it's about, or `None` when it's about a file.
+    pub resource: Option<ResourceId>,
+    pub error: AppError,
+    pub fix: LintFix,
+}
+
+/// A one-click action that resolves a [`Lint`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum LintFix {
+    Delete(ResourceId),
+    /// Unsets, or removes from its list, every reference `owner` has to `target`.
+    ClearReference {
+        owner: ResourceId,
+        target: ResourceId,
+    },
+    RemoveFromSchedule(PassStep),
+    DeleteFile(FilePath),
+}
+
+impl LintFix {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LintFix::Delete(_) => "Delete",
+            LintFix::ClearReference { .. } => "Clear reference",
+            LintFix::RemoveFromSchedule(_) => "Remove from schedule",
+            LintFix::DeleteFile(_) => "Delete file",
+        }
+    }
+}
+
+/// Lints `project`. `files` are the files in the project folder, and
+/// `indirect_files` the ones resources read through other files, such as a
+/// model's materials and their textures.
+pub fn lint<'a>(
+    project: &Project,
+    files: &[FilePath],
+    indirect_files: impl IntoIterator<Item = &'a FilePath>,
+) -> Vec<Lint> {
+    let mut lints = vec![];
+    let mut used = HashSet::new();
+    for (owner, _) in project.project_revisions() {
+        for target in references(project, owner) {
+            if project.label(target).is_some() {
+                used.insert(target);
+                continue;
+            }
+            let fix = match (owner, target) {
+                (ResourceId::Presentation(_), ResourceId::ComputePass(id)) => {
+                    LintFix::RemoveFromSchedule(PassStep::Compute(id))
+                }
+                (ResourceId::Presentation(_), ResourceId::RenderPass(id)) => {
+                    LintFix::RemoveFromSchedule(PassStep::Render(id))
+                }
+                (ResourceId::Presentation(_), ResourceId::CopyPass(id)) => {
+                    LintFix::RemoveFromSchedule(PassStep::Copy(id))
+                }
+                _ => LintFix::ClearReference { owner, target },
+            };
+            let kind = target.kind();
+            lints.push(Lint {
+                resource: Some(owner),
+                error: AppError::DanglingReference { kind },
+                fix,
+            });
+        }
+    }
+
+    for (id, _) in project.project_revisions() {
+        // Viewports are shown as tabs, so they're in use without references.
+        let kind = id.kind();
+        if used.contains(&id) || matches!(kind, ResourceKind::Viewport | ResourceKind::Presentation)
+        {
+            continue;
+        }
+        lints.push(Lint {
+            resource: Some(id),
+            error: AppError::UnusedResource { kind },
+            fix: LintFix::Delete(id),
+        });
+    }
+
+    let used_files: HashSet<&FilePath> = referenced_files(project)
+        .into_iter()
+        .map(|(_, path)| path)
+        .chain(indirect_files)
+        .collect();
+    for path in files {
+        if is_asset_file(path) && !used_files.contains(path) {
+            lints.push(Lint {
+                resource: None,
+                error: AppError::UnusedFile(path.clone()),
+                fix: LintFix::DeleteFile(path.clone()),
+            });
+        }
+    }
+    lints
+}
+
+/// Files that only exist to be read by a resource. Others, like notes or the
+/// project's thumbnail, are never reported as unused.
+fn is_asset_file(path: &FilePath) -> bool {
+    let extension = path.extension().unwrap_or_default();
+    !path.is_thumbnail()
+        && (ShaderSourceKind::from_extension(extension).is_some()
+            || extension == "obj"
+            || is_image_file(path))
+}
+
+/// The files resources read directly.
+pub fn referenced_files(project: &Project) -> Vec<(ResourceId, &FilePath)> {
+    let shaders = project
+        .shaders
+        .list()
+        .filter_map(|(id, shader)| Some((id.into(), shader.source()?)));
+    let models = project
+        .models
+        .list()
+        .filter_map(|(id, model)| Some((id.into(), model.source()?)));
+    let textures = project
+        .textures
+        .list()
+        .filter_map(|(id, texture)| match texture.source() {
+            TextureSource::Image(Some(path)) => Some((id.into(), path)),
+            _ => None,
+        });
+    shaders.chain(models).chain(textures).collect()
+}
+
+/// The resources `id` refers to. Unset references are left out.
+pub fn references(project: &Project, id: ResourceId) -> Vec<ResourceId> {
+    let mut references: Vec<ResourceId> = vec![];
+    match id {
+        ResourceId::Shader(_) | ResourceId::Sampler(_) | ResourceId::Dimension(_) => {}
+        ResourceId::Viewport(id) => {
+            if let Ok(viewport) = project.viewports.get(id) {
+                references.extend(viewport.texture_view_id().map(Into::into));
+                references.extend(viewport.dimension_id().map(Into::into));
+                references.extend(viewport.controls_camera_id().map(Into::into));
+            }
+        }
+        ResourceId::Uniform(id) => {
+            if let Ok(uniform) = project.uniforms.get(id) {
+                for field in uniform.fields() {
+                    match field.source() {
+                        UniformFieldSource::Camera { camera_id, .. } => {
+                            references.extend(camera_id.map(Into::into));
+                        }
+                        UniformFieldSource::Dimension { dimension_id } => {
+                            references.extend(dimension_id.map(Into::into));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        ResourceId::BindGroup(id) => {
+            if let Ok(bind_group) = project.bind_groups.get(id) {
+                references.extend(
+                    bind_group
+                        .entries()
+                        .iter()
+                        .filter_map(|entry| entry.resource.id()),
+                );
+            }
+        }
+        ResourceId::Texture(id) => {
+            if let Ok(texture) = project.textures.get(id) {
+                if let TextureSource::Dimension {
+                    dimension: Some(dimension),
+                    ..
+                } = texture.source()
+                {
+                    references.push((*dimension).into());
+                }
+                if let Some(SwapTrigger::AfterStep(Some(step))) = texture.ping_pong() {
+                    references.push(step.into());
+                }
+            }
+        }
+        ResourceId::TextureView(id) => {
+            if let Ok(texture_view) = project.texture_views.get(id) {
+                references.extend(texture_view.texture_id().map(Into::into));
+            }
+        }
+        ResourceId::Camera(id) => {
+            if let Ok(camera) = project.cameras.get(id) {
+                references.extend(camera.dimension_id().map(Into::into));
+            }
+        }
+        ResourceId::Model(id) => {
+            if let Ok(model) = project.models.get(id) {
+                references.extend(
+                    model
+                        .material_bind_group_ids()
+                        .iter()
+                        .flatten()
+                        .map(|id| (*id).into()),
+                );
+            }
+        }
+        ResourceId::RenderPipeline(id) => {
+            if let Ok(pipeline) = project.render_pipelines.get(id) {
+                references.extend(pipeline.vertex_shader().map(Into::into));
+                references.extend(pipeline.fragment_shader().map(Into::into));
+                if let RenderDrawStrategy::Model {
+                    model_id: Some(model_id),
+                    ..
+                } = pipeline.draw_strategy()
+                {
+                    references.push((*model_id).into());
+                }
+                for target in pipeline.bind_groups() {
+                    if let BindGroupTarget::Static(id) = target {
+                        references.push((*id).into());
+                    }
+                }
+            }
+        }
+        ResourceId::RenderPass(id) => {
+            if let Ok(render_pass) = project.render_passes.get(id) {
+                references.extend(render_pass.target().texture_view_id().map(Into::into));
+                references.extend(
+                    render_pass
+                        .depth_target()
+                        .and_then(|target| target.texture_view_id())
+                        .map(Into::into),
+                );
+                references.extend(render_pass.pipelines().iter().map(|id| (*id).into()));
+            }
+        }
+        ResourceId::ComputePass(id) => {
+            if let Ok(compute_pass) = project.compute_passes.get(id) {
+                references.extend(compute_pass.shader().map(Into::into));
+                references.extend(compute_pass.bind_groups().iter().map(|id| (*id).into()));
+                let size = compute_pass.dispatch_size();
+                for axis in [size.x, size.y, size.z] {
+                    if let WorkSize::Dimension(dimension) = axis {
+                        references.extend(dimension.id.map(Into::into));
+                    }
+                }
+                let iterations = compute_pass.iterations();
+                if let IterationCount::Uniform(field) = iterations.count {
+                    references.extend(field.id.map(Into::into));
+                }
+                if let Some(alternates) = &iterations.alternate_bind_groups {
+                    references.extend(alternates.iter().map(|id| (*id).into()));
+                }
+            }
+        }
+        ResourceId::CopyPass(id) => {
+            if let Ok(copy_pass) = project.copy_passes.get(id) {
+                let mode = copy_pass.mode();
+                references.extend(mode.source());
+                references.extend(mode.destination());
+            }
+        }
+        ResourceId::Presentation(_) => {
+            let presentation = &project.presentation;
+            references.extend(presentation.steps().iter().map(|step| (*step).into()));
+            references.extend(presentation.main_viewport().map(Into::into));
+        }
+    }
+    references
+}
+
+/// `id`, or `None` if it's `target`.
+fn clear<T: Copy + Into<ResourceId>>(id: Option<T>, target: ResourceId) -> Option<T> {
+    id.filter(|id| (*id).into() != target)
+}
+
+/// Unsets, or removes from its list, every reference `owner` has to `target`.
+pub fn clear_reference(project: &mut Project, owner: ResourceId, target: ResourceId) {
+    let is_target = |id: ResourceId| id == target;
+    match owner {
+        ResourceId::Shader(_) | ResourceId::Sampler(_) | ResourceId::Dimension(_) => {}
+        ResourceId::Viewport(id) => {
+            if let Ok(viewport) = project.viewports.get_mut(id) {
+                viewport.set_texture_view_id(clear(viewport.texture_view_id(), target));
+                viewport.set_dimension_id(clear(viewport.dimension_id(), target));
+                viewport.set_controls_camera_id(clear(viewport.controls_camera_id(), target));
+            }
+        }
+        ResourceId::Uniform(id) => {
+            if let Ok(uniform) = project.uniforms.get_mut(id) {
+                let fields = uniform
+                    .fields()
+                    .iter()
+                    .map(|field| {
+                        let source = match field.source().clone() {
+                            UniformFieldSource::Camera { camera_id, field } => {
+                                UniformFieldSource::Camera {
+                                    camera_id: clear(camera_id, target),
+                                    field,
+                                }
+                            }
+                            UniformFieldSource::Dimension { dimension_id } => {
+                                UniformFieldSource::Dimension {
+                                    dimension_id: clear(dimension_id, target),
+                                }
+                            }
+                            source => source,
+                        };
+                        UniformField::new(field.label(), source)
+                    })
+                    .collect();
+                uniform.set_fields(fields);
+            }
+        }
+        ResourceId::BindGroup(id) => {
+            if let Ok(bind_group) = project.bind_groups.get_mut(id) {
+                let mut entries = bind_group.entries().to_vec();
+                for entry in &mut entries {
+                    match &mut entry.resource {
+                        BindGroupResource::Texture {
+                            texture_view_id, ..
+                        }
+                        | BindGroupResource::StorageTexture {
+                            texture_view_id, ..
+                        } => *texture_view_id = clear(*texture_view_id, target),
+                        BindGroupResource::Sampler { sampler_id, .. } => {
+                            *sampler_id = clear(*sampler_id, target);
+                        }
+                        BindGroupResource::Uniform(uniform_id) => {
+                            *uniform_id = clear(*uniform_id, target);
+                        }
+                    }
+                }
+                bind_group.set_entries(entries);
+            }
+        }
+        ResourceId::Texture(id) => {
+            if let Ok(texture) = project.textures.get_mut(id) {
+                if let TextureSource::Dimension { dimension, layers } = *texture.source() {
+                    texture.set_source(TextureSource::Dimension {
+                        dimension: clear(dimension, target),
+                        layers,
+                    });
+                }
+                if let Some(SwapTrigger::AfterStep(step)) = texture.ping_pong() {
+                    let step = step.filter(|step| !is_target((*step).into()));
+                    texture.set_ping_pong(Some(SwapTrigger::AfterStep(step)));
+                }
+            }
+        }
+        ResourceId::TextureView(id) => {
+            if let Ok(texture_view) = project.texture_views.get_mut(id) {
+                texture_view.set_texture_id(clear(texture_view.texture_id(), target));
+            }
+        }
+        ResourceId::Camera(id) => {
+            if let Ok(camera) = project.cameras.get_mut(id) {
+                camera.set_dimension_id(clear(camera.dimension_id(), target));
+            }
+        }
+        ResourceId::Model(id) => {
+            if let Ok(model) = project.models.get_mut(id) {
+                let ids = model
+                    .material_bind_group_ids()
+                    .iter()
+                    .map(|id| clear(*id, target))
+                    .collect();
+                model.set_material_bind_group_ids(ids);
+            }
+        }
+        ResourceId::RenderPipeline(id) => {
+            if let Ok(pipeline) = project.render_pipelines.get_mut(id) {
+                pipeline.set_vertex_shader(clear(pipeline.vertex_shader(), target));
+                pipeline.set_fragment_shader(clear(pipeline.fragment_shader(), target));
+                if let RenderDrawStrategy::Model {
+                    model_id,
+                    instances,
+                    mesh_vertex_slot,
+                } = pipeline.draw_strategy().clone()
+                {
+                    pipeline.set_draw_strategy(RenderDrawStrategy::Model {
+                        model_id: clear(model_id, target),
+                        instances,
+                        mesh_vertex_slot,
+                    });
+                }
+                let bind_groups = pipeline
+                    .bind_groups()
+                    .iter()
+                    .map(|bind_group| match bind_group {
+                        BindGroupTarget::Static(id) if is_target((*id).into()) => {
+                            BindGroupTarget::Empty
+                        }
+                        bind_group => bind_group.clone(),
+                    })
+                    .collect();
+                pipeline.set_bind_groups(bind_groups);
+            }
+        }
+        ResourceId::RenderPass(id) => {
+            if let Ok(render_pass) = project.render_passes.get_mut(id) {
+                let color = render_pass.target();
+                render_pass.set_target(RenderPassTarget::new(
+                    clear(color.texture_view_id(), target),
+                    color.load_operation(),
+                ));
+                if let Some(depth) = render_pass.depth_target() {
+                    render_pass.set_depth_target(Some(RenderPassTarget::new(
+                        clear(depth.texture_view_id(), target),
+                        depth.load_operation(),
+                    )));
+                }
+                let mut pipelines = render_pass.pipelines().to_vec();
+                pipelines.retain(|id| !is_target((*id).into()));
+                render_pass.set_pipelines(pipelines);
+            }
+        }
+        ResourceId::ComputePass(id) => {
+            if let Ok(compute_pass) = project.compute_passes.get_mut(id) {
+                compute_pass.set_shader(clear(compute_pass.shader(), target));
+                let mut bind_groups = compute_pass.bind_groups().to_vec();
+                bind_groups.retain(|id| !is_target((*id).into()));
+                compute_pass.set_bind_groups(bind_groups);
+
+                let mut size = compute_pass.dispatch_size();
+                for axis in [&mut size.x, &mut size.y, &mut size.z] {
+                    if let WorkSize::Dimension(dimension) = axis {
+                        dimension.id = clear(dimension.id, target);
+                    }
+                }
+                compute_pass.set_dispatch_size(size);
+
+                let mut iterations = compute_pass.iterations().clone();
+                if let IterationCount::Uniform(field) = &mut iterations.count {
+                    field.id = clear(field.id, target);
+                }
+                if let Some(alternates) = &mut iterations.alternate_bind_groups {
+                    alternates.retain(|id| !is_target((*id).into()));
+                }
+                compute_pass.set_iterations(iterations);
+            }
+        }
+        ResourceId::CopyPass(id) => {
+            if let Ok(copy_pass) = project.copy_passes.get_mut(id) {
+                let mode = match copy_pass.mode() {
+                    CopyMode::Texture {
+                        source,
+                        destination,
+                    } => CopyMode::Texture {
+                        source: clear(source, target),
+                        destination: clear(destination, target),
+                    },
+                    CopyMode::Region {
+                        source,
+                        destination,
+                        source_origin,
+                        destination_origin,
+                        size,
+                    } => CopyMode::Region {
+                        source: clear(source, target),
+                        destination: clear(destination, target),
+                        source_origin,
+                        destination_origin,
+                        size,
+                    },
+                    CopyMode::Buffer {
+                        source,
+                        destination,
+                        destination_origin,
+                        size,
+                    } => CopyMode::Buffer {
+                        source: clear(source, target),
+                        destination: clear(destination, target),
+                        destination_origin,
+                        size,
+                    },
+                    CopyMode::Blit {
+                        source,
+                        destination,
+                        filter,
+                    } => CopyMode::Blit {
+                        source: clear(source, target),
+                        destination: clear(destination, target),
+                        filter,
+                    },
+                };
+                copy_pass.set_mode(mode);
+            }
+        }
+        ResourceId::Presentation(_) => {
+            let presentation = &mut project.presentation;
+            let mut steps = presentation.steps().to_vec();
+            steps.retain(|step| !is_target((*step).into()));
+            presentation.set_steps(steps);
+            presentation.set_main_viewport(clear(presentation.main_viewport(), target));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{PresentationId, resource::shader::Shader};
+
+    fn path(path: &str) -> FilePath {
+        FilePath::from_str(path).unwrap()
+    }
+
+    fn fixes(project: &Project, files: &[FilePath], indirect: &[FilePath]) -> Vec<LintFix> {
+        lint(project, files, indirect)
+            .into_iter()
+            .map(|lint| lint.fix)
+            .collect()
+    }
+
+    fn dangling_fixes(project: &Project) -> Vec<LintFix> {
+        lint(project, &[], [])
+            .into_iter()
+            .filter(|lint| matches!(lint.error, AppError::DanglingReference { .. }))
+            .map(|lint| lint.fix)
+            .collect()
+    }
+
+    #[test]
+    fn deletes_resources_nothing_uses() {
+        let mut project = Project::default();
+        let texture = project.textures.create("Scene".to_string());
+        let view = project.texture_views.create("Scene View".to_string());
+        project
+            .texture_views
+            .get_mut(view)
+            .unwrap()
+            .set_texture_id(Some(texture));
+        let viewport = project.viewports.create("Main".to_string());
+        project
+            .viewports
+            .get_mut(viewport)
+            .unwrap()
+            .set_texture_view_id(Some(view));
+        let orphan = project.bind_groups.create("Orphan".to_string());
+
+        // The viewport is shown as a tab, and keeps what it shows in use.
+        assert_eq!(
+            fixes(&project, &[], &[]),
+            vec![LintFix::Delete(orphan.into())]
+        );
+    }
+
+    #[test]
+    fn clears_a_dangling_reference() {
+        let mut project = Project::default();
+        let camera = project.cameras.create("Camera".to_string());
+        let dimension = project.dimensions.create("Old".to_string());
+        project
+            .cameras
+            .get_mut(camera)
+            .unwrap()
+            .set_dimension_id(Some(dimension));
+        project.unregister(dimension.into());
+
+        let clear = LintFix::ClearReference {
+            owner: camera.into(),
+            target: dimension.into(),
+        };
+        assert_eq!(dangling_fixes(&project), vec![clear]);
+
+        clear_reference(&mut project, camera.into(), dimension.into());
+        assert_eq!(project.cameras.get(camera).unwrap().dimension_id(), None);
+        assert_eq!(dangling_fixes(&project), vec![]);
+    }
+
+    #[test]
+    fn clears_dangling_references_on_a_render_pass() {
+        let mut project = Project::default();
+        let view = project.texture_views.create("Scene View".to_string());
+        let kept = project.render_pipelines.create("Kept".to_string());
+        let deleted = project.render_pipelines.create("Deleted".to_string());
+        let render_pass = project.render_passes.create("Draw".to_string());
+        let pass = project.render_passes.get_mut(render_pass).unwrap();
+        pass.set_target(RenderPassTarget::new(Some(view), Default::default()));
+        pass.set_pipelines(vec![deleted, kept]);
+        project.unregister(view.into());
+        project.unregister(deleted.into());
+
+        let fixes = dangling_fixes(&project);
+        for target in [ResourceId::from(view), deleted.into()] {
+            let owner = render_pass.into();
+            assert!(fixes.contains(&LintFix::ClearReference { owner, target }));
+            clear_reference(&mut project, owner, target);
+        }
+
+        let pass = project.render_passes.get(render_pass).unwrap();
+        assert_eq!(pass.target().texture_view_id(), None);
+        assert_eq!(pass.pipelines(), [kept]);
+        assert_eq!(dangling_fixes(&project), vec![]);
+    }
+
+    #[test]
+    fn clears_dangling_references_on_a_compute_pass() {
+        let mut project = Project::default();
+        let shader = project
+            .shaders
+            .register(Shader::new("Simulate", path("shaders/simulate.wgsl")));
+        let kept = project.bind_groups.create("Kept".to_string());
+        let deleted = project.bind_groups.create("Deleted".to_string());
+        let compute_pass = project.compute_passes.create("Simulate".to_string());
+        let pass = project.compute_passes.get_mut(compute_pass).unwrap();
+        pass.set_shader(Some(shader));
+        pass.set_bind_groups(vec![kept, deleted]);
+        project.unregister(shader.into());
+        project.unregister(deleted.into());
+
+        let fixes = dangling_fixes(&project);
+        for target in [ResourceId::from(shader), deleted.into()] {
+            let owner = compute_pass.into();
+            assert!(fixes.contains(&LintFix::ClearReference { owner, target }));
+            clear_reference(&mut project, owner, target);
+        }
+
+        let pass = project.compute_passes.get(compute_pass).unwrap();
+        assert_eq!(pass.shader(), None);
+        assert_eq!(pass.bind_groups(), [kept]);
+        assert_eq!(dangling_fixes(&project), vec![]);
+    }
+
+    #[test]
+    fn removes_dangling_steps_from_the_schedule() {
+        let mut project = Project::default();
+        let compute_pass = project.compute_passes.create("Simulate".to_string());
+        let render_pass = project.render_passes.create("Draw".to_string());
+        let kept = PassStep::Render(render_pass);
+        project
+            .presentation
+            .set_steps(vec![PassStep::Compute(compute_pass), kept]);
+        project.unregister(compute_pass.into());
+
+        let step = PassStep::Compute(compute_pass);
+        assert_eq!(
+            dangling_fixes(&project),
+            vec![LintFix::RemoveFromSchedule(step)]
+        );
+
+        clear_reference(&mut project, PresentationId.into(), step.into());
+        assert_eq!(project.presentation.steps(), [kept]);
+        assert_eq!(dangling_fixes(&project), vec![]);
+    }
+
+    #[test]
+    fn deletes_asset_files_nothing_reads() {
+        let mut project = Project::default();
+        project
+            .shaders
+            .register(Shader::new("Main", path("shaders/main.wgsl")));
+        let texture = project.textures.create("Brick".to_string());
+        project
+            .textures
+            .get_mut(texture)
+            .unwrap()
+            .set_source(TextureSource::Image(Some(path("textures/brick.png"))));
+
+        let files = [
+            path("shaders/main.wgsl"),
+            path("shaders/unused.wgsl"),
+            path("textures/brick.png"),
+            path("models/brick.png"),
+            path("models/unused.obj"),
+            path("notes.md"),
+            path("thumbnail.png"),
+            path("textures/thumbnail.png"),
+        ];
+        let indirect = [path("models/brick.png")];
+        let unused_files: Vec<_> = fixes(&project, &files, &indirect)
+            .into_iter()
+            .filter(|fix| matches!(fix, LintFix::DeleteFile(_)))
+            .collect();
+        // Notes and the project's thumbnail aren't assets, so they're kept.
+        assert_eq!(
+            unused_files,
+            [
+                LintFix::DeleteFile(path("shaders/unused.wgsl")),
+                LintFix::DeleteFile(path("models/unused.obj")),
+                LintFix::DeleteFile(path("textures/thumbnail.png")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Versioning of `project.json`.
+//!
+//! Every saved project records the format [`VERSION`] it was written with.
+//! Older files are upgraded on load by running, in order, the [`MIGRATIONS`]
+//! between their version and the current one, each rewriting the raw JSON
+//! into the shape the next version expects. Files without a version predate
+//! versioning and count as version 0.
+//!
+//! Changing the format in a way older files can't deserialize into means
+//! bumping [`VERSION`] and appending the migration that upgrades them.
+
+use serde_json::{Map, Value};
+
+use crate::error::{AppError, AppResult};
+
+/// The version of the format this build reads and writes.
+pub const VERSION: u32 = 1;
+
+/// Key of the version in `project.json`.
+pub const VERSION_KEY: &str = "version";
+
+/// Upgrades a project from the version at its index to the next one.
+type Migration = fn(&mut Map<String, Value>);
+
+const MIGRATIONS: [Migration; VERSION as usize] = [unify_schedule_and_dispatch_size];
+
+/// Upgrades a parsed `project.json` to the current version, and returns it
+/// without its version.
+pub fn migrate(mut json: Value) -> AppResult<Value> {
+    let Some(project) = json.as_object_mut() else {
+        // Leave the error to deserialization, which names what was expected.
+        return Ok(json);
+    };
+
+    let version = match project.remove(VERSION_KEY) {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or(AppError::InvalidProjectVersion(version.to_string()))?,
+    };
+    if version > VERSION {
+        return Err(AppError::ProjectFromNewerVersion { version });
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(project);
+    }
+    Ok(json)
+}
+
+/// The entries of a storage, like `computePasses`, that hold a resource.
+fn resources_mut<'a>(
+    project: &'a mut Map<String, Value>,
+    storage: &str,
+) -> impl Iterator<Item = &'a mut Map<String, Value>> {
+    project
+        .get_mut(storage)
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(|slot| slot.get_mut("value")?.as_object_mut())
+}
+
+fn rename(object: &mut Map<String, Value>, from: &str, to: &str) {
+    if let Some(value) = object.remove(from) {
+        object.entry(to).or_insert(value);
+    }
+}
+
+/// Version 0 to 1: the presentation's separate `computePasses` and
+/// `renderPasses` become one `steps` schedule, with every compute pass first
+/// as they ran before, and compute passes' `workGroups` becomes
+/// `dispatchSize`, its invocation unit's `workgroup_size` `workgroupSize`.
+fn unify_schedule_and_dispatch_size(project: &mut Map<String, Value>) {
+    if let Some(presentation) = project
+        .get_mut("presentation")
+        .and_then(Value::as_object_mut)
+    {
+        let mut passes = |key: &str, step: &str| -> Vec<Value> {
+            let ids = presentation.remove(key);
+            let ids = ids.as_ref().and_then(Value::as_array);
+            ids.into_iter()
+                .flatten()
+                .map(|id| Value::Object(Map::from_iter([(step.to_string(), id.clone())])))
+                .collect()
+        };
+        let mut steps = passes("computePasses", "compute");
+        steps.extend(passes("renderPasses", "render"));
+        presentation.entry("steps").or_insert(Value::Array(steps));
+    }
+
+    for compute_pass in resources_mut(project, "computePasses") {
+        rename(compute_pass, "workGroups", "dispatchSize");
+        let invocation = compute_pass
+            .get_mut("dispatchSize")
+            .and_then(|size| size.get_mut("unit"))
+            .and_then(|unit| unit.get_mut("invocation"))
+            .and_then(Value::as_object_mut);
+        if let Some(invocation) = invocation {
+            rename(invocation, "workgroup_size", "workgroupSize");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{Project, resource::presentation::PassStep};
+
+    fn load(fixture: &str) -> Project {
+        Project::deserialize(fixture.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn upgrades_unversioned_projects() {
+        let project = load(include_str!("migration/fixtures/v0.json"));
+        let (compute_id, compute_pass) = project.compute_passes.list().next().unwrap();
+        let (render_id, _) = project.render_passes.list().next().unwrap();
+        assert_eq!(
+            project.presentation.steps(),
+            [PassStep::Compute(compute_id), PassStep::Render(render_id)]
+        );
+        assert_eq!(
+            serde_json::to_value(compute_pass.dispatch_size()).unwrap(),
+            serde_json::json!({
+                "x": { "fixed": 8 },
+                "y": { "fixed": 4 },
+                "z": { "fixed": 1 },
+                "unit": { "invocation": { "workgroupSize": [8, 8, 1] } }
+            })
+        );
+    }
+
+    #[test]
+    fn merges_legacy_pass_lists_into_steps_with_compute_first() {
+        let id = |idx: u32| serde_json::json!({ "idx": idx, "version": 1 });
+        let json = serde_json::json!({
+            "presentation": {
+                "renderPasses": [id(1), id(2)],
+                "computePasses": [id(3), id(4)],
+            },
+        });
+        let migrated = migrate(json).unwrap();
+        assert_eq!(
+            migrated["presentation"],
+            serde_json::json!({
+                "steps": [
+                    { "compute": id(3) },
+                    { "compute": id(4) },
+                    { "render": id(1) },
+                    { "render": id(2) },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn loads_current_projects_and_saves_them_unchanged() {
+        let project = load(include_str!("migration/fixtures/v1.json"));
+        assert_eq!(project.presentation.steps().len(), 2);
+        let saved = project.serialize().unwrap();
+        let reloaded = load(std::str::from_utf8(&saved).unwrap());
+        assert_eq!(reloaded.serialize().unwrap(), saved);
+    }
+
+    #[test]
+    fn saved_projects_record_the_current_version() {
+        let bytes = Project::default().serialize().unwrap();
+        let json: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json[VERSION_KEY], VERSION);
+    }
+
+    #[test]
+    fn rejects_projects_from_newer_versions() {
+        let newer = serde_json::json!({ "version": VERSION + 1 }).to_string();
+        assert!(matches!(
+            Project::deserialize(newer.as_bytes()),
+            Err(AppError::ProjectFromNewerVersion { .. })
+        ));
+        let invalid = serde_json::json!({ "version": "one" }).to_string();
+        assert!(matches!(
+            Project::deserialize(invalid.as_bytes()),
+            Err(AppError::InvalidProjectVersion(_))
+        ));
+    }
+}
@@ -1,3 +1,7 @@
+use std::collections::HashSet;
+
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use slotmap::{SecondaryMap, new_key_type};
 
@@ -5,26 +9,48 @@ use crate::{
     error::{AppError, AppResult},
     project::{
         render::PresentationRender,
+        render_graph::RenderGraphReport,
         resource::{
-            bindgroup::BindGroup, camera::Camera, compute_pass::ComputePass, dimension::Dimension,
-            model::Model, presentation::Presentation, render_pass::RenderPass,
-            render_pipeline::RenderPipeline, sampler::Sampler, shader::Shader, texture::Texture,
-            texture_view::TextureView, uniform::Uniform, viewport::Viewport,
+            bindgroup::BindGroup,
+            camera::Camera,
+            compute_pass::ComputePass,
+            copy_pass::CopyPass,
+            dimension::Dimension,
+            model::Model,
+            presentation::{PassStep, Presentation},
+            render_pass::RenderPass,
+            render_pipeline::RenderPipeline,
+            sampler::Sampler,
+            shader::Shader,
+            texture::Texture,
+            texture_view::TextureView,
+            uniform::Uniform,
+            viewport::Viewport,
         },
         storage::{RuntimeStorage, Storage},
         sync::Revision,
     },
+    utils::texture_stats::TextureAnalyses,
 };
 
 /// A snapshot of every resource's project revision, used to detect when any
 /// resource has changed since a given point in time.
 pub type ProjectRevisionSnapshot = Vec<(ResourceId, Revision)>;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod check;
+pub mod frame_capture;
+pub mod lint;
 pub mod macros;
+pub mod migration;
 pub mod paths;
+pub mod ping_pong;
 pub mod render;
+pub mod render_graph;
 pub mod resource;
 pub mod save;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod schema;
 pub mod storage;
 pub mod sync;
 
@@ -42,12 +68,48 @@ new_key_type! {
     pub struct RenderPassId;
     pub struct RenderPipelineId;
     pub struct ComputePassId;
+    pub struct CopyPassId;
+}
+
+macro_rules! resource_id_schemas {
+    ($($id:ident),* $(,)?) => {
+        $(
+            #[cfg(not(target_arch = "wasm32"))]
+            impl JsonSchema for $id {
+                fn schema_name() -> std::borrow::Cow<'static, str> {
+                    stringify!($id).into()
+                }
+
+                fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                    schema::resource_id(generator)
+                }
+            }
+        )*
+    };
 }
 
+resource_id_schemas!(
+    UniformId,
+    ShaderId,
+    ViewportId,
+    BindGroupId,
+    TextureId,
+    TextureViewId,
+    SamplerId,
+    DimensionId,
+    CameraId,
+    ModelId,
+    RenderPassId,
+    RenderPipelineId,
+    ComputePassId,
+    CopyPassId,
+);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct PresentationId;
 
 #[derive(Default, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Project {
     pub presentation: Presentation,
@@ -64,6 +126,20 @@ pub struct Project {
     pub render_pipelines: Storage<RenderPipeline>,
     pub render_passes: Storage<RenderPass>,
     pub compute_passes: Storage<ComputePass>,
+    #[serde(default)]
+    pub copy_passes: Storage<CopyPass>,
+}
+
+/// A [`Project`] as saved, led by the format version it was saved with.
+#[derive(Serialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[cfg_attr(not(target_arch = "wasm32"), schemars(title = "Rau project"))]
+struct VersionedProject<'a> {
+    /// The format version the project was saved with.
+    #[cfg_attr(not(target_arch = "wasm32"), schemars(schema_with = "schema::version"))]
+    version: u32,
+    #[serde(flatten)]
+    project: &'a Project,
 }
 
 #[derive(Default)]
@@ -80,9 +156,25 @@ pub struct RuntimeProject {
     pub render_pipelines: RuntimeStorage<RenderPipeline>,
     pub render_passes: RuntimeStorage<RenderPass>,
     pub compute_passes: RuntimeStorage<ComputePass>,
+    pub copy_passes: RuntimeStorage<CopyPass>,
     /// Time accumulated since each [`DispatchPolicy::Periodic`] compute pass last dispatched.
     pub compute_accumulators: SecondaryMap<ComputePassId, instant::Duration>,
+    /// Time accumulated since each [`DispatchPolicy::Periodic`] render pass last drew.
+    pub render_accumulators: SecondaryMap<RenderPassId, instant::Duration>,
+    /// Time accumulated since each [`DispatchPolicy::Periodic`] copy pass last copied.
+    pub copy_accumulators: SecondaryMap<CopyPassId, instant::Duration>,
+    /// Render passes whose dispatch policy asked for a redraw that hasn't been
+    /// encoded yet, e.g. because another render step wasn't ready.
+    pub owed_render_passes: HashSet<RenderPassId>,
+    /// Compute passes asked to dispatch on the next frame, whatever their
+    /// dispatch policy.
+    pub requested_compute_passes: HashSet<ComputePassId>,
+    /// Copy passes asked to copy on the next frame, whatever their dispatch
+    /// policy.
+    pub requested_copy_passes: HashSet<CopyPassId>,
     pub presentation_render: PresentationRender,
+    pub render_graph: RenderGraphReport,
+    pub texture_analyses: TextureAnalyses,
 }
 
 impl Project {
@@ -102,6 +194,7 @@ impl Project {
             ResourceId::Model(id) => self.models.get_label(id),
             ResourceId::Presentation(_) => Ok("Presentation"),
             ResourceId::ComputePass(id) => self.compute_passes.get_label(id),
+            ResourceId::CopyPass(id) => self.copy_passes.get_label(id),
         };
 
         label_err.ok()
@@ -123,6 +216,7 @@ impl Project {
             ResourceKind::RenderPass => self.render_passes.create(label).into(),
             ResourceKind::Presentation => return None,
             ResourceKind::ComputePass => self.compute_passes.create(label).into(),
+            ResourceKind::CopyPass => self.copy_passes.create(label).into(),
         };
         Some(id)
     }
@@ -140,6 +234,7 @@ impl Project {
             ResourceId::Model(id) => self.models.unregister(id),
             ResourceId::Presentation(_) => {}
             ResourceId::ComputePass(id) => self.compute_passes.unregister(id),
+            ResourceId::CopyPass(id) => self.copy_passes.unregister(id),
             ResourceId::Viewport(id) => self.viewports.unregister(id),
             ResourceId::RenderPipeline(id) => self.render_pipelines.unregister(id),
             ResourceId::RenderPass(id) => self.render_passes.unregister(id),
@@ -161,6 +256,7 @@ impl Project {
             .chain(self.render_pipelines.project_revisions())
             .chain(self.render_passes.project_revisions())
             .chain(self.compute_passes.project_revisions())
+            .chain(self.copy_passes.project_revisions())
             .chain(std::iter::once((
                 ResourceId::Presentation(PresentationId),
                 self.presentation.project_revision(),
@@ -176,24 +272,73 @@ impl Project {
         let mut bytes = Vec::new();
         let mut serializer = serde_json::Serializer::with_formatter(&mut bytes, formatter);
 
-        Serialize::serialize(self, &mut serializer)?;
+        let versioned = VersionedProject {
+            version: migration::VERSION,
+            project: self,
+        };
+        Serialize::serialize(&versioned, &mut serializer)?;
         bytes.push(b'\n');
         Ok(bytes)
     }
 
+    /// Parses a `project.json`, upgrading it first if an older version wrote it.
     pub fn deserialize(data: &[u8]) -> AppResult<Self> {
-        serde_json::from_slice(data).map_err(Into::into)
+        let json = migration::migrate(serde_json::from_slice(data)?)?;
+        serde_json::from_value(json).map_err(Into::into)
+    }
+
+    /// The JSON Schema of `project.json` as [`Project::serialize`] writes it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn schema() -> schemars::Schema {
+        schemars::schema_for!(VersionedProject<'static>)
     }
 }
 
 impl RuntimeProject {
+    /// Runs `step` once on the next frame, whatever its dispatch policy.
+    /// Requests for steps that aren't scheduled or are disabled are ignored,
+    /// since those never run to clear them.
+    pub fn request_dispatch(&mut self, presentation: &Presentation, step: PassStep) {
+        if !presentation.can_run(step) {
+            return;
+        }
+        match step {
+            PassStep::Compute(id) => self.requested_compute_passes.insert(id),
+            PassStep::Render(id) => self.owed_render_passes.insert(id),
+            PassStep::Copy(id) => self.requested_copy_passes.insert(id),
+        };
+    }
+
+    /// Reruns every enabled initializer step of the schedule on the next frame,
+    /// in schedule order.
+    pub fn reset_simulation(&mut self, presentation: &Presentation) {
+        for step in presentation.initializer_steps() {
+            self.request_dispatch(presentation, step);
+        }
+    }
+
+    /// Drops the requests for steps unscheduled or disabled since they were
+    /// made. Disabled render passes keep what they owe, so re-enabling one
+    /// redraws it.
+    pub fn drop_stale_requests(&mut self, presentation: &Presentation) {
+        self.requested_compute_passes
+            .retain(|id| presentation.can_run(PassStep::Compute(*id)));
+        self.requested_copy_passes
+            .retain(|id| presentation.can_run(PassStep::Copy(*id)));
+        self.owed_render_passes
+            .retain(|id| presentation.steps().contains(&PassStep::Render(*id)));
+    }
+
     pub fn unregister(&mut self, id: ResourceId) {
         match id {
             ResourceId::Shader(id) => self.shaders.unregister(id),
             ResourceId::Uniform(id) => self.uniforms.unregister(id),
             ResourceId::BindGroup(id) => self.bind_groups.unregister(id),
             ResourceId::Texture(id) => self.textures.unregister(id),
-            ResourceId::TextureView(id) => self.texture_views.unregister(id),
+            ResourceId::TextureView(id) => {
+                self.texture_views.unregister(id);
+                self.texture_analyses.remove(id);
+            }
             ResourceId::Sampler(id) => self.samplers.unregister(id),
             ResourceId::Dimension(id) => self.dimensions.unregister(id),
             ResourceId::Camera(id) => self.cameras.unregister(id),
@@ -204,6 +349,12 @@ impl RuntimeProject {
             ResourceId::ComputePass(id) => {
                 self.compute_passes.unregister(id);
                 self.compute_accumulators.remove(id);
+                self.requested_compute_passes.remove(&id);
+            }
+            ResourceId::CopyPass(id) => {
+                self.copy_passes.unregister(id);
+                self.copy_accumulators.remove(id);
+                self.requested_copy_passes.remove(&id);
             }
             ResourceId::Viewport(_) => {}
         };
@@ -222,6 +373,7 @@ impl RuntimeProject {
             || self.render_pipelines.has_pending()
             || self.render_passes.has_pending()
             || self.compute_passes.has_pending()
+            || self.copy_passes.has_pending()
     }
 
     pub fn iter_errors(&self) -> impl Iterator<Item = (ResourceId, &AppError)> {
@@ -236,6 +388,7 @@ impl RuntimeProject {
             .chain(self.cameras.get_errors())
             .chain(self.models.get_errors())
             .chain(self.compute_passes.get_errors())
+            .chain(self.copy_passes.get_errors())
             .chain(self.render_pipelines.get_errors())
             .chain(self.render_passes.get_errors())
             .chain(
@@ -243,6 +396,13 @@ impl RuntimeProject {
                     .error()
                     .map(|error| (PresentationId.into(), error)),
             )
+            .chain(self.texture_analyses.diagnostics())
+    }
+
+    /// Diagnostics that don't stop the project from running, like the
+    /// schedule's hazards.
+    pub fn iter_warnings(&self) -> impl Iterator<Item = (ResourceId, &AppError)> {
+        self.render_graph.diagnostics()
     }
 
     pub fn get_error(&self, id: impl Into<ResourceId>) -> Option<&AppError> {
@@ -259,6 +419,7 @@ impl RuntimeProject {
             ResourceId::RenderPipeline(id) => self.render_pipelines.get_error(id),
             ResourceId::RenderPass(id) => self.render_passes.get_error(id),
             ResourceId::ComputePass(id) => self.compute_passes.get_error(id),
+            ResourceId::CopyPass(id) => self.copy_passes.get_error(id),
             ResourceId::Presentation(_) => self.presentation_render.error(),
             ResourceId::Viewport(_) => None,
         }
@@ -281,9 +442,12 @@ pub enum ResourceId {
     RenderPass(RenderPassId),
     Presentation(PresentationId),
     ComputePass(ComputePassId),
+    CopyPass(CopyPassId),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, strum::Display)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "title_case")]
 pub enum ResourceKind {
     Shader,
     Viewport,
@@ -299,6 +463,29 @@ pub enum ResourceKind {
     RenderPass,
     Presentation,
     ComputePass,
+    CopyPass,
+}
+
+impl ResourceId {
+    pub fn kind(self) -> ResourceKind {
+        match self {
+            ResourceId::Shader(_) => ResourceKind::Shader,
+            ResourceId::Viewport(_) => ResourceKind::Viewport,
+            ResourceId::Uniform(_) => ResourceKind::Uniform,
+            ResourceId::BindGroup(_) => ResourceKind::BindGroup,
+            ResourceId::Texture(_) => ResourceKind::Texture,
+            ResourceId::TextureView(_) => ResourceKind::TextureView,
+            ResourceId::Sampler(_) => ResourceKind::Sampler,
+            ResourceId::Dimension(_) => ResourceKind::Dimension,
+            ResourceId::Camera(_) => ResourceKind::Camera,
+            ResourceId::Model(_) => ResourceKind::Model,
+            ResourceId::RenderPipeline(_) => ResourceKind::RenderPipeline,
+            ResourceId::RenderPass(_) => ResourceKind::RenderPass,
+            ResourceId::Presentation(_) => ResourceKind::Presentation,
+            ResourceId::ComputePass(_) => ResourceKind::ComputePass,
+            ResourceId::CopyPass(_) => ResourceKind::CopyPass,
+        }
+    }
 }
 
 pub trait ProjectResource {
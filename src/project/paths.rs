@@ -1,5 +1,7 @@
 use std::path::{Component, Path};
 
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
 use serde::{Deserialize, Serialize};
 
 use crate::error::{AppError, AppResult};
@@ -140,6 +142,18 @@ impl FilePath {
     pub fn is_project_json(&self) -> bool {
         self == &FilePath::project_json()
     }
+
+    /// The project's preview image, shown by the featured projects list.
+    pub fn is_thumbnail(&self) -> bool {
+        self.segments() == ["thumbnail.png"]
+    }
+}
+
+/// Whether the file is an image the app can decode, going by its extension.
+pub fn is_image_file(path: &FilePath) -> bool {
+    path.extension()
+        .and_then(image::ImageFormat::from_extension)
+        .is_some_and(|format| format.can_read() && format.reading_enabled())
 }
 
 fn normalize_segment(segment: String) -> AppResult<String> {
@@ -188,3 +202,17 @@ impl<'de> Deserialize<'de> for FilePath {
         Self::from_str(&s).map_err(serde::de::Error::custom)
     }
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+impl JsonSchema for FilePath {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "FilePath".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A path relative to the project folder, with `/` between segments.",
+        })
+    }
+}
@@ -0,0 +1,218 @@
+//! Double-buffered ("ping-pong") textures.
+//!
+//! A double-buffered texture owns two GPU textures, its front and its back,
+//! and swaps them between frames. Everything built on top of one (texture
+//! views, bind groups, render bundles, blits) is built once per arrangement of
+//! the textures it depends on, so following a swap only picks another prebuilt
+//! variant and never rebuilds anything.
+
+use crate::{
+    error::{AppError, AppResult},
+    project::{
+        Project, RuntimeProject, TextureId,
+        resource::{presentation::PassStep, texture::SwapTrigger},
+    },
+};
+
+/// The most double-buffered textures one runtime object can depend on. Each
+/// one doubles the number of variants built up front.
+pub const MAX_SWAPPED_TEXTURES: usize = 4;
+
+/// The double-buffered textures a variant is built with swapped, relative to
+/// their current arrangement.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Swapped<'a> {
+    textures: &'a [TextureId],
+    mask: usize,
+}
+
+impl Swapped<'_> {
+    pub fn contains(&self, texture: TextureId) -> bool {
+        self.textures
+            .iter()
+            .position(|id| *id == texture)
+            .is_some_and(|index| self.mask & (1 << index) != 0)
+    }
+}
+
+/// A runtime object built once per arrangement of the double-buffered textures
+/// it depends on.
+pub struct SwapVariants<T> {
+    textures: Vec<TextureId>,
+    variants: Vec<T>,
+    current: usize,
+}
+
+impl<T> SwapVariants<T> {
+    /// Builds one variant per arrangement of `dependencies`, the first one
+    /// being their current arrangement.
+    pub fn new(
+        dependencies: impl IntoIterator<Item = TextureId>,
+        mut build: impl FnMut(Swapped) -> T,
+    ) -> AppResult<Self> {
+        let textures = unique_textures(dependencies)?;
+        let variants = (0..1 << textures.len())
+            .map(|mask| {
+                build(Swapped {
+                    textures: &textures,
+                    mask,
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            textures,
+            variants,
+            current: 0,
+        })
+    }
+
+    /// Like [`Self::new`], but returns `Ok(None)` as soon as `build` does,
+    /// i.e. a dependency is still pending.
+    pub fn build(
+        dependencies: impl IntoIterator<Item = TextureId>,
+        mut build: impl FnMut(Swapped) -> AppResult<Option<T>>,
+    ) -> AppResult<Option<Self>> {
+        let textures = unique_textures(dependencies)?;
+        let mut variants = Vec::with_capacity(1 << textures.len());
+        for mask in 0..1 << textures.len() {
+            let swapped = Swapped {
+                textures: &textures,
+                mask,
+            };
+            let Some(variant) = build(swapped)? else {
+                return Ok(None);
+            };
+            variants.push(variant);
+        }
+
+        Ok(Some(Self {
+            textures,
+            variants,
+            current: 0,
+        }))
+    }
+
+    /// The variant for the textures' current arrangement.
+    pub fn current(&self) -> &T {
+        &self.variants[self.current]
+    }
+
+    /// The variant for the arrangement with `swapped` swapped.
+    pub fn get(&self, swapped: Swapped) -> &T {
+        let mask = self
+            .textures
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| swapped.contains(**id))
+            .fold(0, |mask, (index, _)| mask | 1 << index);
+        &self.variants[self.current ^ mask]
+    }
+
+    /// The double-buffered textures this object depends on.
+    pub fn textures(&self) -> &[TextureId] {
+        &self.textures
+    }
+
+    /// Follows a swap of `texture`. Does nothing if this object doesn't depend
+    /// on it.
+    pub fn swap(&mut self, texture: TextureId) {
+        if let Some(index) = self.textures.iter().position(|id| *id == texture) {
+            self.current ^= 1 << index;
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.variants.iter()
+    }
+}
+
+fn unique_textures(dependencies: impl IntoIterator<Item = TextureId>) -> AppResult<Vec<TextureId>> {
+    let mut textures = vec![];
+    for id in dependencies {
+        if !textures.contains(&id) {
+            textures.push(id);
+        }
+    }
+    match textures.len() <= MAX_SWAPPED_TEXTURES {
+        true => Ok(textures),
+        false => Err(AppError::SwappedTextureLimitExceeded {
+            count: textures.len(),
+            max: MAX_SWAPPED_TEXTURES,
+        }),
+    }
+}
+
+impl RuntimeProject {
+    /// Swaps every double-buffered texture whose trigger fired this frame and
+    /// returns them. `dispatched` lists the steps that ran this frame.
+    ///
+    /// Call it once the frame is submitted: within a frame, front and back
+    /// stay put.
+    pub fn swap_textures(&mut self, project: &Project, dispatched: &[PassStep]) -> Vec<TextureId> {
+        let due: Vec<TextureId> = project
+            .textures
+            .list()
+            .filter(|(_, texture)| match texture.ping_pong() {
+                Some(SwapTrigger::EveryFrame) => true,
+                Some(SwapTrigger::AfterStep(Some(step))) => dispatched.contains(&step),
+                Some(SwapTrigger::AfterStep(None)) | None => false,
+            })
+            .map(|(id, _)| id)
+            .collect();
+
+        let mut swapped = vec![];
+        for id in due {
+            let Some(texture) = self.textures.get_init_mut(id) else {
+                continue; // still being created: there's nothing to swap yet
+            };
+            texture.swap();
+            for (_, runtime) in self.texture_views.iter_init_mut() {
+                runtime.swap(id);
+            }
+            for (_, runtime) in self.bind_groups.iter_init_mut() {
+                runtime.swap(id);
+            }
+            for (_, runtime) in self.render_passes.iter_init_mut() {
+                runtime.swap(id);
+            }
+            for (_, runtime) in self.copy_passes.iter_init_mut() {
+                runtime.swap(id);
+            }
+            swapped.push(id);
+        }
+        swapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use slotmap::SlotMap;
+
+    use super::*;
+
+    #[test]
+    fn swaps_pick_the_matching_variant() {
+        let mut ids: SlotMap<TextureId, ()> = SlotMap::with_key();
+        let (a, b) = (ids.insert(()), ids.insert(()));
+
+        // Each variant records which textures it was built swapped.
+        let mut variants = SwapVariants::new([a, b, a], |swapped| {
+            (swapped.contains(a), swapped.contains(b))
+        })
+        .unwrap();
+        assert_eq!(variants.textures(), [a, b]);
+        assert_eq!(*variants.current(), (false, false));
+
+        variants.swap(b);
+        assert_eq!(*variants.current(), (false, true));
+
+        // A dependent built now sees b's current arrangement as unswapped.
+        let nested = SwapVariants::new([a], |swapped| *variants.get(swapped)).unwrap();
+        assert_eq!(*nested.current(), (false, true));
+        assert_eq!(
+            nested.iter().collect::<Vec<_>>(),
+            [&(false, true), &(true, true)]
+        );
+    }
+}
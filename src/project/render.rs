@@ -1,37 +1,116 @@
-use std::task::Poll;
+use std::{collections::HashSet, task::Poll};
 
 use slotmap::SecondaryMap;
 
 use crate::{
     error::{AppError, AppResult, RequiredFieldExt},
     project::{
-        ComputePassId, ProjectResource, ProjectRevisionSnapshot, RuntimeProject,
+        ComputePassId, CopyPassId, ProjectResource, ProjectRevisionSnapshot, RenderPassId,
+        RuntimeProject, TextureId,
+        frame_capture::CaptureRecorder,
+        render_graph::{GraphTexture, RenderGraph, StepAccess},
         resource::{
             bindgroup::BindGroup,
-            compute_pass::{ComputePass, DispatchPolicy},
+            compute_pass::ComputePass,
+            copy_pass::CopyPass,
             dimension::Dimension,
-            presentation::Presentation,
-            render_pass::{RenderPass, RenderPassRuntime},
+            dispatch_policy::DispatchPolicy,
+            presentation::{PassStep, Presentation},
+            render_pass::{DrawCommand, RenderPass, RenderPassRuntime},
+            render_pipeline::RenderPipeline,
+            texture::Texture,
             texture_view::TextureView,
+            uniform::Uniform,
         },
         storage::{RuntimeStorage, Storage},
         sync::SyncTracker,
     },
-    utils::async_job::AsyncJob,
+    utils::{async_job::AsyncJob, wgpu_utils::create_command_encoder},
 };
 
+/// A frame's command buffers, one per scheduled step.
+///
+/// A wgpu validation error invalidates the whole command buffer it was encoded
+/// into. Giving every step its own buffer, submitted on its own, keeps an
+/// invalid compute pass from discarding the renders of the same frame, and an
+/// invalid render from discarding the compute work an `OnChange` pass only
+/// does once.
+pub struct FrameEncoder<'a> {
+    device: &'a wgpu::Device,
+    command_buffers: Vec<wgpu::CommandBuffer>,
+}
+
+impl<'a> FrameEncoder<'a> {
+    pub fn new(device: &'a wgpu::Device) -> Self {
+        Self {
+            device,
+            command_buffers: vec![],
+        }
+    }
+
+    /// Encodes `encode` into a command buffer of its own.
+    pub fn encode<R>(
+        &mut self,
+        label: &str,
+        encode: impl FnOnce(&mut wgpu::CommandEncoder) -> R,
+    ) -> R {
+        let mut encoder = create_command_encoder(self.device, label);
+        let result = encode(&mut encoder);
+        self.command_buffers.push(encoder.finish());
+        result
+    }
+
+    /// Submits the command buffers in the order they were encoded, each in a
+    /// submission of its own so an invalid one can't take the others with it.
+    pub fn submit(self, queue: &wgpu::Queue) {
+        for command_buffer in self.command_buffers {
+            queue.submit([command_buffer]);
+        }
+    }
+}
+
 pub struct RenderContext<'a> {
     pub render_passes: &'a Storage<RenderPass>,
+    pub render_pipelines: &'a Storage<RenderPipeline>,
     pub runtime_render_passes: &'a RuntimeStorage<RenderPass>,
     pub runtime_texture_views: &'a RuntimeStorage<TextureView>,
+    pub render_accumulators: &'a mut SecondaryMap<RenderPassId, instant::Duration>,
+    pub owed_render_passes: &'a mut HashSet<RenderPassId>,
+    /// The schedule's read and write sets, which tell the textures earlier
+    /// steps write this frame.
+    pub graph: &'a RenderGraph,
+    /// Whether the render steps are encoded this frame. Render passes still
+    /// accrue the redraws they owe when they aren't.
+    pub should_render: bool,
+    pub tracker: &'a SyncTracker,
+    pub dt: instant::Duration,
 }
 
 pub struct ComputeDispatchContext<'a> {
     pub compute_passes: &'a Storage<ComputePass>,
     pub runtime_compute_passes: &'a mut RuntimeStorage<ComputePass>,
     pub runtime_bind_groups: &'a RuntimeStorage<BindGroup>,
+    pub runtime_uniforms: &'a RuntimeStorage<Uniform>,
     pub dimensions: &'a Storage<Dimension>,
     pub compute_accumulators: &'a mut SecondaryMap<ComputePassId, instant::Duration>,
+    pub requested_compute_passes: &'a mut HashSet<ComputePassId>,
+    pub tracker: &'a SyncTracker,
+    pub dt: instant::Duration,
+}
+
+pub struct CopyDispatchContext<'a> {
+    pub copy_passes: &'a Storage<CopyPass>,
+    pub runtime_copy_passes: &'a mut RuntimeStorage<CopyPass>,
+    pub texture_views: &'a Storage<TextureView>,
+    pub runtime_textures: &'a RuntimeStorage<Texture>,
+    pub runtime_texture_views: &'a RuntimeStorage<TextureView>,
+    pub runtime_uniforms: &'a RuntimeStorage<Uniform>,
+    pub copy_accumulators: &'a mut SecondaryMap<CopyPassId, instant::Duration>,
+    pub requested_copy_passes: &'a mut HashSet<CopyPassId>,
+    /// Textures written by the copies encoded this frame. The caller records
+    /// them as data changes once the frame's tracker is cleared, so their
+    /// consumers rerun next frame.
+    pub written_textures: &'a mut Vec<TextureId>,
     pub tracker: &'a SyncTracker,
     pub dt: instant::Duration,
 }
@@ -107,158 +186,392 @@ impl RuntimeProject {
     }
 }
 
+/// A render pass that runs this frame, with everything needed to encode it.
+type ResolvedRenderPass<'a> = (
+    RenderPassId,
+    &'a RenderPass,
+    &'a RenderPassRuntime,
+    RenderTargets<'a>,
+);
+
 impl Presentation {
-    /// Dispatches the scheduled compute passes in order, into `encoder`, deciding
-    /// per pass whether it runs this frame from its [`DispatchPolicy`].
+    /// Runs the schedule's enabled steps in order, each into its own command
+    /// buffer of `frame`.
+    ///
+    /// Each step runs when its dispatch policy says so, and every render step
+    /// that may run this frame is resolved before anything is encoded. If one of
+    /// them isn't ready (its bundle or a target texture view is still pending,
+    /// or it failed to record), or the frame isn't rendered, all render steps are
+    /// skipped for this frame and only the compute and copy steps are encoded.
+    /// The frame is therefore always safe to submit: compute work is never lost
+    /// to a render bailing out, and a half-rendered frame never reaches the
+    /// viewport, which keeps the previous frame instead of flickering the clear
+    /// color. A render pass that was due stays owed until it's drawn, including
+    /// on frames that aren't rendered.
     ///
-    /// The build step (pipeline creation in [`ComputePass`]'s `sync`) is separate;
-    /// this only emits the dispatches.
-    pub fn dispatch_computes(
+    /// A [`DispatchPolicy::OnChange`] render pass is due when its inputs'
+    /// data changed this frame, such as a uniform one of its bind groups holds,
+    /// and also when a step before it writes a texture it reads and runs this
+    /// frame.
+    ///
+    /// Every step encoded is pushed to `dispatched`, in order. With `capture`,
+    /// every enabled step is also recorded into it, with snapshots of the
+    /// textures it touches encoded around it.
+    ///
+    /// Returns `Ok(true)` if the render steps were encoded and `Ok(false)` if they
+    /// were skipped. `Err` is only returned for presentation-level problems, such
+    /// as a render pass id that no longer resolves to a resource; the compute
+    /// steps are still encoded in that case.
+    pub fn execute(
         &self,
-        encoder: &mut wgpu::CommandEncoder,
-        ctx: &mut ComputeDispatchContext<'_>,
-    ) {
-        for compute_pass_id in self.compute_passes() {
-            let id = *compute_pass_id;
+        frame: &mut FrameEncoder<'_>,
+        compute_ctx: &mut ComputeDispatchContext<'_>,
+        copy_ctx: &mut CopyDispatchContext<'_>,
+        render_ctx: &mut RenderContext<'_>,
+        mut capture: Option<&mut CaptureRecorder<'_>>,
+        dispatched: &mut Vec<PassStep>,
+    ) -> AppResult<bool> {
+        let (render_passes, result) = match self.resolve_render_passes(render_ctx) {
+            Ok(Some(render_passes)) => (render_passes, Ok(true)),
+            Ok(None) => (Vec::new(), Ok(false)),
+            Err(error) => (Vec::new(), Err(error)),
+        };
 
-            let Ok(compute_pass) = ctx.compute_passes.get(id) else {
-                continue; // dangling id in the schedule
-            };
+        let graph = render_ctx.graph;
+        // Textures written by the steps that ran so far this frame.
+        let mut written = HashSet::new();
+        for step in self.steps() {
+            if !self.is_enabled(*step) {
+                continue;
+            }
+            let access = graph.access(*step);
+            if let PassStep::Render(id) = *step
+                && access.is_some_and(|access| access.reads_any(&written))
+                && runs_on_change(render_ctx.render_passes, id)
+            {
+                render_ctx.owed_render_passes.insert(id);
+            }
 
-            let runtime = match ctx.runtime_compute_passes.get_init(id) {
-                Ok(Some(runtime)) => runtime,
-                Ok(None) | Err(_) => continue,
+            let label = match step {
+                PassStep::Compute(_) => "Compute Step Encoder",
+                PassStep::Render(_) => "Render Step Encoder",
+                PassStep::Copy(_) => "Copy Step Encoder",
             };
-
-            let should_dispatch = match compute_pass.dispatch_policy() {
-                DispatchPolicy::EveryFrame => true,
-                // `was_recreated` covers the frame the pipeline was first built or
-                // rebuilt; `inputs_changed` covers data-only changes to its inputs.
-                DispatchPolicy::OnChange => {
-                    ctx.tracker.was_recreated(id) || compute_pass.inputs_changed(ctx.tracker)
+            let ran = frame.encode(label, |encoder| {
+                if let Some(capture) = capture.as_deref_mut() {
+                    capture.begin_step(encoder, *step);
                 }
-                DispatchPolicy::Periodic { interval } => {
-                    let accumulated = ctx
-                        .compute_accumulators
-                        .entry(id)
-                        .expect("compute pass id is valid")
-                        .or_insert(instant::Duration::ZERO);
-                    *accumulated += ctx.dt;
-                    if *accumulated >= interval {
-                        // Subtract one interval (rather than reset to zero) to keep
-                        // the average cadence accurate. Clamp so a long stall can't
-                        // build up a backlog of catch-up dispatches.
-                        *accumulated = (*accumulated - interval).min(interval);
-                        true
-                    } else {
-                        false
+                let mut commands: &[DrawCommand] = &[];
+                let ran = match *step {
+                    PassStep::Compute(id) => dispatch_compute(encoder, compute_ctx, id),
+                    PassStep::Copy(id) => dispatch_copy(encoder, copy_ctx, id),
+                    PassStep::Render(id) => {
+                        let resolved = render_passes
+                            .iter()
+                            .find(|(resolved_id, ..)| *resolved_id == id);
+                        match resolved {
+                            Some((_, render_pass, runtime, targets))
+                                if render_ctx.owed_render_passes.remove(&id) =>
+                            {
+                                render_pass.execute(encoder, runtime, *targets);
+                                commands = runtime.commands();
+                                true
+                            }
+                            _ => false, // not due this frame
+                        }
                     }
+                };
+                if let Some(capture) = capture.as_deref_mut() {
+                    capture.end_step(encoder, *step, ran, commands);
                 }
-            };
-
-            if should_dispatch {
-                // A wgpu validation error during encoding is caught by the frame-wide
-                // error scope in the app's render loop. An `Err` here is a Rust-side
-                // failure (e.g. a bound resource has errored); record it on the pass's
-                // own runtime cell so it surfaces like any other resource error. The
-                // error state only changes on an actual dispatch (or a rebuild), not
-                // every frame, since dispatches don't happen every frame.
-                let encode =
-                    compute_pass.encode(encoder, runtime, ctx.runtime_bind_groups, ctx.dimensions);
-                if let Err(error) = encode {
-                    ctx.runtime_compute_passes.mark_errored(id, error);
-                }
+                ran
+            });
+            if ran {
+                dispatched.push(*step);
+                written.extend(access.into_iter().flat_map(step_writes));
             }
         }
+
+        result
     }
 
-    /// Begins every render pass and replays its recorded bundle into `encoder`.
-    ///
-    /// The draws themselves live in each pass's [`RenderPassRuntime`], recorded once
-    /// during the sync step.
-    ///
-    /// Returns `Ok(false)` as soon as a pass bails out, either because one of its target
-    /// texture views or its own bundle is still pending, or because recording that
-    /// bundle failed. `Err` is only returned for presentation-level problems, such
-    /// as a render pass id that no longer resolves to a resource.
-    ///
-    /// The caller should drop the encoder without finishing it whenever this returns `Ok(false)`,
-    /// so the half-encoded passes never reach the GPU and the viewport keeps the previous frame
-    /// instead of flickering the clear color.
-    pub fn render(
+    /// Records the redraws the render passes' dispatch policies ask for, then
+    /// resolves every enabled render pass that may run this frame to its
+    /// recorded bundle and targets, in schedule order: the ones owed a redraw,
+    /// and the [`DispatchPolicy::OnChange`] ones reading what an earlier step
+    /// may write. Returns `Ok(None)` if the frame isn't rendered or any of them
+    /// isn't ready yet.
+    fn resolve_render_passes<'a>(
         &self,
-        encoder: &mut wgpu::CommandEncoder,
-        render_ctx: &RenderContext<'_>,
-    ) -> AppResult<bool> {
-        for render_pass_id in self.render_passes() {
-            let render_pass = render_ctx.render_passes.get(*render_pass_id)?;
+        render_ctx: &mut RenderContext<'a>,
+    ) -> AppResult<Option<Vec<ResolvedRenderPass<'a>>>> {
+        let render_passes = render_ctx.render_passes;
+        let runtime_render_passes = render_ctx.runtime_render_passes;
+        let runtime_texture_views = render_ctx.runtime_texture_views;
+
+        let mut due = vec![];
+        // Textures the enabled steps so far write if they run this frame.
+        let mut may_write = HashSet::new();
+        for step in self.steps() {
+            let enabled = self.is_enabled(*step);
+            let access = render_ctx.graph.access(*step);
+
+            if let PassStep::Render(render_pass_id) = *step {
+                let render_pass = render_passes.get(render_pass_id)?;
+
+                // Disabled passes still accrue what they owe, so re-enabling one
+                // redraws it if something changed in the meantime.
+                let changed = render_ctx.tracker.was_data_changed(render_pass_id)
+                    || render_pass.inputs_changed(render_ctx.tracker, render_ctx.render_pipelines);
+                let accumulated = render_ctx
+                    .render_accumulators
+                    .entry(render_pass_id)
+                    .expect("render pass id is valid")
+                    .or_insert(instant::Duration::ZERO);
+                if render_pass
+                    .dispatch_policy()
+                    .should_run(changed, accumulated, render_ctx.dt)
+                {
+                    render_ctx.owed_render_passes.insert(render_pass_id);
+                }
+
+                let may_change = access.is_some_and(|access| access.reads_any(&may_write))
+                    && runs_on_change(render_passes, render_pass_id);
+                let may_run = render_ctx.owed_render_passes.contains(&render_pass_id) || may_change;
+                if enabled && may_run && !due.iter().any(|(id, _)| *id == render_pass_id) {
+                    due.push((render_pass_id, render_pass));
+                }
+            }
+
+            if enabled {
+                may_write.extend(access.into_iter().flat_map(step_writes));
+            }
+        }
 
-            let runtime = match render_ctx.runtime_render_passes.get_init(*render_pass_id) {
+        if !render_ctx.should_render {
+            return Ok(None);
+        }
+
+        let mut resolved = vec![];
+        for (render_pass_id, render_pass) in due {
+            let runtime = match runtime_render_passes.get_init(render_pass_id) {
                 Ok(Some(runtime)) => runtime,
                 // the bundle is either still being recorded or failed to record,
-                // so avoid rendering this pass.
-                Ok(None) | Err(_) => return Ok(false),
+                // so avoid rendering this frame.
+                Ok(None) | Err(_) => return Ok(None),
+            };
+
+            let Some(targets) = render_pass.resolve_targets(runtime_texture_views)? else {
+                return Ok(None); // a target texture view is still pending
             };
 
-            if !render_pass.execute(encoder, runtime, render_ctx.runtime_texture_views)? {
-                return Ok(false); // a target texture view is still pending
+            resolved.push((render_pass_id, render_pass, runtime, targets));
+        }
+
+        Ok(Some(resolved))
+    }
+}
+
+fn step_writes(access: &StepAccess) -> impl Iterator<Item = GraphTexture> + '_ {
+    access.writes.iter().map(|(texture, _)| *texture)
+}
+
+/// Whether the render pass redraws when what it reads changes.
+fn runs_on_change(render_passes: &Storage<RenderPass>, id: RenderPassId) -> bool {
+    render_passes
+        .get(id)
+        .is_ok_and(|render_pass| render_pass.dispatch_policy() == DispatchPolicy::OnChange)
+}
+
+/// Dispatches one scheduled compute pass into `encoder`, if its dispatch policy
+/// says it runs this frame or a dispatch was requested. Returns whether it was
+/// dispatched.
+///
+/// The build step (pipeline creation in [`ComputePass`]'s `sync`) is separate;
+/// this only emits the dispatch.
+fn dispatch_compute(
+    encoder: &mut wgpu::CommandEncoder,
+    ctx: &mut ComputeDispatchContext<'_>,
+    id: ComputePassId,
+) -> bool {
+    let Ok(compute_pass) = ctx.compute_passes.get(id) else {
+        return false; // dangling id in the schedule
+    };
+
+    let runtime = match ctx.runtime_compute_passes.get_init(id) {
+        Ok(Some(runtime)) => runtime,
+        Ok(None) | Err(_) => return false,
+    };
+
+    // `was_recreated` covers the frame the pipeline was first built or
+    // rebuilt; `inputs_changed` covers data-only changes to its inputs.
+    let changed = ctx.tracker.was_recreated(id) || compute_pass.inputs_changed(ctx.tracker);
+    let accumulated = ctx
+        .compute_accumulators
+        .entry(id)
+        .expect("compute pass id is valid")
+        .or_insert(instant::Duration::ZERO);
+    let should_dispatch = compute_pass
+        .dispatch_policy()
+        .should_run(changed, accumulated, ctx.dt);
+
+    if !should_dispatch && !ctx.requested_compute_passes.contains(&id) {
+        return false;
+    }
+
+    // A wgpu validation error during encoding is caught by the frame-wide
+    // error scope in the app's render loop. An `Err` here is a Rust-side
+    // failure (e.g. a bound resource has errored); record it on the pass's
+    // own runtime cell so it surfaces like any other resource error. The
+    // error state only changes on an actual dispatch (or a rebuild), not
+    // every frame, since dispatches don't happen every frame.
+    let encode = compute_pass.encode(
+        encoder,
+        runtime,
+        ctx.runtime_bind_groups,
+        ctx.runtime_uniforms,
+        ctx.dimensions,
+    );
+    match encode {
+        Ok(encoded) => {
+            // A requested dispatch that couldn't be encoded yet stays requested.
+            if encoded {
+                ctx.requested_compute_passes.remove(&id);
             }
+            encoded
+        }
+        Err(error) => {
+            ctx.requested_compute_passes.remove(&id);
+            ctx.runtime_compute_passes.mark_errored(id, error);
+            false
         }
+    }
+}
+
+/// Encodes one scheduled copy pass into `encoder`, if its dispatch policy says
+/// it runs this frame or a copy was requested, and records the texture it
+/// wrote. Returns whether it was encoded.
+fn dispatch_copy(
+    encoder: &mut wgpu::CommandEncoder,
+    ctx: &mut CopyDispatchContext<'_>,
+    id: CopyPassId,
+) -> bool {
+    let Ok(copy_pass) = ctx.copy_passes.get(id) else {
+        return false; // dangling id in the schedule
+    };
+
+    let runtime = match ctx.runtime_copy_passes.get_init(id) {
+        Ok(Some(runtime)) => runtime,
+        Ok(None) | Err(_) => return false,
+    };
 
-        Ok(true)
+    let changed = ctx.tracker.was_recreated(id) || copy_pass.inputs_changed(ctx.tracker);
+    let accumulated = ctx
+        .copy_accumulators
+        .entry(id)
+        .expect("copy pass id is valid")
+        .or_insert(instant::Duration::ZERO);
+    let should_copy = copy_pass
+        .dispatch_policy()
+        .should_run(changed, accumulated, ctx.dt);
+    if !should_copy && !ctx.requested_copy_passes.contains(&id) {
+        return false;
+    }
+
+    let encode = copy_pass.encode(
+        encoder,
+        runtime,
+        ctx.runtime_textures,
+        ctx.runtime_texture_views,
+        ctx.runtime_uniforms,
+    );
+    match encode {
+        Ok(true) => {
+            ctx.requested_copy_passes.remove(&id);
+            ctx.written_textures
+                .extend(copy_pass.destination_texture(ctx.texture_views));
+            true
+        }
+        Ok(false) => false,
+        // Like compute passes, a Rust-side failure lands on the pass's own cell.
+        Err(error) => {
+            ctx.requested_copy_passes.remove(&id);
+            ctx.runtime_copy_passes.mark_errored(id, error);
+            false
+        }
     }
 }
 
+/// The texture views a [`RenderPass`] draws into, resolved for one frame.
+#[derive(Clone, Copy)]
+pub struct RenderTargets<'a> {
+    color: &'a wgpu::TextureView,
+    depth: Option<&'a wgpu::TextureView>,
+}
+
 impl RenderPass {
-    /// Begins this pass on `encoder` and executes its recorded bundle in it.
+    /// Resolves the texture views this pass draws into.
     ///
-    /// Returns `Ok(true)` once the pass is encoded, or `Ok(false)` if it bailed out
-    /// because a target texture view is still pending.
-    pub fn execute(
+    /// Returns `Ok(None)` if a target texture view is still pending.
+    pub fn resolve_targets<'a>(
         &self,
-        encoder: &mut wgpu::CommandEncoder,
-        runtime: &RenderPassRuntime,
-        runtime_texture_views: &RuntimeStorage<TextureView>,
-    ) -> AppResult<bool> {
-        let color_target = self.target();
-        let target_texture_id = color_target
+        runtime_texture_views: &'a RuntimeStorage<TextureView>,
+    ) -> AppResult<Option<RenderTargets<'a>>> {
+        let color_texture_id = self
+            .target()
             .texture_view_id()
             .ok_or_uninit_field("Color Target Texture")?;
 
-        let Some(target_texture_view) = runtime_texture_views.get_init(target_texture_id)? else {
-            return Ok(false); // pending: target texture view not ready
+        let Some(color) = runtime_texture_views.get_init(color_texture_id)? else {
+            return Ok(None); // pending: target texture view not ready
         };
 
-        let view = target_texture_view.inner();
-
-        let depth_stencil_attachment = match self.depth_target() {
+        let depth = match self.depth_target() {
             Some(depth_target) => {
-                // TODO: solve this duplicated code from above
                 let depth_texture_id = depth_target
                     .texture_view_id()
                     .ok_or_uninit_field("Depth Target Texture")?;
 
-                let Some(depth_texture_view) = runtime_texture_views.get_init(depth_texture_id)?
-                else {
-                    return Ok(false); // pending: depth texture view not ready
+                let Some(depth) = runtime_texture_views.get_init(depth_texture_id)? else {
+                    return Ok(None); // pending: depth texture view not ready
                 };
 
-                Some(wgpu::RenderPassDepthStencilAttachment {
-                    view: depth_texture_view.inner(),
-                    depth_ops: Some(wgpu::Operations {
-                        load: depth_target.load_operation().into(),
-                        store: wgpu::StoreOp::Store,
-                    }),
-                    stencil_ops: None,
-                })
+                Some(depth.inner())
             }
             None => None,
         };
 
+        Ok(Some(RenderTargets {
+            color: color.inner(),
+            depth,
+        }))
+    }
+
+    /// Begins this pass on `encoder` and executes its recorded bundle in it.
+    pub fn execute(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        runtime: &RenderPassRuntime,
+        targets: RenderTargets<'_>,
+    ) {
+        let color_target = self.target();
+
+        let depth_stencil_attachment = match (self.depth_target(), targets.depth) {
+            (Some(depth_target), Some(view)) => Some(wgpu::RenderPassDepthStencilAttachment {
+                view,
+                depth_ops: Some(wgpu::Operations {
+                    load: depth_target.load_operation().into(),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            _ => None,
+        };
+
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some(self.label()),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view,
+                view: targets.color,
                 ops: wgpu::Operations {
                     load: color_target.load_operation().into(),
                     store: wgpu::StoreOp::Store,
@@ -273,7 +586,5 @@ impl RenderPass {
         });
 
         render_pass.execute_bundles([runtime.bundle()]);
-
-        Ok(true)
     }
 }
@@ -0,0 +1,770 @@
+//! Static analysis of the presentation schedule.
+//!
+//! Every step's bind groups and attachments are resolved down to the textures
+//! they touch, which gives each step a read set and a write set. From those,
+//! [`RenderGraph`] derives the order the data dependencies imply and flags the
+//! hazards a hand-ordered schedule silently gets wrong.
+//!
+//! Only textures are tracked. The only buffers bind groups hold are uniforms,
+//! which the app writes before the frame and no step writes, so they never
+//! order steps.
+
+use std::collections::HashSet;
+
+use crate::{
+    error::AppError,
+    project::{
+        BindGroupId, PresentationId, Project, ProjectRevisionSnapshot, ResourceId, TextureId,
+        TextureViewId,
+        resource::{
+            bindgroup::BindGroupResource,
+            copy_pass::CopyMode,
+            presentation::PassStep,
+            render_pipeline::{BindGroupTarget, RenderDrawStrategy},
+            texture_view::BufferSide,
+        },
+    },
+};
+
+/// A texture as the schedule sees it. Swaps only happen between frames, so
+/// within one the front and back of a double-buffered texture are two
+/// different textures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GraphTexture {
+    pub id: TextureId,
+    pub side: BufferSide,
+}
+
+impl From<TextureId> for GraphTexture {
+    fn from(id: TextureId) -> Self {
+        Self {
+            id,
+            side: BufferSide::Front,
+        }
+    }
+}
+
+/// How a step touches a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessKind {
+    /// Bound as a sampled texture.
+    Sampled,
+    /// Bound as a storage texture.
+    Storage,
+    /// Rendered into as the color target.
+    ColorAttachment,
+    /// Rendered into as the depth target.
+    DepthAttachment,
+    /// Copied from or into by a copy pass.
+    Copy,
+}
+
+/// The textures one schedule step reads and writes, and the bind groups they
+/// are read through.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepAccess {
+    pub step: PassStep,
+    pub bind_groups: Vec<BindGroupId>,
+    pub reads: Vec<(GraphTexture, AccessKind)>,
+    pub writes: Vec<(GraphTexture, AccessKind)>,
+    /// Textures one iteration of an alternating compute pass samples and the
+    /// next one writes. These aren't feedback loops.
+    pub alternated: Vec<GraphTexture>,
+}
+
+impl StepAccess {
+    /// Whether the step reads any of `textures`.
+    pub fn reads_any(&self, textures: &HashSet<GraphTexture>) -> bool {
+        self.reads.iter().any(|(id, _)| textures.contains(id))
+    }
+
+    fn reads(&self, texture: GraphTexture) -> bool {
+        self.reads.iter().any(|(id, _)| *id == texture)
+    }
+
+    fn writes(&self, texture: GraphTexture) -> bool {
+        self.writes.iter().any(|(id, _)| *id == texture)
+    }
+}
+
+/// A problem with the schedule that wgpu won't report.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Hazard {
+    /// The step samples a texture it also writes to.
+    FeedbackLoop {
+        step: PassStep,
+        texture: GraphTexture,
+    },
+    /// The step reads a texture before any step writes it this frame, although
+    /// the writer could run first.
+    ReadBeforeWrite {
+        step: PassStep,
+        texture: GraphTexture,
+        writer: PassStep,
+    },
+    /// The step writes a texture that no step or viewport reads.
+    UnusedOutput {
+        step: PassStep,
+        texture: GraphTexture,
+    },
+}
+
+/// The read/write sets of every step in the schedule, in schedule order.
+#[derive(Debug, Clone, Default)]
+pub struct RenderGraph {
+    steps: Vec<StepAccess>,
+    /// Textures read outside the schedule, by viewports.
+    displayed: HashSet<GraphTexture>,
+}
+
+impl RenderGraph {
+    pub fn new(steps: Vec<StepAccess>, displayed: HashSet<GraphTexture>) -> Self {
+        Self { steps, displayed }
+    }
+
+    pub fn steps(&self) -> &[StepAccess] {
+        &self.steps
+    }
+
+    /// What `step` reads and writes, or `None` if it points at a deleted pass.
+    pub fn access(&self, step: PassStep) -> Option<&StepAccess> {
+        self.steps.iter().find(|access| access.step == step)
+    }
+
+    /// Resolves the project's schedule. Steps pointing at deleted passes are
+    /// left out.
+    pub fn build(project: &Project) -> Self {
+        let steps = project
+            .presentation
+            .steps()
+            .iter()
+            .filter_map(|step| step_access(project, *step))
+            .collect();
+
+        let displayed = project
+            .viewports
+            .list()
+            .filter_map(|(_, viewport)| viewport.texture_view_id())
+            .filter_map(|id| view_texture(project, id))
+            .collect();
+
+        Self::new(steps, displayed)
+    }
+
+    /// Whether step `from` has to run before step `to`: `to` reads what `from`
+    /// writes, or both write the same texture and `from` comes first.
+    fn depends(&self, from: usize, to: usize) -> bool {
+        let (from_step, to_step) = (&self.steps[from], &self.steps[to]);
+        from_step
+            .writes
+            .iter()
+            .any(|(texture, _)| to_step.reads(*texture) || (from < to && to_step.writes(*texture)))
+    }
+
+    /// Whether step `to` can be reached from step `from` through dependencies.
+    fn reaches(&self, from: usize, to: usize) -> bool {
+        let mut visited = vec![false; self.steps.len()];
+        let mut stack = vec![from];
+        while let Some(index) = stack.pop() {
+            if index == to {
+                return true;
+            }
+            if std::mem::replace(&mut visited[index], true) {
+                continue;
+            }
+            stack.extend(
+                (0..self.steps.len()).filter(|next| *next != index && self.depends(index, *next)),
+            );
+        }
+        false
+    }
+
+    /// A topological order of the steps, so every writer runs before the steps
+    /// that read it. Ties, and cycles (a feedback loop across frames, like a
+    /// ping-pong simulation), keep the current relative order.
+    pub fn suggested_order(&self) -> Vec<PassStep> {
+        let count = self.steps.len();
+        let mut placed = vec![false; count];
+        let mut order = Vec::with_capacity(count);
+
+        while order.len() < count {
+            let ready = (0..count).find(|&index| {
+                !placed[index]
+                    && (0..count)
+                        .all(|other| placed[other] || other == index || !self.depends(other, index))
+            });
+            // No step is free of unplaced dependencies: break the cycle at the
+            // earliest remaining step.
+            let next = ready.unwrap_or_else(|| (0..count).find(|&index| !placed[index]).unwrap());
+            placed[next] = true;
+            order.push(self.steps[next].step);
+        }
+
+        order
+    }
+
+    /// [`RenderGraph::suggested_order`] laid over `schedule`, the schedule the
+    /// graph was built from. Steps the graph left out, like ones pointing at
+    /// deleted passes, keep their place instead of being dropped.
+    pub fn suggested_schedule(&self, schedule: &[PassStep]) -> Vec<PassStep> {
+        let mut suggested = self.suggested_order().into_iter();
+        schedule
+            .iter()
+            .map(
+                |step| match self.steps.iter().any(|access| access.step == *step) {
+                    true => suggested.next().unwrap_or(*step),
+                    false => *step,
+                },
+            )
+            .collect()
+    }
+
+    pub fn hazards(&self) -> Vec<Hazard> {
+        let mut hazards = vec![];
+
+        for (index, access) in self.steps.iter().enumerate() {
+            for (texture, kind) in &access.reads {
+                if *kind == AccessKind::Sampled
+                    && access.writes(*texture)
+                    && !access.alternated.contains(texture)
+                {
+                    hazards.push(Hazard::FeedbackLoop {
+                        step: access.step,
+                        texture: *texture,
+                    });
+                    continue;
+                }
+
+                let written_before = self.steps[..index]
+                    .iter()
+                    .any(|earlier| earlier.writes(*texture));
+                if written_before || access.writes(*texture) {
+                    continue;
+                }
+
+                // A later writer that itself depends on this step closes a loop
+                // across frames, so reading last frame's data is intended.
+                let writer = (index + 1..self.steps.len()).find(|&later| {
+                    self.steps[later].writes(*texture) && !self.reaches(index, later)
+                });
+                if let Some(writer) = writer {
+                    hazards.push(Hazard::ReadBeforeWrite {
+                        step: access.step,
+                        texture: *texture,
+                        writer: self.steps[writer].step,
+                    });
+                }
+            }
+
+            let is_read = |texture: GraphTexture| {
+                self.displayed.contains(&texture)
+                    || self.steps.iter().any(|other| other.reads(texture))
+            };
+            for (texture, kind) in &access.writes {
+                // Depth targets are routinely scratch buffers, only used for testing.
+                if *kind == AccessKind::DepthAttachment {
+                    continue;
+                }
+                // A double-buffered texture's back is read as its front after the swap.
+                let front = GraphTexture {
+                    side: BufferSide::Front,
+                    ..*texture
+                };
+                if !is_read(*texture) && !is_read(front) {
+                    hazards.push(Hazard::UnusedOutput {
+                        step: access.step,
+                        texture: *texture,
+                    });
+                }
+            }
+        }
+
+        hazards
+    }
+
+    /// The schedule's diagnostics, keyed by the resource they belong to. They
+    /// are advisory: the schedule still runs, so the error panel lists them as
+    /// warnings.
+    pub fn diagnostics(&self, project: &Project) -> Vec<(ResourceId, AppError)> {
+        let label = |id: ResourceId| project.label(id).unwrap_or("Unknown").to_string();
+        let texture = |texture: GraphTexture| {
+            let label = label(texture.id.into());
+            match texture.side {
+                BufferSide::Front => label,
+                BufferSide::Back => format!("{label} (Back)"),
+            }
+        };
+
+        let mut diagnostics: Vec<(ResourceId, AppError)> = self
+            .hazards()
+            .into_iter()
+            .map(|hazard| match hazard {
+                Hazard::FeedbackLoop { step, texture: id } => (
+                    step.into(),
+                    AppError::RenderGraphFeedbackLoop {
+                        texture: texture(id),
+                    },
+                ),
+                Hazard::ReadBeforeWrite {
+                    step,
+                    texture: id,
+                    writer,
+                } => (
+                    step.into(),
+                    AppError::RenderGraphReadBeforeWrite {
+                        texture: texture(id),
+                        writer: label(writer.into()),
+                    },
+                ),
+                Hazard::UnusedOutput { step, texture: id } => (
+                    step.into(),
+                    AppError::RenderGraphUnusedOutput {
+                        texture: texture(id),
+                    },
+                ),
+            })
+            .collect();
+
+        let current = project.presentation.steps();
+        let suggested = self.suggested_schedule(current);
+        if suggested != current {
+            diagnostics.push((
+                PresentationId.into(),
+                AppError::RenderGraphOrder {
+                    suggested: suggested.iter().map(|step| label((*step).into())).collect(),
+                    steps: suggested,
+                },
+            ));
+        }
+
+        diagnostics
+    }
+}
+
+/// The graph, diagnostics and suggested order of the last analyzed schedule.
+/// Only re-analyzed when the project changes.
+#[derive(Default)]
+pub struct RenderGraphReport {
+    snapshot: ProjectRevisionSnapshot,
+    graph: RenderGraph,
+    diagnostics: Vec<(ResourceId, AppError)>,
+    suggested_schedule: Vec<PassStep>,
+}
+
+impl RenderGraphReport {
+    pub fn refresh(&mut self, project: &Project) {
+        let snapshot = project.snapshot();
+        if snapshot == self.snapshot {
+            return;
+        }
+
+        self.graph = RenderGraph::build(project);
+        self.diagnostics = self.graph.diagnostics(project);
+        self.suggested_schedule = self.graph.suggested_schedule(project.presentation.steps());
+        self.snapshot = snapshot;
+    }
+
+    pub fn graph(&self) -> &RenderGraph {
+        &self.graph
+    }
+
+    /// [`RenderGraph::suggested_schedule`] of the analyzed schedule.
+    pub fn suggested_schedule(&self) -> &[PassStep] {
+        &self.suggested_schedule
+    }
+
+    pub fn diagnostics(&self) -> impl Iterator<Item = (ResourceId, &AppError)> {
+        self.diagnostics.iter().map(|(id, error)| (*id, error))
+    }
+}
+
+fn view_texture(project: &Project, id: TextureViewId) -> Option<GraphTexture> {
+    let view = project.texture_views.get(id).ok()?;
+    Some(GraphTexture {
+        id: view.texture_id()?,
+        side: view.side(),
+    })
+}
+
+fn step_access(project: &Project, step: PassStep) -> Option<StepAccess> {
+    let mut access = StepAccess {
+        step,
+        bind_groups: vec![],
+        reads: vec![],
+        writes: vec![],
+        alternated: vec![],
+    };
+
+    let bind_group_ids: Vec<BindGroupId> = match step {
+        PassStep::Compute(id) => {
+            let compute_pass = project.compute_passes.get(id).ok()?;
+            let iterations = compute_pass.iterations();
+            if let Some(alternate) = &iterations.alternate_bind_groups {
+                access.alternated =
+                    alternated_textures(project, compute_pass.bind_groups(), alternate);
+            }
+            compute_pass
+                .bind_groups()
+                .iter()
+                .copied()
+                .chain(iterations.alternate_bind_group_ids())
+                .collect()
+        }
+        PassStep::Render(id) => {
+            let render_pass = project.render_passes.get(id).ok()?;
+
+            let targets = [
+                (
+                    render_pass.target().texture_view_id(),
+                    AccessKind::ColorAttachment,
+                ),
+                (
+                    render_pass
+                        .depth_target()
+                        .and_then(|target| target.texture_view_id()),
+                    AccessKind::DepthAttachment,
+                ),
+            ];
+            for (view_id, kind) in targets {
+                if let Some(texture) = view_id.and_then(|id| view_texture(project, id)) {
+                    access.writes.push((texture, kind));
+                }
+            }
+
+            render_pass
+                .pipelines()
+                .iter()
+                .filter_map(|id| project.render_pipelines.get(*id).ok())
+                .flat_map(|pipeline| {
+                    let material_bind_groups = match pipeline.draw_strategy() {
+                        RenderDrawStrategy::Model {
+                            model_id: Some(model_id),
+                            ..
+                        } => project
+                            .models
+                            .get(*model_id)
+                            .map(|model| model.get_material_bind_group_ids())
+                            .unwrap_or_default(),
+                        _ => vec![],
+                    };
+                    pipeline
+                        .bind_groups()
+                        .iter()
+                        .flat_map(|target| match target {
+                            BindGroupTarget::Empty => vec![],
+                            BindGroupTarget::Static(id) => vec![*id],
+                            BindGroupTarget::ModelMaterial => material_bind_groups.clone(),
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        }
+        PassStep::Copy(id) => {
+            let copied = |id: Option<TextureId>| id.map(|id| (id.into(), AccessKind::Copy));
+            let (read, write) = match project.copy_passes.get(id).ok()?.mode() {
+                CopyMode::Texture {
+                    source,
+                    destination,
+                }
+                | CopyMode::Region {
+                    source,
+                    destination,
+                    ..
+                } => (copied(source), copied(destination)),
+                CopyMode::Buffer { destination, .. } => (None, copied(destination)),
+                CopyMode::Blit {
+                    source,
+                    destination,
+                    ..
+                } => (
+                    source
+                        .and_then(|id| view_texture(project, id))
+                        .map(|id| (id, AccessKind::Sampled)),
+                    destination
+                        .and_then(|id| view_texture(project, id))
+                        .map(|id| (id, AccessKind::ColorAttachment)),
+                ),
+            };
+            access.reads.extend(read);
+            access.writes.extend(write);
+            vec![]
+        }
+    };
+
+    for id in bind_group_ids {
+        if !access.bind_groups.contains(&id) {
+            access.bind_groups.push(id);
+        }
+    }
+
+    let (reads, writes) = bind_group_textures(project, &access.bind_groups);
+    access.reads.extend(reads);
+    access.writes.extend(writes);
+
+    Some(access)
+}
+
+/// The textures the bind groups read and write.
+fn bind_group_textures(
+    project: &Project,
+    ids: &[BindGroupId],
+) -> (
+    Vec<(GraphTexture, AccessKind)>,
+    Vec<(GraphTexture, AccessKind)>,
+) {
+    let (mut reads, mut writes) = (vec![], vec![]);
+    for bind_group in ids
+        .iter()
+        .filter_map(|id| project.bind_groups.get(*id).ok())
+    {
+        for entry in bind_group.entries() {
+            match entry.resource {
+                BindGroupResource::Texture {
+                    texture_view_id: Some(view_id),
+                    ..
+                } => {
+                    if let Some(texture) = view_texture(project, view_id) {
+                        reads.push((texture, AccessKind::Sampled));
+                    }
+                }
+                BindGroupResource::StorageTexture {
+                    texture_view_id: Some(view_id),
+                    access: storage_access,
+                    ..
+                } => {
+                    let Some(texture) = view_texture(project, view_id) else {
+                        continue;
+                    };
+                    if storage_access != wgpu::StorageTextureAccess::WriteOnly {
+                        reads.push((texture, AccessKind::Storage));
+                    }
+                    if storage_access != wgpu::StorageTextureAccess::ReadOnly {
+                        writes.push((texture, AccessKind::Storage));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    (reads, writes)
+}
+
+/// The textures one bind group set samples and the other writes, where
+/// neither set samples what it writes itself.
+fn alternated_textures(
+    project: &Project,
+    bind_groups: &[BindGroupId],
+    alternate: &[BindGroupId],
+) -> Vec<GraphTexture> {
+    let sampled_and_written = |ids: &[BindGroupId]| {
+        let (reads, writes) = bind_group_textures(project, ids);
+        let sampled: HashSet<GraphTexture> = reads
+            .into_iter()
+            .filter(|(_, kind)| *kind == AccessKind::Sampled)
+            .map(|(texture, _)| texture)
+            .collect();
+        let written: HashSet<GraphTexture> =
+            writes.into_iter().map(|(texture, _)| texture).collect();
+        let looped: HashSet<GraphTexture> = sampled.intersection(&written).copied().collect();
+        (sampled, written, looped)
+    };
+    let (sampled, written, looped) = sampled_and_written(bind_groups);
+    let (alternate_sampled, alternate_written, alternate_looped) = sampled_and_written(alternate);
+
+    sampled
+        .intersection(&alternate_written)
+        .chain(alternate_sampled.intersection(&written))
+        .filter(|texture| !looped.contains(*texture) && !alternate_looped.contains(*texture))
+        .copied()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use slotmap::SlotMap;
+
+    use super::*;
+    use crate::project::ComputePassId;
+
+    struct Ids {
+        textures: SlotMap<TextureId, ()>,
+        passes: SlotMap<ComputePassId, ()>,
+    }
+
+    impl Ids {
+        fn new() -> Self {
+            Self {
+                textures: SlotMap::with_key(),
+                passes: SlotMap::with_key(),
+            }
+        }
+
+        fn texture(&mut self) -> GraphTexture {
+            self.textures.insert(()).into()
+        }
+
+        fn step(&mut self, reads: &[GraphTexture], writes: &[GraphTexture]) -> StepAccess {
+            StepAccess {
+                step: PassStep::Compute(self.passes.insert(())),
+                bind_groups: vec![],
+                reads: reads.iter().map(|id| (*id, AccessKind::Storage)).collect(),
+                writes: writes.iter().map(|id| (*id, AccessKind::Storage)).collect(),
+                alternated: vec![],
+            }
+        }
+    }
+
+    #[test]
+    fn suggests_writers_before_readers_and_flags_the_early_read() {
+        let mut ids = Ids::new();
+        let (scene, blurred) = (ids.texture(), ids.texture());
+        let blur = ids.step(&[scene], &[blurred]);
+        let draw = ids.step(&[], &[scene]);
+
+        let graph = RenderGraph::new(vec![blur.clone(), draw.clone()], HashSet::from([blurred]));
+
+        assert_eq!(graph.suggested_order(), vec![draw.step, blur.step]);
+        assert_eq!(
+            graph.hazards(),
+            vec![Hazard::ReadBeforeWrite {
+                step: blur.step,
+                texture: scene,
+                writer: draw.step,
+            }]
+        );
+    }
+
+    #[test]
+    fn suggested_schedule_keeps_steps_left_out_of_the_graph() {
+        let mut ids = Ids::new();
+        let (scene, blurred) = (ids.texture(), ids.texture());
+        let blur = ids.step(&[scene], &[blurred]);
+        let draw = ids.step(&[], &[scene]);
+        // A step pointing at a deleted pass, which `RenderGraph::build` leaves out.
+        let dangling = ids.step(&[], &[]).step;
+
+        let graph = RenderGraph::new(vec![blur.clone(), draw.clone()], HashSet::from([blurred]));
+
+        assert_eq!(
+            graph.suggested_schedule(&[blur.step, dangling, draw.step]),
+            vec![draw.step, dangling, blur.step]
+        );
+    }
+
+    #[test]
+    fn keeps_feedback_loops_across_frames_in_place() {
+        let mut ids = Ids::new();
+        let (state, next) = (ids.texture(), ids.texture());
+        let simulate = ids.step(&[state], &[next]);
+        let copy = ids.step(&[next], &[state]);
+
+        let graph = RenderGraph::new(vec![simulate.clone(), copy.clone()], HashSet::new());
+
+        assert_eq!(graph.suggested_order(), vec![simulate.step, copy.step]);
+        assert_eq!(graph.hazards(), vec![]);
+    }
+
+    #[test]
+    fn flags_sampling_a_written_texture_and_unused_outputs() {
+        let mut ids = Ids::new();
+        let (target, unused) = (ids.texture(), ids.texture());
+        let mut pass = ids.step(&[], &[unused]);
+        pass.reads.push((target, AccessKind::Sampled));
+        pass.writes.push((target, AccessKind::ColorAttachment));
+
+        let graph = RenderGraph::new(vec![pass.clone()], HashSet::new());
+
+        assert_eq!(
+            graph.hazards(),
+            vec![
+                Hazard::FeedbackLoop {
+                    step: pass.step,
+                    texture: target,
+                },
+                Hazard::UnusedOutput {
+                    step: pass.step,
+                    texture: unused,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reading_the_front_while_writing_the_back_is_not_a_loop() {
+        let mut ids = Ids::new();
+        let front = ids.texture();
+        let back = GraphTexture {
+            side: BufferSide::Back,
+            ..front
+        };
+        let mut simulate = ids.step(&[], &[back]);
+        simulate.reads.push((front, AccessKind::Sampled));
+
+        let graph = RenderGraph::new(vec![simulate], HashSet::from([front]));
+
+        assert_eq!(graph.hazards(), vec![]);
+    }
+
+    #[test]
+    fn alternating_iterations_are_not_a_loop() {
+        let mut ids = Ids::new();
+        let (a, b) = (ids.texture(), ids.texture());
+        // Even iterations sample A into B, odd ones sample B back into A.
+        let mut simulate = ids.step(&[], &[a, b]);
+        simulate.reads = vec![(a, AccessKind::Sampled), (b, AccessKind::Sampled)];
+
+        let graph = RenderGraph::new(vec![simulate.clone()], HashSet::from([a]));
+        assert_eq!(graph.hazards().len(), 2);
+
+        simulate.alternated = vec![a, b];
+        let graph = RenderGraph::new(vec![simulate], HashSet::from([a]));
+        assert_eq!(graph.hazards(), vec![]);
+    }
+
+    #[test]
+    fn uniform_buffers_shared_by_steps_add_no_ordering() {
+        use crate::project::resource::bindgroup::{BindGroup, BindGroupEntry};
+
+        // A compute pass and a later-scheduled render pass bind the same
+        // uniform buffer. The app writes it before the frame, so neither
+        // depends on the other.
+        let mut project = Project::default();
+        let uniform = project.uniforms.create("Params".to_string());
+        let bind_group = project.bind_groups.register(BindGroup::new(
+            "Params",
+            vec![BindGroupEntry::new(
+                BindGroupResource::Uniform(Some(uniform)),
+                wgpu::ShaderStages::all(),
+            )],
+        ));
+        let compute = project.compute_passes.create("Simulate".to_string());
+        project
+            .compute_passes
+            .get_mut(compute)
+            .unwrap()
+            .set_bind_groups(vec![bind_group]);
+        let pipeline = project.render_pipelines.create("Draw".to_string());
+        project
+            .render_pipelines
+            .get_mut(pipeline)
+            .unwrap()
+            .set_bind_groups(vec![BindGroupTarget::Static(bind_group)]);
+        let render = project.render_passes.create("Draw".to_string());
+        project
+            .render_passes
+            .get_mut(render)
+            .unwrap()
+            .set_pipelines(vec![pipeline]);
+        let schedule = vec![PassStep::Render(render), PassStep::Compute(compute)];
+        project.presentation.set_steps(schedule.clone());
+
+        let graph = RenderGraph::build(&project);
+        let compute_access = graph.access(PassStep::Compute(compute)).unwrap();
+        assert_eq!(compute_access.bind_groups, vec![bind_group]);
+        assert_eq!(compute_access.writes, vec![]);
+        assert_eq!(graph.suggested_order(), schedule);
+        assert_eq!(graph.hazards(), vec![]);
+    }
+}
@@ -1,10 +1,16 @@
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::task::Poll;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::project::schema;
 use crate::{
     error::{AppError, AppResult},
     project::{
-        BindGroupId, Creatable, ProjectResource, ResourceId, SamplerId, TextureViewId, UniformId,
+        BindGroupId, Creatable, ProjectResource, ResourceId, SamplerId, TextureId, TextureViewId,
+        UniformId,
+        ping_pong::{SwapVariants, Swapped},
         resource::{sampler::Sampler, texture_view::TextureView, uniform::Uniform},
         storage::RuntimeStorage,
         sync::{Revision, SyncOutcome, SyncResource, SyncTracker},
@@ -21,6 +27,7 @@ pub struct BindGroupCreationContext<'a> {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BindGroup {
     label: String,
@@ -34,17 +41,23 @@ pub struct BindGroup {
 pub struct BindGroupRuntime {
     layout: wgpu::BindGroupLayout,
     layout_entries: Vec<wgpu::BindGroupLayoutEntry>,
-    inner: wgpu::BindGroup,
+    groups: SwapVariants<wgpu::BindGroup>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BindGroupEntry {
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        schemars(schema_with = "schema::shader_stages")
+    )]
     pub visibility: wgpu::ShaderStages,
     pub resource: BindGroupResource,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(
     tag = "type",
     content = "value",
@@ -56,18 +69,38 @@ pub enum BindGroupResource {
         texture_view_id: Option<TextureViewId>,
         // These two fields are used on the layout creation
         // TODO: Decide if we keep this here, or move it to the TextureViewId, or separate the layout from the BindGroup
+        #[cfg_attr(
+            not(target_arch = "wasm32"),
+            schemars(schema_with = "schema::texture_view_dimension")
+        )]
         view_dimension: wgpu::TextureViewDimension,
+        #[cfg_attr(
+            not(target_arch = "wasm32"),
+            schemars(schema_with = "schema::texture_sample_type")
+        )]
         sample_type: wgpu::TextureSampleType,
     },
     StorageTexture {
         texture_view_id: Option<TextureViewId>,
+        #[cfg_attr(
+            not(target_arch = "wasm32"),
+            schemars(schema_with = "schema::storage_texture_access")
+        )]
         access: wgpu::StorageTextureAccess,
+        #[cfg_attr(
+            not(target_arch = "wasm32"),
+            schemars(schema_with = "schema::texture_view_dimension")
+        )]
         view_dimension: wgpu::TextureViewDimension,
     },
     Sampler {
         sampler_id: Option<SamplerId>,
         // This field is used on the layout creation
         // TODO: Decide if we keep this here, or move it to the TextureViewId, or separate the layout from the BindGroup
+        #[cfg_attr(
+            not(target_arch = "wasm32"),
+            schemars(schema_with = "schema::sampler_binding_type")
+        )]
         sampler_binding_type: wgpu::SamplerBindingType,
     },
     Uniform(Option<UniformId>),
@@ -97,6 +130,7 @@ impl BindGroup {
     fn resolve_entries<'a>(
         ctx: &'a BindGroupCreationContext<'a>,
         entries: &[BindGroupEntry],
+        swapped: Swapped,
     ) -> AppResult<
         Option<(
             Vec<wgpu::BindGroupLayoutEntry>,
@@ -107,7 +141,8 @@ impl BindGroup {
         let mut group_entries = Vec::new();
 
         for (index, entry) in entries.iter().copied().enumerate() {
-            let Some(group_entry) = entry.create_bind_group_entry(index as u32, ctx)? else {
+            let Some(group_entry) = entry.create_bind_group_entry(index as u32, ctx, swapped)?
+            else {
                 return Ok(None);
             };
             let Some(layout_entry) = entry.create_bind_group_layout_entry(index as u32, ctx)?
@@ -121,25 +156,22 @@ impl BindGroup {
         Ok(Some((layout_entries, group_entries)))
     }
 
-    fn create_layout_and_bind_group(
-        ctx: &BindGroupCreationContext,
-        label: &str,
-        layout_entries: &[wgpu::BindGroupLayoutEntry],
-        group_entries: &[wgpu::BindGroupEntry<'_>],
-    ) -> (wgpu::BindGroupLayout, wgpu::BindGroup) {
-        let device = ctx.device;
-
-        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some(&format!("{label} Layout")),
-            entries: layout_entries,
-        });
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some(label),
-            layout: &layout,
-            entries: group_entries,
-        });
-
-        (layout, bind_group)
+    /// The double-buffered textures the entries' texture views follow.
+    fn swap_textures(ctx: &BindGroupCreationContext, entries: &[BindGroupEntry]) -> Vec<TextureId> {
+        entries
+            .iter()
+            .filter_map(|entry| match entry.resource {
+                BindGroupResource::Texture {
+                    texture_view_id, ..
+                }
+                | BindGroupResource::StorageTexture {
+                    texture_view_id, ..
+                } => texture_view_id,
+                BindGroupResource::Sampler { .. } | BindGroupResource::Uniform(_) => None,
+            })
+            .filter_map(|id| ctx.runtime_texture_views.get_init(id).ok().flatten())
+            .flat_map(|view| view.swap_textures().iter().copied())
+            .collect()
     }
 }
 
@@ -153,7 +185,22 @@ impl BindGroupRuntime {
     }
 
     pub fn inner(&self) -> &wgpu::BindGroup {
-        &self.inner
+        self.groups.current()
+    }
+
+    /// The bind group with the double-buffered textures in `swapped` swapped,
+    /// for prebuilding what follows a swap.
+    pub fn variant(&self, swapped: Swapped) -> &wgpu::BindGroup {
+        self.groups.get(swapped)
+    }
+
+    /// The double-buffered textures the bind group follows.
+    pub fn swap_textures(&self) -> &[TextureId] {
+        self.groups.textures()
+    }
+
+    pub fn swap(&mut self, texture: TextureId) {
+        self.groups.swap(texture);
     }
 }
 
@@ -195,6 +242,7 @@ impl BindGroupEntry {
         &self,
         binding: u32,
         ctx: &'a BindGroupCreationContext<'a>,
+        swapped: Swapped,
     ) -> AppResult<Option<wgpu::BindGroupEntry<'a>>> {
         let resource = match self.resource {
             BindGroupResource::Texture {
@@ -211,7 +259,7 @@ impl BindGroupEntry {
                 else {
                     return Ok(None);
                 };
-                let inner = texture_view_runtime.inner();
+                let inner = texture_view_runtime.variant(swapped);
 
                 wgpu::BindingResource::TextureView(inner)
             }
@@ -243,16 +291,24 @@ impl BindGroupEntry {
         binding: u32,
         ctx: &BindGroupCreationContext,
     ) -> AppResult<Option<wgpu::BindGroupLayoutEntry>> {
-        let Some(ty) = self.resource.to_wgpu_binding_type(binding, ctx)? else {
-            return Ok(None);
-        };
+        let storage_format = self.resource.storage_format(binding, ctx)?;
+        Ok(self.layout_entry(binding, storage_format))
+    }
 
-        Ok(Some(wgpu::BindGroupLayoutEntry {
+    /// The layout entry at `binding`. Storage textures are laid out with the
+    /// format of the texture they bind, `storage_format`, and have no entry
+    /// without it.
+    pub fn layout_entry(
+        &self,
+        binding: u32,
+        storage_format: Option<wgpu::TextureFormat>,
+    ) -> Option<wgpu::BindGroupLayoutEntry> {
+        Some(wgpu::BindGroupLayoutEntry {
             binding,
             visibility: self.visibility,
-            ty,
+            ty: self.resource.binding_type(storage_format)?,
             count: None,
-        }))
+        })
     }
 
     fn resource_recreated(&self, tracker: &SyncTracker) -> bool {
@@ -283,56 +339,67 @@ impl BindGroupResource {
         }
     }
 
-    fn to_wgpu_binding_type(
+    /// The format of the texture a storage texture entry binds, or `None`
+    /// for other entries and while the texture view is pending.
+    fn storage_format(
         self,
         binding: u32,
         ctx: &BindGroupCreationContext,
-    ) -> AppResult<Option<wgpu::BindingType>> {
-        Ok(match self {
+    ) -> AppResult<Option<wgpu::TextureFormat>> {
+        let BindGroupResource::StorageTexture {
+            texture_view_id, ..
+        } = self
+        else {
+            return Ok(None);
+        };
+
+        let limits = ctx.device.limits();
+        if limits.max_storage_textures_per_shader_stage == 0 {
+            return Err(AppError::UnsupportedRendererFeature("Storage Textures"));
+        }
+
+        let texture_view_id = texture_view_id.ok_or(AppError::uninit_field(format!(
+            "Binding {binding} Texture View Id"
+        )))?;
+        let Some(texture_view_runtime) = ctx.runtime_texture_views.get_init(texture_view_id)?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(texture_view_runtime.inner().texture().format()))
+    }
+
+    fn binding_type(
+        self,
+        storage_format: Option<wgpu::TextureFormat>,
+    ) -> Option<wgpu::BindingType> {
+        Some(match self {
             BindGroupResource::Texture {
                 view_dimension,
                 sample_type,
                 ..
-            } => Some(wgpu::BindingType::Texture {
+            } => wgpu::BindingType::Texture {
                 sample_type,
                 view_dimension,
                 multisampled: false,
-            }),
+            },
             BindGroupResource::Sampler {
                 sampler_binding_type,
                 ..
-            } => Some(wgpu::BindingType::Sampler(sampler_binding_type)),
-            BindGroupResource::Uniform(_) => Some(wgpu::BindingType::Buffer {
+            } => wgpu::BindingType::Sampler(sampler_binding_type),
+            BindGroupResource::Uniform(_) => wgpu::BindingType::Buffer {
                 ty: wgpu::BufferBindingType::Uniform,
                 has_dynamic_offset: false,
                 min_binding_size: None,
-            }),
+            },
             BindGroupResource::StorageTexture {
-                texture_view_id,
                 access,
                 view_dimension,
-            } => {
-                let limits = ctx.device.limits();
-                if limits.max_storage_textures_per_shader_stage == 0 {
-                    return Err(AppError::UnsupportedRendererFeature("Storage Textures"));
-                }
-
-                let texture_view_id = texture_view_id.ok_or(AppError::uninit_field(format!(
-                    "Binding {binding} Texture View Id"
-                )))?;
-                let Some(texture_view_runtime) =
-                    ctx.runtime_texture_views.get_init(texture_view_id)?
-                else {
-                    return Ok(None);
-                };
-                let format = texture_view_runtime.inner().texture().format();
-
-                Some(wgpu::BindingType::StorageTexture {
-                    access,
-                    view_dimension,
-                    format,
-                })
-            }
+                ..
+            } => wgpu::BindingType::StorageTexture {
+                access,
+                view_dimension,
+                format: storage_format?,
+            },
         })
     }
 }
@@ -362,24 +429,45 @@ impl SyncResource for BindGroup {
     ) -> AppResult<SyncOutcome<Self::Runtime, Self::Job>> {
         match job {
             BindGroupJob::Start => {
-                let Some((layout_entries, group_entries)) =
-                    Self::resolve_entries(ctx, &self.entries)?
+                let Some((layout_entries, _)) =
+                    Self::resolve_entries(ctx, &self.entries, Swapped::default())?
                 else {
                     return Ok(SyncOutcome::Pending(BindGroupJob::Start));
                 };
 
                 let scope = WgpuErrorScope::push(ctx.device);
-                let (layout, inner) = Self::create_layout_and_bind_group(
-                    ctx,
-                    &self.label,
-                    &layout_entries,
-                    &group_entries,
-                );
+                let layout =
+                    ctx.device
+                        .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                            label: Some(&format!("{} Layout", self.label)),
+                            entries: &layout_entries,
+                        });
+
+                // Views of double-buffered textures get a bind group per
+                // arrangement, so a swap never rebuilds it.
+                let shared: &BindGroupCreationContext = ctx;
+                let swaps = Self::swap_textures(shared, &self.entries);
+                let groups = SwapVariants::build(swaps, |swapped| {
+                    let Some((_, group_entries)) =
+                        Self::resolve_entries(shared, &self.entries, swapped)?
+                    else {
+                        return Ok(None);
+                    };
+                    let bind_group = shared.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                        label: Some(&self.label),
+                        layout: &layout,
+                        entries: &group_entries,
+                    });
+                    Ok(Some(bind_group))
+                })?;
+                let Some(groups) = groups else {
+                    return Ok(SyncOutcome::Pending(BindGroupJob::Start));
+                };
 
                 let runtime = Self::Runtime {
                     layout,
                     layout_entries,
-                    inner,
+                    groups,
                 };
 
                 let job = BindGroupJob::Validation(runtime, scope.pop());
@@ -2,6 +2,8 @@ use std::f32::consts::FRAC_PI_2;
 
 use derive_more::{Add, AddAssign, Deref};
 use glam::{Mat4, Vec3, Vec4};
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIter};
 
@@ -32,6 +34,7 @@ const MAX_FOVY: Deg = Deg(179.0);
 const SAFE_FRAC_PI_2: f32 = FRAC_PI_2 - MIN_ZNEAR;
 
 #[derive(Debug, Clone, Copy, PartialEq, Add, AddAssign, Deref, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(transparent)]
 pub struct Rad(pub f32);
 
@@ -52,24 +55,29 @@ impl From<Rad> for Deg {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Deref, Add, AddAssign, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(transparent)]
 pub struct Yaw(Rad);
 
 #[derive(Debug, Clone, Copy, PartialEq, Deref, Add, AddAssign, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(transparent)]
 pub struct Pitch(Rad);
 
 #[derive(Debug, Clone, Copy, PartialEq, Deref, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(transparent)]
 pub struct Fov(Rad);
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 pub struct ClipRange {
     znear: f32,
     zfar: f32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Deref, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(transparent)]
 pub struct PositiveF32(f32);
 
@@ -128,8 +136,9 @@ impl PositiveF32 {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Deref, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(transparent)]
-pub struct LookAt(Vec3);
+pub struct LookAt(#[cfg_attr(not(target_arch = "wasm32"), schemars(with = "[f32; 3]"))] Vec3);
 
 impl LookAt {
     pub fn new(eye: Vec3, target: Vec3) -> Self {
@@ -144,6 +153,7 @@ impl LookAt {
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, EnumIter, Display, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum CameraMode {
     #[default]
@@ -159,9 +169,11 @@ pub struct CameraCreationContext<'a> {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Camera {
     label: String,
+    #[cfg_attr(not(target_arch = "wasm32"), schemars(with = "[f32; 3]"))]
     position: Vec3,
     yaw: Yaw,
     pitch: Pitch,
@@ -171,6 +183,7 @@ pub struct Camera {
     clip: ClipRange,
     mode: CameraMode,
     looking_at: LookAt,
+    #[cfg_attr(not(target_arch = "wasm32"), schemars(with = "[f32; 3]"))]
     current_speed: Vec3,
     max_speed: PositiveF32,
     acceleration: PositiveF32,
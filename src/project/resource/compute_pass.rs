@@ -1,36 +1,41 @@
-use instant::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::task::Poll;
 
 use crate::{
-    error::{AppError, AppResult},
+    error::{AppError, AppResult, RequiredFieldExt},
     project::{
         BindGroupId, ComputePassId, Creatable, DimensionId, ProjectResource, ShaderId,
         resource::{
             bindgroup::BindGroup,
             dimension::{Axis, Dimension, DimensionRef},
+            dispatch_policy::DispatchPolicy,
             shader::Shader,
+            uniform::{Uniform, UniformFieldData, UniformFieldDataKind, UniformFieldRef},
         },
         storage::{RuntimeStorage, Storage},
         sync::{Revision, SyncOutcome, SyncResource, SyncTracker},
     },
     resource_getters, resource_setters,
     utils::{
-        async_job::AsyncJob, validate_bind_group_layouts::validate_bind_group_layouts,
-        wgpu_error_scope::WgpuErrorScope,
+        async_job::AsyncJob, shader_reflection,
+        validate_bind_group_layouts::validate_bind_group_layouts, wgpu_error_scope::WgpuErrorScope,
     },
 };
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ComputePass {
     label: String,
     bind_groups: Vec<BindGroupId>,
     shader: Option<ShaderId>,
-    #[serde(alias = "workGroups")]
     dispatch_size: DispatchSize,
     #[serde(default)]
     dispatch_policy: DispatchPolicy,
+    #[serde(default, skip_serializing_if = "Iterations::is_single")]
+    iterations: Iterations,
     #[serde(skip)]
     runtime_revision: Revision,
     #[serde(skip)]
@@ -44,6 +49,7 @@ pub struct ComputePass {
 ///
 /// Each axis is either a constant or read from a [`Dimension`].
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct DispatchSize {
     pub x: WorkSize,
@@ -58,89 +64,166 @@ pub struct DispatchSize {
 /// multiplication apart. The unit decides which of them the work sizes are.
 ///
 /// Against a shader declaring `@workgroup_size(16, 16, 1)`, these dispatch the
-/// same 128x128 invocations:
+/// same 8x8 workgroups of 16x16 invocations:
 ///
 /// - `new_fixed(8, 8, 1, Workgroup)`
-/// - `new_fixed(128, 128, 1, Invocation { workgroup_size: [16, 16, 1] })`
+/// - `new_fixed(128, 128, 1, Invocation { workgroup_size: None })`, which reads
+///   the 16x16 from the shader
+/// - `new_fixed(128, 128, 1, Invocation { workgroup_size: Some([16, 16, 1]) })`,
+///   which overrides it with the same size
 ///
 /// [`Invocation`](Self::Invocation) is the practical choice when an axis reads
 /// from a [`Dimension`] and the compute pass is expected to run for every pixel
 /// of that dimension.
 #[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
 pub enum DispatchUnit {
     /// Workgroups, passed to `dispatch_workgroups` unchanged.
     #[default]
     Workgroup,
-    /// Invocations, divided by `workgroup_size` to get the workgroup counts.
+    /// Invocations, divided by the workgroup size to get the workgroup counts.
     ///
     /// The division rounds up, so the last workgroup along each axis runs past
     /// the requested range; the shader needs its own bounds check on
     /// `@builtin(global_invocation_id)`.
     ///
-    /// `workgroup_size` has to be kept in step with the `@workgroup_size`
-    /// for this to make sense.
+    /// The workgroup size is read from the shader's `@workgroup_size` unless
+    /// `workgroup_size` overrides it.
     Invocation {
-        // TODO: grab from shader via reflection instead of having the user specify it
-        #[serde(alias = "workgroup_size")]
-        workgroup_size: [u32; 3],
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        workgroup_size: Option<[u32; 3]>,
     },
 }
 
 /// One axis of a [`DispatchSize`]: a constant, or the width or height of a
 /// [`Dimension`].
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum WorkSize {
     Fixed(u32),
     Dimension(DimensionRef),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "camelCase")]
-pub enum DispatchPolicy {
-    /// Dispatch only on a frame where one of the pass's inputs changed, or the
-    /// pipeline was just (re)built.
-    #[default]
-    OnChange,
-    /// Dispatch on every rendered frame.
-    EveryFrame,
-    /// Dispatch at a fixed cadence, independent of the framerate.
-    Periodic {
-        #[serde(with = "duration_secs")]
-        interval: Duration,
-    },
+/// The most iterations a [`ComputePass`] runs each time it's dispatched.
+pub const MAX_ITERATIONS: u32 = 1024;
+
+/// How many times a [`ComputePass`] dispatches each time it runs, and with
+/// which bind groups.
+///
+/// Every iteration is a separate dispatch within the same compute pass, so each
+/// one sees what the previous one wrote. A shader that declares
+/// `var<immediate> iteration: u32` receives the iteration index there.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Iterations {
+    pub count: IterationCount,
+    /// Bind groups bound instead of the pass's own on odd iterations, so
+    /// consecutive iterations can ping-pong between two sets of resources.
+    /// Each one needs the layout of the bind group it replaces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alternate_bind_groups: Option<Vec<BindGroupId>>,
+}
+
+/// The number of iterations in [`Iterations`]: a constant, or read from a
+/// `UInt32` or `Float` uniform field.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum IterationCount {
+    Fixed(u32),
+    Uniform(UniformFieldRef),
 }
 
-/// Serializes a [`Duration`] as plain seconds (e.g. `0.05`) instead of the
-/// verbose `{ secs, nanos }` form, keeping `project.json` readable.
-mod duration_secs {
-    use instant::Duration;
-    use serde::{Deserialize, Deserializer, Serializer};
+impl Default for IterationCount {
+    fn default() -> Self {
+        Self::Fixed(1)
+    }
+}
 
-    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
-        serializer.serialize_f32(duration.as_secs_f32())
+impl Iterations {
+    /// A single dispatch with the pass's own bind groups.
+    pub fn is_single(&self) -> bool {
+        *self == Self::default()
     }
 
-    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
-        Ok(Duration::from_secs_f32(f32::deserialize(deserializer)?))
+    /// Every alternate bind group, or none if the pass doesn't alternate.
+    pub fn alternate_bind_group_ids(&self) -> impl Iterator<Item = BindGroupId> {
+        self.alternate_bind_groups.iter().flatten().copied()
+    }
+}
+
+impl IterationCount {
+    /// Checks that the uniform field the count reads exists and holds a
+    /// `UInt32` or a `Float`. Fields are referenced by index, so removing or
+    /// retyping one can leave the reference pointing elsewhere.
+    pub fn validate(&self, uniforms: &Storage<Uniform>) -> AppResult<()> {
+        let IterationCount::Uniform(field_ref) = self else {
+            return Ok(());
+        };
+        let id = field_ref.id.ok_or_uninit_field("Uniform")?;
+        let field = uniforms
+            .get(id)?
+            .get_field(field_ref.field)
+            .ok_or_uninit_field("Uniform Field")?;
+        match field.kind() {
+            UniformFieldDataKind::UInt32 | UniformFieldDataKind::Float => Ok(()),
+            actual => Err(AppError::IterationCountType { actual }),
+        }
+    }
+
+    /// Resolves the count, or `None` while the uniform it reads is pending.
+    pub fn resolve(&self, runtime_uniforms: &RuntimeStorage<Uniform>) -> AppResult<Option<u32>> {
+        let count = match self {
+            IterationCount::Fixed(count) => *count,
+            IterationCount::Uniform(field_ref) => match field_ref.resolve(runtime_uniforms)? {
+                None => return Ok(None),
+                Some(UniformFieldData::UInt32(count)) => *count,
+                // Saturates: negative counts run no iterations.
+                Some(UniformFieldData::Float(count)) => count.round() as u32,
+                Some(data) => {
+                    return Err(AppError::IterationCountType {
+                        actual: data.kind(),
+                    });
+                }
+            },
+        };
+
+        match count <= MAX_ITERATIONS {
+            true => Ok(Some(count)),
+            false => Err(AppError::IterationLimitExceeded {
+                count,
+                max: MAX_ITERATIONS,
+            }),
+        }
     }
 }
 
 pub struct Context<'a> {
     pub device: &'a wgpu::Device,
+    pub uniforms: &'a Storage<Uniform>,
     pub runtime_shaders: &'a RuntimeStorage<Shader>,
     pub runtime_bind_groups: &'a RuntimeStorage<BindGroup>,
 }
 
 pub struct ComputePassRuntime {
     pipeline: wgpu::ComputePipeline,
+    workgroup_size: Option<[u32; 3]>,
+    /// Whether the shader reads the iteration index from its immediate data.
+    iteration_index: bool,
 }
 
 impl ComputePassRuntime {
     pub fn pipeline(&self) -> &wgpu::ComputePipeline {
         &self.pipeline
     }
+
+    /// The `@workgroup_size` of the compute entry point the pipeline was built from.
+    pub fn workgroup_size(&self) -> Option<[u32; 3]> {
+        self.workgroup_size
+    }
 }
 
 #[derive(Default)]
@@ -158,6 +241,7 @@ impl Creatable for ComputePass {
             shader: Default::default(),
             dispatch_size: DispatchSize::new_fixed(1, 1, 1, DispatchUnit::Workgroup),
             dispatch_policy: DispatchPolicy::default(),
+            iterations: Iterations::default(),
             runtime_revision: Default::default(),
             project_revision: Default::default(),
         }
@@ -178,6 +262,7 @@ impl ComputePass {
             shader,
             dispatch_size,
             dispatch_policy,
+            iterations: Iterations::default(),
             runtime_revision: Revision::default(),
             project_revision: Revision::default(),
         }
@@ -189,6 +274,7 @@ impl ComputePass {
         pub fn shader() -> Option<ShaderId>;
         pub fn dispatch_size() -> DispatchSize;
         pub fn dispatch_policy() -> DispatchPolicy;
+        pub fn iterations() -> &Iterations;
     }
 
     resource_setters! {
@@ -200,6 +286,20 @@ impl ComputePass {
         pub fn set_dispatch(dispatch_policy: DispatchPolicy);
     }
 
+    pub fn with_iterations(mut self, iterations: Iterations) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn set_iterations(&mut self, iterations: Iterations) {
+        // Only the alternate bind groups are part of the pipeline's validation.
+        if self.iterations.alternate_bind_groups != iterations.alternate_bind_groups {
+            self.runtime_revision.increase();
+        }
+        self.iterations = iterations;
+        self.project_revision.increase();
+    }
+
     /// Whether any of this pass's inputs changed their data this frame. Used by
     /// [`DispatchPolicy::OnChange`] to decide whether to re-dispatch.
     pub fn inputs_changed(&self, tracker: &SyncTracker) -> bool {
@@ -207,46 +307,89 @@ impl ComputePass {
             || self
                 .bind_groups
                 .iter()
-                .any(|id| tracker.was_data_changed(*id))
+                .copied()
+                .chain(self.iterations.alternate_bind_group_ids())
+                .any(|id| tracker.was_data_changed(id))
+            || matches!(
+                self.iterations.count,
+                IterationCount::Uniform(UniformFieldRef { id: Some(id), .. })
+                    if tracker.was_data_changed(id)
+            )
             || self
                 .dispatch_size
                 .dimension_ids()
                 .any(|id| tracker.was_data_changed(id))
     }
 
-    /// Encodes one dispatch of this pass into `encoder`.
+    /// Encodes this pass's iterations into `encoder`, all within one compute
+    /// pass.
     ///
-    /// Returns `Ok(true)` once fully encoded, or `Ok(false)` if a bind group is
-    /// still rebuilding (the caller should try again next frame). Mirrors
+    /// Returns `Ok(true)` once fully encoded, or `Ok(false)` if a bind group or
+    /// the iteration count's uniform is still rebuilding (the caller should try
+    /// again next frame) or the count is zero, which dispatches nothing. Mirrors
     /// [`crate::project::resource::render_pass::RenderPass::submit`].
     pub fn encode(
         &self,
         encoder: &mut wgpu::CommandEncoder,
         runtime: &ComputePassRuntime,
         runtime_bind_groups: &RuntimeStorage<BindGroup>,
+        runtime_uniforms: &RuntimeStorage<Uniform>,
         dimensions: &Storage<Dimension>,
     ) -> AppResult<bool> {
-        let mut bind_groups = Vec::with_capacity(self.bind_groups.len());
-        for id in self.bind_groups.iter().copied() {
-            let Some(bind_group) = runtime_bind_groups.get_init(id)? else {
-                return Ok(false); // pending: a bind group is still rebuilding
-            };
-            bind_groups.push(bind_group);
+        let Some(count) = self.iterations.count.resolve(runtime_uniforms)? else {
+            return Ok(false); // pending: the count's uniform is still rebuilding
+        };
+        if count == 0 {
+            return Ok(false);
         }
 
+        let resolve = |ids: &[BindGroupId]| -> AppResult<Option<Vec<&wgpu::BindGroup>>> {
+            let mut bind_groups = Vec::with_capacity(ids.len());
+            for id in ids.iter().copied() {
+                let Some(bind_group) = runtime_bind_groups.get_init(id)? else {
+                    return Ok(None); // pending: a bind group is still rebuilding
+                };
+                bind_groups.push(bind_group.inner());
+            }
+            Ok(Some(bind_groups))
+        };
+        let Some(bind_groups) = resolve(&self.bind_groups)? else {
+            return Ok(false);
+        };
+        let alternate_bind_groups = match &self.iterations.alternate_bind_groups {
+            Some(ids) => match resolve(ids)? {
+                Some(bind_groups) => Some(bind_groups),
+                None => return Ok(false),
+            },
+            None => None,
+        };
+
+        let (x, y, z) = self
+            .dispatch_size()
+            .into_work_groups(dimensions, runtime.workgroup_size())?;
+
         let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: Some(&format!("{} (Compute Pass)", self.label)),
             timestamp_writes: None,
         });
 
         pass.set_pipeline(runtime.pipeline());
-        for (index, bind_group) in bind_groups.into_iter().enumerate() {
-            pass.set_bind_group(index as u32, bind_group.inner(), &[]);
+        for iteration in 0..count {
+            // Without alternate bind groups, the first iteration's stay bound.
+            let set = match &alternate_bind_groups {
+                Some(alternate) if iteration % 2 == 1 => Some(alternate),
+                Some(_) => Some(&bind_groups),
+                None => (iteration == 0).then_some(&bind_groups),
+            };
+            for (index, bind_group) in set.into_iter().flatten().enumerate() {
+                pass.set_bind_group(index as u32, *bind_group, &[]);
+            }
+            if runtime.iteration_index {
+                pass.set_immediates(0, &iteration.to_ne_bytes());
+            }
+            pass.dispatch_workgroups(x, y, z);
         }
 
-        let (x, y, z) = self.dispatch_size().into_work_groups(dimensions)?;
-        pass.dispatch_workgroups(x, y, z);
-
         Ok(true)
     }
 }
@@ -287,20 +430,30 @@ impl DispatchSize {
             })
     }
 
-    pub fn into_work_groups(self, dimensions: &Storage<Dimension>) -> AppResult<(u32, u32, u32)> {
+    /// Resolves the workgroup counts to dispatch. `shader_workgroup_size` is the
+    /// shader's `@workgroup_size`, used by [`DispatchUnit::Invocation`] when no
+    /// override is set.
+    pub fn into_work_groups(
+        self,
+        dimensions: &Storage<Dimension>,
+        shader_workgroup_size: Option<[u32; 3]>,
+    ) -> AppResult<(u32, u32, u32)> {
         let x = self.x.resolve(dimensions)?;
         let y = self.y.resolve(dimensions)?;
         let z = self.z.resolve(dimensions)?;
 
         Ok(match self.unit {
             DispatchUnit::Workgroup => (x, y, z),
-            DispatchUnit::Invocation {
-                workgroup_size: [wx, wy, wz],
-            } => (
-                x.div_ceil(wx.max(1)), // `max(1)` avoids dividing by zero
-                y.div_ceil(wy.max(1)),
-                z.div_ceil(wz.max(1)),
-            ),
+            DispatchUnit::Invocation { workgroup_size } => {
+                let [wx, wy, wz] = workgroup_size
+                    .or(shader_workgroup_size)
+                    .ok_or_uninit_field("Workgroup Size")?;
+                (
+                    x.div_ceil(wx.max(1)), // `max(1)` avoids dividing by zero
+                    y.div_ceil(wy.max(1)),
+                    z.div_ceil(wz.max(1)),
+                )
+            }
         })
     }
 }
@@ -337,7 +490,17 @@ impl SyncResource for ComputePass {
 
     fn needs_rebuild(&self, _: Self::Id, _: &Self::Context<'_>, tracker: &SyncTracker) -> bool {
         self.shader.is_some_and(|id| tracker.was_recreated(id))
-            || self.bind_groups.iter().any(|id| tracker.was_recreated(*id))
+            || self
+                .bind_groups
+                .iter()
+                .copied()
+                .chain(self.iterations.alternate_bind_group_ids())
+                .any(|id| tracker.was_recreated(id))
+            || matches!(
+                self.iterations.count,
+                IterationCount::Uniform(UniformFieldRef { id: Some(id), .. })
+                    if tracker.was_recreated(id)
+            )
     }
 
     fn sync<'a>(
@@ -361,21 +524,64 @@ impl SyncResource for ComputePass {
             return Err(AppError::UnsupportedRendererFeature("Compute Passes"));
         }
 
+        self.iterations.count.validate(ctx.uniforms)?;
+
         let mut bind_group_layouts = vec![];
+        let mut bind_group_entries = vec![];
         for id in self.bind_groups.iter().copied() {
             let Some(bind_group_runtime) = ctx.runtime_bind_groups.get_init(id)? else {
                 return Ok(SyncOutcome::Pending(ComputePassJob::Start));
             };
             bind_group_layouts.push(Some(bind_group_runtime.inner_layout()));
+            bind_group_entries.push(Some(bind_group_runtime.layout_entries()));
         }
 
         validate_bind_group_layouts(&bind_group_layouts, &limits)?;
 
+        if let Some(alternate_bind_groups) = &self.iterations.alternate_bind_groups {
+            for group in 0..bind_group_entries.len().max(alternate_bind_groups.len()) {
+                let Some(id) = alternate_bind_groups.get(group).copied() else {
+                    return Err(AppError::AlternateBindGroupMismatch { group });
+                };
+                let Some(bind_group_runtime) = ctx.runtime_bind_groups.get_init(id)? else {
+                    return Ok(SyncOutcome::Pending(ComputePassJob::Start));
+                };
+                if bind_group_entries.get(group).copied().flatten()
+                    != Some(bind_group_runtime.layout_entries())
+                {
+                    return Err(AppError::AlternateBindGroupMismatch { group });
+                }
+            }
+        }
+
         let shader_id = self.shader.ok_or(AppError::uninit_field("Shader"))?;
         let Some(shader_runtime) = ctx.runtime_shaders.get_init(shader_id)? else {
             return Ok(SyncOutcome::Pending(ComputePassJob::Start));
         };
 
+        shader_reflection::validate_bindings(
+            shader_runtime.module(),
+            shader_runtime.info(),
+            naga::ShaderStage::Compute,
+            &bind_group_entries,
+        )?;
+
+        // The iteration index is the only immediate data a compute pass provides.
+        let iteration_index = match shader_reflection::immediate_type(shader_runtime.module()) {
+            None => false,
+            Some(_) if !ctx.device.features().contains(wgpu::Features::IMMEDIATES) => {
+                return Err(AppError::UnsupportedRendererFeature("Immediates"));
+            }
+            Some(ty) => match shader_runtime.module().types[ty].inner {
+                naga::TypeInner::Scalar(naga::Scalar::U32) => true,
+                _ => {
+                    return Err(AppError::IterationIndexType {
+                        actual: shader_reflection::type_label(shader_runtime.module(), ty),
+                    });
+                }
+            },
+        };
+
         let scope = WgpuErrorScope::push(ctx.device);
 
         let pipeline_layout = ctx
@@ -383,7 +589,10 @@ impl SyncResource for ComputePass {
             .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                 label: Some(&format!("{} (Pipeline Layout)", self.label)),
                 bind_group_layouts: &bind_group_layouts,
-                immediate_size: 0,
+                immediate_size: match iteration_index {
+                    true => size_of::<u32>() as u32,
+                    false => 0,
+                },
             });
 
         let pipeline = ctx
@@ -397,7 +606,11 @@ impl SyncResource for ComputePass {
                 cache: None,
             });
 
-        let runtime = ComputePassRuntime { pipeline };
+        let runtime = ComputePassRuntime {
+            pipeline,
+            workgroup_size: shader_reflection::workgroup_size(shader_runtime.module()),
+            iteration_index,
+        };
         self.sync(
             _id,
             ctx,
@@ -406,3 +619,113 @@ impl SyncResource for ComputePass {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        project::resource::uniform::{UniformField, UniformFieldSource},
+        ui::size::Size2d,
+    };
+
+    const SHADER_WORKGROUP_SIZE: Option<[u32; 3]> = Some([16, 16, 1]);
+
+    fn work_groups(size: DispatchSize) -> AppResult<(u32, u32, u32)> {
+        size.into_work_groups(&Storage::default(), SHADER_WORKGROUP_SIZE)
+    }
+
+    #[test]
+    fn workgroups_dispatch_unchanged() {
+        let size = DispatchSize::new_fixed(8, 4, 2, DispatchUnit::Workgroup);
+        assert_eq!(work_groups(size).unwrap(), (8, 4, 2));
+    }
+
+    #[test]
+    fn invocations_round_up_to_whole_workgroups() {
+        let unit = DispatchUnit::Invocation {
+            workgroup_size: None,
+        };
+        assert_eq!(
+            work_groups(DispatchSize::new_fixed(128, 128, 1, unit)).unwrap(),
+            (8, 8, 1)
+        );
+        assert_eq!(
+            work_groups(DispatchSize::new_fixed(129, 1, 1, unit)).unwrap(),
+            (9, 1, 1)
+        );
+        assert_eq!(
+            work_groups(DispatchSize::new_fixed(0, 0, 0, unit)).unwrap(),
+            (0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn workgroup_size_override_wins_over_the_shader() {
+        let unit = DispatchUnit::Invocation {
+            workgroup_size: Some([8, 4, 1]),
+        };
+        let size = DispatchSize::new_fixed(128, 128, 1, unit);
+        assert_eq!(work_groups(size).unwrap(), (16, 32, 1));
+
+        let zero = DispatchUnit::Invocation {
+            workgroup_size: Some([0, 0, 0]),
+        };
+        let size = DispatchSize::new_fixed(5, 5, 5, zero);
+        assert_eq!(work_groups(size).unwrap(), (5, 5, 5));
+    }
+
+    #[test]
+    fn invocations_need_a_workgroup_size() {
+        let unit = DispatchUnit::Invocation {
+            workgroup_size: None,
+        };
+        let size = DispatchSize::new_fixed(128, 128, 1, unit);
+        assert!(size.into_work_groups(&Storage::default(), None).is_err());
+    }
+
+    #[test]
+    fn invocations_follow_dimension_sizes() {
+        let mut dimensions = Storage::default();
+        let dimension =
+            dimensions.register(Dimension::new_persistent("Screen", Size2d::new(100, 50)));
+        let unit = DispatchUnit::Invocation {
+            workgroup_size: None,
+        };
+        let size = DispatchSize::new_dimension(dimension, 1, unit);
+        assert_eq!(
+            size.into_work_groups(&dimensions, SHADER_WORKGROUP_SIZE)
+                .unwrap(),
+            (7, 4, 1)
+        );
+    }
+
+    #[test]
+    fn iteration_counts_need_a_scalar_field_that_exists() {
+        let mut uniforms = Storage::default();
+        let field =
+            |label, data| UniformField::new(label, UniformFieldSource::new_user_defined(data));
+        let id = uniforms.register(Uniform::new(
+            "Iterations",
+            vec![
+                field("count", UniformFieldData::UInt32(4)),
+                field("tint", UniformFieldData::Vec3f([1.0; 3])),
+            ],
+        ));
+        let count = |field| {
+            IterationCount::Uniform(UniformFieldRef {
+                id: Some(id),
+                field,
+            })
+        };
+
+        assert!(IterationCount::Fixed(0).validate(&uniforms).is_ok());
+        assert!(count(0).validate(&uniforms).is_ok());
+        assert!(matches!(
+            count(1).validate(&uniforms),
+            Err(AppError::IterationCountType {
+                actual: UniformFieldDataKind::Vec3f
+            })
+        ));
+        assert!(count(2).validate(&uniforms).is_err());
+    }
+}
@@ -0,0 +1,818 @@
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::task::Poll;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::project::schema;
+use crate::{
+    error::{AppError, AppResult, RequiredFieldExt},
+    project::{
+        CopyPassId, Creatable, ProjectResource, ResourceId, TextureId, TextureViewId, UniformId,
+        ping_pong::SwapVariants,
+        resource::{
+            dispatch_policy::DispatchPolicy,
+            texture::Texture,
+            texture_view::{TextureView, TextureViewRuntime},
+            uniform::Uniform,
+        },
+        storage::{RuntimeStorage, Storage},
+        sync::{Revision, SyncOutcome, SyncResource, SyncTracker},
+    },
+    resource_getters, resource_setters,
+    utils::{async_job::AsyncJob, wgpu_error_scope::WgpuErrorScope},
+};
+
+/// Copies texture contents without a shader of its own: whole textures, regions,
+/// buffer uploads, or a filtered blit that scales and converts formats.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CopyPass {
+    label: String,
+    mode: CopyMode,
+    #[serde(default = "default_dispatch_policy")]
+    dispatch_policy: DispatchPolicy,
+    #[serde(skip)]
+    runtime_revision: Revision,
+    #[serde(skip)]
+    project_revision: Revision,
+}
+
+/// Copy sources are usually written on the GPU, which the sync tracker can't
+/// see, so copy passes run every frame unless told otherwise.
+fn default_dispatch_policy() -> DispatchPolicy {
+    DispatchPolicy::EveryFrame
+}
+
+/// What a [`CopyPass`] copies, and where to.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum CopyMode {
+    /// The whole source texture into a destination of the same size and format.
+    Texture {
+        source: Option<TextureId>,
+        destination: Option<TextureId>,
+    },
+    /// A box of texels from one texture into another, between the given origins.
+    Region {
+        source: Option<TextureId>,
+        destination: Option<TextureId>,
+        source_origin: [u32; 3],
+        destination_origin: [u32; 3],
+        size: [u32; 3],
+    },
+    /// A uniform's buffer, read as tightly packed texels, into a box of a texture.
+    ///
+    /// When the box spans more than one row, each row starts on a multiple of
+    /// [`wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`] bytes, as wgpu requires.
+    Buffer {
+        source: Option<UniformId>,
+        destination: Option<TextureId>,
+        destination_origin: [u32; 3],
+        size: [u32; 3],
+    },
+    /// The source view drawn over the whole destination view, sampled with
+    /// `filter`. Sizes and formats may differ.
+    Blit {
+        source: Option<TextureViewId>,
+        destination: Option<TextureViewId>,
+        #[cfg_attr(
+            not(target_arch = "wasm32"),
+            schemars(schema_with = "schema::filter_mode")
+        )]
+        filter: wgpu::FilterMode,
+    },
+}
+
+impl CopyMode {
+    /// The resource the copy reads from, if set.
+    pub fn source(&self) -> Option<ResourceId> {
+        match *self {
+            CopyMode::Texture { source, .. } | CopyMode::Region { source, .. } => {
+                source.map(Into::into)
+            }
+            CopyMode::Buffer { source, .. } => source.map(Into::into),
+            CopyMode::Blit { source, .. } => source.map(Into::into),
+        }
+    }
+
+    /// The resource the copy writes to, if set.
+    pub fn destination(&self) -> Option<ResourceId> {
+        match *self {
+            CopyMode::Texture { destination, .. }
+            | CopyMode::Region { destination, .. }
+            | CopyMode::Buffer { destination, .. } => destination.map(Into::into),
+            CopyMode::Blit { destination, .. } => destination.map(Into::into),
+        }
+    }
+}
+
+pub struct Context<'a> {
+    pub device: &'a wgpu::Device,
+    pub runtime_textures: &'a RuntimeStorage<Texture>,
+    pub runtime_texture_views: &'a RuntimeStorage<TextureView>,
+    pub runtime_uniforms: &'a RuntimeStorage<Uniform>,
+}
+
+pub struct CopyPassRuntime {
+    blit: Option<BlitRuntime>,
+}
+
+impl CopyPassRuntime {
+    pub fn swap(&mut self, texture: TextureId) {
+        if let Some(blit) = &mut self.blit {
+            blit.bind_groups.swap(texture);
+        }
+    }
+}
+
+/// The pipeline and bindings a [`CopyMode::Blit`] draws with.
+struct BlitRuntime {
+    pipeline: wgpu::RenderPipeline,
+    bind_groups: SwapVariants<wgpu::BindGroup>,
+}
+
+#[derive(Default)]
+pub enum CopyPassJob {
+    #[default]
+    Start,
+    Validation(AsyncJob<AppResult<()>>, CopyPassRuntime),
+}
+
+impl Creatable for CopyPass {
+    fn create(label: String) -> Self {
+        Self::new(
+            label,
+            CopyMode::Texture {
+                source: None,
+                destination: None,
+            },
+            default_dispatch_policy(),
+        )
+    }
+}
+
+impl CopyPass {
+    pub fn new(label: impl Into<String>, mode: CopyMode, dispatch_policy: DispatchPolicy) -> Self {
+        Self {
+            label: label.into(),
+            mode,
+            dispatch_policy,
+            runtime_revision: Revision::default(),
+            project_revision: Revision::default(),
+        }
+    }
+
+    resource_getters! {
+        pub fn label() -> &str;
+        pub fn mode() -> CopyMode;
+        pub fn dispatch_policy() -> DispatchPolicy;
+    }
+
+    resource_setters! {
+        increases: [runtime_revision, project_revision];
+        pub fn set_label(label: String);
+        pub fn set_mode(mode: CopyMode);
+    }
+
+    resource_setters! {
+        increases: [project_revision];
+        pub fn set_dispatch_policy(dispatch_policy: DispatchPolicy);
+    }
+
+    /// The texture this pass writes, resolving a blit's destination view.
+    pub fn destination_texture(&self, texture_views: &Storage<TextureView>) -> Option<TextureId> {
+        match self.mode.destination()? {
+            ResourceId::Texture(id) => Some(id),
+            ResourceId::TextureView(id) => texture_views.get(id).ok()?.texture_id(),
+            _ => None,
+        }
+    }
+
+    /// Whether the copied data changed this frame. Used by
+    /// [`DispatchPolicy::OnChange`] to decide whether to copy again.
+    pub fn inputs_changed(&self, tracker: &SyncTracker) -> bool {
+        self.mode
+            .source()
+            .is_some_and(|id| tracker.was_data_changed(id))
+    }
+
+    /// Encodes the copy into `encoder`.
+    ///
+    /// Returns `Ok(true)` once encoded, or `Ok(false)` if the source or the
+    /// destination is still rebuilding.
+    pub fn encode(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        runtime: &CopyPassRuntime,
+        runtime_textures: &RuntimeStorage<Texture>,
+        runtime_texture_views: &RuntimeStorage<TextureView>,
+        runtime_uniforms: &RuntimeStorage<Uniform>,
+    ) -> AppResult<bool> {
+        let texture = |id: Option<TextureId>, field: &'static str| -> AppResult<_> {
+            let id = id.ok_or_uninit_field(field)?;
+            Ok(runtime_textures
+                .get_init(id)?
+                .map(|runtime| runtime.inner()))
+        };
+
+        match self.mode {
+            CopyMode::Texture {
+                source,
+                destination,
+            } => {
+                let (Some(source), Some(destination)) = (
+                    texture(source, "Source")?,
+                    texture(destination, "Destination")?,
+                ) else {
+                    return Ok(false);
+                };
+                encoder.copy_texture_to_texture(
+                    source.as_image_copy(),
+                    destination.as_image_copy(),
+                    source.size(),
+                );
+            }
+            CopyMode::Region {
+                source,
+                destination,
+                source_origin,
+                destination_origin,
+                size,
+            } => {
+                let (Some(source), Some(destination)) = (
+                    texture(source, "Source")?,
+                    texture(destination, "Destination")?,
+                ) else {
+                    return Ok(false);
+                };
+                encoder.copy_texture_to_texture(
+                    texel_copy(source, source_origin),
+                    texel_copy(destination, destination_origin),
+                    extent(size),
+                );
+            }
+            CopyMode::Buffer {
+                source,
+                destination,
+                destination_origin,
+                size,
+            } => {
+                let source = source.ok_or_uninit_field("Source")?;
+                let (Some(uniform), Some(destination)) = (
+                    runtime_uniforms.get_init(source)?,
+                    texture(destination, "Destination")?,
+                ) else {
+                    return Ok(false);
+                };
+                let layout = BufferRowLayout::new(destination.format(), size)?;
+                encoder.copy_buffer_to_texture(
+                    wgpu::TexelCopyBufferInfo {
+                        buffer: uniform.buffer().inner(),
+                        layout: wgpu::TexelCopyBufferLayout {
+                            offset: 0,
+                            bytes_per_row: Some(layout.bytes_per_row),
+                            rows_per_image: Some(size[1]),
+                        },
+                    },
+                    texel_copy(destination, destination_origin),
+                    extent(size),
+                );
+            }
+            CopyMode::Blit { destination, .. } => {
+                let destination = destination.ok_or_uninit_field("Destination")?;
+                let Some(destination) = runtime_texture_views.get_init(destination)? else {
+                    return Ok(false);
+                };
+                let blit = runtime.blit.as_ref().ok_or_uninit_field("Source")?;
+
+                let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some(&format!("{} (Blit)", self.label)),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: destination.inner(),
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                            store: wgpu::StoreOp::Store,
+                        },
+                        depth_slice: None,
+                        resolve_target: None,
+                    })],
+                    depth_stencil_attachment: None,
+                    occlusion_query_set: None,
+                    timestamp_writes: None,
+                    multiview_mask: None,
+                });
+                pass.set_pipeline(&blit.pipeline);
+                pass.set_bind_group(0, blit.bind_groups.current(), &[]);
+                pass.draw(0..3, 0..1);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+fn extent([width, height, depth_or_array_layers]: [u32; 3]) -> wgpu::Extent3d {
+    wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers,
+    }
+}
+
+fn extent_label(extent: wgpu::Extent3d) -> String {
+    format!(
+        "{}x{}x{}",
+        extent.width, extent.height, extent.depth_or_array_layers
+    )
+}
+
+fn texel_copy(texture: &wgpu::Texture, [x, y, z]: [u32; 3]) -> wgpu::TexelCopyTextureInfo<'_> {
+    wgpu::TexelCopyTextureInfo {
+        texture,
+        mip_level: 0,
+        origin: wgpu::Origin3d { x, y, z },
+        aspect: wgpu::TextureAspect::All,
+    }
+}
+
+/// How a [`CopyMode::Buffer`] lays the texels of a box out in the buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BufferRowLayout {
+    /// The stride between rows, padded when the box spans several rows.
+    bytes_per_row: u32,
+    /// The bytes the copy reads: every row but the last is padded.
+    required_size: u64,
+}
+
+impl BufferRowLayout {
+    fn new(format: wgpu::TextureFormat, [width, height, depth]: [u32; 3]) -> AppResult<Self> {
+        let texel_size = format
+            .block_copy_size(None)
+            .filter(|_| format.block_dimensions() == (1, 1))
+            .ok_or_else(|| AppError::CopyBufferFormat {
+                format: format!("{format:?}"),
+            })?;
+
+        let row_size = width * texel_size;
+        let rows = height * depth;
+        let bytes_per_row = match rows > 1 {
+            true => row_size.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+            false => row_size,
+        };
+        let required_size = bytes_per_row as u64 * rows.saturating_sub(1) as u64 + row_size as u64;
+
+        Ok(Self {
+            bytes_per_row,
+            required_size,
+        })
+    }
+}
+
+fn check_usage(
+    texture: &wgpu::Texture,
+    role: &'static str,
+    usage: wgpu::TextureUsages,
+    usage_label: &'static str,
+) -> AppResult<()> {
+    match texture.usage().contains(usage) {
+        true => Ok(()),
+        false => Err(AppError::CopyTextureUsage {
+            role,
+            usage: usage_label,
+        }),
+    }
+}
+
+fn check_formats(from: wgpu::TextureFormat, to: wgpu::TextureFormat) -> AppResult<()> {
+    match from.remove_srgb_suffix() == to.remove_srgb_suffix() {
+        true => Ok(()),
+        false => Err(AppError::CopyFormatMismatch {
+            from: format!("{from:?}"),
+            to: format!("{to:?}"),
+        }),
+    }
+}
+
+/// Checks that the box at `origin` of `size` fits inside a texture of
+/// `texture_size`.
+fn check_bounds(
+    texture_size: wgpu::Extent3d,
+    role: &'static str,
+    origin: [u32; 3],
+    size: [u32; 3],
+) -> AppResult<()> {
+    let limits = [
+        texture_size.width,
+        texture_size.height,
+        texture_size.depth_or_array_layers,
+    ];
+    let end = [0, 1, 2].map(|axis| origin[axis].saturating_add(size[axis]));
+    match end.iter().zip(limits).all(|(end, limit)| *end <= limit) {
+        true => Ok(()),
+        false => Err(AppError::CopyRegionOutOfBounds {
+            role,
+            end: extent_label(extent(end)),
+            size: extent_label(texture_size),
+        }),
+    }
+}
+
+const BLIT_SHADER: &str = r#"
+@group(0) @binding(0) var source: texture_2d<f32>;
+@group(0) @binding(1) var source_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(source, source_sampler, in.uv);
+}
+"#;
+
+impl CopyPass {
+    /// Validates a texture-to-texture mode, returning `None` while a texture is
+    /// still pending.
+    fn validate_textures<'a>(
+        &self,
+        ctx: &Context<'a>,
+        source: Option<TextureId>,
+        destination: Option<TextureId>,
+    ) -> AppResult<Option<(&'a wgpu::Texture, &'a wgpu::Texture)>> {
+        let source = source.ok_or_uninit_field("Source")?;
+        let destination = destination.ok_or_uninit_field("Destination")?;
+        let (Some(source), Some(destination)) = (
+            ctx.runtime_textures.get_init(source)?,
+            ctx.runtime_textures.get_init(destination)?,
+        ) else {
+            return Ok(None);
+        };
+        let (source, destination) = (source.inner(), destination.inner());
+
+        check_usage(
+            source,
+            "source",
+            wgpu::TextureUsages::COPY_SRC,
+            "Copy Source",
+        )?;
+        check_usage(
+            destination,
+            "destination",
+            wgpu::TextureUsages::COPY_DST,
+            "Copy Destination",
+        )?;
+        check_formats(source.format(), destination.format())?;
+        Ok(Some((source, destination)))
+    }
+
+    fn create_blit(
+        &self,
+        ctx: &Context<'_>,
+        source: &TextureViewRuntime,
+        destination_format: wgpu::TextureFormat,
+        filter: wgpu::FilterMode,
+    ) -> AppResult<BlitRuntime> {
+        let filterable = filter == wgpu::FilterMode::Linear;
+
+        let bind_group_layout =
+            ctx.device
+                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: Some(&format!("{} (Blit Bind Group Layout)", self.label)),
+                    entries: &[
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Texture {
+                                sample_type: wgpu::TextureSampleType::Float { filterable },
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Sampler(match filterable {
+                                true => wgpu::SamplerBindingType::Filtering,
+                                false => wgpu::SamplerBindingType::NonFiltering,
+                            }),
+                            count: None,
+                        },
+                    ],
+                });
+
+        let sampler = ctx.device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some(&format!("{} (Blit Sampler)", self.label)),
+            mag_filter: filter,
+            min_filter: filter,
+            ..Default::default()
+        });
+
+        // A double-buffered source gets a bind group per half.
+        let bind_groups = SwapVariants::new(source.swap_textures().to_vec(), |swapped| {
+            ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(&format!("{} (Blit Bind Group)", self.label)),
+                layout: &bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(source.variant(swapped)),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&sampler),
+                    },
+                ],
+            })
+        })?;
+
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(&format!("{} (Blit Shader)", self.label)),
+                source: wgpu::ShaderSource::Wgsl(BLIT_SHADER.into()),
+            });
+
+        let pipeline_layout = ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some(&format!("{} (Blit Pipeline Layout)", self.label)),
+                bind_group_layouts: &[Some(&bind_group_layout)],
+                immediate_size: 0,
+            });
+
+        let pipeline = ctx
+            .device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(&format!("{} (Blit Pipeline)", self.label)),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &module,
+                    entry_point: None,
+                    compilation_options: Default::default(),
+                    buffers: &[],
+                },
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                fragment: Some(wgpu::FragmentState {
+                    module: &module,
+                    entry_point: None,
+                    compilation_options: Default::default(),
+                    targets: &[Some(destination_format.into())],
+                }),
+                multiview_mask: None,
+                cache: None,
+            });
+
+        Ok(BlitRuntime {
+            pipeline,
+            bind_groups,
+        })
+    }
+}
+
+impl ProjectResource for CopyPass {
+    type Id = CopyPassId;
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn project_revision(&self) -> Revision {
+        self.project_revision
+    }
+}
+
+impl SyncResource for CopyPass {
+    type Context<'a> = Context<'a>;
+    type Runtime = CopyPassRuntime;
+    type Job = CopyPassJob;
+
+    fn runtime_revision(&self) -> Revision {
+        self.runtime_revision
+    }
+
+    fn needs_rebuild(&self, _: Self::Id, _: &Self::Context<'_>, tracker: &SyncTracker) -> bool {
+        // Sizes, formats and usages are checked against the current textures,
+        // and a blit binds its source view.
+        [self.mode.source(), self.mode.destination()]
+            .into_iter()
+            .flatten()
+            .any(|id| tracker.was_recreated(id))
+    }
+
+    fn sync<'a>(
+        &self,
+        id: Self::Id,
+        ctx: &mut Self::Context<'a>,
+        _previous: Option<Self::Runtime>,
+        job: Self::Job,
+    ) -> AppResult<SyncOutcome<Self::Runtime, Self::Job>> {
+        if let CopyPassJob::Validation(mut future, runtime) = job {
+            return match future.try_resolve() {
+                Poll::Ready(result) => result.map(|()| SyncOutcome::Recreated(runtime)),
+                Poll::Pending => Ok(SyncOutcome::Pending(CopyPassJob::Validation(
+                    future, runtime,
+                ))),
+            };
+        }
+
+        let pending = Ok(SyncOutcome::Pending(CopyPassJob::Start));
+        let copied = Ok(SyncOutcome::Recreated(CopyPassRuntime { blit: None }));
+
+        match self.mode {
+            CopyMode::Texture {
+                source,
+                destination,
+            } => {
+                let Some((source, destination)) =
+                    self.validate_textures(ctx, source, destination)?
+                else {
+                    return pending;
+                };
+                if source.size() != destination.size() {
+                    return Err(AppError::CopySizeMismatch {
+                        from: extent_label(source.size()),
+                        to: extent_label(destination.size()),
+                    });
+                }
+                copied
+            }
+            CopyMode::Region {
+                source,
+                destination,
+                source_origin,
+                destination_origin,
+                size,
+            } => {
+                let Some((source, destination)) =
+                    self.validate_textures(ctx, source, destination)?
+                else {
+                    return pending;
+                };
+                check_bounds(source.size(), "source", source_origin, size)?;
+                check_bounds(destination.size(), "destination", destination_origin, size)?;
+                copied
+            }
+            CopyMode::Buffer {
+                source,
+                destination,
+                destination_origin,
+                size,
+            } => {
+                let source = source.ok_or_uninit_field("Source")?;
+                let destination = destination.ok_or_uninit_field("Destination")?;
+                let (Some(uniform), Some(destination)) = (
+                    ctx.runtime_uniforms.get_init(source)?,
+                    ctx.runtime_textures.get_init(destination)?,
+                ) else {
+                    return pending;
+                };
+                let destination = destination.inner();
+
+                check_usage(
+                    destination,
+                    "destination",
+                    wgpu::TextureUsages::COPY_DST,
+                    "Copy Destination",
+                )?;
+                check_bounds(destination.size(), "destination", destination_origin, size)?;
+
+                let layout = BufferRowLayout::new(destination.format(), size)?;
+                let actual = uniform.buffer().inner().size();
+                if layout.required_size > actual {
+                    return Err(AppError::CopyBufferTooSmall {
+                        required: layout.required_size,
+                        actual,
+                    });
+                }
+                copied
+            }
+            CopyMode::Blit {
+                source,
+                destination,
+                filter,
+            } => {
+                let source_id = source.ok_or_uninit_field("Source")?;
+                let destination = destination.ok_or_uninit_field("Destination")?;
+                let (Some(source), Some(destination)) = (
+                    ctx.runtime_texture_views.get_init(source_id)?,
+                    ctx.runtime_texture_views.get_init(destination)?,
+                ) else {
+                    return pending;
+                };
+                // Checked before the scope is pushed, which is popped whether or
+                // not the blit is created.
+                check_usage(
+                    source.inner().texture(),
+                    "source",
+                    wgpu::TextureUsages::TEXTURE_BINDING,
+                    "Texture Binding",
+                )?;
+                check_usage(
+                    destination.inner().texture(),
+                    "destination",
+                    wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    "Render Attachment",
+                )?;
+
+                let scope = WgpuErrorScope::push(ctx.device);
+                let blit = self.create_blit(ctx, source, destination.format(), filter);
+                let validation = scope.pop();
+                let runtime = CopyPassRuntime { blit: Some(blit?) };
+                self.sync(id, ctx, None, CopyPassJob::Validation(validation, runtime))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pads_every_row_but_the_last() {
+        let layout = BufferRowLayout::new(wgpu::TextureFormat::Rgba8Unorm, [4, 3, 1]).unwrap();
+        assert_eq!(layout.bytes_per_row, 256);
+        assert_eq!(layout.required_size, 256 * 2 + 16);
+
+        let single_row = BufferRowLayout::new(wgpu::TextureFormat::R32Float, [4, 1, 1]).unwrap();
+        assert_eq!(single_row.bytes_per_row, 16);
+        assert_eq!(single_row.required_size, 16);
+    }
+
+    #[test]
+    fn pads_rows_across_layers() {
+        // Two layers of two rows: the last row of the last layer is unpadded.
+        let layout = BufferRowLayout::new(wgpu::TextureFormat::Rgba8Unorm, [2, 2, 2]).unwrap();
+        assert_eq!(layout.bytes_per_row, 256);
+        assert_eq!(layout.required_size, 256 * 3 + 8);
+    }
+
+    #[test]
+    fn rejects_block_compressed_buffer_formats() {
+        let layout = BufferRowLayout::new(wgpu::TextureFormat::Bc1RgbaUnorm, [4, 4, 1]);
+        assert!(matches!(layout, Err(AppError::CopyBufferFormat { .. })));
+    }
+
+    #[test]
+    fn boxes_fit_up_to_the_texture_edge() {
+        let size = extent([16, 8, 1]);
+        assert!(check_bounds(size, "source", [0, 0, 0], [16, 8, 1]).is_ok());
+        assert!(check_bounds(size, "source", [12, 4, 0], [4, 4, 1]).is_ok());
+
+        let past_edge = check_bounds(size, "source", [12, 4, 0], [5, 4, 1]);
+        assert!(matches!(
+            past_edge,
+            Err(AppError::CopyRegionOutOfBounds { end, .. }) if end == "17x8x1"
+        ));
+    }
+
+    #[test]
+    fn huge_origins_saturate_instead_of_wrapping() {
+        // `u32::MAX + 2` would wrap to 1 and pass the check.
+        let result = check_bounds(
+            extent([16, 8, 1]),
+            "destination",
+            [u32::MAX, 0, 0],
+            [2, 1, 1],
+        );
+        assert!(matches!(
+            result,
+            Err(AppError::CopyRegionOutOfBounds { role: "destination", end, .. })
+                if end == format!("{}x1x1", u32::MAX)
+        ));
+    }
+
+    #[test]
+    fn formats_match_up_to_the_srgb_suffix() {
+        use wgpu::TextureFormat::*;
+
+        assert!(check_formats(Rgba8Unorm, Rgba8UnormSrgb).is_ok());
+        assert!(check_formats(Bgra8UnormSrgb, Bgra8Unorm).is_ok());
+        assert!(matches!(
+            check_formats(Rgba8Unorm, Bgra8Unorm),
+            Err(AppError::CopyFormatMismatch { .. })
+        ));
+        assert!(check_formats(Rgba8Unorm, Rgba16Float).is_err());
+    }
+}
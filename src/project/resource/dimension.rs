@@ -1,3 +1,5 @@
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -12,6 +14,7 @@ use crate::{
 };
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Dimension {
     label: String,
@@ -30,7 +33,26 @@ pub enum DimensionSize {
     Persistent(Size2d),
 }
 
+/// A runtime size is saved without its value, which is only known while the
+/// project runs.
+#[cfg(not(target_arch = "wasm32"))]
+impl JsonSchema for DimensionSize {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "DimensionSize".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "oneOf": [
+                { "const": "runtime" },
+                generator.subschema_for::<Size2d>(),
+            ],
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct DimensionRef {
     pub id: Option<DimensionId>,
@@ -38,6 +60,7 @@ pub struct DimensionRef {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum Axis {
     #[default]
@@ -0,0 +1,129 @@
+//! When a schedule step runs: the [`DispatchPolicy`] shared by compute, render
+//! and copy passes.
+
+use instant::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DispatchPolicy {
+    /// Dispatch only on a frame where one of the pass's inputs changed, or the
+    /// pipeline was just (re)built.
+    #[default]
+    OnChange,
+    /// Dispatch on every rendered frame.
+    EveryFrame,
+    /// Dispatch at a fixed cadence, independent of the framerate.
+    Periodic {
+        #[serde(with = "duration_secs")]
+        #[cfg_attr(not(target_arch = "wasm32"), schemars(with = "f32"))]
+        interval: Duration,
+    },
+    /// Dispatch only when asked to, from the inspector or by resetting the
+    /// simulation.
+    Manual,
+}
+
+impl DispatchPolicy {
+    /// Whether a pass with this policy runs this frame on its own. `changed`
+    /// tells whether its inputs changed; `accumulated` is the time since it last
+    /// ran, only advanced for [`DispatchPolicy::Periodic`].
+    ///
+    /// A requested dispatch runs regardless, see
+    /// [`RuntimeProject::request_dispatch`](crate::project::RuntimeProject::request_dispatch).
+    pub fn should_run(&self, changed: bool, accumulated: &mut Duration, dt: Duration) -> bool {
+        match *self {
+            DispatchPolicy::EveryFrame => true,
+            DispatchPolicy::OnChange => changed,
+            DispatchPolicy::Manual => false,
+            DispatchPolicy::Periodic { interval } => {
+                *accumulated += dt;
+                if *accumulated >= interval {
+                    // Subtract one interval (rather than reset to zero) to keep
+                    // the average cadence accurate. Clamp so a long stall can't
+                    // build up a backlog of catch-up runs.
+                    *accumulated = (*accumulated - interval).min(interval);
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// Serializes a [`Duration`] as plain seconds (e.g. `0.05`) instead of the
+/// verbose `{ secs, nanos }` form, keeping `project.json` readable.
+mod duration_secs {
+    use instant::Duration;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f32(duration.as_secs_f32())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        Ok(Duration::from_secs_f32(f32::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(10);
+
+    fn runs(policy: DispatchPolicy, changed: bool) -> bool {
+        policy.should_run(changed, &mut Duration::ZERO, FRAME)
+    }
+
+    #[test]
+    fn frame_policies_follow_changes() {
+        assert!(runs(DispatchPolicy::EveryFrame, false));
+        assert!(runs(DispatchPolicy::EveryFrame, true));
+        assert!(!runs(DispatchPolicy::OnChange, false));
+        assert!(runs(DispatchPolicy::OnChange, true));
+        assert!(!runs(DispatchPolicy::Manual, false));
+        assert!(!runs(DispatchPolicy::Manual, true));
+    }
+
+    #[test]
+    fn manual_never_runs_on_its_own() {
+        let mut accumulated = Duration::ZERO;
+        for changed in [false, true] {
+            assert!(!DispatchPolicy::Manual.should_run(
+                changed,
+                &mut accumulated,
+                Duration::from_secs(60)
+            ));
+        }
+        assert_eq!(accumulated, Duration::ZERO);
+    }
+
+    #[test]
+    fn periodic_runs_once_per_interval_and_keeps_the_remainder() {
+        let policy = DispatchPolicy::Periodic {
+            interval: Duration::from_millis(25),
+        };
+        let mut accumulated = Duration::ZERO;
+        let ran: Vec<bool> = (0..5)
+            .map(|_| policy.should_run(false, &mut accumulated, FRAME))
+            .collect();
+        assert_eq!(ran, [false, false, true, false, true]);
+        assert_eq!(accumulated, Duration::from_millis(0));
+    }
+
+    #[test]
+    fn periodic_catches_up_at_most_one_interval_after_a_stall() {
+        let interval = Duration::from_millis(25);
+        let policy = DispatchPolicy::Periodic { interval };
+        let mut accumulated = Duration::ZERO;
+        assert!(policy.should_run(false, &mut accumulated, Duration::from_secs(1)));
+        assert_eq!(accumulated, interval);
+        assert!(policy.should_run(false, &mut accumulated, Duration::ZERO));
+        assert!(!policy.should_run(false, &mut accumulated, Duration::ZERO));
+    }
+}
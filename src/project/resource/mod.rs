@@ -1,7 +1,9 @@
 pub mod bindgroup;
 pub mod camera;
 pub mod compute_pass;
+pub mod copy_pass;
 pub mod dimension;
+pub mod dispatch_policy;
 pub mod model;
 pub mod presentation;
 pub mod render_pass;
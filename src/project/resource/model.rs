@@ -1,5 +1,7 @@
 use std::task::Poll;
 
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use slotmap::SecondaryMap;
 use strum::{Display, EnumIter};
@@ -32,6 +34,7 @@ pub struct ModelCreationContext<'a> {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
     label: String,
@@ -71,6 +74,7 @@ pub struct Mesh {
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(tag = "type", content = "value", rename_all = "camelCase")]
 pub enum MeshMaterialSelection {
     #[default]
@@ -334,7 +338,7 @@ impl ModelRuntime {
 }
 
 impl Material {
-    fn from_obj_material(
+    pub fn from_obj_material(
         material: tobj::Material,
         base_path: Option<&FilePath>,
     ) -> AppResult<Material> {
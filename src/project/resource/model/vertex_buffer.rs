@@ -1,13 +1,23 @@
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use strum::{Display, EnumIter};
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+use crate::{
+    error::{AppError, AppResult},
+    project::ModelId,
+    utils::shader_reflection::{self, VertexInput},
+};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct VertexBufferSpec {
     pub fields: Vec<VertexBufferField>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, Display, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum VertexBufferField {
     #[strum(to_string = "Position")]
@@ -51,6 +61,87 @@ impl VertexBufferSpec {
         (attributes, offset)
     }
 
+    /// Checks that every vertex shader input reads an attribute of the same type
+    /// from this spec. On mismatch, the error carries a reordered spec that
+    /// matches the shader, if one can be built from the known attributes.
+    pub fn validate_vertex_inputs(
+        &self,
+        model_id: ModelId,
+        inputs: &[VertexInput],
+    ) -> AppResult<()> {
+        for input in inputs {
+            let field = self.fields.get(input.location as usize);
+            if field.is_some_and(|field| Some(field.vertex_format()) == input.format) {
+                continue;
+            }
+
+            let actual = match field {
+                Some(field) => format!(
+                    "{field} ({})",
+                    shader_reflection::vertex_format_label(field.vertex_format())
+                ),
+                None => "nothing".to_string(),
+            };
+            return Err(AppError::VertexInputMismatch {
+                location: input.location,
+                expected: input.type_label.clone(),
+                actual,
+                model_id,
+                suggested_spec: self.matching_vertex_inputs(inputs),
+            });
+        }
+        Ok(())
+    }
+
+    /// Reorders the attributes so each shader input finds one of its type at its
+    /// location. Inputs are matched by name first (`position`, `uv`, `normal`,
+    /// ...) and then by type, preferring attributes already in the spec.
+    /// Attributes the shader doesn't read are kept after the matched ones.
+    pub fn matching_vertex_inputs(&self, inputs: &[VertexInput]) -> Option<VertexBufferSpec> {
+        let location_count = inputs.iter().map(|input| input.location + 1).max();
+        let mut slots: Vec<Option<VertexBufferField>> =
+            vec![None; location_count.unwrap_or_default() as usize];
+        let mut unused = self.fields.clone();
+
+        for input in inputs {
+            let format = input.format?;
+            let by_name = input
+                .name
+                .as_deref()
+                .and_then(VertexBufferField::from_input_name)
+                .filter(|field| field.vertex_format() == format);
+            let field = by_name
+                .or_else(|| {
+                    unused
+                        .iter()
+                        .copied()
+                        .find(|field| field.vertex_format() == format)
+                })
+                .or_else(|| {
+                    VertexBufferField::iter().find(|field| field.vertex_format() == format)
+                })?;
+
+            if let Some(index) = unused.iter().position(|unused| *unused == field) {
+                unused.remove(index);
+            }
+            slots[input.location as usize] = Some(field);
+        }
+
+        // Locations the shader skips still need an attribute to keep the rest in place.
+        let mut fields = Vec::with_capacity(slots.len() + unused.len());
+        for slot in slots {
+            let field = match slot {
+                Some(field) => field,
+                None if !unused.is_empty() => unused.remove(0),
+                None => VertexBufferField::Position,
+            };
+            fields.push(field);
+        }
+        fields.extend(unused);
+
+        Some(VertexBufferSpec { fields })
+    }
+
     pub(super) fn compute_vertex_contents(
         &self,
         positions: &[[f32; 3]],
@@ -93,6 +184,24 @@ impl VertexBufferSpec {
 }
 
 impl VertexBufferField {
+    /// Guesses the attribute a shader input is meant to read from its name.
+    fn from_input_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if name.contains("bitangent") {
+            Some(VertexBufferField::Bitangent)
+        } else if name.contains("tangent") {
+            Some(VertexBufferField::Tangent)
+        } else if name.contains("normal") {
+            Some(VertexBufferField::Normal)
+        } else if name.contains("uv") || name.contains("tex") {
+            Some(VertexBufferField::TextureCoordinates)
+        } else if name.contains("pos") {
+            Some(VertexBufferField::Position)
+        } else {
+            None
+        }
+    }
+
     pub fn vertex_format(&self) -> wgpu::VertexFormat {
         match self {
             VertexBufferField::Position => wgpu::VertexFormat::Float32x3,
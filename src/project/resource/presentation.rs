@@ -1,36 +1,117 @@
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     project::{
-        ComputePassId, PresentationId, ProjectResource, RenderPassId, ViewportId, sync::Revision,
+        ComputePassId, CopyPassId, PresentationId, ProjectResource, RenderPassId, ResourceId,
+        ViewportId, sync::Revision,
     },
     resource_getters, resource_setters,
 };
 
 #[derive(Default, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Presentation {
-    render_passes: Vec<RenderPassId>,
-    #[serde(default)]
-    compute_passes: Vec<ComputePassId>,
+    steps: Vec<PassStep>,
+    /// Steps kept in the schedule but not run. Their outputs keep whatever
+    /// they last held.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    disabled_steps: Vec<PassStep>,
+    /// Steps that set up a simulation's initial state, rerun by resetting it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    initializer_steps: Vec<PassStep>,
     main_viewport: Option<ViewportId>,
     #[serde(skip)]
     project_revision: Revision,
 }
 
+/// One step of the frame schedule. Steps run in order, so a compute pass can
+/// consume what an earlier render pass drew in the same frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum PassStep {
+    Compute(ComputePassId),
+    Render(RenderPassId),
+    Copy(CopyPassId),
+}
+
+impl From<PassStep> for ResourceId {
+    fn from(step: PassStep) -> Self {
+        match step {
+            PassStep::Compute(id) => id.into(),
+            PassStep::Render(id) => id.into(),
+            PassStep::Copy(id) => id.into(),
+        }
+    }
+}
+
 impl Presentation {
     resource_getters! {
-        pub fn render_passes() -> &[RenderPassId];
-        pub fn compute_passes() -> &[ComputePassId];
+        pub fn steps() -> &[PassStep];
         pub fn main_viewport() -> Option<ViewportId>;
     }
 
     resource_setters! {
         increases: [project_revision];
-        pub fn set_render_passes(render_passes: Vec<RenderPassId>);
-        pub fn set_compute_passes(compute_passes: Vec<ComputePassId>);
         pub fn set_main_viewport(main_viewport: Option<ViewportId>);
     }
+
+    pub fn set_steps(&mut self, steps: Vec<PassStep>) {
+        if self.steps != steps {
+            // Forget the disabled and initializer state of steps that left the
+            // schedule.
+            self.disabled_steps.retain(|step| steps.contains(step));
+            self.initializer_steps.retain(|step| steps.contains(step));
+            self.steps = steps;
+            self.project_revision.increase();
+        }
+    }
+
+    pub fn is_enabled(&self, step: PassStep) -> bool {
+        !self.disabled_steps.contains(&step)
+    }
+
+    /// Whether `step` is scheduled and enabled, so it can run at all.
+    pub fn can_run(&self, step: PassStep) -> bool {
+        self.steps.contains(&step) && self.is_enabled(step)
+    }
+
+    pub fn set_enabled(&mut self, step: PassStep, enabled: bool) {
+        if self.is_enabled(step) == enabled {
+            return;
+        }
+        match enabled {
+            true => self.disabled_steps.retain(|disabled| *disabled != step),
+            false => self.disabled_steps.push(step),
+        }
+        self.project_revision.increase();
+    }
+
+    pub fn is_initializer(&self, step: PassStep) -> bool {
+        self.initializer_steps.contains(&step)
+    }
+
+    pub fn set_initializer(&mut self, step: PassStep, initializer: bool) {
+        if self.is_initializer(step) == initializer {
+            return;
+        }
+        match initializer {
+            true => self.initializer_steps.push(step),
+            false => self.initializer_steps.retain(|other| *other != step),
+        }
+        self.project_revision.increase();
+    }
+
+    /// The initializer steps, in execution order.
+    pub fn initializer_steps(&self) -> impl Iterator<Item = PassStep> + '_ {
+        self.steps
+            .iter()
+            .copied()
+            .filter(|step| self.is_initializer(*step))
+    }
 }
 
 impl ProjectResource for Presentation {
@@ -44,3 +125,60 @@ impl ProjectResource for Presentation {
         self.project_revision
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use slotmap::SlotMap;
+
+    use super::*;
+    use crate::project::RuntimeProject;
+
+    fn steps() -> [PassStep; 3] {
+        let mut compute_passes = SlotMap::<ComputePassId, ()>::with_key();
+        let mut copy_passes = SlotMap::<CopyPassId, ()>::with_key();
+        [
+            PassStep::Compute(compute_passes.insert(())),
+            PassStep::Copy(copy_passes.insert(())),
+            PassStep::Compute(compute_passes.insert(())),
+        ]
+    }
+
+    #[test]
+    fn initializer_steps_follow_the_schedule_order() {
+        let [seed, copy, simulate] = steps();
+        let mut presentation = Presentation::default();
+        presentation.set_steps(vec![seed, copy, simulate]);
+        presentation.set_initializer(simulate, true);
+        presentation.set_initializer(seed, true);
+
+        assert_eq!(
+            presentation.initializer_steps().collect::<Vec<_>>(),
+            [seed, simulate]
+        );
+
+        presentation.set_steps(vec![simulate, copy, seed]);
+        assert_eq!(
+            presentation.initializer_steps().collect::<Vec<_>>(),
+            [simulate, seed]
+        );
+    }
+
+    #[test]
+    fn requests_only_stay_for_steps_that_can_run() {
+        let [compute, copy, unscheduled] = steps();
+        let mut presentation = Presentation::default();
+        presentation.set_steps(vec![compute, copy]);
+        presentation.set_enabled(copy, false);
+
+        let mut runtime = RuntimeProject::default();
+        for step in [compute, copy, unscheduled] {
+            runtime.request_dispatch(&presentation, step);
+        }
+        assert_eq!(runtime.requested_compute_passes.len(), 1);
+        assert!(runtime.requested_copy_passes.is_empty());
+
+        presentation.set_steps(vec![copy]);
+        runtime.drop_stale_requests(&presentation);
+        assert!(runtime.requested_compute_passes.is_empty());
+    }
+}
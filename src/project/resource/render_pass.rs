@@ -1,13 +1,18 @@
-use std::task::Poll;
+use std::{ops::Range, task::Poll};
 
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{AppResult, RequiredFieldExt},
     project::{
-        Creatable, ProjectResource, RenderPassId, RenderPipelineId, TextureViewId,
+        BindGroupId, Creatable, ModelId, ProjectResource, RenderPassId, RenderPipelineId,
+        TextureId, TextureViewId,
+        ping_pong::{SwapVariants, Swapped},
         resource::{
             bindgroup::BindGroup,
+            dispatch_policy::DispatchPolicy,
             model::Model,
             render_pipeline::{BindGroupTarget, RenderDrawStrategy, RenderPipeline},
             texture_view::TextureView,
@@ -30,12 +35,16 @@ pub struct Context<'a> {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct RenderPass {
     label: String,
     target: RenderPassTarget<Color>,
     depth_target: Option<RenderPassTarget<f32>>,
     pipelines: Vec<RenderPipelineId>,
+    /// When the pass redraws. Skipped frames keep the targets' last contents.
+    #[serde(default = "default_dispatch_policy")]
+    dispatch_policy: DispatchPolicy,
     #[serde(skip)]
     runtime_revision: Revision,
     #[serde(skip)]
@@ -43,6 +52,7 @@ pub struct RenderPass {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct RenderPassTarget<T> {
     texture_view_id: Option<TextureViewId>,
@@ -50,6 +60,7 @@ pub struct RenderPassTarget<T> {
 }
 
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(tag = "type", content = "value", rename_all = "camelCase")]
 pub enum LoadOperation<T> {
     Clear(T),
@@ -57,11 +68,38 @@ pub enum LoadOperation<T> {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase", transparent)]
 pub struct Color(pub [f32; 4]);
 
 pub struct RenderPassRuntime {
-    bundle: wgpu::RenderBundle,
+    bundles: SwapVariants<wgpu::RenderBundle>,
+    /// What the bundles were recorded with, since a bundle can't be inspected.
+    commands: Vec<DrawCommand>,
+}
+
+/// One command recorded into a render pass's bundle, as the frame capture
+/// shows it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCommand {
+    SetPipeline(RenderPipelineId),
+    SetBindGroup {
+        slot: u32,
+        bind_group: Option<BindGroupId>,
+    },
+    /// Binds the vertex and index buffers of one of the model's meshes.
+    SetMesh {
+        model: ModelId,
+        mesh: usize,
+    },
+    Draw {
+        vertices: Range<u32>,
+        instances: Range<u32>,
+    },
+    DrawIndexed {
+        indices: Range<u32>,
+        instances: Range<u32>,
+    },
 }
 
 #[derive(Default)]
@@ -71,6 +109,12 @@ pub enum RenderPassJob {
     Validation(RenderPassRuntime, AsyncJob<AppResult<()>>),
 }
 
+/// Render passes redraw every frame unless told otherwise, unlike compute
+/// passes, since their output is usually on screen.
+fn default_dispatch_policy() -> DispatchPolicy {
+    DispatchPolicy::EveryFrame
+}
+
 struct AttachmentFormats {
     color: wgpu::TextureFormat,
     depth: Option<wgpu::TextureFormat>,
@@ -83,6 +127,7 @@ impl Creatable for RenderPass {
             target: Default::default(),
             depth_target: Default::default(),
             pipelines: Default::default(),
+            dispatch_policy: default_dispatch_policy(),
             runtime_revision: Default::default(),
             project_revision: Default::default(),
         }
@@ -112,6 +157,7 @@ impl RenderPass {
             target,
             depth_target,
             pipelines: Default::default(),
+            dispatch_policy: default_dispatch_policy(),
             runtime_revision: Default::default(),
             project_revision: Default::default(),
         }
@@ -121,6 +167,7 @@ impl RenderPass {
         pub fn target() -> RenderPassTarget<Color>;
         pub fn depth_target() -> Option<RenderPassTarget<f32>>;
         pub fn pipelines() -> &[RenderPipelineId];
+        pub fn dispatch_policy() -> DispatchPolicy;
     }
 
     resource_setters! {
@@ -131,6 +178,31 @@ impl RenderPass {
         pub fn set_pipelines(pipelines: Vec<RenderPipelineId>);
     }
 
+    resource_setters! {
+        increases: [project_revision];
+        pub fn set_dispatch_policy(dispatch_policy: DispatchPolicy);
+    }
+
+    /// Whether any of this pass's inputs changed their data this frame: its
+    /// pipelines, or the bind groups they bind, such as one over a rewritten
+    /// uniform. Used by [`DispatchPolicy::OnChange`] to decide whether to
+    /// redraw.
+    pub fn inputs_changed(
+        &self,
+        tracker: &SyncTracker,
+        render_pipelines: &Storage<RenderPipeline>,
+    ) -> bool {
+        self.pipelines.iter().any(|&pipeline_id| {
+            tracker.was_data_changed(pipeline_id)
+                || render_pipelines.get(pipeline_id).is_ok_and(|pipeline| {
+                    pipeline.bind_groups().iter().any(|target| match target {
+                        BindGroupTarget::Static(id) => tracker.was_data_changed(*id),
+                        BindGroupTarget::Empty | BindGroupTarget::ModelMaterial => false,
+                    })
+                })
+        })
+    }
+
     /// The texture views this pass renders into, color first.
     fn target_texture_view_ids(&self) -> impl Iterator<Item = TextureViewId> {
         let depth = self.depth_target.as_ref();
@@ -177,7 +249,42 @@ impl RenderPass {
         }))
     }
 
-    /// Records every pipeline's draw commands into `encoder`.
+    /// The double-buffered textures followed by the bind groups this pass sets.
+    fn swap_textures(&self, ctx: &Context) -> Vec<TextureId> {
+        let mut bind_group_ids = vec![];
+        for pipeline in self
+            .pipelines
+            .iter()
+            .filter_map(|id| ctx.render_pipelines.get(*id).ok())
+        {
+            for target in pipeline.bind_groups() {
+                match (target, pipeline.draw_strategy()) {
+                    (BindGroupTarget::Static(id), _) => bind_group_ids.push(*id),
+                    (
+                        BindGroupTarget::ModelMaterial,
+                        RenderDrawStrategy::Model {
+                            model_id: Some(model_id),
+                            ..
+                        },
+                    ) => {
+                        if let Ok(model) = ctx.models.get(*model_id) {
+                            bind_group_ids.extend(model.get_material_bind_group_ids());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        bind_group_ids
+            .into_iter()
+            .filter_map(|id| ctx.runtime_bind_groups.get_init(id).ok().flatten())
+            .flat_map(|bind_group| bind_group.swap_textures().iter().copied())
+            .collect()
+    }
+
+    /// Records every pipeline's draw commands into `encoder`, with the bind
+    /// groups' variants for `swapped`, and logs them into `commands`.
     ///
     /// Returns `Ok(None)` if a runtime resource (pipeline, bind group, model) is
     /// still rebuilding.
@@ -185,6 +292,8 @@ impl RenderPass {
         &self,
         encoder: &mut wgpu::RenderBundleEncoder<'enc>,
         ctx: &Context<'enc>,
+        swapped: Swapped,
+        commands: &mut Vec<DrawCommand>,
     ) -> AppResult<bool> {
         let Context {
             models,
@@ -202,6 +311,7 @@ impl RenderPass {
             };
 
             encoder.set_pipeline(&pipeline_runtime.inner);
+            commands.push(DrawCommand::SetPipeline(*id));
 
             let mut material_bind_group_slots = vec![];
             for (slot, bind_group_target) in pipeline.bind_groups().iter().enumerate() {
@@ -209,12 +319,20 @@ impl RenderPass {
                 match bind_group_target {
                     BindGroupTarget::Empty => {
                         encoder.set_bind_group(slot, None, &[]);
+                        commands.push(DrawCommand::SetBindGroup {
+                            slot,
+                            bind_group: None,
+                        });
                     }
                     BindGroupTarget::Static(id) => {
                         let Some(bind_group) = runtime_bind_groups.get_init(*id)? else {
                             return Ok(false); // pending: static bind group not ready
                         };
-                        encoder.set_bind_group(slot, bind_group.inner(), &[]);
+                        encoder.set_bind_group(slot, bind_group.variant(swapped), &[]);
+                        commands.push(DrawCommand::SetBindGroup {
+                            slot,
+                            bind_group: Some(*id),
+                        });
                     }
                     BindGroupTarget::ModelMaterial => {
                         material_bind_group_slots.push(slot);
@@ -242,6 +360,10 @@ impl RenderPass {
 
                         let index_buffer = mesh.index_buffer().inner().slice(..);
                         encoder.set_index_buffer(index_buffer, wgpu::IndexFormat::Uint32);
+                        commands.push(DrawCommand::SetMesh {
+                            model: model_id,
+                            mesh: mesh_index,
+                        });
 
                         if !material_bind_group_slots.is_empty() {
                             let material_index = model
@@ -267,18 +389,32 @@ impl RenderPass {
                             };
 
                             for slot in &material_bind_group_slots {
-                                encoder.set_bind_group(*slot, bind_group.inner(), &[]);
+                                encoder.set_bind_group(*slot, bind_group.variant(swapped), &[]);
+                                commands.push(DrawCommand::SetBindGroup {
+                                    slot: *slot,
+                                    bind_group: Some(bind_group_id),
+                                });
                             }
                         }
 
                         let index_num = mesh.indices().len() as u32;
                         encoder.draw_indexed(0..index_num, 0, instances.clone());
+                        commands.push(DrawCommand::DrawIndexed {
+                            indices: 0..index_num,
+                            instances: instances.clone(),
+                        });
                     }
                 }
                 RenderDrawStrategy::Direct {
                     vertices,
                     instances,
-                } => encoder.draw(vertices.clone(), instances.clone()),
+                } => {
+                    encoder.draw(vertices.clone(), instances.clone());
+                    commands.push(DrawCommand::Draw {
+                        vertices: vertices.clone(),
+                        instances: instances.clone(),
+                    });
+                }
             }
         }
 
@@ -288,7 +424,16 @@ impl RenderPass {
 
 impl RenderPassRuntime {
     pub fn bundle(&self) -> &wgpu::RenderBundle {
-        &self.bundle
+        self.bundles.current()
+    }
+
+    pub fn swap(&mut self, texture: TextureId) {
+        self.bundles.swap(texture);
+    }
+
+    /// The commands the bundle was recorded with.
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
     }
 }
 
@@ -314,6 +459,15 @@ impl SyncResource for RenderPass {
         targets_recreated || pipelines_recreated
     }
 
+    fn forwards_data_changes(
+        &self,
+        _: Self::Id,
+        ctx: &Self::Context<'_>,
+        tracker: &SyncTracker,
+    ) -> bool {
+        self.inputs_changed(tracker, ctx.render_pipelines)
+    }
+
     fn sync<'a>(
         &self,
         _id: Self::Id,
@@ -336,29 +490,46 @@ impl SyncResource for RenderPass {
 
         let scope = WgpuErrorScope::push(ctx.device);
 
-        let mut encoder =
-            ctx.device
-                .create_render_bundle_encoder(&wgpu::RenderBundleEncoderDescriptor {
-                    label: Some(&self.label),
-                    color_formats: &[Some(formats.color)],
-                    depth_stencil: formats.depth.map(|format| wgpu::RenderBundleDepthStencil {
-                        format,
-                        depth_read_only: false,
-                        stencil_read_only: true,
-                    }),
-                    sample_count: 1,
-                    multiview: None,
-                });
-
-        if !self.record(&mut encoder, ctx)? {
+        // Bind groups following double-buffered textures get a bundle per
+        // arrangement, so a swap never re-records the pass.
+        let shared: &Context = ctx;
+        let mut commands = None;
+        let bundles = SwapVariants::build(self.swap_textures(shared), |swapped| {
+            let mut encoder =
+                shared
+                    .device
+                    .create_render_bundle_encoder(&wgpu::RenderBundleEncoderDescriptor {
+                        label: Some(&self.label),
+                        color_formats: &[Some(formats.color)],
+                        depth_stencil: formats.depth.map(|format| wgpu::RenderBundleDepthStencil {
+                            format,
+                            depth_read_only: false,
+                            stencil_read_only: true,
+                        }),
+                        sample_count: 1,
+                        multiview: None,
+                    });
+
+            let mut recorded = vec![];
+            if !self.record(&mut encoder, shared, swapped, &mut recorded)? {
+                return Ok(None);
+            }
+            // Every arrangement records the same commands, only with other
+            // variants of the bind groups.
+            commands.get_or_insert(recorded);
+
+            Ok(Some(encoder.finish(&wgpu::RenderBundleDescriptor {
+                label: Some(&self.label),
+            })))
+        })?;
+        let Some(bundles) = bundles else {
             return Ok(SyncOutcome::Pending(RenderPassJob::Start));
-        }
-
-        let bundle = encoder.finish(&wgpu::RenderBundleDescriptor {
-            label: Some(&self.label),
-        });
+        };
 
-        let runtime = RenderPassRuntime { bundle };
+        let runtime = RenderPassRuntime {
+            bundles,
+            commands: commands.unwrap_or_default(),
+        };
         let job = RenderPassJob::Validation(runtime, scope.pop());
         self.sync(_id, ctx, None, job)
     }
@@ -430,3 +601,35 @@ impl From<Color> for wgpu::Color {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inputs_changed_follows_pipeline_bind_groups() {
+        let mut bind_groups = Storage::<BindGroup>::default();
+        let params = bind_groups.create("Params".to_string());
+        let unbound = bind_groups.create("Unbound".to_string());
+        let mut render_pipelines = Storage::<RenderPipeline>::default();
+        let pipeline = render_pipelines.create("Draw".to_string());
+        render_pipelines
+            .get_mut(pipeline)
+            .unwrap()
+            .set_bind_groups(vec![BindGroupTarget::Static(params)]);
+        let mut render_pass = RenderPass::new("Draw", Default::default(), None);
+        render_pass.set_pipelines(vec![pipeline]);
+
+        let mut tracker = SyncTracker::default();
+        tracker.push_data_changes([unbound.into()]);
+        assert!(!render_pass.inputs_changed(&tracker, &render_pipelines));
+
+        // A bind group over a rewritten uniform forwards the change.
+        tracker.push_data_changes([params.into()]);
+        assert!(render_pass.inputs_changed(&tracker, &render_pipelines));
+
+        let mut tracker = SyncTracker::default();
+        tracker.push_data_changes([pipeline.into()]);
+        assert!(render_pass.inputs_changed(&tracker, &render_pipelines));
+    }
+}
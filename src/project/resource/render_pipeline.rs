@@ -1,5 +1,7 @@
 use std::{ops::Range, task::Poll};
 
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -13,6 +15,7 @@ use crate::{
     resource_getters, resource_setters,
     utils::{
         async_job::AsyncJob,
+        shader_reflection,
         validate_bind_group_layouts::validate_bind_group_layouts,
         wgpu_error_scope::WgpuErrorScope,
         wgpu_utils::{PrimitiveState, TextureFormat},
@@ -20,6 +23,7 @@ use crate::{
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct RenderPipeline {
     label: String,
@@ -39,6 +43,7 @@ pub struct RenderPipeline {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(
     tag = "type",
     rename_all = "camelCase",
@@ -61,6 +66,7 @@ pub enum RenderDrawStrategy {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum BindGroupTarget {
     #[default]
@@ -247,6 +253,34 @@ impl SyncResource for RenderPipeline {
         draw_strategy_needs_rebuild || shaders_needs_rebuild || bind_groups_needs_rebuild
     }
 
+    fn forwards_data_changes(
+        &self,
+        _: Self::Id,
+        ctx: &Self::Context<'_>,
+        tracker: &SyncTracker,
+    ) -> bool {
+        let model_id = match self.draw_strategy {
+            RenderDrawStrategy::Model { model_id, .. } => model_id,
+            RenderDrawStrategy::Direct { .. } => None,
+        };
+        let model = model_id.and_then(|id| ctx.models.get(id).ok());
+        let material_bind_groups = model
+            .map(|model| model.material_bind_group_ids())
+            .unwrap_or_default()
+            .iter()
+            .flatten();
+
+        let static_bind_groups = self.bind_groups.iter().filter_map(|target| match target {
+            BindGroupTarget::Static(id) => Some(id),
+            BindGroupTarget::Empty | BindGroupTarget::ModelMaterial => None,
+        });
+
+        model_id.is_some_and(|id| tracker.was_data_changed(id))
+            || static_bind_groups
+                .chain(material_bind_groups)
+                .any(|id| tracker.was_data_changed(*id))
+    }
+
     fn sync<'a>(
         &self,
         _id: Self::Id,
@@ -282,6 +316,8 @@ impl SyncResource for RenderPipeline {
                 let model_id = model_id.ok_or_uninit_field("Draw Strategy Model Id")?;
                 let model = ctx.models.get(model_id)?;
                 let spec = model.vertex_buffer_spec();
+                let inputs = shader_reflection::vertex_inputs(vertex_shader.module());
+                spec.validate_vertex_inputs(model_id, &inputs)?;
                 Some(spec.to_wgpu_attributes_and_stride())
             }
             RenderDrawStrategy::Direct { .. } => None,
@@ -300,6 +336,7 @@ impl SyncResource for RenderPipeline {
         let bind_group_ids = self.collect_bind_group_ids(ctx.models)?;
 
         let mut bind_group_layouts = vec![];
+        let mut bind_group_entries = vec![];
         for id in &bind_group_ids {
             let result = match id {
                 Some(id) => {
@@ -308,15 +345,28 @@ impl SyncResource for RenderPipeline {
                         return Ok(SyncOutcome::Pending(RenderPipelineCreationJob::Start));
                     };
 
-                    Some(bind_group.inner_layout())
+                    Some(bind_group)
                 }
                 None => None,
             };
 
-            bind_group_layouts.push(result);
+            bind_group_layouts.push(result.map(|bind_group| bind_group.inner_layout()));
+            bind_group_entries.push(result.map(|bind_group| bind_group.layout_entries()));
         }
 
         validate_bind_group_layouts(&bind_group_layouts, &ctx.device.limits())?;
+        shader_reflection::validate_bindings(
+            vertex_shader.module(),
+            vertex_shader.info(),
+            naga::ShaderStage::Vertex,
+            &bind_group_entries,
+        )?;
+        shader_reflection::validate_bindings(
+            fragment_shader.module(),
+            fragment_shader.info(),
+            naga::ShaderStage::Fragment,
+            &bind_group_entries,
+        )?;
 
         let scope = WgpuErrorScope::push(ctx.device);
 
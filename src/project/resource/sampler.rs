@@ -1,7 +1,11 @@
 use std::task::Poll;
 
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::project::schema;
 use crate::{
     error::AppResult,
     project::{
@@ -12,14 +16,31 @@ use crate::{
 };
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct SamplerSpec {
     pub address_mode: AddressMode,
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        schemars(schema_with = "schema::filter_mode")
+    )]
     pub mag_filter: wgpu::FilterMode,
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        schemars(schema_with = "schema::filter_mode")
+    )]
     pub min_filter: wgpu::FilterMode,
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        schemars(schema_with = "schema::filter_mode")
+    )]
     pub mipmap_filter: wgpu::MipmapFilterMode,
     pub lod_min_clamp: f32,
     pub lod_max_clamp: f32,
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        schemars(schema_with = "schema::optional_compare_function")
+    )]
     pub compare: Option<wgpu::CompareFunction>,
 }
 
@@ -38,6 +59,7 @@ impl Default for SamplerSpec {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Sampler {
     label: String,
@@ -1,5 +1,7 @@
 use std::task::Poll;
 
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -17,6 +19,7 @@ use crate::{
 };
 
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Shader {
     label: String,
@@ -30,6 +33,10 @@ pub struct Shader {
 
 pub struct ShaderRuntime {
     inner: wgpu::ShaderModule,
+    /// The validated naga module the shader was compiled from, kept for
+    /// reflection.
+    module: naga::Module,
+    info: naga::valid::ModuleInfo,
 }
 
 #[derive(Default)]
@@ -71,6 +78,14 @@ impl ShaderRuntime {
     pub fn inner(&self) -> &wgpu::ShaderModule {
         &self.inner
     }
+
+    pub fn module(&self) -> &naga::Module {
+        &self.module
+    }
+
+    pub fn info(&self) -> &naga::valid::ModuleInfo {
+        &self.info
+    }
 }
 
 impl Creatable for Shader {
@@ -150,10 +165,16 @@ impl SyncResource for Shader {
         let kind = ShaderSourceKind::from_extension(extension)
             .ok_or_else(|| AppError::UnsupportedShaderExtension(extension.to_string()))?;
 
+        let (module, info) = utils::wgpu_utils::parse_shader(&source, kind)?;
+
         let scope = WgpuErrorScope::push(ctx.device);
-        let inner = utils::wgpu_utils::compile_shader(ctx.device, &self.label, &source, kind)?;
+        let inner = utils::wgpu_utils::compile_shader(ctx.device, &self.label, module.clone());
 
-        let runtime = ShaderRuntime { inner };
+        let runtime = ShaderRuntime {
+            inner,
+            module,
+            info,
+        };
         self.sync(_id, ctx, None, ShaderJob::Validation(runtime, scope.pop()))
     }
 
@@ -1,15 +1,19 @@
 use half::f16;
 use image::GenericImageView;
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::task::Poll;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::project::schema;
 use crate::{
     error::{AppError, AppResult},
     file::file_storage::FileStorage,
     project::{
         Creatable, DimensionId, ProjectResource, TextureId,
         paths::FilePath,
-        resource::dimension::Dimension,
+        resource::{dimension::Dimension, presentation::PassStep, texture_view::BufferSide},
         storage::Storage,
         sync::{Revision, SyncOutcome, SyncResource, SyncTracker},
     },
@@ -27,12 +31,21 @@ pub struct TextureCreationContext<'a> {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Texture {
     label: String,
     format: TextureFormat,
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        schemars(schema_with = "schema::texture_usages")
+    )]
     usage: wgpu::TextureUsages,
     source: TextureSource,
+    /// Makes the texture double-buffered: a front and a back of the same size
+    /// and format, swapped when the trigger fires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ping_pong: Option<SwapTrigger>,
     #[serde(skip)]
     runtime_revision: Revision,
     #[serde(skip)]
@@ -41,6 +54,18 @@ pub struct Texture {
 
 pub struct TextureRuntime {
     inner: wgpu::Texture,
+    back: Option<wgpu::Texture>,
+}
+
+/// When a double-buffered texture swaps its front and back.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum SwapTrigger {
+    /// After every frame.
+    EveryFrame,
+    /// After every frame the step ran in.
+    AfterStep(Option<PassStep>),
 }
 
 #[derive(Default)]
@@ -52,6 +77,7 @@ pub enum TextureJob {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(tag = "type", content = "value", rename_all = "camelCase")]
 pub enum TextureSource {
     // Grab size from dimension
@@ -61,6 +87,10 @@ pub enum TextureSource {
     },
     Image(Option<FilePath>),
     Manual {
+        #[cfg_attr(
+            not(target_arch = "wasm32"),
+            schemars(schema_with = "schema::extent_3d")
+        )]
         size: wgpu::Extent3d,
     },
 }
@@ -90,15 +120,23 @@ impl Texture {
             format,
             usage,
             source,
+            ping_pong: None,
             runtime_revision: Revision::default(),
             project_revision: Revision::default(),
         }
     }
 
+    /// Makes the texture double-buffered, swapping on `trigger`.
+    pub fn with_ping_pong(mut self, trigger: SwapTrigger) -> Self {
+        self.ping_pong = Some(trigger);
+        self
+    }
+
     resource_getters! {
         pub fn format() -> TextureFormat;
         pub fn usage() -> wgpu::TextureUsages;
         pub fn source() -> &TextureSource;
+        pub fn ping_pong() -> Option<SwapTrigger>;
     }
 
     resource_setters! {
@@ -108,12 +146,57 @@ impl Texture {
         pub fn set_usage(usage: wgpu::TextureUsages);
         pub fn set_source(source: TextureSource);
     }
+
+    pub fn set_ping_pong(&mut self, ping_pong: Option<SwapTrigger>) {
+        // Only turning double-buffering on or off changes the GPU textures.
+        if self.ping_pong.is_some() != ping_pong.is_some() {
+            self.runtime_revision.increase();
+        }
+        self.ping_pong = ping_pong;
+        self.project_revision.increase();
+    }
+
+    fn create_texture(
+        &self,
+        device: &wgpu::Device,
+        label: &str,
+        size: wgpu::Extent3d,
+        view_formats: &[wgpu::TextureFormat],
+    ) -> wgpu::Texture {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format.to_wgpu(),
+            usage: self.usage,
+            view_formats,
+        })
+    }
 }
 
 impl TextureRuntime {
+    /// The texture, or the front of a double-buffered one.
     pub fn inner(&self) -> &wgpu::Texture {
         &self.inner
     }
+
+    /// The front or back of the texture. Only double-buffered textures have a
+    /// back.
+    pub fn side(&self, side: BufferSide) -> Option<&wgpu::Texture> {
+        match side {
+            BufferSide::Front => Some(&self.inner),
+            BufferSide::Back => self.back.as_ref(),
+        }
+    }
+
+    /// Swaps the front and back of a double-buffered texture.
+    pub fn swap(&mut self) {
+        if let Some(back) = &mut self.back {
+            std::mem::swap(&mut self.inner, back);
+        }
+    }
 }
 
 impl Creatable for Texture {
@@ -123,6 +206,7 @@ impl Creatable for Texture {
             format: TextureFormat::Rgba8UnormSrgb,
             usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
             source: TextureSource::Image(None),
+            ping_pong: None,
             runtime_revision: Revision::default(),
             project_revision: Revision::default(),
         }
@@ -226,22 +310,23 @@ impl SyncResource for Texture {
 
         let scope = WgpuErrorScope::push(ctx.device);
 
-        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
-            label: Some(&self.label),
-            size,
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: self.format.to_wgpu(),
-            usage: self.usage,
-            view_formats: &view_formats,
+        let texture = self.create_texture(ctx.device, &self.label, size, &view_formats);
+        let back = self.ping_pong.map(|_| {
+            let label = format!("{} (Back)", self.label);
+            self.create_texture(ctx.device, &label, size, &view_formats)
         });
 
-        if let Some(image_to_write) = image_to_write {
+        // Both halves of a double-buffered texture start out with the image.
+        let written = image_to_write.iter().flat_map(|image| {
+            std::iter::once(&texture)
+                .chain(&back)
+                .map(move |texture| (image, texture))
+        });
+        for (image_to_write, texture) in written {
             match self.format {
                 TextureFormat::Rgba32Float => {
                     let rgba = image_to_write.to_rgba32f();
-                    write_image_to_texture(ctx.queue, &texture, &rgba, size);
+                    write_image_to_texture(ctx.queue, texture, &rgba, size);
                 }
                 TextureFormat::Rgba16Float => {
                     // `to_rgba16` returns the result in `u16` format (0 black, 65535 white)
@@ -255,7 +340,7 @@ impl SyncResource for Texture {
                     let bytes_per_row = rgba.width() * 4 * size_of::<f16>() as u32;
                     write_bytes_to_texture(
                         ctx.queue,
-                        &texture,
+                        texture,
                         bytemuck::cast_slice(&halves),
                         bytes_per_row,
                         size,
@@ -263,18 +348,21 @@ impl SyncResource for Texture {
                 }
                 TextureFormat::R32Float => {
                     let luma = image_to_write.to_luma32f();
-                    write_image_to_texture(ctx.queue, &texture, &luma, size);
+                    write_image_to_texture(ctx.queue, texture, &luma, size);
                 }
                 TextureFormat::Rgba8UnormSrgb
                 | TextureFormat::Rgba8Unorm
                 | TextureFormat::Depth32Float => {
                     let rgba = image_to_write.to_rgba8();
-                    write_image_to_texture(ctx.queue, &texture, &rgba, size);
+                    write_image_to_texture(ctx.queue, texture, &rgba, size);
                 }
             }
         }
 
-        let runtime = TextureRuntime { inner: texture };
+        let runtime = TextureRuntime {
+            inner: texture,
+            back,
+        };
         self.sync(_id, ctx, None, TextureJob::Validation(runtime, scope.pop()))
     }
 
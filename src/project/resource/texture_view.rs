@@ -1,12 +1,17 @@
 use std::task::Poll;
 
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::project::schema;
 use crate::{
     error::{AppError, AppResult},
     project::{
         Creatable, ProjectResource, TextureId, TextureViewId,
-        resource::texture::{Texture, TextureRuntime},
+        ping_pong::{SwapVariants, Swapped},
+        resource::texture::Texture,
         storage::{RuntimeStorage, Storage},
         sync::{Revision, SyncOutcome, SyncResource, SyncTracker},
     },
@@ -14,6 +19,10 @@ use crate::{
     utils::{async_job::AsyncJob, wgpu_error_scope::WgpuErrorScope, wgpu_utils::TextureFormat},
 };
 
+pub mod preview;
+
+use preview::{PreviewPipeline, PreviewRemap, PreviewTarget};
+
 pub struct TextureViewCreationContext<'a> {
     pub textures: &'a Storage<Texture>,
     pub textures_runtime: &'a RuntimeStorage<Texture>,
@@ -23,12 +32,23 @@ pub struct TextureViewCreationContext<'a> {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TextureView {
     label: String,
     format: Option<TextureViewFormat>,
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        schemars(schema_with = "schema::optional_texture_view_dimension")
+    )]
     dimension: Option<wgpu::TextureViewDimension>,
     texture_id: Option<TextureId>,
+    /// Which half of a double-buffered texture the view shows.
+    #[serde(default)]
+    side: BufferSide,
+    /// How the view is previewed when egui can't show its format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    preview_remap: Option<PreviewRemap>,
     #[serde(skip)]
     runtime_revision: Revision,
     #[serde(skip)]
@@ -36,10 +56,19 @@ pub struct TextureView {
 }
 
 pub struct TextureViewRuntime {
-    inner: wgpu::TextureView,
-    egui_id: Option<egui::TextureId>,
+    variants: SwapVariants<ViewVariant>,
     format: Option<wgpu::TextureFormat>,
     dimension: Option<wgpu::TextureViewDimension>,
+    /// Converts the halves into their preview proxies, for formats egui can't
+    /// show.
+    preview: Option<PreviewPipeline>,
+}
+
+/// The view of one of the texture's halves.
+struct ViewVariant {
+    inner: wgpu::TextureView,
+    egui_id: Option<egui::TextureId>,
+    preview: Option<PreviewTarget>,
 }
 
 #[derive(Default)]
@@ -47,16 +76,39 @@ pub enum TextureViewJob {
     #[default]
     Start,
     PendingResource {
-        previous_egui_id: Option<egui::TextureId>,
+        previous_egui_ids: Vec<egui::TextureId>,
     },
     Validation(TextureViewRuntime, AsyncJob<AppResult<()>>),
 }
 
+/// A half of a double-buffered texture. Passes read the front, which holds the
+/// last finished frame, and write the back; a swap turns one into the other.
+///
+/// Plain textures only have a front.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum BufferSide {
+    #[default]
+    Front,
+    Back,
+}
+
+impl BufferSide {
+    pub fn other(self) -> Self {
+        match self {
+            BufferSide::Front => BufferSide::Back,
+            BufferSide::Back => BufferSide::Front,
+        }
+    }
+}
+
 /// As currently the texture view format is only allowed to change by srgb-ness
 /// This will allow the user to easily specify it
 ///
 /// Check [`wgpu::wgt::TextureDescriptor::view_formats`]
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum TextureViewFormat {
     Srgb,
@@ -75,15 +127,25 @@ impl TextureView {
             format,
             dimension,
             texture_id,
+            side: BufferSide::Front,
+            preview_remap: None,
             runtime_revision: Revision::default(),
             project_revision: Revision::default(),
         }
     }
 
+    /// Shows the `side` half of a double-buffered texture.
+    pub fn with_side(mut self, side: BufferSide) -> Self {
+        self.side = side;
+        self
+    }
+
     resource_getters! {
         pub fn texture_id() -> Option<TextureId>;
         pub fn format() -> Option<TextureViewFormat>;
         pub fn dimension() -> Option<wgpu::TextureViewDimension>;
+        pub fn side() -> BufferSide;
+        pub fn preview_remap() -> Option<PreviewRemap>;
     }
 
     resource_setters! {
@@ -92,12 +154,26 @@ impl TextureView {
         pub fn set_texture_id(texture_id: Option<TextureId>);
         pub fn set_format(format: Option<TextureViewFormat>);
         pub fn set_dimension(dimension: Option<wgpu::TextureViewDimension>);
+        pub fn set_side(side: BufferSide);
+        pub fn set_preview_remap(preview_remap: Option<PreviewRemap>);
+    }
+
+    /// Changes the settings of the view's remap. Unlike
+    /// [`Self::set_preview_remap`], the preview isn't rebuilt: the settings are
+    /// uploaded each frame.
+    pub fn update_preview_remap(&mut self, remap: PreviewRemap) {
+        if let Some(current) = &mut self.preview_remap
+            && *current != remap
+        {
+            *current = remap;
+            self.project_revision.increase();
+        }
     }
 
     fn create_view(
         label: &str,
         texture: &Texture,
-        runtime: &TextureRuntime,
+        runtime: &wgpu::Texture,
         format: Option<TextureViewFormat>,
         dimension: Option<wgpu::TextureViewDimension>,
         downlevel_flags: wgpu::DownlevelFlags,
@@ -114,7 +190,7 @@ impl TextureView {
             _ => None,
         };
 
-        let inner = runtime.inner().create_view(&wgpu::TextureViewDescriptor {
+        let inner = runtime.create_view(&wgpu::TextureViewDescriptor {
             label: Some(label),
             format: wgpu_format,
             dimension,
@@ -126,14 +202,14 @@ impl TextureView {
 
     fn create_egui_view(
         label: &str,
-        runtime: &TextureRuntime,
+        runtime: &wgpu::Texture,
         dimension: Option<wgpu::TextureViewDimension>,
         downlevel_flags: wgpu::DownlevelFlags,
     ) -> wgpu::TextureView {
         let supports_view_formats = downlevel_flags.contains(wgpu::DownlevelFlags::VIEW_FORMATS);
-        let format = supports_view_formats.then(|| runtime.inner().format().remove_srgb_suffix());
+        let format = supports_view_formats.then(|| runtime.format().remove_srgb_suffix());
 
-        runtime.inner().create_view(&wgpu::TextureViewDescriptor {
+        runtime.create_view(&wgpu::TextureViewDescriptor {
             label: Some(label),
             format,
             dimension,
@@ -144,27 +220,60 @@ impl TextureView {
 
 impl TextureViewRuntime {
     pub fn inner(&self) -> &wgpu::TextureView {
-        &self.inner
+        &self.variants.current().inner
+    }
+
+    /// The view with the double-buffered textures in `swapped` swapped, for
+    /// prebuilding what follows a swap.
+    pub fn variant(&self, swapped: Swapped) -> &wgpu::TextureView {
+        &self.variants.get(swapped).inner
+    }
+
+    /// The double-buffered texture the view follows, if any.
+    pub fn swap_textures(&self) -> &[TextureId] {
+        self.variants.textures()
+    }
+
+    pub fn swap(&mut self, texture: TextureId) {
+        self.variants.swap(texture);
     }
 
     /// Returns the egui texture ID.
     /// Only returns `Some` for previewable formats (see [`is_previewable`]),
-    /// due to egui texture requirements.
+    /// due to egui texture requirements, or for views with a [`PreviewRemap`].
     pub fn egui_id(&self) -> Option<egui::TextureId> {
-        self.egui_id
+        self.variants.current().egui_id
+    }
+
+    /// Converts each half into its preview proxy with `remap`. Does nothing
+    /// for views egui shows directly.
+    pub fn encode_preview(
+        &self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        remap: &PreviewRemap,
+    ) {
+        if let Some(preview) = &self.preview {
+            let targets = self
+                .variants
+                .iter()
+                .filter_map(|variant| variant.preview.as_ref());
+            preview.encode(queue, encoder, remap, targets);
+        }
     }
 
     /// The actual format the view was created with, resolving `From Texture`
     /// against the parent texture.
     pub fn format(&self) -> wgpu::TextureFormat {
-        self.format.unwrap_or_else(|| self.inner.texture().format())
+        self.format
+            .unwrap_or_else(|| self.inner().texture().format())
     }
 
     /// The actual view dimension the view was created with, resolving `From
     /// Texture` against the parent texture.
     pub fn dimension(&self) -> wgpu::TextureViewDimension {
         self.dimension
-            .unwrap_or_else(|| default_view_dimension(self.inner.texture()))
+            .unwrap_or_else(|| default_view_dimension(self.inner().texture()))
     }
 }
 
@@ -217,15 +326,19 @@ impl SyncResource for TextureView {
     ) -> AppResult<SyncOutcome<Self::Runtime, Self::Job>> {
         match job {
             TextureViewJob::Start => {
-                let previous_egui_id = previous.as_ref().and_then(|runtime| runtime.egui_id);
+                let previous_egui_ids = previous
+                    .iter()
+                    .flat_map(|runtime| runtime.variants.iter())
+                    .filter_map(|variant| variant.egui_id)
+                    .collect();
                 self.sync(
                     _id,
                     ctx,
                     None,
-                    TextureViewJob::PendingResource { previous_egui_id },
+                    TextureViewJob::PendingResource { previous_egui_ids },
                 )
             }
-            TextureViewJob::PendingResource { previous_egui_id } => {
+            TextureViewJob::PendingResource { previous_egui_ids } => {
                 let texture_id = self
                     .texture_id
                     .ok_or(AppError::uninit_field("Texture Id"))?;
@@ -233,58 +346,103 @@ impl SyncResource for TextureView {
                 let texture = ctx.textures.get(texture_id)?;
                 let Some(runtime_texture) = ctx.textures_runtime.get_init(texture_id)? else {
                     return Ok(SyncOutcome::Pending(TextureViewJob::PendingResource {
-                        previous_egui_id,
+                        previous_egui_ids,
                     }));
                 };
 
                 let scope = WgpuErrorScope::push(ctx.device);
 
-                let (inner, format) = Self::create_view(
-                    &self.label,
-                    texture,
-                    runtime_texture,
-                    self.format,
-                    self.dimension,
-                    ctx.downlevel_flags,
-                );
-
-                let egui_id = if is_previewable(texture.format()) {
-                    let egui_view = Self::create_egui_view(
+                // Formats egui can't show are converted into a proxy, but only
+                // once the user picked how.
+                let preview = match (is_previewable(texture.format()), self.preview_remap) {
+                    (false, Some(_)) => {
+                        PreviewPipeline::new(ctx.device, &self.label, runtime_texture.inner())
+                    }
+                    _ => None,
+                };
+
+                // A double-buffered texture gets a view of each half, and the
+                // egui ids registered for the old views are reused.
+                let swaps = texture.ping_pong().map(|_| texture_id);
+                let mut previous_egui_ids = previous_egui_ids.into_iter();
+                let mut format = None;
+                let variants = SwapVariants::build(swaps, |swapped| {
+                    let side = match swapped.contains(texture_id) {
+                        true => self.side.other(),
+                        false => self.side,
+                    };
+                    let Some(half) = runtime_texture.side(side) else {
+                        return Ok(None);
+                    };
+
+                    let (inner, wgpu_format) = Self::create_view(
                         &self.label,
-                        runtime_texture,
+                        texture,
+                        half,
+                        self.format,
                         self.dimension,
                         ctx.downlevel_flags,
                     );
-                    let mut renderer = ctx.egui_renderer.write();
-                    let egui_id = match previous_egui_id {
-                        Some(egui_id) => {
-                            renderer.update_egui_texture_from_wgpu_texture(
+                    format = wgpu_format;
+
+                    let preview_target = preview
+                        .as_ref()
+                        .map(|preview| preview.create_target(ctx.device, &self.label, half));
+                    let egui_view = match &preview_target {
+                        Some(target) => Some(target.view().clone()),
+                        None => is_previewable(texture.format()).then(|| {
+                            Self::create_egui_view(
+                                &self.label,
+                                half,
+                                self.dimension,
+                                ctx.downlevel_flags,
+                            )
+                        }),
+                    };
+
+                    let egui_id = egui_view.map(|egui_view| {
+                        let mut renderer = ctx.egui_renderer.write();
+                        match previous_egui_ids.next() {
+                            Some(egui_id) => {
+                                renderer.update_egui_texture_from_wgpu_texture(
+                                    ctx.device,
+                                    &egui_view,
+                                    wgpu::FilterMode::Linear,
+                                    egui_id,
+                                );
+                                egui_id
+                            }
+                            None => renderer.register_native_texture(
                                 ctx.device,
                                 &egui_view,
                                 wgpu::FilterMode::Linear,
-                                egui_id,
-                            );
-                            egui_id
+                            ),
                         }
-                        None => renderer.register_native_texture(
-                            ctx.device,
-                            &egui_view,
-                            wgpu::FilterMode::Linear,
-                        ),
-                    };
-                    Some(egui_id)
-                } else {
-                    if let Some(egui_id) = previous_egui_id {
-                        ctx.egui_renderer.write().free_texture(&egui_id);
-                    }
-                    None
+                    });
+
+                    Ok(Some(ViewVariant {
+                        inner,
+                        egui_id,
+                        preview: preview_target,
+                    }))
+                });
+
+                for egui_id in previous_egui_ids {
+                    ctx.egui_renderer.write().free_texture(&egui_id);
+                }
+
+                let Some(variants) = variants? else {
+                    // Only the back of a plain texture can't be viewed.
+                    return Err(AppError::NotDoubleBuffered {
+                        texture: texture.label().to_string(),
+                    });
                 };
 
                 let runtime = TextureViewRuntime {
-                    inner,
-                    egui_id,
+                    variants,
                     format,
                     dimension: self.dimension,
+                    preview,
                 };
                 let job = TextureViewJob::Validation(runtime, scope.pop());
                 self.sync(_id, ctx, None, job)
@@ -319,25 +477,23 @@ impl SyncResource for TextureView {
     }
 }
 
-/// The formats a texture view can be previewed in.
+/// The formats egui can show a texture view in directly.
 ///
 /// egui-wgpu binds every user texture through one layout, a filterable float
 /// `texture_2d<f32>`, so unfilterable float and depth formats can't be handed
 /// to it.
 ///
-/// The list stops there on purpose. Supporting more would mean guessing the
-/// mapping: a depth buffer's near and far, whether an `R32Float` channel is a
-/// mask or a distance.
-///
-/// Users who want one can render the texture into an RGBA target with the
-/// conversion they want via an authored shader.
+/// Other formats are previewed through a proxy once the view has a
+/// [`PreviewRemap`]. The remap is never picked for the user, since that would
+/// mean guessing the mapping: a depth buffer's near and far, whether an
+/// `R32Float` channel is a mask or a distance.
 pub const PREVIEWABLE_FORMATS: &[TextureFormat] = &[
     TextureFormat::Rgba8UnormSrgb,
     TextureFormat::Rgba8Unorm,
     TextureFormat::Rgba16Float,
 ];
 
-fn is_previewable(format: TextureFormat) -> bool {
+pub fn is_previewable(format: TextureFormat) -> bool {
     PREVIEWABLE_FORMATS.contains(&format)
 }
 
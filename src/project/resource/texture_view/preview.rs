@@ -0,0 +1,371 @@
+//! Previews of texture views whose format egui can't show, such as depth and
+//! unfilterable float textures.
+//!
+//! A built-in pass converts each half of the view into an `Rgba8Unorm` proxy
+//! with the view's [`PreviewRemap`], and egui shows the proxy. Every setting
+//! of the remap is the user's: a depth buffer's near and far, or which range
+//! of an `R32Float` is interesting, can't be read off the data.
+
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// How a texture view's values are turned into preview colors.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewRemap {
+    pub range: PreviewRange,
+    pub channel: PreviewChannel,
+    /// Shows the channel with a color ramp instead of as gray.
+    #[serde(default)]
+    pub false_color: bool,
+}
+
+impl Default for PreviewRemap {
+    fn default() -> Self {
+        Self {
+            range: PreviewRange::Linear { min: 0.0, max: 1.0 },
+            channel: PreviewChannel::All,
+            false_color: false,
+        }
+    }
+}
+
+impl PreviewRemap {
+    /// The remap as the conversion shader's `Params` struct.
+    fn params(&self) -> [u8; 24] {
+        let (mode, [a, b]) = match self.range {
+            PreviewRange::Linear { min, max } => (0, [min, max]),
+            PreviewRange::Depth { near, far } => (1, [near, far]),
+        };
+        let words: [u32; 6] = [
+            a.to_bits(),
+            b.to_bits(),
+            mode,
+            self.channel as u32,
+            self.false_color as u32,
+            0,
+        ];
+        bytemuck::cast(words)
+    }
+}
+
+/// The range of values mapped to black through white.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PreviewRange {
+    /// `min` shows as black and `max` as white.
+    Linear { min: f32, max: f32 },
+    /// Depth written with a perspective projection, turned back into the
+    /// distance from the camera, so `near` shows as black and `far` as white.
+    Depth { near: f32, far: f32 },
+}
+
+/// Which channels the preview shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum PreviewChannel {
+    /// Red, green and blue as they are. With false color, the red channel.
+    All = 0,
+    R = 1,
+    G = 2,
+    B = 3,
+    A = 4,
+}
+
+/// The conversion pipeline shared by every half of one view.
+pub(crate) struct PreviewPipeline {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    params: wgpu::Buffer,
+    depth: bool,
+}
+
+/// The proxy one half of a view is converted into.
+pub(crate) struct PreviewTarget {
+    bind_group: wgpu::BindGroup,
+    proxy: wgpu::TextureView,
+}
+
+impl PreviewTarget {
+    /// The proxy's view, for egui to show.
+    pub(crate) fn view(&self) -> &wgpu::TextureView {
+        &self.proxy
+    }
+}
+
+impl PreviewPipeline {
+    /// A pipeline converting textures like `texture`, or `None` if it can't be
+    /// bound.
+    pub(crate) fn new(device: &wgpu::Device, label: &str, texture: &wgpu::Texture) -> Option<Self> {
+        if !texture
+            .usage()
+            .contains(wgpu::TextureUsages::TEXTURE_BINDING)
+        {
+            return None;
+        }
+
+        let depth = texture.format().is_depth_stencil_format();
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{label} (Preview Bind Group Layout)")),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: match depth {
+                            true => wgpu::TextureSampleType::Depth,
+                            false => wgpu::TextureSampleType::Float { filterable: false },
+                        },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let source = match depth {
+            true => DEPTH_SOURCE,
+            false => COLOR_SOURCE,
+        };
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(&format!("{label} (Preview Shader)")),
+            source: wgpu::ShaderSource::Wgsl([source, PREVIEW_SHADER].concat().into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(&format!("{label} (Preview Pipeline Layout)")),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(&format!("{label} (Preview Pipeline)")),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &module,
+                entry_point: None,
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &module,
+                entry_point: None,
+                compilation_options: Default::default(),
+                targets: &[Some(PROXY_FORMAT.into())],
+            }),
+            multiview_mask: None,
+            cache: None,
+        });
+
+        let params = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("{label} (Preview Params)")),
+            size: 24,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Some(Self {
+            pipeline,
+            bind_group_layout,
+            params,
+            depth,
+        })
+    }
+
+    /// A proxy the size of `half`, and the bindings to convert `half` into it.
+    /// Only the first layer and mip level are previewed.
+    pub(crate) fn create_target(
+        &self,
+        device: &wgpu::Device,
+        label: &str,
+        half: &wgpu::Texture,
+    ) -> PreviewTarget {
+        let source = half.create_view(&wgpu::TextureViewDescriptor {
+            label: Some(&format!("{label} (Preview Source)")),
+            dimension: Some(wgpu::TextureViewDimension::D2),
+            aspect: match self.depth {
+                true => wgpu::TextureAspect::DepthOnly,
+                false => wgpu::TextureAspect::All,
+            },
+            mip_level_count: Some(1),
+            array_layer_count: Some(1),
+            ..Default::default()
+        });
+
+        let proxy = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(&format!("{label} (Preview)")),
+            size: wgpu::Extent3d {
+                width: half.width(),
+                height: half.height(),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: PROXY_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let proxy = proxy.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&format!("{label} (Preview Bind Group)")),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&source),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: self.params.as_entire_binding(),
+                },
+            ],
+        });
+
+        PreviewTarget { bind_group, proxy }
+    }
+
+    /// Converts each of `targets` with `remap`.
+    pub(crate) fn encode<'a>(
+        &self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        remap: &PreviewRemap,
+        targets: impl Iterator<Item = &'a PreviewTarget>,
+    ) {
+        queue.write_buffer(&self.params, 0, &remap.params());
+        for target in targets {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Texture View Preview"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &target.proxy,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                    resolve_target: None,
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+                multiview_mask: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &target.bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+    }
+}
+
+const PROXY_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+const COLOR_SOURCE: &str = r#"
+@group(0) @binding(0) var source: texture_2d<f32>;
+
+fn load(coords: vec2<i32>) -> vec4<f32> {
+    return textureLoad(source, coords, 0);
+}
+"#;
+
+const DEPTH_SOURCE: &str = r#"
+@group(0) @binding(0) var source: texture_depth_2d;
+
+fn load(coords: vec2<i32>) -> vec4<f32> {
+    let depth = textureLoad(source, coords, 0);
+    return vec4<f32>(depth, depth, depth, 1.0);
+}
+"#;
+
+const PREVIEW_SHADER: &str = r#"
+struct Params {
+    range: vec2<f32>,
+    mode: u32,
+    channel: u32,
+    false_color: u32,
+}
+
+@group(0) @binding(1) var<uniform> params: Params;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+}
+
+fn remap(value: f32) -> f32 {
+    if params.mode == 1u {
+        let near = params.range.x;
+        let far = params.range.y;
+        let distance = near * far / (far - value * (far - near));
+        return saturate((distance - near) / (far - near));
+    }
+    return saturate((value - params.range.x) / (params.range.y - params.range.x));
+}
+
+// Polynomial fit of the Turbo color map.
+fn turbo(x: f32) -> vec3<f32> {
+    let v4 = vec4<f32>(1.0, x, x * x, x * x * x);
+    let v2 = v4.zw * v4.z;
+    return saturate(vec3<f32>(
+        dot(v4, vec4<f32>(0.13572138, 4.6153926, -42.66032258, 132.13108234))
+            + dot(v2, vec2<f32>(-152.94239396, 59.28637943)),
+        dot(v4, vec4<f32>(0.09140261, 2.19418839, 4.84296658, -14.18503333))
+            + dot(v2, vec2<f32>(4.27729857, 2.82956604)),
+        dot(v4, vec4<f32>(0.1066733, 12.64194608, -60.58204836, 110.36276771))
+            + dot(v2, vec2<f32>(-89.90310912, 27.34824973)),
+    ));
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let texel = load(vec2<i32>(position.xy));
+    if params.channel == 0u && params.false_color == 0u {
+        return vec4<f32>(remap(texel.r), remap(texel.g), remap(texel.b), 1.0);
+    }
+
+    let value = remap(texel[max(params.channel, 1u) - 1u]);
+    if params.false_color != 0u {
+        return vec4<f32>(turbo(value), 1.0);
+    }
+    return vec4<f32>(value, value, value, 1.0);
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_shaders_validate() {
+        for source in [COLOR_SOURCE, DEPTH_SOURCE] {
+            let source = [source, PREVIEW_SHADER].concat();
+            let module = naga::front::wgsl::parse_str(&source).unwrap();
+            naga::valid::Validator::new(
+                naga::valid::ValidationFlags::all(),
+                naga::valid::Capabilities::empty(),
+            )
+            .validate(&module)
+            .unwrap();
+        }
+    }
+}
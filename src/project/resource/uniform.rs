@@ -1,15 +1,22 @@
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::task::Poll;
 
+pub mod animation;
 pub mod camera;
 #[cfg(test)]
 mod tests;
 
 use crate::{
-    error::{AppError, AppResult},
+    error::{AppError, AppResult, RequiredFieldExt},
     project::{
         CameraId, Creatable, DimensionId, ProjectResource, UniformId,
-        resource::{camera::Camera, dimension::Dimension, uniform::camera::CameraField},
+        resource::{
+            camera::Camera,
+            dimension::Dimension,
+            uniform::{animation::Animation, camera::CameraField},
+        },
         storage::{RuntimeStorage, Storage},
         sync::{Revision, SyncOutcome, SyncResource, SyncTracker},
     },
@@ -31,6 +38,7 @@ pub struct UniformCreationContext<'a> {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Uniform {
     label: String,
@@ -62,6 +70,7 @@ pub struct UniformRuntimeField {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct UniformField {
     label: String,
@@ -69,6 +78,7 @@ pub struct UniformField {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(
     rename_all = "camelCase",
     rename_all_fields = "camelCase",
@@ -85,9 +95,20 @@ pub enum UniformFieldSource {
         dimension_id: Option<DimensionId>,
     },
     Time,
+    Animated(Animation),
+}
+
+/// One field of a [`Uniform`], by index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UniformFieldRef {
+    pub id: Option<UniformId>,
+    pub field: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Transform {
     pub position: [f32; 3],
@@ -121,6 +142,7 @@ impl Transform {
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase", tag = "dataType", content = "data")]
 pub enum UniformFieldData {
     UInt32(u32),
@@ -247,6 +269,24 @@ impl UniformRuntime {
     }
 }
 
+impl UniformFieldRef {
+    /// The field's current value, or `None` while the uniform is pending.
+    pub fn resolve<'a>(
+        &self,
+        runtime_uniforms: &'a RuntimeStorage<Uniform>,
+    ) -> AppResult<Option<&'a UniformFieldData>> {
+        let id = self.id.ok_or_uninit_field("Uniform")?;
+        let Some(runtime) = runtime_uniforms.get_init(id)? else {
+            return Ok(None);
+        };
+        runtime
+            .fields()
+            .get(self.field)
+            .map(|field| Some(field.data()))
+            .ok_or_uninit_field("Uniform Field")
+    }
+}
+
 impl UniformRuntimeField {
     pub fn data(&self) -> &UniformFieldData {
         &self.data
@@ -298,7 +338,10 @@ impl SyncResource for Uniform {
                     }));
                 };
                 let content = cast_fields(&fields);
-                let usage = wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST;
+                // `COPY_SRC` lets copy passes upload the buffer into a texture.
+                let usage = wgpu::BufferUsages::UNIFORM
+                    | wgpu::BufferUsages::COPY_DST
+                    | wgpu::BufferUsages::COPY_SRC;
 
                 match previous {
                     Some(mut runtime) => {
@@ -395,6 +438,7 @@ impl UniformField {
             UniformFieldSource::Transform(_) => UniformFieldDataKind::Mat4x4f,
             UniformFieldSource::Time => UniformFieldDataKind::Float,
             UniformFieldSource::Dimension { .. } => UniformFieldDataKind::Vec2u,
+            UniformFieldSource::Animated(animation) => animation.kind(),
         }
     }
 
@@ -416,7 +460,7 @@ impl UniformField {
                 tracker.was_data_changed(dimension_id)
             }
             // Time advances every frame, so the uniform must always be re-evaluated.
-            UniformFieldSource::Time => true,
+            UniformFieldSource::Time | UniformFieldSource::Animated(_) => true,
         }
     }
 
@@ -441,6 +485,7 @@ impl UniformField {
                 transform.to_matrix().to_cols_array_2d(),
             ))),
             UniformFieldSource::Time => Ok(Some(UniformFieldData::Float(context.time))),
+            UniformFieldSource::Animated(animation) => animation.evaluate(context.time).map(Some),
             UniformFieldSource::Dimension { dimension_id } => {
                 let dimension_id = dimension_id.ok_or(AppError::uninit_field(format!(
                     "Uniform Field {index} Dimension Id",
@@ -475,6 +520,10 @@ impl UniformFieldSource {
     pub fn new_time() -> Self {
         Self::Time
     }
+
+    pub fn new_animated(animation: Animation) -> Self {
+        Self::Animated(animation)
+    }
 }
 
 impl UniformFieldData {
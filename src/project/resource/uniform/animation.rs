@@ -0,0 +1,280 @@
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+use crate::{
+    error::{AppError, AppResult},
+    project::resource::uniform::{UniformFieldData, UniformFieldDataKind},
+};
+
+/// A keyframed value evaluated against the project clock.
+///
+/// Each keyframe's [`Interpolation`] shapes the segment that runs from it to the
+/// next keyframe. Keyframes are kept sorted by time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Animation {
+    /// Length of one cycle, in seconds.
+    pub duration: f32,
+    pub loop_mode: LoopMode,
+    pub keyframes: Vec<Keyframe>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Keyframe {
+    /// Seconds from the start of the cycle.
+    pub time: f32,
+    pub value: UniformFieldData,
+    pub interpolation: Interpolation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase",
+    tag = "type"
+)]
+pub enum Interpolation {
+    /// Holds the keyframe value until the next keyframe.
+    Step,
+    Linear,
+    /// A CSS-style easing curve from `(0, 0)` to `(1, 1)` with two control
+    /// points. The x coordinates are clamped to `[0, 1]`.
+    CubicBezier {
+        p1: [f32; 2],
+        p2: [f32; 2],
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, EnumIter, Display, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum LoopMode {
+    /// Plays once and holds the last value.
+    Once,
+    #[default]
+    Repeat,
+    #[strum(to_string = "Ping Pong")]
+    PingPong,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self {
+            duration: 1.0,
+            loop_mode: LoopMode::Repeat,
+            keyframes: vec![
+                Keyframe::new(0.0, UniformFieldData::Float(0.0), Interpolation::Linear),
+                Keyframe::new(1.0, UniformFieldData::Float(1.0), Interpolation::Linear),
+            ],
+        }
+    }
+}
+
+impl Animation {
+    /// The type of the animated value, taken from the first keyframe.
+    pub fn kind(&self) -> UniformFieldDataKind {
+        self.keyframes
+            .first()
+            .map_or(UniformFieldDataKind::Float, |keyframe| {
+                keyframe.value.kind()
+            })
+    }
+
+    /// Sorts keyframes by time. Call after editing keyframe times.
+    pub fn sort_keyframes(&mut self) {
+        self.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+
+    /// Maps the project clock onto a time inside one cycle, applying the loop mode.
+    pub fn local_time(&self, time: f32) -> f32 {
+        let duration = self.duration;
+        if duration <= 0.0 {
+            return 0.0;
+        }
+
+        match self.loop_mode {
+            LoopMode::Once => time.clamp(0.0, duration),
+            LoopMode::Repeat => time.rem_euclid(duration),
+            LoopMode::PingPong => {
+                let cycle = time.rem_euclid(2.0 * duration);
+                if cycle > duration {
+                    2.0 * duration - cycle
+                } else {
+                    cycle
+                }
+            }
+        }
+    }
+
+    /// Evaluates the animation at the given project time.
+    pub fn evaluate(&self, time: f32) -> AppResult<UniformFieldData> {
+        self.evaluate_local(self.local_time(time))
+    }
+
+    /// Evaluates the animation at a time inside one cycle, without looping.
+    pub fn evaluate_local(&self, time: f32) -> AppResult<UniformFieldData> {
+        let kind = self.kind();
+        for (index, keyframe) in self.keyframes.iter().enumerate() {
+            if keyframe.value.kind() != kind {
+                return Err(AppError::AnimationKeyframeTypeMismatch {
+                    index,
+                    expected: kind,
+                    actual: keyframe.value.kind(),
+                });
+            }
+        }
+
+        let (first, last) = match (self.keyframes.first(), self.keyframes.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(AppError::uninit_field("Animation Keyframes")),
+        };
+
+        if time <= first.time {
+            return Ok(first.value.clone());
+        }
+        if time >= last.time {
+            return Ok(last.value.clone());
+        }
+
+        // Index of the first keyframe strictly after `time`; never 0 or len here.
+        let next = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.time <= time);
+        let from = &self.keyframes[next - 1];
+        let to = &self.keyframes[next];
+
+        let span = to.time - from.time;
+        let t = if span > 0.0 {
+            (time - from.time) / span
+        } else {
+            1.0
+        };
+        let t = from.interpolation.ease(t);
+
+        let a = components(&from.value);
+        let b = components(&to.value);
+        let mixed: Vec<f32> = a.iter().zip(&b).map(|(a, b)| a + (b - a) * t).collect();
+
+        Ok(from_components(kind, &mixed))
+    }
+}
+
+impl Keyframe {
+    pub fn new(time: f32, value: UniformFieldData, interpolation: Interpolation) -> Self {
+        Self {
+            time,
+            value,
+            interpolation,
+        }
+    }
+}
+
+impl Interpolation {
+    pub const EASE_IN_OUT: Interpolation = Interpolation::CubicBezier {
+        p1: [0.42, 0.0],
+        p2: [0.58, 1.0],
+    };
+
+    /// Remaps the normalized segment progress `t` (in `[0, 1]`).
+    pub fn ease(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            Interpolation::Step => 0.0,
+            Interpolation::Linear => t,
+            Interpolation::CubicBezier { p1, p2 } => {
+                let x1 = p1[0].clamp(0.0, 1.0);
+                let x2 = p2[0].clamp(0.0, 1.0);
+                let s = solve_bezier_parameter(x1, x2, t);
+                bezier(p1[1], p2[1], s)
+            }
+        }
+    }
+}
+
+/// One-dimensional cubic Bézier with endpoints fixed at 0 and 1.
+fn bezier(c1: f32, c2: f32, s: f32) -> f32 {
+    let inv = 1.0 - s;
+    3.0 * inv * inv * s * c1 + 3.0 * inv * s * s * c2 + s * s * s
+}
+
+fn bezier_derivative(c1: f32, c2: f32, s: f32) -> f32 {
+    let inv = 1.0 - s;
+    3.0 * inv * inv * c1 + 6.0 * inv * s * (c2 - c1) + 3.0 * s * s * (1.0 - c2)
+}
+
+/// Finds the curve parameter whose x coordinate equals `x`. Newton's method
+/// converges quickly for typical easing curves; bisection covers the rest.
+fn solve_bezier_parameter(x1: f32, x2: f32, x: f32) -> f32 {
+    let mut s = x;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, s) - x;
+        if error.abs() < 1e-6 {
+            return s;
+        }
+        let slope = bezier_derivative(x1, x2, s);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        s = (s - error / slope).clamp(0.0, 1.0);
+    }
+
+    let (mut low, mut high) = (0.0f32, 1.0f32);
+    s = x;
+    for _ in 0..32 {
+        let value = bezier(x1, x2, s);
+        if (value - x).abs() < 1e-6 {
+            break;
+        }
+        if value < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) * 0.5;
+    }
+    s
+}
+
+/// Flattens a value into its scalar components, in declaration order.
+pub fn components(data: &UniformFieldData) -> Vec<f32> {
+    match data {
+        UniformFieldData::UInt32(v) => vec![*v as f32],
+        UniformFieldData::Float(v) => vec![*v],
+        UniformFieldData::Vec2u(v) => v.iter().map(|v| *v as f32).collect(),
+        UniformFieldData::Vec3u(v) => v.iter().map(|v| *v as f32).collect(),
+        UniformFieldData::Vec4u(v) => v.iter().map(|v| *v as f32).collect(),
+        UniformFieldData::Vec2f(v) => v.to_vec(),
+        UniformFieldData::Vec3f(v) | UniformFieldData::Rgb(v) => v.to_vec(),
+        UniformFieldData::Vec4f(v) | UniformFieldData::Rgba(v) => v.to_vec(),
+        UniformFieldData::Mat4x4f(m) => m.concat(),
+    }
+}
+
+/// Inverse of [`components`]. Integer kinds are rounded to the nearest value
+/// and clamped at zero; missing components are filled with zero.
+pub fn from_components(kind: UniformFieldDataKind, values: &[f32]) -> UniformFieldData {
+    let float = |i: usize| values.get(i).copied().unwrap_or(0.0);
+    let uint = |i: usize| float(i).round().max(0.0) as u32;
+
+    match kind {
+        UniformFieldDataKind::UInt32 => UniformFieldData::UInt32(uint(0)),
+        UniformFieldDataKind::Float => UniformFieldData::Float(float(0)),
+        UniformFieldDataKind::Vec2u => UniformFieldData::Vec2u(std::array::from_fn(uint)),
+        UniformFieldDataKind::Vec3u => UniformFieldData::Vec3u(std::array::from_fn(uint)),
+        UniformFieldDataKind::Vec4u => UniformFieldData::Vec4u(std::array::from_fn(uint)),
+        UniformFieldDataKind::Vec2f => UniformFieldData::Vec2f(std::array::from_fn(float)),
+        UniformFieldDataKind::Vec3f => UniformFieldData::Vec3f(std::array::from_fn(float)),
+        UniformFieldDataKind::Vec4f => UniformFieldData::Vec4f(std::array::from_fn(float)),
+        UniformFieldDataKind::Rgb => UniformFieldData::Rgb(std::array::from_fn(float)),
+        UniformFieldDataKind::Rgba => UniformFieldData::Rgba(std::array::from_fn(float)),
+        UniformFieldDataKind::Mat4x4f => UniformFieldData::Mat4x4f(std::array::from_fn(|col| {
+            std::array::from_fn(|row| float(col * 4 + row))
+        })),
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIter};
 
@@ -5,6 +7,7 @@ use crate::project::resource::camera::{Camera, CameraRuntime};
 use crate::project::resource::uniform::{UniformFieldData, UniformFieldDataKind};
 
 #[derive(Debug, Clone, Copy, PartialEq, EnumIter, Display, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum CameraField {
     Position,
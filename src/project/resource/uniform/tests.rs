@@ -1,3 +1,4 @@
+use super::animation::{Interpolation, Keyframe};
 use super::*;
 
 fn runtime_field(data: UniformFieldData) -> UniformRuntimeField {
@@ -100,3 +101,67 @@ fn cast_matches_wgsl_offsets_for_a_point_light() {
     assert_eq!(result[7], 0.09, "linear sits at byte 28");
     assert_eq!(result[8], 0.032, "quadratic sits at byte 32");
 }
+
+fn float_animation(loop_mode: animation::LoopMode) -> Animation {
+    Animation {
+        duration: 2.0,
+        loop_mode,
+        keyframes: vec![
+            Keyframe::new(0.0, UniformFieldData::Float(0.0), Interpolation::Linear),
+            Keyframe::new(2.0, UniformFieldData::Float(4.0), Interpolation::Linear),
+        ],
+    }
+}
+
+#[test]
+fn animation_interpolates_linearly_between_keyframes() {
+    let animation = float_animation(animation::LoopMode::Once);
+    assert_eq!(
+        animation.evaluate(0.5).unwrap(),
+        UniformFieldData::Float(1.0)
+    );
+    assert_eq!(
+        animation.evaluate(5.0).unwrap(),
+        UniformFieldData::Float(4.0)
+    );
+}
+
+#[test]
+fn animation_wraps_according_to_loop_mode() {
+    let repeat = float_animation(animation::LoopMode::Repeat);
+    assert_eq!(repeat.evaluate(2.5).unwrap(), UniformFieldData::Float(1.0));
+
+    let ping_pong = float_animation(animation::LoopMode::PingPong);
+    assert_eq!(
+        ping_pong.evaluate(3.5).unwrap(),
+        UniformFieldData::Float(1.0)
+    );
+}
+
+#[test]
+fn animation_step_holds_until_the_next_keyframe() {
+    let mut animation = float_animation(animation::LoopMode::Once);
+    animation.keyframes[0].interpolation = Interpolation::Step;
+    assert_eq!(
+        animation.evaluate(1.9).unwrap(),
+        UniformFieldData::Float(0.0)
+    );
+}
+
+#[test]
+fn animation_cubic_bezier_eases_in_and_out() {
+    let ease = Interpolation::EASE_IN_OUT;
+    assert!(ease.ease(0.25) < 0.25);
+    assert!((ease.ease(0.5) - 0.5).abs() < 1e-4);
+    assert!(ease.ease(0.75) > 0.75);
+}
+
+#[test]
+fn animation_rejects_mixed_keyframe_types() {
+    let mut animation = float_animation(animation::LoopMode::Once);
+    animation.keyframes[1].value = UniformFieldData::Vec2f([1.0, 2.0]);
+    assert!(matches!(
+        animation.evaluate(1.0),
+        Err(AppError::AnimationKeyframeTypeMismatch { index: 1, .. })
+    ));
+}
@@ -0,0 +1,206 @@
+//! JSON Schema of `project.json`.
+//!
+//! The schema is derived from the same types the project is deserialized into,
+//! so it can't drift from the format. The wgpu types the project stores don't
+//! implement [`JsonSchema`]; the functions here describe them as they
+//! serialize, for `#[schemars(schema_with = "...")]`.
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+
+use crate::project::migration;
+
+/// Bit flags serialize as their names joined by ` | `, like
+/// `"VERTEX | FRAGMENT"`, or as an empty string when none are set.
+fn flags(description: &str) -> Schema {
+    json_schema!({
+        "type": "string",
+        "pattern": r"^([A-Z0-9_]+( \| [A-Z0-9_]+)*)?$",
+        "description": description,
+    })
+}
+
+fn strings(values: &[&str]) -> Schema {
+    json_schema!({
+        "type": "string",
+        "enum": values,
+    })
+}
+
+fn nullable(schema: Schema) -> Schema {
+    json_schema!({
+        "anyOf": [schema, { "type": "null" }],
+    })
+}
+
+const TEXTURE_VIEW_DIMENSIONS: &[&str] = &["1d", "2d", "2d-array", "cube", "cube-array", "3d"];
+const FILTER_MODES: &[&str] = &["nearest", "linear"];
+const COMPARE_FUNCTIONS: &[&str] = &[
+    "never",
+    "less",
+    "equal",
+    "less-equal",
+    "greater",
+    "not-equal",
+    "greater-equal",
+    "always",
+];
+
+/// Projects are saved with the version this build writes, and older ones are
+/// upgraded on load, so only the current version matches the schema.
+pub fn version(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "integer",
+        "const": migration::VERSION,
+    })
+}
+
+pub fn shader_stages(_: &mut SchemaGenerator) -> Schema {
+    flags("Shader stages, like \"VERTEX | FRAGMENT\" or \"COMPUTE\".")
+}
+
+pub fn texture_usages(_: &mut SchemaGenerator) -> Schema {
+    flags("Texture usages, like \"TEXTURE_BINDING | RENDER_ATTACHMENT\".")
+}
+
+pub fn texture_view_dimension(_: &mut SchemaGenerator) -> Schema {
+    strings(TEXTURE_VIEW_DIMENSIONS)
+}
+
+pub fn optional_texture_view_dimension(_: &mut SchemaGenerator) -> Schema {
+    nullable(strings(TEXTURE_VIEW_DIMENSIONS))
+}
+
+pub fn texture_sample_type(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "oneOf": [
+            {
+                "type": "object",
+                "properties": {
+                    "Float": {
+                        "type": "object",
+                        "properties": { "filterable": { "type": "boolean" } },
+                        "required": ["filterable"],
+                    },
+                },
+                "required": ["Float"],
+                "additionalProperties": false,
+            },
+            { "type": "string", "enum": ["Depth", "Sint", "Uint"] },
+        ],
+    })
+}
+
+pub fn storage_texture_access(_: &mut SchemaGenerator) -> Schema {
+    strings(&["write-only", "read-only", "read-write", "atomic"])
+}
+
+pub fn sampler_binding_type(_: &mut SchemaGenerator) -> Schema {
+    strings(&["filtering", "non-filtering", "comparison"])
+}
+
+pub fn filter_mode(_: &mut SchemaGenerator) -> Schema {
+    strings(FILTER_MODES)
+}
+
+pub fn optional_compare_function(_: &mut SchemaGenerator) -> Schema {
+    nullable(strings(COMPARE_FUNCTIONS))
+}
+
+pub fn extent_3d(generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "object",
+        "properties": {
+            "width": generator.subschema_for::<u32>(),
+            "height": generator.subschema_for::<u32>(),
+            "depthOrArrayLayers": generator.subschema_for::<u32>(),
+        },
+        "required": ["width", "height"],
+    })
+}
+
+pub fn primitive_topology(_: &mut SchemaGenerator) -> Schema {
+    strings(&[
+        "point-list",
+        "line-list",
+        "line-strip",
+        "triangle-list",
+        "triangle-strip",
+    ])
+}
+
+pub fn optional_index_format(_: &mut SchemaGenerator) -> Schema {
+    nullable(strings(&["uint16", "uint32"]))
+}
+
+pub fn front_face(_: &mut SchemaGenerator) -> Schema {
+    strings(&["ccw", "cw"])
+}
+
+pub fn optional_face(_: &mut SchemaGenerator) -> Schema {
+    nullable(strings(&["front", "back"]))
+}
+
+pub fn polygon_mode(_: &mut SchemaGenerator) -> Schema {
+    strings(&["fill", "line", "point"])
+}
+
+/// A resource id: the slot of the resource in its storage, and the version of
+/// that slot, which tells a deleted resource from the one that replaced it.
+pub fn resource_id(generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "object",
+        "properties": {
+            "idx": generator.subschema_for::<u32>(),
+            "version": generator.subschema_for::<u32>(),
+        },
+        "required": ["idx", "version"],
+    })
+}
+
+/// A storage of resources serializes as its slots, each holding a resource or
+/// nothing, and the version of the slot.
+pub fn storage<R: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "value": generator.subschema_for::<Option<R>>(),
+                "version": generator.subschema_for::<u32>(),
+            },
+            "required": ["value", "version"],
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::project::Project;
+
+    #[test]
+    fn bundled_projects_match_the_schema() {
+        let schema = serde_json::to_value(Project::schema()).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+
+        let projects = Path::new(env!("CARGO_MANIFEST_DIR")).join("projects");
+        let mut checked = 0;
+        for entry in std::fs::read_dir(projects).unwrap() {
+            let path = entry.unwrap().path().join("project.json");
+            if !path.exists() {
+                continue;
+            }
+            let bytes = std::fs::read(&path).unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            let errors: Vec<String> = validator
+                .iter_errors(&json)
+                .map(|error| format!("{} at {}", error, error.instance_path))
+                .collect();
+            assert!(errors.is_empty(), "{}: {errors:#?}", path.display());
+            Project::deserialize(&bytes).unwrap();
+            checked += 1;
+        }
+        assert!(checked > 0);
+    }
+}
@@ -1,7 +1,11 @@
 use itertools::Itertools;
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use slotmap::{SecondaryMap, SlotMap};
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::project::schema;
 use crate::{
     error::{AppError, AppResult},
     project::{
@@ -140,6 +144,25 @@ where
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+impl<R> JsonSchema for Storage<R>
+where
+    R: ProjectResource + JsonSchema,
+    R::Id: slotmap::Key,
+{
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        format!("{}Storage", R::schema_name()).into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schema::storage::<R>(generator)
+    }
+}
+
 pub struct RuntimeStorage<R>
 where
     R: SyncResource,
@@ -180,6 +203,22 @@ where
         }
     }
 
+    /// The runtime for `key`, if it's created, for updating it in place.
+    pub fn get_init_mut(&mut self, key: R::Id) -> Option<&mut R::Runtime> {
+        match self.map.get_mut(key) {
+            Some(RuntimeCell::Created { runtime, .. }) => Some(runtime),
+            _ => None,
+        }
+    }
+
+    /// Every created runtime, for updating them in place.
+    pub fn iter_init_mut(&mut self) -> impl Iterator<Item = (R::Id, &mut R::Runtime)> {
+        self.map.iter_mut().filter_map(|(key, cell)| match cell {
+            RuntimeCell::Created { runtime, .. } => Some((key, runtime)),
+            _ => None,
+        })
+    }
+
     /// Returns a mutable reference to the [`RuntimeCell`] for the given key.
     /// Returns `AppError::InvalidResource` if the key is not found.
     pub(super) fn cell_mut(
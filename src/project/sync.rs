@@ -316,6 +316,12 @@ impl SyncTracker {
         self.data_changes.push(id);
     }
 
+    /// Marks the resources' data as changed without recreating them, e.g. a
+    /// texture a copy pass wrote, so their consumers rerun.
+    pub(crate) fn push_data_changes(&mut self, ids: impl IntoIterator<Item = ResourceId>) {
+        self.data_changes.extend(ids);
+    }
+
     pub(crate) fn push_file_changes(&mut self, paths: impl IntoIterator<Item = FilePath>) {
         self.file_changes.extend(paths);
     }
@@ -32,6 +32,7 @@ use crate::{
             bindgroup::{BindGroup, BindGroupEntry, BindGroupResource},
             camera::{Camera, CameraMode, ClipRange, Deg, Fov},
             dimension::Dimension,
+            presentation::PassStep,
             render_pass::{Color, LoadOperation, RenderPass, RenderPassTarget},
             render_pipeline::{BindGroupTarget, RenderDrawStrategy, RenderPipeline},
             shader::Shader,
@@ -296,9 +297,10 @@ pub async fn create_scene() -> AppResult<Project> {
     depth_view_pass.set_pipelines(vec![depth_view_pipeline_id]);
     let depth_view_pass_id = project.render_passes.register(depth_view_pass);
 
-    project
-        .presentation
-        .set_render_passes(vec![scene_pass_id, depth_view_pass_id]);
+    project.presentation.set_steps(vec![
+        PassStep::Render(scene_pass_id),
+        PassStep::Render(depth_view_pass_id),
+    ]);
     project
         .presentation
         .set_main_viewport(Some(color_viewport_id));
@@ -9,6 +9,7 @@ use crate::{
             camera::{Camera, CameraMode, LookAt},
             dimension::Dimension,
             model::{Model, ModelRuntime},
+            presentation::PassStep,
             render_pass::{Color, LoadOperation, RenderPass, RenderPassTarget},
             render_pipeline::{BindGroupTarget, RenderDrawStrategy, RenderPipeline},
             shader::Shader,
@@ -197,7 +198,9 @@ pub async fn create_scene(device: &wgpu::Device, file_storage: &FileStorage) ->
         Some(camera_id),
     ));
 
-    project.presentation.set_render_passes(vec![render_pass_id]);
+    project
+        .presentation
+        .set_steps(vec![PassStep::Render(render_pass_id)]);
     project.presentation.set_main_viewport(Some(viewport_id));
 
     Ok(project)
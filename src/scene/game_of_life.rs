@@ -1,18 +1,19 @@
 //! Conway's Game of Life running entirely on the GPU.
 //!
-//! The simulation lives in two `Rgba8Unorm` storage textures (grid A and grid B)
-//! that are ping-ponged each simulation step:
+//! The simulation lives in a double-buffered `Rgba8Unorm` storage texture, the
+//! grid, whose front and back swap after every simulation step:
 //!
-//! 1. `Init` seeds a pseudo-random soup into grid A. It uses the `OnChange`
-//!    dispatch policy, so it runs exactly once.
-//! 2. `Simulate` reads grid A as a sampled texture, computes one generation, and
-//!    writes it into grid B as a storage texture.
-//! 3. `Copy` writes grid B back into grid A so the next step keeps advancing.
-//! 4. A render pass samples grid A onto a full-screen triangle for display.
+//! 1. `Init` seeds a pseudo-random soup into the front. It uses the `OnChange`
+//!    dispatch policy, so it runs once, and it's the schedule's initializer, so
+//!    resetting the simulation reseeds the grid.
+//! 2. `Simulate` reads the front as a sampled texture, computes one generation,
+//!    and writes it into the back as a storage texture. The grid swaps once the
+//!    frame is submitted, so the new generation becomes the front.
+//! 3. A render pass samples the front onto a full-screen triangle for display.
 //!
-//! `Simulate` and `Copy` use the `Periodic` dispatch policy at [`STEP_INTERVAL`],
-//! so the simulation advances at a steady rate independent of the framerate. The
-//! presentation's compute pass schedule runs them in the order above.
+//! `Simulate` uses the `Periodic` dispatch policy at [`STEP_INTERVAL`], so the
+//! simulation advances at a steady rate independent of the framerate. The
+//! presentation schedule runs the passes in the order above.
 
 use crate::{
     error::AppResult,
@@ -21,13 +22,15 @@ use crate::{
         paths::FilePath,
         resource::{
             bindgroup::{BindGroup, BindGroupEntry, BindGroupResource},
-            compute_pass::{ComputePass, DispatchPolicy, DispatchSize, DispatchUnit},
+            compute_pass::{ComputePass, DispatchSize, DispatchUnit},
             dimension::Dimension,
+            dispatch_policy::DispatchPolicy,
+            presentation::PassStep,
             render_pass::{Color, LoadOperation, RenderPass, RenderPassTarget},
             render_pipeline::{BindGroupTarget, RenderDrawStrategy, RenderPipeline},
             shader::Shader,
-            texture::{Texture, TextureSource},
-            texture_view::TextureView,
+            texture::{SwapTrigger, Texture, TextureSource},
+            texture_view::{BufferSide, TextureView},
             uniform::{Uniform, UniformField, UniformFieldData, UniformFieldSource},
             viewport::Viewport,
         },
@@ -47,9 +50,6 @@ pub async fn create_scene() -> AppResult<Project> {
         "Simulate Shader",
         FilePath::from_str("simulate.wgsl")?,
     ));
-    let copy_shader_id = project
-        .shaders
-        .register(Shader::new("Copy Shader", FilePath::from_str("copy.wgsl")?));
     let render_shader_id = project.shaders.register(Shader::new(
         "Render Shader",
         FilePath::from_str("render.wgsl")?,
@@ -64,33 +64,28 @@ pub async fn create_scene() -> AppResult<Project> {
         .dimensions
         .register(Dimension::new_runtime("Display Dimension"));
 
-    // Two ping-pong state textures. Each is written as a storage texture and read
-    // back as a sampled texture, so both usages are required.
-    let grid_usage = wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING;
-    let grid_a_texture_id = project.textures.register(Texture::new(
-        "Grid A",
-        TextureFormat::Rgba8Unorm,
-        grid_usage,
-        TextureSource::dimension(grid_dimension_id),
-    ));
-    let grid_b_texture_id = project.textures.register(Texture::new(
-        "Grid B",
-        TextureFormat::Rgba8Unorm,
-        grid_usage,
-        TextureSource::dimension(grid_dimension_id),
-    ));
-    let grid_a_view_id = project.texture_views.register(TextureView::new(
-        "Grid A View",
-        Some(grid_a_texture_id),
-        None,
-        None,
-    ));
-    let grid_b_view_id = project.texture_views.register(TextureView::new(
-        "Grid B View",
-        Some(grid_b_texture_id),
+    // The double-buffered state texture. Each half is written as a storage
+    // texture and read back as a sampled texture. It swaps after `Simulate`,
+    // which is scheduled below.
+    let grid_texture_id = project.textures.register(
+        Texture::new(
+            "Grid",
+            TextureFormat::Rgba8Unorm,
+            wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING,
+            TextureSource::dimension(grid_dimension_id),
+        )
+        .with_ping_pong(SwapTrigger::AfterStep(None)),
+    );
+    let grid_front_view_id = project.texture_views.register(TextureView::new(
+        "Grid Front",
+        Some(grid_texture_id),
         None,
         None,
     ));
+    let grid_back_view_id = project.texture_views.register(
+        TextureView::new("Grid Back", Some(grid_texture_id), None, None)
+            .with_side(BufferSide::Back),
+    );
 
     // Helpers for the two flavours of grid binding used by the compute passes.
     let sampled_grid = |view_id| BindGroupResource::Texture {
@@ -114,30 +109,21 @@ pub async fn create_scene() -> AppResult<Project> {
         )],
     ));
 
-    // Init: write the seed into grid A, using the seed uniform for the hash.
+    // Init: write the seed into the front, using the seed uniform for the hash.
     let init_bind_group_id = project.bind_groups.register(BindGroup::new(
         "Init Bind Group",
         vec![
-            BindGroupEntry::new_compute(storage_grid(grid_a_view_id)),
+            BindGroupEntry::new_compute(storage_grid(grid_front_view_id)),
             BindGroupEntry::new_compute(BindGroupResource::Uniform(Some(seed_uniform_id))),
         ],
     ));
 
-    // Simulate: read grid A, write the next generation into grid B.
+    // Simulate: read the front, write the next generation into the back.
     let simulate_bind_group_id = project.bind_groups.register(BindGroup::new(
         "Simulate Bind Group",
         vec![
-            BindGroupEntry::new_compute(sampled_grid(grid_a_view_id)),
-            BindGroupEntry::new_compute(storage_grid(grid_b_view_id)),
-        ],
-    ));
-
-    // Copy: read grid B, write it back into grid A.
-    let copy_bind_group_id = project.bind_groups.register(BindGroup::new(
-        "Copy Bind Group",
-        vec![
-            BindGroupEntry::new_compute(sampled_grid(grid_b_view_id)),
-            BindGroupEntry::new_compute(storage_grid(grid_a_view_id)),
+            BindGroupEntry::new_compute(sampled_grid(grid_front_view_id)),
+            BindGroupEntry::new_compute(storage_grid(grid_back_view_id)),
         ],
     ));
 
@@ -145,12 +131,11 @@ pub async fn create_scene() -> AppResult<Project> {
         grid_dimension_id,
         1,
         DispatchUnit::Invocation {
-            workgroup_size: [8, 8, 1],
+            workgroup_size: None,
         },
     );
-    // Init seeds the grid once; Simulate and Copy advance one generation per
-    // `STEP_INTERVAL`. Giving both the same interval keeps them in lockstep, so a
-    // step is always a Simulate (A -> B) immediately followed by a Copy (B -> A).
+    // Init seeds the grid once; Simulate advances one generation per
+    // `STEP_INTERVAL`.
     let init_pass_id = project.compute_passes.register(ComputePass::new(
         "Init",
         vec![init_bind_group_id],
@@ -171,17 +156,14 @@ pub async fn create_scene() -> AppResult<Project> {
             interval: STEP_INTERVAL,
         },
     ));
-    let copy_pass_id = project.compute_passes.register(ComputePass::new(
-        "Copy",
-        vec![copy_bind_group_id],
-        Some(copy_shader_id),
-        grid_dispatch,
-        DispatchPolicy::Periodic {
-            interval: STEP_INTERVAL,
-        },
-    ));
+    project
+        .textures
+        .get_mut(grid_texture_id)?
+        .set_ping_pong(Some(SwapTrigger::AfterStep(Some(PassStep::Compute(
+            simulate_pass_id,
+        )))));
 
-    // Display: a full-screen triangle that samples grid A.
+    // Display: a full-screen triangle that samples the front.
     let color_format = TextureFormat::Rgba8Unorm;
     let viewport_texture_id = project.textures.register(Texture::new(
         "Viewport Texture",
@@ -199,7 +181,7 @@ pub async fn create_scene() -> AppResult<Project> {
     let render_bind_group_id = project.bind_groups.register(BindGroup::new(
         "Render Bind Group",
         vec![BindGroupEntry::new_vertex_fragment(sampled_grid(
-            grid_a_view_id,
+            grid_front_view_id,
         ))],
     ));
 
@@ -241,11 +223,15 @@ pub async fn create_scene() -> AppResult<Project> {
         None,
     ));
 
-    project.presentation.set_render_passes(vec![render_pass_id]);
-    // Execution order: seed, then step (simulate then commit) each frame they fire.
+    // Execution order: seed, then step, then display.
+    project.presentation.set_steps(vec![
+        PassStep::Compute(init_pass_id),
+        PassStep::Compute(simulate_pass_id),
+        PassStep::Render(render_pass_id),
+    ]);
     project
         .presentation
-        .set_compute_passes(vec![init_pass_id, simulate_pass_id, copy_pass_id]);
+        .set_initializer(PassStep::Compute(init_pass_id), true);
     project.presentation.set_main_viewport(Some(viewport_id));
 
     Ok(project)
@@ -27,6 +27,7 @@ use crate::{
             bindgroup::{BindGroup, BindGroupEntry, BindGroupResource},
             camera::{Camera, CameraMode},
             dimension::Dimension,
+            presentation::PassStep,
             render_pass::{Color, LoadOperation, RenderPass, RenderPassTarget},
             render_pipeline::{BindGroupTarget, RenderDrawStrategy, RenderPipeline},
             shader::Shader,
@@ -218,7 +219,9 @@ pub async fn create_scene() -> AppResult<Project> {
     render_pass.set_pipelines(vec![ground_pipeline_id, grass_pipeline_id]);
     let render_pass_id = project.render_passes.register(render_pass);
 
-    project.presentation.set_render_passes(vec![render_pass_id]);
+    project
+        .presentation
+        .set_steps(vec![PassStep::Render(render_pass_id)]);
     project.presentation.set_main_viewport(Some(viewport_id));
 
     Ok(project)
@@ -7,9 +7,11 @@ use crate::{
         resource::{
             bindgroup::{BindGroup, BindGroupEntry, BindGroupResource},
             camera::{Camera, Deg, Pitch, Yaw},
-            compute_pass::{ComputePass, DispatchPolicy, DispatchSize, DispatchUnit},
+            compute_pass::{ComputePass, DispatchSize, DispatchUnit},
             dimension::Dimension,
+            dispatch_policy::DispatchPolicy,
             model::{Model, ModelRuntime, TextureType},
+            presentation::PassStep,
             render_pass::{LoadOperation, RenderPass, RenderPassTarget},
             render_pipeline::{BindGroupTarget, RenderDrawStrategy, RenderPipeline},
             sampler::{Sampler, SamplerSpec},
@@ -303,7 +305,7 @@ pub async fn create_scene(device: &wgpu::Device, file_storage: &FileStorage) ->
             cube_face_dimension_id,
             6,
             DispatchUnit::Invocation {
-                workgroup_size: [16, 16, 1],
+                workgroup_size: None,
             },
         ),
         DispatchPolicy::OnChange,
@@ -473,11 +475,11 @@ pub async fn create_scene(device: &wgpu::Device, file_storage: &FileStorage) ->
 
     let hdr_render_pass_id = project.render_passes.register(hdr_render_pass);
 
-    let entries = vec![main_render_pass_id, hdr_render_pass_id];
-    project.presentation.set_render_passes(entries);
-    project
-        .presentation
-        .set_compute_passes(vec![compute_pass_id]);
+    project.presentation.set_steps(vec![
+        PassStep::Compute(compute_pass_id),
+        PassStep::Render(main_render_pass_id),
+        PassStep::Render(hdr_render_pass_id),
+    ]);
     project.presentation.set_main_viewport(Some(viewport_id));
 
     Ok(project)
@@ -9,6 +9,7 @@ use crate::{
             camera::{Camera, CameraMode, LookAt},
             dimension::Dimension,
             model::{Model, ModelRuntime, TextureType},
+            presentation::PassStep,
             render_pass::{Color, LoadOperation, RenderPass, RenderPassTarget},
             render_pipeline::{BindGroupTarget, RenderDrawStrategy, RenderPipeline},
             sampler::{Sampler, SamplerSpec},
@@ -203,7 +204,9 @@ pub async fn create_scene(device: &wgpu::Device, file_storage: &FileStorage) ->
     render_pass.set_pipelines(vec![pipeline_id]);
     let render_pass_id = project.render_passes.register(render_pass);
 
-    project.presentation.set_render_passes(vec![render_pass_id]);
+    project
+        .presentation
+        .set_steps(vec![PassStep::Render(render_pass_id)]);
     project.presentation.set_main_viewport(Some(viewport_id));
 
     Ok(project)
@@ -46,8 +46,10 @@ use crate::{
         resource::{
             bindgroup::{BindGroup, BindGroupEntry, BindGroupResource},
             camera::{Camera, CameraMode, Deg, Pitch, Yaw},
-            compute_pass::{ComputePass, DispatchPolicy, DispatchSize, DispatchUnit, WorkSize},
+            compute_pass::{ComputePass, DispatchSize, DispatchUnit, WorkSize},
             dimension::{Axis, Dimension, DimensionRef},
+            dispatch_policy::DispatchPolicy,
+            presentation::PassStep,
             render_pass::{Color, LoadOperation, RenderPass, RenderPassTarget},
             render_pipeline::{BindGroupTarget, RenderDrawStrategy, RenderPipeline},
             shader::Shader,
@@ -304,7 +306,7 @@ pub async fn create_scene() -> AppResult<Project> {
         y: WorkSize::Fixed(1),
         z: WorkSize::Fixed(1),
         unit: DispatchUnit::Invocation {
-            workgroup_size: [64, 1, 1],
+            workgroup_size: None,
         },
     };
     // One invocation per pixel for everything that walks the framebuffer.
@@ -312,7 +314,7 @@ pub async fn create_scene() -> AppResult<Project> {
         display_dimension_id,
         1,
         DispatchUnit::Invocation {
-            workgroup_size: [8, 8, 1],
+            workgroup_size: None,
         },
     );
 
@@ -399,10 +401,12 @@ pub async fn create_scene() -> AppResult<Project> {
         Some(camera_id),
     ));
 
-    project.presentation.set_render_passes(vec![render_pass_id]);
-    project
-        .presentation
-        .set_compute_passes(vec![generate_pass_id, reset_pass_id, trace_pass_id]);
+    project.presentation.set_steps(vec![
+        PassStep::Compute(generate_pass_id),
+        PassStep::Compute(reset_pass_id),
+        PassStep::Compute(trace_pass_id),
+        PassStep::Render(render_pass_id),
+    ]);
     project.presentation.set_main_viewport(Some(viewport_id));
 
     Ok(project)
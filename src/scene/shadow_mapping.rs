@@ -31,6 +31,7 @@ use crate::{
             bindgroup::{BindGroup, BindGroupEntry, BindGroupResource},
             camera::{Camera, CameraMode, ClipRange, Deg, Fov, LookAt},
             dimension::Dimension,
+            presentation::PassStep,
             render_pass::{Color, LoadOperation, RenderPass, RenderPassTarget},
             render_pipeline::{BindGroupTarget, RenderDrawStrategy, RenderPipeline},
             shader::Shader,
@@ -322,9 +323,10 @@ pub async fn create_scene() -> AppResult<Project> {
     scene_pass.set_pipelines(vec![scene_pipeline_id]);
     let scene_pass_id = project.render_passes.register(scene_pass);
 
-    project
-        .presentation
-        .set_render_passes(vec![shadow_pass_id, scene_pass_id]);
+    project.presentation.set_steps(vec![
+        PassStep::Render(shadow_pass_id),
+        PassStep::Render(scene_pass_id),
+    ]);
     project.presentation.set_main_viewport(Some(viewport_id));
     // The light viewport shares no schedule of its own; it just displays the
     // shadow pass's colour target and drives the light camera when focused.
@@ -49,6 +49,7 @@ use crate::{
             camera::{Camera, CameraMode, LookAt},
             dimension::Dimension,
             model::Model,
+            presentation::PassStep,
             render_pass::{Color, LoadOperation, RenderPass, RenderPassTarget},
             render_pipeline::{BindGroupTarget, RenderDrawStrategy, RenderPipeline},
             sampler::{Sampler, SamplerSpec},
@@ -584,12 +585,12 @@ pub async fn create_scene() -> AppResult<Project> {
     lighting_pass.set_pipelines(vec![lighting_pipeline_id]);
     let lighting_pass_id = project.render_passes.register(lighting_pass);
 
-    project.presentation.set_render_passes(vec![
-        position_pass_id,
-        normal_pass_id,
-        ssao_pass_id,
-        blur_pass_id,
-        lighting_pass_id,
+    project.presentation.set_steps(vec![
+        PassStep::Render(position_pass_id),
+        PassStep::Render(normal_pass_id),
+        PassStep::Render(ssao_pass_id),
+        PassStep::Render(blur_pass_id),
+        PassStep::Render(lighting_pass_id),
     ]);
     project.presentation.set_main_viewport(Some(viewport_id));
 
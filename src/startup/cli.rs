@@ -2,12 +2,29 @@ use std::path::PathBuf;
 
 use crate::{
     StartupAction,
-    error::AppResult,
-    file::identifier::{ProjectIdentifier, ProjectSource},
+    error::{AppError, AppResult},
+    file::{
+        file_system::AppFileSystem,
+        identifier::{ProjectIdentifier, ProjectSource},
+    },
+    headless::{self, HeadlessOptions},
+    project::{
+        Project, ResourceKind,
+        check::{self, Diagnostic, Severity},
+    },
     scene::{self, GenerateTemplate},
-    ui::components::create_project_modal::{GithubProjectSource, ProjectCreationSource},
+    ui::{
+        components::create_project_modal::{GithubProjectSource, ProjectCreationSource},
+        size::Size2d,
+    },
+    utils::{
+        image_compare::{Comparison, Tolerance},
+        recording::RecordingFormat,
+        texture_capture::ImageFileFormat,
+    },
 };
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use serde::Serialize;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -32,6 +49,267 @@ enum Command {
         /// Folder to write the generated project into.
         target_folder: PathBuf,
     },
+    /// Render a project without a window, saving the main viewport after every frame.
+    Render {
+        #[command(flatten)]
+        headless: HeadlessArgs,
+        /// Folder to write the frames into.
+        #[arg(long)]
+        output: PathBuf,
+        /// Image format of the frames. Defaults to EXR for float textures and PNG otherwise.
+        #[arg(long)]
+        format: Option<ImageFileFormat>,
+    },
+    /// Record the main viewport of a project without a window.
+    Record {
+        #[command(flatten)]
+        headless: HeadlessArgs,
+        /// File to write the video into, or folder for a PNG sequence.
+        #[arg(long)]
+        output: PathBuf,
+        #[arg(long, default_value = "y4m")]
+        format: RecordingFormat,
+    },
+    /// Compare an image, or a rendered frame of a project, against a reference image.
+    ///
+    /// Exits with a non-zero status when they differ by more than the tolerance.
+    Compare {
+        /// The expected image.
+        reference: PathBuf,
+        /// The image to check.
+        #[arg(required_unless_present = "render")]
+        image: Option<PathBuf>,
+        /// Render the last frame of this project at the reference's size, and check it instead.
+        #[arg(long, value_name = "PROJECT_FOLDER", conflicts_with = "image")]
+        render: Option<PathBuf>,
+        /// Number of frames to render.
+        #[arg(long, default_value_t = 1, requires = "render")]
+        frames: u32,
+        /// Frame rate of the clock the rendered project sees.
+        #[arg(long, default_value_t = 60.0, value_parser = parse_fps, requires = "render")]
+        fps: f32,
+        /// Render with the software adapter, on machines without a GPU.
+        #[arg(long, requires = "render")]
+        software: bool,
+        /// Write a heat map of the differences to this PNG file.
+        #[arg(long)]
+        diff: Option<PathBuf>,
+        /// Largest difference, from 0 to 1, a pixel's channels may have for it to count as equal.
+        #[arg(long, default_value_t = Tolerance::default().pixel)]
+        tolerance: f32,
+        /// Fraction of the pixels, from 0 to 1, that may differ.
+        #[arg(long, default_value_t = Tolerance::default().fraction)]
+        threshold: f32,
+    },
+    /// Check a project for errors without a GPU: broken references, missing files,
+    /// invalid shaders and incompatible texture formats or usages.
+    ///
+    /// Exits with a non-zero status when it finds any error.
+    Check {
+        project_folder: PathBuf,
+        #[arg(long, default_value = "human")]
+        format: CheckFormat,
+    },
+    /// Print the JSON Schema of `project.json`, for editors to validate and
+    /// complete projects with.
+    Schema {
+        /// Write the schema to this file instead.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// How `check` prints its diagnostics.
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum CheckFormat {
+    /// One line per diagnostic.
+    Human,
+    /// A JSON array of diagnostics.
+    Json,
+}
+
+/// How a project is run without a window.
+#[derive(Args)]
+struct HeadlessArgs {
+    project_folder: PathBuf,
+    /// Number of frames to render.
+    #[arg(long, default_value_t = 1)]
+    frames: u32,
+    /// Frame rate of the clock the project sees.
+    #[arg(long, default_value_t = 60.0, value_parser = parse_fps)]
+    fps: f32,
+    /// Size of the main viewport, as WIDTHxHEIGHT.
+    #[arg(long, default_value = "1280x720", value_parser = parse_size)]
+    size: Size2d,
+    /// Render with the software adapter, on machines without a GPU.
+    #[arg(long)]
+    software: bool,
+}
+
+impl From<HeadlessArgs> for HeadlessOptions {
+    fn from(args: HeadlessArgs) -> Self {
+        HeadlessOptions {
+            project_folder: args.project_folder,
+            frames: args.frames,
+            fps: args.fps,
+            software: args.software,
+            size: args.size,
+        }
+    }
+}
+
+fn parse_fps(value: &str) -> Result<f32, String> {
+    match value.parse::<f32>() {
+        Ok(fps) if fps.is_finite() && fps > 0.0 => Ok(fps),
+        _ => Err("expected a positive number".to_string()),
+    }
+}
+
+fn parse_size(value: &str) -> Result<Size2d, String> {
+    let error = || "expected WIDTHxHEIGHT, such as 1280x720".to_string();
+    let (width, height) = value.split_once('x').ok_or_else(error)?;
+    let width = width.parse::<u32>().map_err(|_| error())?;
+    let height = height.parse::<u32>().map_err(|_| error())?;
+    if width == 0 || height == 0 {
+        return Err(error());
+    }
+    Ok(Size2d::new(width, height))
+}
+
+/// Compares the image against the reference and prints the metrics,
+/// returning whether they match.
+fn compare(
+    reference: PathBuf,
+    image: Option<PathBuf>,
+    render: Option<HeadlessOptions>,
+    diff: Option<PathBuf>,
+    tolerance: Tolerance,
+) -> AppResult<bool> {
+    let reference = image::open(reference)?;
+    let image = match (image, render) {
+        (_, Some(mut options)) => {
+            options.size = Size2d::new(reference.width(), reference.height());
+            headless::render_image(options)?
+        }
+        (Some(image), None) => image::open(image)?,
+        (None, None) => unreachable!("clap requires an image or a project to render"),
+    };
+
+    let comparison = Comparison::new(&image, &reference)?;
+    let differing = comparison.differing_pixels(tolerance.pixel);
+    println!("PSNR: {:.2} dB", comparison.psnr);
+    println!("SSIM: {:.5}", comparison.ssim);
+    println!("Max difference: {:.5}", comparison.max_difference);
+    println!("Mean difference: {:.5}", comparison.mean_difference);
+    println!(
+        "Differing pixels: {differing} of {}",
+        comparison.pixel_count()
+    );
+
+    if let Some(diff) = diff {
+        comparison
+            .heat_map()
+            .save_with_format(&diff, image::ImageFormat::Png)?;
+        log::info!("Wrote {}", diff.display());
+    }
+    Ok(comparison.matches(tolerance))
+}
+
+/// A diagnostic as `check` prints it in JSON.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CheckOutput {
+    severity: Severity,
+    kind: Option<ResourceKind>,
+    resource: Option<String>,
+    message: String,
+}
+
+impl CheckOutput {
+    fn new(project: &Project, diagnostic: Diagnostic) -> Self {
+        let Diagnostic {
+            severity,
+            resource,
+            error,
+        } = diagnostic;
+        Self {
+            severity,
+            kind: resource.map(|id| id.kind()),
+            resource: resource
+                .and_then(|id| project.label(id))
+                .map(str::to_string),
+            message: error.to_string(),
+        }
+    }
+
+    /// A project that couldn't be checked at all.
+    fn failed(error: AppError) -> Self {
+        Self {
+            severity: Severity::Error,
+            kind: None,
+            resource: None,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Checks the project and prints its diagnostics, returning whether it has
+/// no errors.
+fn check_project(project_folder: PathBuf, format: CheckFormat) -> AppResult<bool> {
+    let project_id = ProjectIdentifier::extract_identifier(project_folder)?;
+    let outputs: Vec<CheckOutput> = pollster::block_on(async {
+        let app_file_system = AppFileSystem::open().await?;
+        let (file_system, _) = app_file_system
+            .mount_project(ProjectSource::Persistent(project_id))
+            .await?;
+        let outputs = match check::load_project(&file_system).await {
+            Ok(project) => check::check(&project, &file_system)
+                .await
+                .into_iter()
+                .map(|diagnostic| CheckOutput::new(&project, diagnostic))
+                .collect(),
+            Err(error) => vec![CheckOutput::failed(error)],
+        };
+        AppResult::Ok(outputs)
+    })?;
+
+    match format {
+        CheckFormat::Human => {
+            for output in &outputs {
+                match (output.kind, &output.resource) {
+                    (Some(kind), Some(label)) => {
+                        println!("{}: {kind} “{label}”: {}", output.severity, output.message)
+                    }
+                    _ => println!("{}: {}", output.severity, output.message),
+                }
+            }
+            let count = |severity| {
+                outputs
+                    .iter()
+                    .filter(|output| output.severity == severity)
+                    .count()
+            };
+            println!(
+                "{} errors, {} warnings",
+                count(Severity::Error),
+                count(Severity::Warning)
+            );
+        }
+        CheckFormat::Json => println!("{}", serde_json::to_string_pretty(&outputs)?),
+    }
+    Ok(outputs
+        .iter()
+        .all(|output| output.severity != Severity::Error))
+}
+
+/// Prints the schema of `project.json`, or writes it to `output`.
+fn write_schema(output: Option<PathBuf>) -> AppResult<()> {
+    let schema = serde_json::to_string_pretty(&Project::schema())?;
+    match output {
+        Some(path) => std::fs::write(path, schema + "\n")?,
+        None => println!("{schema}"),
+    }
+    Ok(())
 }
 
 /// Where the new project is stored.
@@ -144,6 +422,85 @@ pub fn main() {
             }
             return;
         }
+        Some(Command::Render {
+            headless,
+            output,
+            format,
+        }) => {
+            if let Err(e) = headless::render(headless.into(), output, format) {
+                log::error!("Failed to render project: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Record {
+            headless,
+            output,
+            format,
+        }) => {
+            if let Err(e) = headless::record(headless.into(), output, format) {
+                log::error!("Failed to record project: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Compare {
+            reference,
+            image,
+            render,
+            frames,
+            fps,
+            software,
+            diff,
+            tolerance,
+            threshold,
+        }) => {
+            let render = render.map(|project_folder| HeadlessOptions {
+                project_folder,
+                frames,
+                fps,
+                software,
+                // Replaced by the reference's size.
+                size: Size2d::new(1, 1),
+            });
+            let tolerance = Tolerance {
+                pixel: tolerance,
+                fraction: threshold,
+            };
+            match compare(reference, image, render, diff, tolerance) {
+                Ok(true) => {}
+                Ok(false) => {
+                    log::error!("The image differs from the reference");
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    log::error!("Failed to compare images: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some(Command::Check {
+            project_folder,
+            format,
+        }) => {
+            match check_project(project_folder, format) {
+                Ok(true) => {}
+                Ok(false) => std::process::exit(1),
+                Err(e) => {
+                    log::error!("Failed to check project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some(Command::Schema { output }) => {
+            if let Err(e) = write_schema(output) {
+                log::error!("Failed to write schema: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
     };
 
     let startup_action = match action {
@@ -158,3 +515,47 @@ pub fn main() {
         log::error!("Failed to run: {}", e);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_json_names_each_diagnostic_resource() {
+        let mut project = Project::default();
+        let texture = project.textures.create("Scene".to_string());
+        let unused = AppError::UnusedResource {
+            kind: ResourceKind::Texture,
+        };
+        let message = unused.to_string();
+        let outputs = [
+            CheckOutput::new(
+                &project,
+                Diagnostic {
+                    severity: Severity::Warning,
+                    resource: Some(texture.into()),
+                    error: unused,
+                },
+            ),
+            CheckOutput::failed(AppError::MissingProjectJson),
+        ];
+
+        assert_eq!(
+            serde_json::to_value(outputs).unwrap(),
+            serde_json::json!([
+                {
+                    "severity": "warning",
+                    "kind": "texture",
+                    "resource": "Scene",
+                    "message": message,
+                },
+                {
+                    "severity": "error",
+                    "kind": null,
+                    "resource": null,
+                    "message": "Missing project.json.",
+                },
+            ])
+        );
+    }
+}
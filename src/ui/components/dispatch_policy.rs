@@ -0,0 +1,100 @@
+use egui::{AsIdSalt, Ui};
+
+use crate::{
+    project::resource::{
+        dispatch_policy::DispatchPolicy,
+        presentation::{PassStep, Presentation},
+    },
+    ui::components::{
+        field,
+        field_docs::{FieldDoc, field_doc},
+        inspector,
+    },
+};
+
+fn dispatch_label(policy: &DispatchPolicy) -> &'static str {
+    match policy {
+        DispatchPolicy::OnChange => "On Change",
+        DispatchPolicy::EveryFrame => "Every Frame",
+        DispatchPolicy::Periodic { .. } => "Periodic",
+        DispatchPolicy::Manual => "Manual",
+    }
+}
+
+/// Grid rows editing a pass's [`DispatchPolicy`]: the policy itself and, when
+/// periodic, its interval. Returns whether the policy changed.
+pub fn dispatch_policy_rows(
+    ui: &mut Ui,
+    id_salt: impl AsIdSalt,
+    label: &str,
+    doc: impl FieldDoc,
+    policy: &mut DispatchPolicy,
+) -> bool {
+    let mut changed = field::row_doc(ui, label, doc, |ui| {
+        let before = std::mem::discriminant(&*policy);
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(dispatch_label(policy))
+            .show_ui(ui, |ui| {
+                dispatch_option(ui, policy, DispatchPolicy::OnChange);
+                dispatch_option(ui, policy, DispatchPolicy::EveryFrame);
+                // Keep the existing interval if already Periodic; otherwise
+                // seed a sensible default when switching in.
+                let is_periodic = matches!(policy, DispatchPolicy::Periodic { .. });
+                if ui.selectable_label(is_periodic, "Periodic").clicked() && !is_periodic {
+                    *policy = DispatchPolicy::Periodic {
+                        interval: instant::Duration::from_millis(50),
+                    };
+                }
+                dispatch_option(ui, policy, DispatchPolicy::Manual);
+            });
+        std::mem::discriminant(&*policy) != before
+    });
+
+    if let DispatchPolicy::Periodic { interval } = policy {
+        let mut secs = interval.as_secs_f32();
+        if inspector::f32_drag_row_doc(
+            ui,
+            "Interval (s)",
+            field_doc!(
+                "Seconds between runs. The pass runs once each interval of \
+                accumulated frame time, so the rate is the same on any monitor."
+            ),
+            &mut secs,
+            0.0001..=10.0,
+            0.001,
+            4,
+        ) {
+            *interval = instant::Duration::from_secs_f32(secs.max(0.0001));
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// A button running `step` once on the next frame, whatever its dispatch
+/// policy. Only enabled while the step is scheduled and enabled. Returns
+/// whether it was clicked.
+pub fn dispatch_now_button(ui: &mut Ui, presentation: &Presentation, step: PassStep) -> bool {
+    let scheduled = presentation.steps().contains(&step);
+    ui.add_enabled(
+        presentation.can_run(step),
+        egui::Button::new("Dispatch Now"),
+    )
+    .on_hover_text("Run this pass once on the next frame, whatever its dispatch policy.")
+    .on_disabled_hover_text(match scheduled {
+        true => "This pass is disabled in the presentation's schedule.",
+        false => "Add this pass to the presentation's schedule first.",
+    })
+    .clicked()
+}
+
+fn dispatch_option(ui: &mut Ui, policy: &mut DispatchPolicy, option: DispatchPolicy) {
+    let selected = std::mem::discriminant(policy) == std::mem::discriminant(&option);
+    if ui
+        .selectable_label(selected, dispatch_label(&option))
+        .clicked()
+    {
+        *policy = option;
+    }
+}
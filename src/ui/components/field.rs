@@ -14,6 +14,11 @@ pub fn error_label(ui: &mut Ui, text: impl Into<RichText>) -> Response {
     ui.add(egui::Label::new(text).selectable(true))
 }
 
+pub fn warning_label(ui: &mut Ui, text: impl Into<RichText>) -> Response {
+    let text = text.into().color(ui.visuals().warn_fg_color);
+    ui.add(egui::Label::new(text).selectable(true))
+}
+
 pub fn weak_label(ui: &mut Ui, text: impl Into<RichText>) -> Response {
     ui.label(text.into().size(11.0).color(ui.visuals().weak_text_color()))
 }
@@ -4,6 +4,7 @@ pub mod create_project_modal;
 pub mod delete_project_confirmation_modal;
 pub mod derive_model_material_modal;
 pub mod dimension_ref;
+pub mod dispatch_policy;
 pub mod draggable_list;
 pub mod field;
 pub mod field_docs;
@@ -11,9 +12,11 @@ pub mod flags_selector;
 pub mod hint;
 pub mod inspector;
 pub mod main_menu;
+pub mod pixel_probe;
 pub mod rename_text_edit;
 pub mod renameable_label;
 pub mod resource_icons;
 pub mod tiles;
 pub mod tree_node;
+pub mod uniform_field_ref;
 pub mod viewport;
@@ -0,0 +1,135 @@
+use egui::{Color32, Response, Ui};
+
+use crate::{
+    project::{TextureViewId, resource::texture_view::TextureViewRuntime},
+    ui::components::field,
+    utils::{
+        pixel_probe::{MAGNIFIER_TEXELS, PixelProbe},
+        readback::Readout,
+        wgpu_utils::TextureFormat,
+    },
+};
+
+/// Side of one magnified texel, in points.
+const MAGNIFIER_CELL: f32 = 12.0;
+
+/// Shows the texel under the cursor in a tooltip while it rests on
+/// `response`, an image of the whole texture view: its coordinates, its
+/// channels decoded from the texture's format, and a magnifier around it.
+pub fn show(
+    response: Response,
+    probe: &mut PixelProbe,
+    texture_view_id: TextureViewId,
+    texture_view: &TextureViewRuntime,
+) {
+    let Some(pointer) = response.hover_pos() else {
+        return;
+    };
+    let texture = texture_view.inner().texture();
+    let format = texture.format();
+    let (width, height) = (texture.width(), texture.height());
+    let uv = ((pointer - response.rect.min) / response.rect.size())
+        .clamp(egui::Vec2::ZERO, egui::Vec2::splat(1.0));
+    let texel = [
+        ((uv.x * width as f32) as u32).min(width.saturating_sub(1)),
+        ((uv.y * height as f32) as u32).min(height.saturating_sub(1)),
+    ];
+
+    response.on_hover_ui_at_pointer(|ui| {
+        probe.hover(texture_view_id, texel);
+
+        field::field_grid(ui, "pixel_probe_grid", |ui| {
+            field::row(ui, "Pixel", |ui| {
+                ui.monospace(format!("{}, {}", texel[0], texel[1]))
+            });
+            field::row(ui, "UV", |ui| {
+                ui.monospace(format!("{:.4}, {:.4}", uv.x, uv.y))
+            });
+            field::row(ui, "Format", |ui| match TextureFormat::from_wgpu(format) {
+                Some(format) => ui.label(format.label()),
+                None => ui.label(format!("{format:?}")),
+            });
+
+            match probe.readout(texture_view_id) {
+                Some(Ok(readout)) => match readout.texel(texel[0], texel[1]) {
+                    Some(values) => {
+                        for (column, value) in readout.layout().columns.iter().zip(values) {
+                            field::row(ui, column.name.as_str(), |ui| ui.monospace(value));
+                        }
+                    }
+                    // The cursor left the last read square; the next read catches up.
+                    None => {
+                        field::row(ui, "", field::spinner);
+                    }
+                },
+                Some(Err(error)) => {
+                    field::row(ui, "", |ui| field::error_label(ui, error.to_string()));
+                }
+                None => {
+                    field::row(ui, "", field::spinner);
+                }
+            }
+        });
+
+        if let Some(Ok(readout)) = probe.readout(texture_view_id) {
+            ui.add_space(4.0);
+            magnifier(ui, readout, texel);
+        }
+    });
+}
+
+/// Draws the texels around `center` as flat squares, so zooming in never
+/// blurs them. Texels outside the last read are left empty.
+fn magnifier(ui: &mut Ui, readout: &Readout, center: [u32; 2]) {
+    let side = MAGNIFIER_TEXELS as f32 * MAGNIFIER_CELL;
+    let (rect, _) = ui.allocate_exact_size(egui::Vec2::splat(side), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+
+    let half = (MAGNIFIER_TEXELS / 2) as i64;
+    for row in 0..MAGNIFIER_TEXELS {
+        for column in 0..MAGNIFIER_TEXELS {
+            let x = center[0] as i64 + column as i64 - half;
+            let y = center[1] as i64 + row as i64 - half;
+            let (Ok(x), Ok(y)) = (u32::try_from(x), u32::try_from(y)) else {
+                continue;
+            };
+            let Some(values) = readout.texel_numbers(x, y) else {
+                continue;
+            };
+            let min = rect.min + egui::vec2(column as f32, row as f32) * MAGNIFIER_CELL;
+            let cell = egui::Rect::from_min_size(min, egui::Vec2::splat(MAGNIFIER_CELL));
+            painter.rect_filled(cell, 0.0, texel_color(readout, &values));
+        }
+    }
+
+    let min = rect.min + egui::Vec2::splat(half as f32 * MAGNIFIER_CELL);
+    let center = egui::Rect::from_min_size(min, egui::Vec2::splat(MAGNIFIER_CELL));
+    painter.rect_stroke(
+        center,
+        0.0,
+        egui::Stroke::new(1.5, ui.visuals().strong_text_color()),
+        egui::StrokeKind::Outside,
+    );
+}
+
+/// The texel's color as the preview shows it, ignoring alpha. egui shows a
+/// texture's values as they are, without decoding sRGB, so neither does this.
+/// Missing channels read as zero, like in a shader, and depth or stencil
+/// texels show as gray.
+fn texel_color(readout: &Readout, values: &[f32]) -> Color32 {
+    let columns = &readout.layout().columns;
+    let channel = |name: &str| {
+        columns
+            .iter()
+            .position(|column| column.name == name)
+            .map(|index| values[index].clamp(0.0, 1.0))
+    };
+    let gray = values.first().map_or(0.0, |value| value.clamp(0.0, 1.0));
+    let (r, g, b) = match channel("r") {
+        Some(r) => (r, channel("g").unwrap_or(0.0), channel("b").unwrap_or(0.0)),
+        None => (gray, gray, gray),
+    };
+    let byte = |value: f32| (value * 255.0).round() as u8;
+    Color32::from_rgb(byte(r), byte(g), byte(b))
+}
@@ -43,6 +43,7 @@ pub fn resource_kind_icon(kind: ResourceKind) -> Icon {
         K::RenderPipeline => Icon::new(regular::GRAPH, [218, 105, 105]),
         K::RenderPass => Icon::new(regular::PAINT_BRUSH, [234, 129, 89]),
         K::ComputePass => Icon::new(regular::CPU, [117, 117, 230]),
+        K::CopyPass => Icon::new(regular::COPY, [104, 168, 226]),
         K::Presentation => Icon::new(regular::PRESENTATION, [226, 170, 68]),
     }
 }
@@ -68,6 +69,7 @@ fn resource_id_kind(id: ResourceId) -> ResourceKind {
         I::RenderPass(_) => ResourceKind::RenderPass,
         I::Presentation(_) => ResourceKind::Presentation,
         I::ComputePass(_) => ResourceKind::ComputePass,
+        I::CopyPass(_) => ResourceKind::CopyPass,
     }
 }
 
@@ -0,0 +1,45 @@
+use egui::{AsIdSalt, RichText, Ui};
+
+use crate::{
+    project::{
+        resource::uniform::{Uniform, UniformFieldRef},
+        storage::Storage,
+    },
+    ui::components::{field, inspector},
+};
+
+pub fn uniform_field_ref_edit(
+    ui: &mut Ui,
+    id_salt: impl AsIdSalt,
+    uniforms: &Storage<Uniform>,
+    value: &mut UniformFieldRef,
+) -> bool {
+    ui.horizontal(|ui| {
+        let mut changed =
+            inspector::storage_combo(ui, (&id_salt, "uniform"), uniforms, &mut value.id);
+        if changed {
+            value.field = 0;
+        }
+
+        let Some(uniform) = value.id.and_then(|id| uniforms.get(id).ok()) else {
+            return changed;
+        };
+        if uniform.fields().is_empty() {
+            field::weak_label(ui, "No fields");
+            return changed;
+        }
+        changed |= inspector::value_combo_with(
+            ui,
+            (&id_salt, "field"),
+            0..uniform.fields().len(),
+            |index| match uniform.get_field(*index) {
+                Some(field) => RichText::new(field.label()),
+                None => RichText::new("Select...").weak(),
+            },
+            &mut value.field,
+        );
+
+        changed
+    })
+    .inner
+}
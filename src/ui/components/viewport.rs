@@ -12,7 +12,7 @@ pub fn ui(
     viewport_id: ViewportId,
     egui_texture_id: egui::TextureId,
     last_size: Option<Size2d>,
-) -> Vec<StateEvent> {
+) -> (egui::Response, Vec<StateEvent>) {
     let mut events = Vec::new();
 
     let size_points = ui.available_size().max(egui::Vec2::new(1.0, 1.0));
@@ -81,7 +81,7 @@ pub fn ui(
         }
     }
 
-    events
+    (response, events)
 }
 
 fn get_last_sent_keyboard_state(ui: &mut Ui, viewport_id: ViewportId) -> Option<KeyboardState> {
@@ -1,7 +1,7 @@
 use crate::{
     app::AppEvent,
     file::file_storage::FileStorage,
-    project::{Project, RuntimeProject},
+    project::{Project, RuntimeProject, frame_capture::FrameCapture},
     ui::{
         components::tiles::TreePane,
         panels::{
@@ -10,7 +10,10 @@ use crate::{
         },
         rename::RenameState,
     },
-    utils::{event_queue::EventQueue, fps::FrameTimeTracker},
+    utils::{
+        event_queue::EventQueue, fps::FrameTimeTracker, image_compare::ImageCompare,
+        pixel_probe::PixelProbe, readback::Readbacks,
+    },
     workspace::StateEvent,
 };
 
@@ -21,6 +24,12 @@ pub struct StateSnapshot<'a> {
     pub runtime_project: &'a mut RuntimeProject,
     pub rename_state: &'a mut Option<RenameState>,
     pub file_storage: &'a mut FileStorage,
+    pub readbacks: &'a mut Readbacks,
+    pub pixel_probe: &'a mut PixelProbe,
+    pub frame_capture: &'a mut FrameCapture,
+    pub image_compare: &'a mut ImageCompare,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub recording: &'a mut crate::utils::recording::Recording,
     pub backend: wgpu::Backend,
     pub present_mode: wgpu::PresentMode,
     pub frame_time: &'a FrameTimeTracker,
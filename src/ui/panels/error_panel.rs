@@ -2,7 +2,8 @@ use egui::{Frame, RichText, Stroke};
 use egui_phosphor::regular;
 
 use crate::{
-    project::{Project, ResourceId, RuntimeProject},
+    error::AppError,
+    project::{Project, ResourceId, RuntimeProject, check::Severity},
     ui::{
         components::{field, resource_icons},
         pane::StateSnapshot,
@@ -14,18 +15,80 @@ use crate::{
 #[derive(Default)]
 pub struct ErrorPanel {
     open: bool,
-    errors: Vec<(ResourceId, String)>,
+    errors: Vec<ErrorEntry>,
+}
+
+struct ErrorEntry {
+    id: ResourceId,
+    severity: Severity,
+    message: String,
+    fix: Option<ErrorFix>,
+}
+
+impl ErrorEntry {
+    fn new(id: ResourceId, severity: Severity, error: &AppError) -> Self {
+        Self {
+            id,
+            severity,
+            message: error.to_string(),
+            fix: ErrorFix::for_error(error),
+        }
+    }
+}
+
+/// A one-click action that resolves an error.
+struct ErrorFix {
+    label: &'static str,
+    event: StateEvent,
+}
+
+impl ErrorFix {
+    fn for_error(error: &AppError) -> Option<Self> {
+        match error {
+            AppError::VertexInputMismatch {
+                model_id,
+                suggested_spec: Some(spec),
+                ..
+            } => Some(ErrorFix {
+                label: "Reorder vertex buffer to match shader",
+                event: StateEvent::SetVertexBufferSpec {
+                    model_id: *model_id,
+                    spec: spec.clone(),
+                },
+            }),
+            AppError::RenderGraphOrder { steps, .. } => Some(ErrorFix {
+                label: "Apply suggested order",
+                event: StateEvent::SetPresentationSteps(steps.clone()),
+            }),
+            _ => None,
+        }
+    }
 }
 
 impl ErrorPanel {
     pub fn tick(&mut self, runtime_project: &RuntimeProject) {
         let current: Vec<_> = runtime_project
             .iter_errors()
-            .map(|(id, error)| (id, error.to_string()))
+            .map(|(id, error)| ErrorEntry::new(id, Severity::Error, error))
+            .chain(
+                runtime_project
+                    .iter_warnings()
+                    .map(|(id, error)| ErrorEntry::new(id, Severity::Warning, error)),
+            )
             .collect();
 
-        if current.iter().any(|id| !self.errors.contains(id)) {
+        let is_new = |entry: &ErrorEntry| {
+            !self
+                .errors
+                .iter()
+                .any(|previous| previous.id == entry.id && previous.message == entry.message)
+        };
+        if current
+            .iter()
+            .any(|entry| entry.severity == Severity::Error && is_new(entry))
+        {
             // Auto-open the panel if any resource becomes erroring that wasn't already erroring last frame.
+            // Warnings are advisory and wait for the user to open it.
             self.open = true;
         }
 
@@ -38,16 +101,40 @@ impl ErrorPanel {
             false => regular::CARET_UP,
         };
 
-        let (text, color) = match self.errors.len() {
-            0 => (
+        let count = |severity| {
+            self.errors
+                .iter()
+                .filter(|entry| entry.severity == severity)
+                .count()
+        };
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        let (text, color) = match (count(Severity::Error), count(Severity::Warning)) {
+            (0, 0) => (
                 format!("{} No errors {caret}", regular::CHECK_CIRCLE),
                 ui.visuals().weak_text_color(),
             ),
-            count => (
+            (0, warnings) => (
+                format!(
+                    "{} {warnings} warning{} {caret}",
+                    regular::WARNING,
+                    plural(warnings),
+                ),
+                ui.visuals().warn_fg_color,
+            ),
+            (errors, 0) => (
+                format!(
+                    "{} {errors} error{} {caret}",
+                    regular::WARNING,
+                    plural(errors)
+                ),
+                ui.visuals().error_fg_color,
+            ),
+            (errors, warnings) => (
                 format!(
-                    "{} {count} error{} {caret}",
+                    "{} {errors} error{}, {warnings} warning{} {caret}",
                     regular::WARNING,
-                    if count == 1 { "" } else { "s" },
+                    plural(errors),
+                    plural(warnings),
                 ),
                 ui.visuals().error_fg_color,
             ),
@@ -88,7 +175,7 @@ fn error_list_content(
     ui: &mut egui::Ui,
     project: &Project,
     event_queue: &mut EventQueue<StateEvent>,
-    errors: &[(ResourceId, String)],
+    errors: &[ErrorEntry],
 ) {
     ui.horizontal(|ui| {
         ui.scope(|ui| {
@@ -126,8 +213,8 @@ fn error_list_content(
                 return;
             }
 
-            for (id, error) in errors {
-                error_card(ui, project, event_queue, *id, error);
+            for entry in errors {
+                error_card(ui, project, event_queue, entry);
                 ui.add_space(4.0);
             }
         });
@@ -137,10 +224,19 @@ fn error_card(
     ui: &mut egui::Ui,
     project: &Project,
     event_queue: &mut EventQueue<StateEvent>,
-    id: ResourceId,
-    error: &str,
+    entry: &ErrorEntry,
 ) {
-    let error_color = ui.visuals().error_fg_color;
+    let ErrorEntry {
+        id,
+        severity,
+        message: error,
+        fix,
+    } = entry;
+    let id = *id;
+    let error_color = match severity {
+        Severity::Error => ui.visuals().error_fg_color,
+        Severity::Warning => ui.visuals().warn_fg_color,
+    };
 
     Frame::new()
         .fill(ui.visuals().faint_bg_color)
@@ -182,6 +278,16 @@ fn error_card(
                 });
             });
 
-            field::error_label(ui, RichText::new(error).monospace());
+            match severity {
+                Severity::Error => field::error_label(ui, RichText::new(error).monospace()),
+                Severity::Warning => field::warning_label(ui, RichText::new(error).monospace()),
+            };
+
+            if let Some(fix) = fix {
+                let text = format!("{} {}", regular::WRENCH, fix.label);
+                if ui.small_button(text).clicked() {
+                    event_queue.add(fix.event.clone());
+                }
+            }
         });
 }
@@ -0,0 +1,259 @@
+//! Frame debugger: a captured frame's steps as a timeline, and for each one
+//! the textures it read and wrote at that point, the commands recorded into
+//! its bundle and the uniforms it had bound.
+
+use egui::load::SizedTexture;
+
+use crate::{
+    project::{
+        Project, ResourceId,
+        frame_capture::{CapturedStep, Snapshot},
+        render_graph::AccessKind,
+        resource::{presentation::PassStep, render_pass::DrawCommand, texture_view::BufferSide},
+    },
+    ui::{
+        components::{field, inspector, resource_icons},
+        pane::StateSnapshot,
+    },
+    utils::{event_queue::EventQueue, wgpu_utils::TextureFormat},
+    workspace::StateEvent,
+};
+
+/// Longest side of a snapshot thumbnail, in points.
+const THUMBNAIL_SIZE: f32 = 160.0;
+/// Longest side of a snapshot shown on hover, in points.
+const ENLARGED_SIZE: f32 = 512.0;
+
+pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        let capturing = state.frame_capture.is_requested();
+        if ui
+            .add_enabled(!capturing, egui::Button::new("Capture Frame"))
+            .clicked()
+        {
+            state.frame_capture.request();
+        }
+        if capturing {
+            field::spinner(ui);
+        }
+    });
+    ui.separator();
+
+    let Some(frame) = state.frame_capture.frame() else {
+        field::centered(ui, |ui| {
+            field::weak_label(
+                ui,
+                "Capture a frame to step through its passes and see what each one read and wrote.",
+            );
+        });
+        return;
+    };
+    if frame.steps.is_empty() {
+        field::weak_label(ui, "The captured frame had no enabled steps.");
+        return;
+    }
+
+    let mut selected = state.frame_capture.selected.min(frame.steps.len() - 1);
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(selected > 0, egui::Button::new("◀ Previous"))
+            .clicked()
+        {
+            selected -= 1;
+        }
+        if ui
+            .add_enabled(
+                selected + 1 < frame.steps.len(),
+                egui::Button::new("Next ▶"),
+            )
+            .clicked()
+        {
+            selected += 1;
+        }
+        ui.label(format!("Step {} of {}", selected + 1, frame.steps.len()));
+        field::weak_label(
+            ui,
+            format!("captured at {:.2}s", frame.elapsed.as_secs_f32()),
+        );
+    });
+
+    egui::ScrollArea::horizontal()
+        .id_salt("frame_capture_timeline")
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                for (index, step) in frame.steps.iter().enumerate() {
+                    let label = state.project.label(step.step).unwrap_or("Unknown");
+                    let icon = resource_icons::resource_id_icon(step.step.into());
+                    let mut text = resource_icons::icon_text(ui, icon, label);
+                    if !step.ran {
+                        text = egui::RichText::new(label).weak().into();
+                    }
+                    if ui.selectable_label(index == selected, text).clicked() {
+                        selected = index;
+                    }
+                }
+            });
+        });
+    ui.separator();
+
+    let step = &frame.steps[selected];
+    step_ui(ui, state.project, state.event_queue, step);
+    state.frame_capture.selected = selected;
+}
+
+fn step_ui(
+    ui: &mut egui::Ui,
+    project: &Project,
+    event_queue: &mut EventQueue<StateEvent>,
+    step: &CapturedStep,
+) {
+    ui.horizontal(|ui| {
+        let label = project.label(step.step).unwrap_or("Unknown");
+        ui.strong(label);
+        if ui.small_button("Inspect").clicked() {
+            event_queue.inspect_resource(step.step);
+        }
+    });
+    if !step.ran {
+        field::weak_label(
+            ui,
+            "It didn't run this frame, because of its dispatch policy or because a render pass wasn't ready.",
+        );
+        return;
+    }
+
+    inspector::section(ui, "Inputs", |ui| {
+        snapshots_ui(ui, project, &step.inputs, "It reads no textures.");
+    });
+    inspector::section(ui, "Outputs", |ui| {
+        snapshots_ui(ui, project, &step.outputs, "It writes no textures.");
+    });
+
+    if let PassStep::Render(_) = step.step {
+        inspector::section(ui, "Draw Calls", |ui| {
+            commands_ui(ui, project, &step.commands);
+        });
+    }
+
+    inspector::section(ui, "Uniforms", |ui| {
+        if step.uniforms.is_empty() {
+            field::weak_label(ui, "Its bind groups hold no uniforms.");
+        }
+        for uniform in &step.uniforms {
+            ui.strong(project.label(uniform.id).unwrap_or("Unknown"));
+            field::field_grid(ui, ("frame_capture_uniform", uniform.id), |ui| {
+                for (label, value) in &uniform.fields {
+                    field::row(ui, label.as_str(), |ui| ui.monospace(value));
+                }
+            });
+        }
+    });
+}
+
+fn snapshots_ui(ui: &mut egui::Ui, project: &Project, snapshots: &[Snapshot], empty: &str) {
+    if snapshots.is_empty() {
+        field::weak_label(ui, empty);
+        return;
+    }
+    ui.horizontal_wrapped(|ui| {
+        for snapshot in snapshots {
+            ui.vertical(|ui| {
+                ui.set_max_width(THUMBNAIL_SIZE);
+                let mut label = project
+                    .label(snapshot.texture.id)
+                    .unwrap_or("Unknown")
+                    .to_string();
+                if snapshot.texture.side == BufferSide::Back {
+                    label.push_str(" (Back)");
+                }
+                ui.label(label);
+                field::weak_label(ui, access_label(snapshot.access));
+
+                let image = match &snapshot.image {
+                    Ok(image) => image,
+                    Err(error) => {
+                        field::error_label(ui, error.to_string());
+                        return;
+                    }
+                };
+                match image.egui_id() {
+                    Some(egui_id) => {
+                        let [width, height] = image.size().map(|side| side.max(1) as f32);
+                        let fit = |longest: f32| {
+                            let scale = longest / width.max(height);
+                            SizedTexture::new(egui_id, (width * scale, height * scale))
+                        };
+                        ui.add(egui::Image::new(fit(THUMBNAIL_SIZE)).sense(egui::Sense::hover()))
+                            .on_hover_ui(|ui| {
+                                ui.add(egui::Image::new(fit(ENLARGED_SIZE)));
+                            });
+                    }
+                    None => {
+                        field::weak_label(ui, "This format can't be shown.");
+                    }
+                }
+
+                let [width, height] = image.size();
+                let format = image.format();
+                let format = match TextureFormat::from_wgpu(format) {
+                    Some(format) => format.label().to_string(),
+                    None => format!("{format:?}"),
+                };
+                field::weak_label(ui, format!("{width} × {height} · {format}"));
+            });
+        }
+    });
+}
+
+fn commands_ui(ui: &mut egui::Ui, project: &Project, commands: &[DrawCommand]) {
+    if commands.is_empty() {
+        field::weak_label(ui, "The bundle holds no commands.");
+        return;
+    }
+    egui::Grid::new("frame_capture_commands")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for (index, command) in commands.iter().enumerate() {
+                field::weak_label(ui, index.to_string());
+                let label = |id: ResourceId| project.label(id).unwrap_or("Unknown").to_string();
+                let (name, arguments) = match command {
+                    DrawCommand::SetPipeline(id) => ("Set Pipeline", label((*id).into())),
+                    DrawCommand::SetBindGroup { slot, bind_group } => (
+                        "Set Bind Group",
+                        match bind_group {
+                            Some(id) => format!("{slot}: {}", label((*id).into())),
+                            None => format!("{slot}: empty"),
+                        },
+                    ),
+                    DrawCommand::SetMesh { model, mesh } => {
+                        ("Set Mesh", format!("{} #{mesh}", label((*model).into())))
+                    }
+                    DrawCommand::Draw {
+                        vertices,
+                        instances,
+                    } => (
+                        "Draw",
+                        format!("vertices {vertices:?}, instances {instances:?}"),
+                    ),
+                    DrawCommand::DrawIndexed { indices, instances } => (
+                        "Draw Indexed",
+                        format!("indices {indices:?}, instances {instances:?}"),
+                    ),
+                };
+                ui.monospace(name);
+                ui.monospace(arguments);
+                ui.end_row();
+            }
+        });
+}
+
+fn access_label(access: AccessKind) -> &'static str {
+    match access {
+        AccessKind::Sampled => "Sampled",
+        AccessKind::Storage => "Storage",
+        AccessKind::ColorAttachment => "Color Target",
+        AccessKind::DepthAttachment => "Depth Target",
+        AccessKind::Copy => "Copy",
+    }
+}
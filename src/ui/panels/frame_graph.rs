@@ -0,0 +1,375 @@
+//! Node graph of the presentation schedule: every pass, the bind groups and
+//! uniforms feeding it and the textures it reads and writes, laid out left to
+//! right in execution order.
+
+use egui::{Align2, Color32, CornerRadius, FontId, Pos2, Rect, Sense, Stroke, StrokeKind, Vec2};
+
+use crate::{
+    project::{
+        BindGroupId, Project, ProjectResource, ResourceId, RuntimeProject, TextureId, UniformId,
+        render_graph::RenderGraph, resource::bindgroup::BindGroupResource,
+    },
+    ui::{
+        components::{field, resource_icons},
+        pane::StateSnapshot,
+    },
+};
+
+const NODE_WIDTH: f32 = 170.0;
+const COLUMN_GAP: f32 = 64.0;
+const NODE_GAP: f32 = 16.0;
+const HEADER_HEIGHT: f32 = 26.0;
+const ROW_HEIGHT: f32 = 20.0;
+const THUMBNAIL_HEIGHT: f32 = 96.0;
+const MARGIN: f32 = 12.0;
+
+struct Node {
+    id: ResourceId,
+    column: usize,
+    content: NodeContent,
+}
+
+enum NodeContent {
+    Pass,
+    Texture,
+    BindGroup { uniforms: Vec<UniformId> },
+}
+
+struct Edge {
+    from: usize,
+    to: usize,
+    write: bool,
+}
+
+#[derive(Default)]
+struct FrameGraph {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+impl FrameGraph {
+    /// Step `i` sits in column `2i + 1`, with its bind groups in the column
+    /// before it and the textures it writes in the column after. Textures no
+    /// step writes are inputs and go in the first column.
+    fn build(project: &Project) -> Self {
+        let render_graph = RenderGraph::build(project);
+        let mut graph = Self::default();
+
+        for (index, access) in render_graph.steps().iter().enumerate() {
+            let pass = graph.node(access.step.into(), 2 * index + 1, || NodeContent::Pass);
+
+            for bind_group_id in &access.bind_groups {
+                let node = graph.node((*bind_group_id).into(), 2 * index, || {
+                    NodeContent::BindGroup {
+                        uniforms: bind_group_uniforms(project, *bind_group_id),
+                    }
+                });
+                graph.edge(node, pass, false);
+            }
+
+            for (texture, _) in &access.writes {
+                let node = graph.node(texture.id.into(), 2 * index + 2, || NodeContent::Texture);
+                graph.edge(pass, node, true);
+            }
+        }
+
+        // Reads are linked once every written texture has its column.
+        for access in render_graph.steps() {
+            let pass = graph.node(access.step.into(), 0, || NodeContent::Pass);
+            for (texture, _) in &access.reads {
+                let node = graph.node(texture.id.into(), 0, || NodeContent::Texture);
+                graph.edge(node, pass, false);
+            }
+        }
+
+        graph
+    }
+
+    fn node(
+        &mut self,
+        id: ResourceId,
+        column: usize,
+        content: impl FnOnce() -> NodeContent,
+    ) -> usize {
+        if let Some(index) = self.nodes.iter().position(|node| node.id == id) {
+            return index;
+        }
+        self.nodes.push(Node {
+            id,
+            column,
+            content: content(),
+        });
+        self.nodes.len() - 1
+    }
+
+    fn edge(&mut self, from: usize, to: usize, write: bool) {
+        let exists = self
+            .edges
+            .iter()
+            .any(|edge| edge.from == from && edge.to == to);
+        if !exists {
+            self.edges.push(Edge { from, to, write });
+        }
+    }
+
+    /// Stacks the nodes of each column, centering the columns vertically.
+    fn layout(&self, origin: Pos2) -> (Vec<Rect>, Vec2) {
+        let column_count = self.nodes.iter().map(|node| node.column + 1).max();
+        let mut column_heights = vec![0.0; column_count.unwrap_or_default()];
+        for node in &self.nodes {
+            column_heights[node.column] += node.height() + NODE_GAP;
+        }
+        let height = column_heights.iter().copied().fold(0.0, f32::max) - NODE_GAP;
+
+        let mut cursors: Vec<f32> = column_heights
+            .iter()
+            .map(|column_height| (height - (column_height - NODE_GAP)) * 0.5)
+            .collect();
+        let rects = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let x = node.column as f32 * (NODE_WIDTH + COLUMN_GAP);
+                let y = &mut cursors[node.column];
+                let rect = Rect::from_min_size(
+                    origin + egui::vec2(x, *y),
+                    egui::vec2(NODE_WIDTH, node.height()),
+                );
+                *y += node.height() + NODE_GAP;
+                rect
+            })
+            .collect();
+
+        let width = column_heights.len() as f32 * (NODE_WIDTH + COLUMN_GAP) - COLUMN_GAP;
+        (rects, egui::vec2(width, height.max(0.0)))
+    }
+}
+
+impl Node {
+    fn height(&self) -> f32 {
+        match &self.content {
+            NodeContent::Pass => HEADER_HEIGHT,
+            NodeContent::Texture => HEADER_HEIGHT + THUMBNAIL_HEIGHT,
+            NodeContent::BindGroup { uniforms } => {
+                HEADER_HEIGHT + uniforms.len() as f32 * ROW_HEIGHT
+            }
+        }
+    }
+}
+
+fn bind_group_uniforms(project: &Project, bind_group_id: BindGroupId) -> Vec<UniformId> {
+    let Ok(bind_group) = project.bind_groups.get(bind_group_id) else {
+        return vec![];
+    };
+    bind_group
+        .entries()
+        .iter()
+        .filter_map(|entry| match entry.resource {
+            BindGroupResource::Uniform(uniform_id) => uniform_id,
+            _ => None,
+        })
+        .collect()
+}
+
+/// The first previewable view of the texture, and the texture's aspect ratio.
+fn thumbnail(
+    project: &Project,
+    runtime_project: &RuntimeProject,
+    texture_id: TextureId,
+) -> Option<(egui::TextureId, f32)> {
+    let egui_id = project
+        .texture_views
+        .list_sorted()
+        .filter(|(_, view)| view.texture_id() == Some(texture_id))
+        .find_map(|(id, _)| {
+            let runtime = runtime_project.texture_views.get_init(id).ok()??;
+            runtime.egui_id()
+        })?;
+
+    let aspect = match runtime_project.textures.get_init(texture_id) {
+        Ok(Some(texture)) => {
+            let texture = texture.inner();
+            texture.width() as f32 / texture.height().max(1) as f32
+        }
+        _ => 1.0,
+    };
+    Some((egui_id, aspect))
+}
+
+pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui) {
+    let graph = FrameGraph::build(state.project);
+    if graph.nodes.is_empty() {
+        field::centered(ui, |ui| {
+            field::weak_label(
+                ui,
+                "The schedule is empty. Add passes to the presentation to see how data flows between them.",
+            );
+        });
+        return;
+    }
+
+    let origin = ui.cursor().min + Vec2::splat(MARGIN);
+    let (rects, size) = graph.layout(origin);
+    let (_, painter) = ui.allocate_painter(size + Vec2::splat(MARGIN * 2.0), Sense::hover());
+
+    let visuals = ui.visuals().clone();
+    for edge in &graph.edges {
+        let from = rects[edge.from].right_center();
+        let to = rects[edge.to].left_center();
+        let bend = ((to.x - from.x).abs() * 0.5).max(COLUMN_GAP * 0.5);
+        let color = match edge.write {
+            true => visuals.selection.bg_fill,
+            false => visuals.weak_text_color(),
+        };
+        painter.add(egui::epaint::CubicBezierShape::from_points_stroke(
+            [
+                from,
+                from + egui::vec2(bend, 0.0),
+                to - egui::vec2(bend, 0.0),
+                to,
+            ],
+            false,
+            Color32::TRANSPARENT,
+            Stroke::new(1.5, color),
+        ));
+    }
+
+    for (node, rect) in graph.nodes.iter().zip(&rects) {
+        let node_response = ui
+            .interact(
+                *rect,
+                ui.id().with(("frame_graph_node", node.id)),
+                Sense::click(),
+            )
+            .on_hover_cursor(egui::CursorIcon::PointingHand);
+
+        let stroke = match node_response.hovered() {
+            true => visuals.widgets.hovered.bg_stroke,
+            false => visuals.widgets.noninteractive.bg_stroke,
+        };
+        let fill = match node.content {
+            NodeContent::Pass => visuals.widgets.inactive.bg_fill,
+            _ => visuals.extreme_bg_color,
+        };
+        painter.rect(
+            *rect,
+            CornerRadius::same(4),
+            fill,
+            stroke,
+            StrokeKind::Inside,
+        );
+
+        let label = state.project.label(node.id).unwrap_or("Unknown");
+        let header = Rect::from_min_size(rect.min, egui::vec2(NODE_WIDTH, HEADER_HEIGHT));
+        node_label(
+            &painter,
+            header,
+            resource_icons::resource_id_icon(node.id),
+            label,
+            &visuals,
+        );
+
+        if node_response.clicked() {
+            state.event_queue.inspect_resource(node.id);
+        }
+
+        match &node.content {
+            NodeContent::Pass => {}
+            NodeContent::Texture => {
+                let area = Rect::from_min_max(
+                    rect.left_top() + egui::vec2(4.0, HEADER_HEIGHT),
+                    rect.right_bottom() - egui::vec2(4.0, 4.0),
+                );
+                let ResourceId::Texture(texture_id) = node.id else {
+                    continue;
+                };
+                match thumbnail(state.project, state.runtime_project, texture_id) {
+                    Some((egui_id, aspect)) => {
+                        let size = match aspect > area.aspect_ratio() {
+                            true => egui::vec2(area.width(), area.width() / aspect),
+                            false => egui::vec2(area.height() * aspect, area.height()),
+                        };
+                        let image_rect = Rect::from_center_size(area.center(), size);
+                        let uv = Rect::from_min_max(Pos2::ZERO, egui::pos2(1.0, 1.0));
+                        painter.image(egui_id, image_rect, uv, Color32::WHITE);
+                    }
+                    None => {
+                        painter.text(
+                            area.center(),
+                            Align2::CENTER_CENTER,
+                            "No preview",
+                            FontId::proportional(11.0),
+                            visuals.weak_text_color(),
+                        );
+                    }
+                }
+            }
+            NodeContent::BindGroup { uniforms } => {
+                for (row, uniform_id) in uniforms.iter().enumerate() {
+                    let row_rect = Rect::from_min_size(
+                        rect.left_top() + egui::vec2(0.0, HEADER_HEIGHT + row as f32 * ROW_HEIGHT),
+                        egui::vec2(NODE_WIDTH, ROW_HEIGHT),
+                    );
+                    let row_response = ui
+                        .interact(
+                            row_rect,
+                            ui.id().with(("frame_graph_uniform", node.id, row)),
+                            Sense::click(),
+                        )
+                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                    if row_response.hovered() {
+                        painter.rect_filled(
+                            row_rect.shrink(2.0),
+                            2.0,
+                            visuals.widgets.hovered.bg_fill,
+                        );
+                    }
+
+                    let label = state
+                        .project
+                        .uniforms
+                        .get(*uniform_id)
+                        .map(|uniform| uniform.label())
+                        .unwrap_or("Unknown");
+                    let icon = resource_icons::resource_id_icon((*uniform_id).into());
+                    node_label(
+                        &painter,
+                        row_rect.shrink2(egui::vec2(8.0, 0.0)),
+                        icon,
+                        label,
+                        &visuals,
+                    );
+
+                    if row_response.clicked() {
+                        state.event_queue.inspect_resource(*uniform_id);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Paints the icon and label of a node row, truncating the label to fit.
+fn node_label(
+    painter: &egui::Painter,
+    rect: Rect,
+    icon: resource_icons::Icon,
+    label: &str,
+    visuals: &egui::Visuals,
+) {
+    let font = FontId::proportional(13.0);
+    let glyph = painter.text(
+        rect.left_center() + egui::vec2(8.0, 0.0),
+        Align2::LEFT_CENTER,
+        icon.glyph,
+        font.clone(),
+        icon.color,
+    );
+
+    let mut job =
+        egui::text::LayoutJob::simple_singleline(label.to_string(), font, visuals.text_color());
+    job.wrap = egui::text::TextWrapping::truncate_at_width(rect.right() - glyph.right() - 14.0);
+    let galley = painter.layout_job(job);
+    let pos = egui::pos2(glyph.right() + 6.0, rect.center().y - galley.size().y * 0.5);
+    painter.galley(pos, galley, visuals.text_color());
+}
@@ -0,0 +1,142 @@
+//! Image comparison: a texture view, as the last frame rendered it, against a
+//! reference image from the project, with a heat map of where they differ.
+
+use egui::load::SizedTexture;
+use egui_phosphor::regular;
+
+use crate::{
+    project::paths::is_image_file,
+    ui::{
+        components::{field, inspector},
+        pane::StateSnapshot,
+    },
+    utils::image_compare::{Comparison, Tolerance},
+};
+
+/// Longest side of the heat map, in points.
+const HEAT_MAP_SIZE: f32 = 512.0;
+
+pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui) {
+    let compare = &mut *state.image_compare;
+    field::field_grid(ui, "image_compare_settings", |ui| {
+        field::row(ui, "Texture View", |ui| {
+            inspector::storage_combo(
+                ui,
+                "image_compare_texture_view",
+                &state.project.texture_views,
+                &mut compare.texture_view,
+            );
+        });
+        field::row(ui, "Reference", |ui| match state.file_storage.files() {
+            Some(files) => {
+                inspector::file_combo(
+                    ui,
+                    "image_compare_reference",
+                    files,
+                    &mut compare.reference,
+                    is_image_file,
+                );
+            }
+            None => {
+                field::spinner(ui);
+            }
+        });
+        field::row(ui, "Pixel Tolerance", |ui| {
+            ui.add(
+                egui::DragValue::new(&mut compare.tolerance.pixel)
+                    .range(0.0..=1.0)
+                    .speed(0.001),
+            );
+        });
+        field::row(ui, "Allowed Differing", |ui| {
+            let mut percent = compare.tolerance.fraction * 100.0;
+            let drag = egui::DragValue::new(&mut percent)
+                .range(0.0..=100.0)
+                .speed(0.01)
+                .suffix("%");
+            if ui.add(drag).changed() {
+                compare.tolerance.fraction = percent / 100.0;
+            }
+        });
+    });
+
+    ui.horizontal(|ui| {
+        let running = compare.is_running();
+        if ui
+            .add_enabled(!running, egui::Button::new("Compare"))
+            .clicked()
+        {
+            compare.request();
+        }
+        if running {
+            field::spinner(ui);
+        }
+    });
+    ui.separator();
+
+    let heat_map = compare.heat_map_texture(ui.ctx()).cloned();
+    match compare.result() {
+        None => {
+            field::centered(ui, |ui| {
+                field::weak_label(
+                    ui,
+                    "Compare a texture view against a reference image to measure how far it drifted.",
+                );
+            });
+        }
+        Some(Err(error)) => {
+            field::error_label(ui, format!("Failed to compare: {error}"));
+        }
+        Some(Ok(comparison)) => {
+            metrics_ui(ui, comparison, compare.tolerance);
+            if let Some(heat_map) = heat_map {
+                inspector::section(ui, "Heat Map", |ui| {
+                    let size = heat_map.size_vec2();
+                    let scale = (HEAT_MAP_SIZE / size.max_elem()).min(1.0);
+                    ui.image(SizedTexture::new(heat_map.id(), size * scale));
+                    field::weak_label(
+                        ui,
+                        "Black where the images match, brightening to white at the largest difference.",
+                    );
+                });
+            }
+        }
+    }
+}
+
+fn metrics_ui(ui: &mut egui::Ui, comparison: &Comparison, tolerance: Tolerance) {
+    if comparison.matches(tolerance) {
+        ui.colored_label(
+            egui::Color32::from_rgb(102, 208, 177),
+            format!("{} Matches", regular::CHECK_CIRCLE),
+        );
+    } else {
+        field::error_label(ui, format!("{} Differs", regular::X_CIRCLE));
+    }
+
+    field::field_grid(ui, "image_compare_metrics", |ui| {
+        field::row(ui, "PSNR", |ui| {
+            ui.label(match comparison.psnr.is_finite() {
+                true => format!("{:.2} dB", comparison.psnr),
+                false => "∞ (identical)".to_string(),
+            });
+        });
+        field::row(ui, "SSIM", |ui| {
+            ui.label(format!("{:.5}", comparison.ssim));
+        });
+        field::row(ui, "Max Difference", |ui| {
+            ui.label(format!("{:.5}", comparison.max_difference));
+        });
+        field::row(ui, "Mean Difference", |ui| {
+            ui.label(format!("{:.5}", comparison.mean_difference));
+        });
+        field::row(ui, "Differing Pixels", |ui| {
+            let differing = comparison.differing_pixels(tolerance.pixel);
+            let total = comparison.pixel_count();
+            ui.label(format!(
+                "{differing} of {total} ({:.3}%)",
+                100.0 * differing as f64 / total.max(1) as f64
+            ));
+        });
+    });
+}
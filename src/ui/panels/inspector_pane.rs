@@ -1,19 +1,23 @@
 use std::fmt::Debug;
 
+use egui_phosphor::regular;
+
 use crate::{
     file::file_storage::OpenFileState,
     project::{
-        BindGroupId, CameraId, ComputePassId, DimensionId, ModelId, PresentationId, RenderPassId,
-        RenderPipelineId, ResourceId, SamplerId, ShaderId, TextureId, TextureViewId, UniformId,
-        ViewportId, paths::FilePath,
+        BindGroupId, CameraId, ComputePassId, CopyPassId, DimensionId, ModelId, PresentationId,
+        RenderPassId, RenderPipelineId, ResourceId, SamplerId, ShaderId, TextureId, TextureViewId,
+        UniformId, ViewportId, paths::FilePath,
     },
     ui::{
         components::{
-            resource_icons::{file_icon, icon_tab_title, resource_id_icon},
+            resource_icons::{Icon, file_icon, icon_tab_title, resource_id_icon},
             tiles::Pane,
         },
         pane::StateSnapshot,
+        panels::{frame_capture, frame_graph, image_compare, lint, readback},
     },
+    utils::readback::ReadbackTarget,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -33,6 +37,12 @@ pub enum InspectorPane {
     RenderPass(RenderPassId),
     Presentation(PresentationId),
     ComputePass(ComputePassId),
+    CopyPass(CopyPassId),
+    FrameGraph,
+    FrameCapture,
+    ImageCompare,
+    Lint,
+    Readback(ReadbackTarget),
 }
 
 fn resource_tab_title(id: impl Into<ResourceId>, state: &StateSnapshot<'_>) -> egui::WidgetText {
@@ -75,6 +85,28 @@ impl Pane for InspectorPane {
             InspectorPane::RenderPass(id) => resource_tab_title(*id, state),
             InspectorPane::Presentation(id) => resource_tab_title(*id, state),
             InspectorPane::ComputePass(id) => resource_tab_title(*id, state),
+            InspectorPane::CopyPass(id) => resource_tab_title(*id, state),
+            InspectorPane::FrameGraph => icon_tab_title(
+                Icon::new(regular::TREE_STRUCTURE, [226, 170, 68]),
+                "Frame Graph",
+            ),
+            InspectorPane::FrameCapture => icon_tab_title(
+                Icon::new(regular::FILM_STRIP, [226, 122, 68]),
+                "Frame Capture",
+            ),
+            InspectorPane::ImageCompare => {
+                icon_tab_title(Icon::new(regular::IMAGES, [208, 102, 161]), "Image Compare")
+            }
+            InspectorPane::Lint => {
+                icon_tab_title(Icon::new(regular::BROOM, [170, 186, 96]), "Project Lint")
+            }
+            InspectorPane::Readback(target) => {
+                let label = state.project.label(*target).unwrap_or_default();
+                icon_tab_title(
+                    Icon::new(regular::TABLE, [102, 208, 177]),
+                    &format!("{label} · Readback"),
+                )
+            }
         }
     }
 
@@ -83,6 +115,10 @@ impl Pane for InspectorPane {
             InspectorPane::File(file_path) => {
                 return !state.file_storage.is_file_known_deleted(file_path);
             }
+            InspectorPane::FrameGraph
+            | InspectorPane::FrameCapture
+            | InspectorPane::ImageCompare
+            | InspectorPane::Lint => return true,
             InspectorPane::Uniform(id) => (*id).into(),
             InspectorPane::BindGroup(id) => (*id).into(),
             InspectorPane::Shader(id) => (*id).into(),
@@ -97,6 +133,8 @@ impl Pane for InspectorPane {
             InspectorPane::RenderPass(id) => (*id).into(),
             InspectorPane::Presentation(id) => (*id).into(),
             InspectorPane::ComputePass(id) => (*id).into(),
+            InspectorPane::CopyPass(id) => (*id).into(),
+            InspectorPane::Readback(target) => (*target).into(),
         };
         state.project.label(resource_id).is_some()
     }
@@ -156,6 +194,24 @@ impl Pane for InspectorPane {
                         InspectorPane::ComputePass(compute_pass_id) => {
                             state.compute_pass_inspector_ui(ui, *compute_pass_id);
                         }
+                        InspectorPane::CopyPass(copy_pass_id) => {
+                            state.copy_pass_inspector_ui(ui, *copy_pass_id);
+                        }
+                        InspectorPane::FrameGraph => {
+                            frame_graph::ui(state, ui);
+                        }
+                        InspectorPane::FrameCapture => {
+                            frame_capture::ui(state, ui);
+                        }
+                        InspectorPane::ImageCompare => {
+                            image_compare::ui(state, ui);
+                        }
+                        InspectorPane::Lint => {
+                            lint::ui(state, ui);
+                        }
+                        InspectorPane::Readback(target) => {
+                            readback::ui(state, ui, *target);
+                        }
                     };
                 });
             });
@@ -5,9 +5,11 @@ use crate::{
         BindGroupId, ComputePassId,
         resource::{
             bindgroup::BindGroup,
-            compute_pass::{ComputePass, DispatchPolicy, DispatchUnit, WorkSize},
+            compute_pass::{ComputePass, DispatchUnit, IterationCount, MAX_ITERATIONS, WorkSize},
             dimension::{Dimension, DimensionRef},
+            presentation::{PassStep, Presentation},
             shader::Shader,
+            uniform::{Uniform, UniformFieldRef},
         },
         storage::Storage,
     },
@@ -15,15 +17,17 @@ use crate::{
         components::{
             code_editor::shader_code_section,
             dimension_ref::dimension_ref_edit,
+            dispatch_policy::{dispatch_now_button, dispatch_policy_rows},
             draggable_list::{ListEdits, draggable_list},
             field,
             field_docs::{FieldDoc, field_doc},
             inspector::{self, AsRichText},
             resource_icons,
+            uniform_field_ref::uniform_field_ref_edit,
         },
         pane::StateSnapshot,
     },
-    utils::shader_preview::ShaderGenCtx,
+    utils::{shader_preview::ShaderGenCtx, shader_reflection},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -76,9 +80,36 @@ impl AsRichText for DispatchUnitKind {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum IterationCountKind {
+    Fixed,
+    Uniform,
+}
+
+impl IterationCountKind {
+    fn from_count(count: &IterationCount) -> Self {
+        match count {
+            IterationCount::Fixed(_) => Self::Fixed,
+            IterationCount::Uniform(_) => Self::Uniform,
+        }
+    }
+}
+
+impl AsRichText for IterationCountKind {
+    fn as_rich_text(&self) -> egui::RichText {
+        match self {
+            Self::Fixed => "Fixed",
+            Self::Uniform => "Uniform",
+        }
+        .into()
+    }
+}
+
 const WORK_SIZE_KINDS: [WorkSizeKind; 2] = [WorkSizeKind::Fixed, WorkSizeKind::Dimension];
 const DISPATCH_UNITS: [DispatchUnitKind; 2] =
     [DispatchUnitKind::Workgroup, DispatchUnitKind::Invocation];
+const ITERATION_COUNT_KINDS: [IterationCountKind; 2] =
+    [IterationCountKind::Fixed, IterationCountKind::Uniform];
 
 impl StateSnapshot<'_> {
     pub fn compute_pass_inspector_ui(&mut self, ui: &mut egui::Ui, compute_pass_id: ComputePassId) {
@@ -87,15 +118,34 @@ impl StateSnapshot<'_> {
             return;
         };
 
-        compute_pass_fields_ui(
+        let shader_workgroup_size = compute_pass
+            .shader()
+            .and_then(|id| self.runtime_project.shaders.get_init(id).ok().flatten())
+            .and_then(|runtime| shader_reflection::workgroup_size(runtime.module()));
+
+        let step = PassStep::Compute(compute_pass_id);
+        if compute_pass_fields_ui(
             ui,
             compute_pass,
             &self.project.shaders,
             &self.project.dimensions,
-        );
+            &self.project.presentation,
+            step,
+            shader_workgroup_size,
+        ) {
+            self.runtime_project
+                .request_dispatch(&self.project.presentation, step);
+        }
 
         compute_pass_bind_groups_ui(ui, compute_pass_id, compute_pass, &self.project.bind_groups);
 
+        compute_pass_iterations_ui(
+            ui,
+            compute_pass,
+            &self.project.uniforms,
+            &self.project.bind_groups,
+        );
+
         if let Ok(pass) = self.project.compute_passes.get(compute_pass_id) {
             let ctx = ShaderGenCtx::from_project(self.project);
             shader_code_section(ui, pass, &ctx);
@@ -103,13 +153,17 @@ impl StateSnapshot<'_> {
     }
 }
 
+/// Returns whether **Dispatch Now** was clicked.
 fn compute_pass_fields_ui(
     ui: &mut egui::Ui,
     compute_pass: &mut ComputePass,
     shaders: &Storage<Shader>,
     dimensions: &Storage<Dimension>,
-) {
-    inspector::section(ui, "Settings", |ui| {
+    presentation: &Presentation,
+    step: PassStep,
+    shader_workgroup_size: Option<[u32; 3]>,
+) -> bool {
+    let dispatch_now = inspector::section(ui, "Settings", |ui| {
         field::field_grid(ui, "compute_pass_inspector_grid", |ui| {
             let mut shader_id = compute_pass.shader();
             if field::row_doc(
@@ -128,15 +182,20 @@ fn compute_pass_fields_ui(
 
             compute_pass_dispatch_ui(ui, compute_pass);
         });
+
+        dispatch_now_button(ui, presentation, step)
     });
 
-    compute_pass_dispatch_size_ui(ui, compute_pass, dimensions);
+    compute_pass_dispatch_size_ui(ui, compute_pass, dimensions, shader_workgroup_size);
+
+    dispatch_now
 }
 
 fn compute_pass_dispatch_size_ui(
     ui: &mut egui::Ui,
     compute_pass: &mut ComputePass,
     dimensions: &Storage<Dimension>,
+    shader_workgroup_size: Option<[u32; 3]>,
 ) {
     inspector::section_doc(
         ui,
@@ -152,7 +211,7 @@ fn compute_pass_dispatch_size_ui(
             of 64 (8x8x1) invocations each, for 128x128 invocations in total.\n\n\
             **Example**: to run the shader once per pixel of a 1920x1080 Dimension, with a \
             shader declaring `@workgroup_size(8, 8, 1)`:\n\
-            - Set **Unit** to Invocations, and **Workgroup Size** to 8, 8, 1.\n\
+            - Set **Unit** to Invocations. The workgroup size is read from the shader.\n\
             - Set **Size X** and **Size Y** to that Dimension's width and height, and \
             **Size Z** to 1.\n\
             - In the shader, read `@builtin(global_invocation_id)`: its `x` and `y` are the \
@@ -185,7 +244,7 @@ fn compute_pass_dispatch_size_ui(
                     dispatch_size.unit = match unit_kind {
                         DispatchUnitKind::Workgroup => DispatchUnit::Workgroup,
                         DispatchUnitKind::Invocation => DispatchUnit::Invocation {
-                            workgroup_size: [1, 1, 1],
+                            workgroup_size: None,
                         },
                     };
                 }
@@ -195,22 +254,42 @@ fn compute_pass_dispatch_size_ui(
                         ui,
                         "Workgroup Size",
                         field_doc!(
-                            "The workgroup size the sizes below are divided by. Set it to the \
-                             same value the shader declares in its `@workgroup_size`.\n\n\
-                             Nothing checks the two against each other.\n\n\
-                             If set **larger** than the shader declares, it dispatches too few \
-                             workgroups and leaves part of the range unprocessed.\n\n\
-                             If set **smaller**, it dispatches invocations past the range, \
-                             which matters only if the shader acts on ids beyond it.\n\n\
+                            "The workgroup size the sizes below are divided by. By default it \
+                             is read from the `@workgroup_size` the shader declares, and follows \
+                             it whenever the shader recompiles.\n\n\
+                             Check **Override** to enter it by hand instead. If set **larger** \
+                             than the shader declares, it dispatches too few workgroups and \
+                             leaves part of the range unprocessed. If set **smaller**, it \
+                             dispatches invocations past the range, which matters only if the \
+                             shader acts on ids beyond it.\n\n\
                              [WGSL spec](https://www.w3.org/TR/WGSL/#compute-shader-workgroups)"
                         ),
                         |ui| {
                             ui.horizontal(|ui| {
-                                for axis in workgroup_size.iter_mut() {
-                                    egui::DragValue::new(axis)
-                                        .speed(1)
-                                        .range(1..=u32::MAX)
-                                        .ui(ui);
+                                let mut overridden = workgroup_size.is_some();
+                                if ui.checkbox(&mut overridden, "Override").changed() {
+                                    *workgroup_size = overridden
+                                        .then(|| shader_workgroup_size.unwrap_or([1, 1, 1]));
+                                }
+
+                                match (workgroup_size.as_mut(), shader_workgroup_size) {
+                                    (Some(workgroup_size), _) => {
+                                        for axis in workgroup_size.iter_mut() {
+                                            egui::DragValue::new(axis)
+                                                .speed(1)
+                                                .range(1..=u32::MAX)
+                                                .ui(ui);
+                                        }
+                                    }
+                                    (None, Some([x, y, z])) => {
+                                        field::weak_label(
+                                            ui,
+                                            format!("{x}, {y}, {z} (from shader)"),
+                                        );
+                                    }
+                                    (None, None) => {
+                                        field::weak_label(ui, "Waiting for the shader to compile");
+                                    }
                                 }
                             });
                         },
@@ -252,7 +331,7 @@ fn compute_pass_dispatch_size_ui(
                 );
             });
 
-            match dispatch_size.into_work_groups(dimensions) {
+            match dispatch_size.into_work_groups(dimensions, shader_workgroup_size) {
                 Ok((x, y, z)) => {
                     field::weak_label(ui, format!("Resolves to dispatchWorkgroups({x}, {y}, {z})"))
                 }
@@ -299,82 +378,28 @@ fn work_size_row(
     });
 }
 
-fn dispatch_label(policy: &DispatchPolicy) -> &'static str {
-    match policy {
-        DispatchPolicy::OnChange => "On Change",
-        DispatchPolicy::EveryFrame => "Every Frame",
-        DispatchPolicy::Periodic { .. } => "Periodic",
-    }
-}
-
 fn compute_pass_dispatch_ui(ui: &mut egui::Ui, compute_pass: &mut ComputePass) {
     let mut policy = compute_pass.dispatch_policy();
-    let mut changed = false;
-
-    changed |= field::row_doc(
+    if dispatch_policy_rows(
         ui,
+        "compute_pass_dispatch",
         "Dispatch",
         field_doc!(
             "When this pass re-dispatches.\n\n\
             **On Change** runs only when an input changes (good for one-shot bakes). \
             **Every Frame** runs once per rendered frame. \
-            **Periodic** runs at a fixed cadence independent of the framerate.\n\n\
+            **Periodic** runs at a fixed cadence independent of the framerate. \
+            **Manual** runs only from **Dispatch Now** or when the simulation resets.\n\n\
+            **Dispatch Now** runs the pass once on the next frame whatever the policy, to step \
+            a simulation by hand.\n\n\
             Make sure to also add this pass to the presentation's compute pass list, or it won't run at all."
         ),
-        |ui| {
-            let before = std::mem::discriminant(&policy);
-            egui::ComboBox::from_id_salt("compute_pass_dispatch")
-                .selected_text(dispatch_label(&policy))
-                .show_ui(ui, |ui| {
-                    dispatch_option(ui, &mut policy, DispatchPolicy::OnChange);
-                    dispatch_option(ui, &mut policy, DispatchPolicy::EveryFrame);
-                    // Keep the existing interval if already Periodic; otherwise
-                    // seed a sensible default when switching in.
-                    let is_periodic = matches!(policy, DispatchPolicy::Periodic { .. });
-                    if ui.selectable_label(is_periodic, "Periodic").clicked() && !is_periodic {
-                        policy = DispatchPolicy::Periodic {
-                            interval: instant::Duration::from_millis(50),
-                        };
-                    }
-                });
-            std::mem::discriminant(&policy) != before
-        },
-    );
-
-    if let DispatchPolicy::Periodic { interval } = &mut policy {
-        let mut secs = interval.as_secs_f32();
-        if inspector::f32_drag_row_doc(
-            ui,
-            "Interval (s)",
-            field_doc!(
-                "Seconds between dispatches. The pass runs once each interval of \
-                accumulated frame time, so the rate is the same on any monitor."
-            ),
-            &mut secs,
-            0.0001..=10.0,
-            0.001,
-            4,
-        ) {
-            *interval = instant::Duration::from_secs_f32(secs.max(0.0001));
-            changed = true;
-        }
-    }
-
-    if changed {
+        &mut policy,
+    ) {
         compute_pass.set_dispatch(policy);
     }
 }
 
-fn dispatch_option(ui: &mut egui::Ui, policy: &mut DispatchPolicy, option: DispatchPolicy) {
-    let selected = std::mem::discriminant(policy) == std::mem::discriminant(&option);
-    if ui
-        .selectable_label(selected, dispatch_label(&option))
-        .clicked()
-    {
-        *policy = option;
-    }
-}
-
 fn compute_pass_bind_groups_ui(
     ui: &mut egui::Ui,
     compute_pass_id: ComputePassId,
@@ -434,6 +459,115 @@ fn compute_pass_bind_groups_ui(
     );
 }
 
+fn compute_pass_iterations_ui(
+    ui: &mut egui::Ui,
+    compute_pass: &mut ComputePass,
+    uniforms: &Storage<Uniform>,
+    bind_groups: &Storage<BindGroup>,
+) {
+    inspector::section_doc(
+        ui,
+        "Iterations",
+        field_doc!(
+            "How many times this pass dispatches each time it runs, for simulations that take \
+            several substeps per frame.\n\n\
+            Every iteration is a separate dispatch within the same compute pass, so each one \
+            sees what the previous one wrote.\n\n\
+            To read the iteration index, declare `var<immediate> iteration: u32;` in the \
+            shader. Immediates aren't available on every renderer."
+        ),
+        |ui| {
+            let before = compute_pass.iterations().clone();
+            let mut iterations = before.clone();
+
+            field::field_grid(ui, "compute_pass_iterations_grid", |ui| {
+                field::row_doc(
+                    ui,
+                    "Count",
+                    field_doc!(
+                        "The number of dispatches.\n\n\
+                        - **Fixed**: a constant entered here.\n\
+                        - **Uniform**: read from a `UInt32` or `Float` uniform field, so it can \
+                        be changed without touching the pass. A count of 0 dispatches nothing."
+                    ),
+                    |ui| {
+                        ui.horizontal(|ui| {
+                            let mut kind = IterationCountKind::from_count(&iterations.count);
+                            if inspector::value_combo(
+                                ui,
+                                ("compute_pass_iterations", "kind"),
+                                ITERATION_COUNT_KINDS,
+                                &mut kind,
+                            ) {
+                                iterations.count = match kind {
+                                    IterationCountKind::Fixed => IterationCount::Fixed(1),
+                                    IterationCountKind::Uniform => {
+                                        IterationCount::Uniform(UniformFieldRef::default())
+                                    }
+                                };
+                            }
+
+                            match &mut iterations.count {
+                                IterationCount::Fixed(count) => {
+                                    egui::DragValue::new(count)
+                                        .speed(1)
+                                        .range(1..=MAX_ITERATIONS)
+                                        .ui(ui);
+                                }
+                                IterationCount::Uniform(field_ref) => {
+                                    uniform_field_ref_edit(
+                                        ui,
+                                        ("compute_pass_iterations", "ref"),
+                                        uniforms,
+                                        field_ref,
+                                    );
+                                }
+                            }
+                        });
+                    },
+                );
+
+                let slots = compute_pass.bind_groups();
+                let mut alternate = iterations.alternate_bind_groups.is_some();
+                if inspector::checkbox_row_doc(
+                    ui,
+                    "Alternate",
+                    field_doc!(
+                        "Bind other bind groups on odd iterations, to ping-pong between two \
+                        sets of resources: iteration 0 reads A and writes B, iteration 1 reads B \
+                        and writes A, and so on.\n\n\
+                        Each alternate needs the same layout as the bind group it replaces."
+                    ),
+                    &mut alternate,
+                ) {
+                    iterations.alternate_bind_groups = alternate.then(|| slots.to_vec());
+                }
+
+                if let Some(alternate) = &mut iterations.alternate_bind_groups {
+                    // One alternate per slot; new slots start with the pass's own.
+                    alternate.truncate(slots.len());
+                    alternate.extend_from_slice(&slots[alternate.len()..]);
+
+                    for (index, bind_group_id) in alternate.iter_mut().enumerate() {
+                        field::row(ui, format!("Odd Slot {index}"), |ui| {
+                            inspector::storage_id_combo(
+                                ui,
+                                ("compute_pass_alternate_bind_group", index),
+                                bind_groups,
+                                bind_group_id,
+                            )
+                        });
+                    }
+                }
+            });
+
+            if iterations != before {
+                compute_pass.set_iterations(iterations);
+            }
+        },
+    );
+}
+
 fn compute_pass_bind_group_row_ui(
     ui: &mut egui::Ui,
     handle: egui_dnd::Handle<'_>,
@@ -0,0 +1,345 @@
+use egui::Widget;
+
+use crate::{
+    project::{
+        CopyPassId, Project, TextureId,
+        resource::{copy_pass::CopyMode, presentation::PassStep},
+    },
+    ui::{
+        components::{
+            dispatch_policy::{dispatch_now_button, dispatch_policy_rows},
+            field,
+            field_docs::{FieldDoc, field_doc},
+            inspector::{self, AsRichText},
+        },
+        pane::StateSnapshot,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CopyModeKind {
+    Texture,
+    Region,
+    Buffer,
+    Blit,
+}
+
+impl CopyModeKind {
+    fn from_mode(mode: &CopyMode) -> Self {
+        match mode {
+            CopyMode::Texture { .. } => Self::Texture,
+            CopyMode::Region { .. } => Self::Region,
+            CopyMode::Buffer { .. } => Self::Buffer,
+            CopyMode::Blit { .. } => Self::Blit,
+        }
+    }
+
+    /// The mode of this kind, keeping the textures of `mode` where both copy
+    /// between textures.
+    fn into_mode(self, mode: &CopyMode) -> CopyMode {
+        let (source, destination) = match *mode {
+            CopyMode::Texture {
+                source,
+                destination,
+            }
+            | CopyMode::Region {
+                source,
+                destination,
+                ..
+            } => (source, destination),
+            CopyMode::Buffer { destination, .. } => (None, destination),
+            CopyMode::Blit { .. } => (None, None),
+        };
+
+        match self {
+            Self::Texture => CopyMode::Texture {
+                source,
+                destination,
+            },
+            Self::Region => CopyMode::Region {
+                source,
+                destination,
+                source_origin: [0; 3],
+                destination_origin: [0; 3],
+                size: [1; 3],
+            },
+            Self::Buffer => CopyMode::Buffer {
+                source: None,
+                destination,
+                destination_origin: [0; 3],
+                size: [1; 3],
+            },
+            Self::Blit => CopyMode::Blit {
+                source: None,
+                destination: None,
+                filter: wgpu::FilterMode::Linear,
+            },
+        }
+    }
+}
+
+impl AsRichText for CopyModeKind {
+    fn as_rich_text(&self) -> egui::RichText {
+        match self {
+            Self::Texture => "Texture",
+            Self::Region => "Region",
+            Self::Buffer => "Buffer",
+            Self::Blit => "Blit",
+        }
+        .into()
+    }
+}
+
+const COPY_MODE_KINDS: [CopyModeKind; 4] = [
+    CopyModeKind::Texture,
+    CopyModeKind::Region,
+    CopyModeKind::Buffer,
+    CopyModeKind::Blit,
+];
+
+const FILTER_MODES: [wgpu::FilterMode; 2] = [wgpu::FilterMode::Nearest, wgpu::FilterMode::Linear];
+
+impl StateSnapshot<'_> {
+    pub fn copy_pass_inspector_ui(&mut self, ui: &mut egui::Ui, copy_pass_id: CopyPassId) {
+        let Ok(copy_pass) = self.project.copy_passes.get(copy_pass_id) else {
+            ui.label("Copy Pass couldn't be found.");
+            return;
+        };
+
+        let before = copy_pass.mode();
+        let mut mode = before;
+        let mut policy = copy_pass.dispatch_policy();
+        let mut policy_changed = false;
+        let step = PassStep::Copy(copy_pass_id);
+
+        let dispatch_now = inspector::section(ui, "Settings", |ui| {
+            field::field_grid(ui, "copy_pass_inspector_grid", |ui| {
+                let mut kind = CopyModeKind::from_mode(&mode);
+                if inspector::combo_row_doc(
+                    ui,
+                    "Mode",
+                    field_doc!(
+                        "What this pass copies.\n\n\
+                        - **Texture**: the whole source texture into a destination of the same \
+                        size and format.\n\
+                        - **Region**: a box of texels between two textures of the same format.\n\
+                        - **Buffer**: a Uniform's bytes, as packed texels, into a box of a \
+                        texture.\n\
+                        - **Blit**: draws a Texture View over another, scaling with a filter and \
+                        converting between formats.\n\n\
+                        The copying modes need the **Copy Source** usage on the source texture and \
+                        **Copy Destination** on the destination. A blit samples its source, so it needs \
+                        **Texture Binding** there and **Render Attachment** on the destination."
+                    ),
+                    "copy_pass_mode",
+                    COPY_MODE_KINDS,
+                    &mut kind,
+                ) {
+                    mode = kind.into_mode(&mode);
+                }
+
+                copy_mode_rows(ui, self.project, &mut mode);
+
+                policy_changed = dispatch_policy_rows(
+                    ui,
+                    "copy_pass_dispatch",
+                    "Dispatch",
+                    field_doc!(
+                        "When this pass copies.\n\n\
+                        **Every Frame** copies once per rendered frame. **On Change** copies \
+                        only when the source's data changes on the CPU side (an uploaded image, \
+                        an edited uniform); it can't see what other passes draw into the source. \
+                        **Periodic** copies at a fixed cadence independent of the framerate. \
+                        **Manual** copies only from **Dispatch Now** or when the simulation \
+                        resets.\n\n\
+                        Passes reading the destination with **On Change** rerun on the frame \
+                        after each copy.\n\n\
+                        Add this pass to the presentation's schedule, or it won't run at all."
+                    ),
+                    &mut policy,
+                );
+            });
+
+            dispatch_now_button(ui, &self.project.presentation, step)
+        });
+        if dispatch_now {
+            self.runtime_project
+                .request_dispatch(&self.project.presentation, step);
+        }
+
+        let Ok(copy_pass) = self.project.copy_passes.get_mut(copy_pass_id) else {
+            return;
+        };
+        if mode != before {
+            copy_pass.set_mode(mode);
+        }
+        if policy_changed {
+            copy_pass.set_dispatch_policy(policy);
+        }
+    }
+}
+
+fn copy_mode_rows(ui: &mut egui::Ui, project: &Project, mode: &mut CopyMode) {
+    match mode {
+        CopyMode::Texture {
+            source,
+            destination,
+        } => {
+            texture_rows(ui, project, source, destination);
+        }
+        CopyMode::Region {
+            source,
+            destination,
+            source_origin,
+            destination_origin,
+            size,
+        } => {
+            texture_rows(ui, project, source, destination);
+            u32x3_row(
+                ui,
+                "Source Origin",
+                field_doc!("The texel of the source where the copied box starts: x, y, layer."),
+                source_origin,
+                0,
+            );
+            u32x3_row(
+                ui,
+                "Destination Origin",
+                field_doc!("The texel of the destination the box is copied to: x, y, layer."),
+                destination_origin,
+                0,
+            );
+            u32x3_row(
+                ui,
+                "Size",
+                field_doc!("The width, height and layer count of the copied box."),
+                size,
+                1,
+            );
+        }
+        CopyMode::Buffer {
+            source,
+            destination,
+            destination_origin,
+            size,
+        } => {
+            field::row_doc(
+                ui,
+                "Source",
+                field_doc!(
+                    "The Uniform whose buffer is uploaded. Its bytes are read in field order, \
+                    with the padding WGSL adds between fields, as tightly packed texels of the \
+                    destination's format.\n\n\
+                    When the box spans several rows, each row starts every 256 bytes, as \
+                    WebGPU requires."
+                ),
+                |ui| inspector::storage_combo(ui, "copy_pass_source", &project.uniforms, source),
+            );
+            field::row_doc(
+                ui,
+                "Destination",
+                field_doc!("The texture the buffer is uploaded into."),
+                |ui| {
+                    inspector::storage_combo(
+                        ui,
+                        "copy_pass_destination",
+                        &project.textures,
+                        destination,
+                    )
+                },
+            );
+            u32x3_row(
+                ui,
+                "Destination Origin",
+                field_doc!("The texel of the destination the upload starts at: x, y, layer."),
+                destination_origin,
+                0,
+            );
+            u32x3_row(
+                ui,
+                "Size",
+                field_doc!("The width, height and layer count of the uploaded box, in texels."),
+                size,
+                1,
+            );
+        }
+        CopyMode::Blit {
+            source,
+            destination,
+            filter,
+        } => {
+            field::row_doc(
+                ui,
+                "Source",
+                field_doc!("The Texture View sampled across the whole destination."),
+                |ui| {
+                    inspector::storage_combo(ui, "copy_pass_source", &project.texture_views, source)
+                },
+            );
+            field::row_doc(
+                ui,
+                "Destination",
+                field_doc!(
+                    "The Texture View drawn into. Its whole area is covered, so the source is \
+                    stretched to fit."
+                ),
+                |ui| {
+                    inspector::storage_combo(
+                        ui,
+                        "copy_pass_destination",
+                        &project.texture_views,
+                        destination,
+                    )
+                },
+            );
+            inspector::combo_row_doc(
+                ui,
+                "Filter",
+                field_doc!(
+                    "How the source is sampled when scaled.\n\n\
+                    **Nearest** keeps hard texel edges; **Linear** blends neighbouring texels \
+                    and needs a filterable source format."
+                ),
+                "copy_pass_filter",
+                FILTER_MODES,
+                filter,
+            );
+        }
+    }
+}
+
+fn texture_rows(
+    ui: &mut egui::Ui,
+    project: &Project,
+    source: &mut Option<TextureId>,
+    destination: &mut Option<TextureId>,
+) {
+    field::row_doc(
+        ui,
+        "Source",
+        field_doc!("The texture copied from. Needs the **Copy Source** usage."),
+        |ui| inspector::storage_combo(ui, "copy_pass_source", &project.textures, source),
+    );
+    field::row_doc(
+        ui,
+        "Destination",
+        field_doc!(
+            "The texture copied into. Needs the **Copy Destination** usage and the source's format, \
+            give or take sRGB."
+        ),
+        |ui| inspector::storage_combo(ui, "copy_pass_destination", &project.textures, destination),
+    );
+}
+
+fn u32x3_row(ui: &mut egui::Ui, label: &str, doc: impl FieldDoc, value: &mut [u32; 3], min: u32) {
+    field::row_doc(ui, label, doc, |ui| {
+        ui.horizontal(|ui| {
+            for axis in value.iter_mut() {
+                egui::DragValue::new(axis)
+                    .speed(1)
+                    .range(min..=u32::MAX)
+                    .ui(ui);
+            }
+        });
+    });
+}
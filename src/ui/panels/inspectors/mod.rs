@@ -1,6 +1,7 @@
 pub mod bind_group_inspector;
 pub mod camera_inspector;
 pub mod compute_pass_inspector;
+pub mod copy_pass_inspector;
 pub mod dimension_inspector;
 pub mod file_inspector;
 pub mod model_inspector;
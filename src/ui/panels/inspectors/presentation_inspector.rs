@@ -1,9 +1,5 @@
 use crate::{
-    project::{
-        ComputePassId, RenderPassId,
-        resource::{compute_pass::ComputePass, render_pass::RenderPass},
-        storage::Storage,
-    },
+    project::{Project, resource::presentation::PassStep},
     ui::{
         components::{
             draggable_list::{ListEdits, draggable_list},
@@ -43,188 +39,220 @@ impl StateSnapshot<'_> {
 
         inspector::section_doc(
             ui,
-            "Compute Passes",
-            field_doc!(
-                "The ordered **compute** passes that can be dispatched each frame, before the render \
-                passes. Order here is execution order.\n\n\
-                Each pass's dispatch policy (On Change / Every Frame / Periodic) decides whether \
-                it actually runs on a given frame.\n\n\
-                Drag to reorder, right-click to remove."
-            ),
-            |ui| {
-                presentation_compute_pass_list_ui(ui, self);
-            },
-        );
-
-        inspector::section_doc(
-            ui,
-            "Render Passes",
+            "Schedule",
             field_doc!(
-                "The ordered list of **steps** run every frame to produce the presentation.\n\n\
-                Drag to reorder, right-click to remove."
+                "The ordered **steps** run every frame to produce the presentation: compute \
+                passes, render passes and copy passes, in execution order.\n\n\
+                A compute pass placed after a render pass can read what it drew in the same \
+                frame (for downsampling, histograms or other reductions).\n\n\
+                Each pass's dispatch policy (On Change / Every Frame / Periodic / Manual) \
+                decides whether it actually runs on a given frame. Untick a step to disable it: \
+                it stays in the schedule and its outputs keep what they last held.\n\n\
+                **Order by Dependencies** and the schedule's warnings follow textures: the ones \
+                each pass samples, writes as storage, renders into or copies. Uniform buffers \
+                are written before the frame and never order steps.\n\n\
+                Mark the steps that seed a simulation as **initializers**: **Reset Simulation** \
+                reruns them all on the next frame, in schedule order.\n\n\
+                Drag to reorder, right-click to remove or mark as an initializer."
             ),
             |ui| {
-                presentation_render_pass_list_ui(ui, self);
+                presentation_schedule_ui(ui, self);
             },
         );
     }
 }
 
-fn presentation_compute_pass_list_ui(ui: &mut egui::Ui, state: &mut StateSnapshot<'_>) {
-    let before = state.project.presentation.compute_passes().to_vec();
-    let mut compute_passes = before.clone();
+/// A change to one step's flags, applied once the schedule list is drawn.
+enum StepEdit {
+    Enabled(PassStep, bool),
+    Initializer(PassStep, bool),
+}
+
+fn presentation_schedule_ui(ui: &mut egui::Ui, state: &mut StateSnapshot<'_>) {
+    let before = state.project.presentation.steps().to_vec();
+    let mut steps = before.clone();
+    let suggested = state
+        .runtime_project
+        .render_graph
+        .suggested_schedule()
+        .to_vec();
 
-    if compute_passes.is_empty() {
-        ui.label("No compute passes in the presentation.");
+    if steps.is_empty() {
+        ui.label("No passes in the presentation.");
     }
 
-    let compute_pass_storage = &state.project.compute_passes;
+    let project = &*state.project;
+    let mut step_edit = None;
 
     let mut edits = draggable_list(
         ui,
-        "presentation_compute_pass_list",
-        &compute_passes,
-        |ui, compute_pass_id, index, handle, edits| {
-            presentation_compute_pass_row_ui(
-                ui,
-                handle,
-                index,
-                *compute_pass_id,
-                compute_pass_storage,
-                edits,
-            );
+        "presentation_schedule_list",
+        &steps,
+        |ui, step, index, handle, edits| {
+            presentation_step_row_ui(ui, handle, index, *step, project, edits, &mut step_edit);
         },
     );
 
     ui.add_space(6.0);
 
-    inspector::add_from_storage_menu(
-        ui,
-        "Add Compute Pass",
-        compute_pass_storage,
-        "No compute passes.",
-        |id| edits.push_add_edit(id),
-    );
-
-    edits.apply(&mut compute_passes);
-
-    if compute_passes != before {
-        state
-            .project
-            .presentation
-            .set_compute_passes(compute_passes);
-    }
-}
-
-fn presentation_compute_pass_row_ui(
-    ui: &mut egui::Ui,
-    handle: egui_dnd::Handle<'_>,
-    index: usize,
-    compute_pass_id: ComputePassId,
-    compute_passes: &Storage<ComputePass>,
-    edits: &mut ListEdits<ComputePassId>,
-) {
-    handle.ui(ui, |ui| {
-        let label = resource_icons::drag_handle_text(ui, &format!("Step {}", index + 1));
-        ui.add(egui::Label::new(label).sense(egui::Sense::click()))
-            .context_menu(|ui| {
-                if ui.button("Remove Compute Pass").clicked() {
-                    edits.push_remove_edit(index);
-                    ui.close();
-                }
-            });
-    });
-
-    let mut selected = compute_pass_id;
-
-    ui.indent(("presentation_compute_pass_select", index), |ui| {
-        inspector::storage_id_combo(
+    ui.horizontal(|ui| {
+        inspector::add_from_storage_menu(
             ui,
-            ("presentation_compute_pass_select", index),
-            compute_passes,
-            &mut selected,
+            "Add Compute Pass",
+            &project.compute_passes,
+            "No compute passes.",
+            |id| edits.push_add_edit(PassStep::Compute(id)),
+        );
+        inspector::add_from_storage_menu(
+            ui,
+            "Add Render Pass",
+            &project.render_passes,
+            "No render passes.",
+            |id| edits.push_add_edit(PassStep::Render(id)),
+        );
+        inspector::add_from_storage_menu(
+            ui,
+            "Add Copy Pass",
+            &project.copy_passes,
+            "No copy passes.",
+            |id| edits.push_add_edit(PassStep::Copy(id)),
         );
     });
 
-    if selected != compute_pass_id {
-        edits.push_set_edit(index, selected);
-    }
-}
+    edits.apply(&mut steps);
 
-fn presentation_render_pass_list_ui(ui: &mut egui::Ui, state: &mut StateSnapshot<'_>) {
-    let before = state.project.presentation.render_passes().to_vec();
-    let mut render_passes = before.clone();
-
-    if render_passes.is_empty() {
-        ui.label("No render passes in the presentation.");
+    let can_sort = suggested != steps && suggested.len() == steps.len();
+    let has_initializers = project.presentation.initializer_steps().next().is_some();
+    let (sort, reset) = ui
+        .horizontal(|ui| {
+            let sort = ui
+                .add_enabled(can_sort, egui::Button::new("Order by Dependencies"))
+                .on_hover_text(
+                    "Reorder the steps so every pass runs after the passes whose textures it reads.",
+                );
+            let reset = ui
+                .add_enabled(has_initializers, egui::Button::new("Reset Simulation"))
+                .on_hover_text("Rerun every initializer step on the next frame.")
+                .on_disabled_hover_text("Right-click a step to mark it as an initializer.");
+            (sort.clicked(), reset.clicked())
+        })
+        .inner;
+    if sort {
+        steps = suggested;
     }
 
-    let render_pass_storage = &state.project.render_passes;
-
-    let mut edits = draggable_list(
-        ui,
-        "presentation_render_pass_list",
-        &render_passes,
-        |ui, render_pass_id, index, handle, edits| {
-            presentation_render_pass_row_ui(
-                ui,
-                handle,
-                index,
-                *render_pass_id,
-                render_pass_storage,
-                edits,
-            );
-        },
-    );
-
-    ui.add_space(6.0);
-
-    inspector::add_from_storage_menu(
-        ui,
-        "Add Render Pass",
-        render_pass_storage,
-        "No render passes.",
-        |id| edits.push_add_edit(id),
-    );
-
-    edits.apply(&mut render_passes);
-
-    if render_passes != before {
-        state.project.presentation.set_render_passes(render_passes);
+    if steps != before {
+        state.project.presentation.set_steps(steps);
+    }
+    match step_edit {
+        Some(StepEdit::Enabled(step, enabled)) => {
+            state.project.presentation.set_enabled(step, enabled);
+        }
+        Some(StepEdit::Initializer(step, initializer)) => {
+            state
+                .project
+                .presentation
+                .set_initializer(step, initializer);
+        }
+        None => {}
+    }
+    if reset {
+        state
+            .runtime_project
+            .reset_simulation(&state.project.presentation);
     }
 }
 
-fn presentation_render_pass_row_ui(
+fn presentation_step_row_ui(
     ui: &mut egui::Ui,
     handle: egui_dnd::Handle<'_>,
     index: usize,
-    render_pass_id: RenderPassId,
-    render_passes: &Storage<RenderPass>,
-    edits: &mut ListEdits<RenderPassId>,
+    step: PassStep,
+    project: &Project,
+    edits: &mut ListEdits<PassStep>,
+    step_edit: &mut Option<StepEdit>,
 ) {
+    let kind = match step {
+        PassStep::Compute(_) => "Compute",
+        PassStep::Render(_) => "Render",
+        PassStep::Copy(_) => "Copy",
+    };
+
+    let initializer = project.presentation.is_initializer(step);
     handle.ui(ui, |ui| {
-        let label = resource_icons::drag_handle_text(ui, &format!("Step {}", index + 1));
+        let suffix = if initializer { " · Initializer" } else { "" };
+        let label =
+            resource_icons::drag_handle_text(ui, &format!("Step {} · {kind}{suffix}", index + 1));
         ui.add(egui::Label::new(label).sense(egui::Sense::click()))
             .context_menu(|ui| {
-                if ui.button("Remove Render Pass").clicked() {
+                let toggle = match initializer {
+                    true => "Unmark as Initializer",
+                    false => "Mark as Initializer",
+                };
+                if ui.button(toggle).clicked() {
+                    *step_edit = Some(StepEdit::Initializer(step, !initializer));
+                    ui.close();
+                }
+                if ui.button(format!("Remove {kind} Pass")).clicked() {
                     edits.push_remove_edit(index);
                     ui.close();
                 }
             });
     });
 
-    let mut selected = render_pass_id;
+    let enabled = project.presentation.is_enabled(step);
+    let id_salt = ("presentation_step_select", index);
+    let selected = ui
+        .indent(id_salt, |ui| {
+            ui.horizontal(|ui| {
+                let mut checked = enabled;
+                let checkbox = ui.checkbox(&mut checked, "").on_hover_text(match enabled {
+                    true => "Disable this step, keeping its last output",
+                    false => "Enable this step",
+                });
+                if checkbox.changed() {
+                    *step_edit = Some(StepEdit::Enabled(step, checked));
+                }
 
-    ui.indent(("presentation_render_pass_select", index), |ui| {
-        inspector::storage_id_combo(
-            ui,
-            ("presentation_render_pass_select", index),
-            render_passes,
-            &mut selected,
-        );
-    });
+                ui.add_enabled_ui(enabled, |ui| match step {
+                    PassStep::Compute(id) => {
+                        let mut selected = id;
+                        inspector::storage_id_combo(
+                            ui,
+                            id_salt,
+                            &project.compute_passes,
+                            &mut selected,
+                        );
+                        PassStep::Compute(selected)
+                    }
+                    PassStep::Render(id) => {
+                        let mut selected = id;
+                        inspector::storage_id_combo(
+                            ui,
+                            id_salt,
+                            &project.render_passes,
+                            &mut selected,
+                        );
+                        PassStep::Render(selected)
+                    }
+                    PassStep::Copy(id) => {
+                        let mut selected = id;
+                        inspector::storage_id_combo(
+                            ui,
+                            id_salt,
+                            &project.copy_passes,
+                            &mut selected,
+                        );
+                        PassStep::Copy(selected)
+                    }
+                })
+                .inner
+            })
+            .inner
+        })
+        .inner;
 
-    if selected != render_pass_id {
+    if selected != step {
         edits.push_set_edit(index, selected);
     }
 }
@@ -4,6 +4,7 @@ use crate::{
     project::{
         RenderPassId, RenderPipelineId, TextureViewId,
         resource::{
+            presentation::PassStep,
             render_pass::{LoadOperation, RenderPass, RenderPassTarget},
             render_pipeline::RenderPipeline,
             texture_view::TextureView,
@@ -13,6 +14,7 @@ use crate::{
     ui::{
         components::{
             color_edit::color_edit_rgba,
+            dispatch_policy::{dispatch_now_button, dispatch_policy_rows},
             draggable_list::{ListEdits, draggable_list},
             field,
             field_docs::field_doc,
@@ -52,6 +54,8 @@ impl StateSnapshot<'_> {
     pub fn render_pass_inspector_ui(&mut self, ui: &mut egui::Ui, render_pass_id: RenderPassId) {
         let texture_views = &self.project.texture_views;
         let render_pipelines = &self.project.render_pipelines;
+        let presentation = &self.project.presentation;
+        let step = PassStep::Render(render_pass_id);
 
         let Ok(render_pass) = self.project.render_passes.get_mut(render_pass_id) else {
             ui.label("Render Pass couldn't be found.");
@@ -136,6 +140,34 @@ impl StateSnapshot<'_> {
             },
         );
 
+        inspector::section(ui, "Redraw", |ui| {
+            field::field_grid(ui, "render_pass_redraw_grid", |ui| {
+                let mut policy = render_pass.dispatch_policy();
+                if dispatch_policy_rows(
+                    ui,
+                    "render_pass_redraw",
+                    "Redraw",
+                    field_doc!(
+                        "When this pass draws.\n\n\
+                        **Every Frame** draws once per rendered frame. \
+                        **On Change** draws only when a pipeline, bind group or uniform it uses \
+                        changes (good for static backgrounds and precomputed lookup tables). \
+                        **Periodic** draws at a fixed cadence independent of the framerate. \
+                        **Manual** draws only from **Dispatch Now** or when the simulation \
+                        resets.\n\n\
+                        On frames the pass doesn't draw, its targets keep their last contents."
+                    ),
+                    &mut policy,
+                ) {
+                    render_pass.set_dispatch_policy(policy);
+                }
+            });
+
+            if dispatch_now_button(ui, presentation, step) {
+                self.runtime_project.request_dispatch(presentation, step);
+            }
+        });
+
         inspector::section_doc_wide(
             ui,
             &format!("Pipelines ({})", render_pass.pipelines().len()),
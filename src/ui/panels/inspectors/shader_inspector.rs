@@ -1,10 +1,14 @@
 use crate::{
     project::{ShaderId, paths::FilePath},
     ui::{
-        components::{field, field_docs::field_doc, inspector},
+        components::{field, field_docs::field_doc, inspector, resource_icons},
         pane::StateSnapshot,
     },
-    utils::wgpu_utils::ShaderSourceKind,
+    utils::{
+        shader_reflection::{self, ReflectedMember},
+        wgpu_utils::ShaderSourceKind,
+    },
+    workspace::StateEvent,
 };
 
 impl StateSnapshot<'_> {
@@ -50,5 +54,89 @@ impl StateSnapshot<'_> {
                 }
             });
         });
+
+        inspector::section_doc(
+            ui,
+            "Structs",
+            field_doc!(
+                "The structs declared in this shader, as seen by the compiler.\n\n\
+                **Create Uniform** adds a Uniform whose fields match the struct's members. \
+                Members whose types have no uniform field equivalent (such as `i32` or arrays) \
+                are marked, and stop generation: **Create Uniform** is disabled for that \
+                struct and its tooltip says why."
+            ),
+            |ui| {
+                let module = match self.runtime_project.shaders.get_init(shader_id) {
+                    Ok(Some(runtime)) => runtime.module(),
+                    Ok(None) => {
+                        field::spinner(ui);
+                        return;
+                    }
+                    Err(_) => {
+                        field::weak_label(ui, "Compile the shader to list its structs.");
+                        return;
+                    }
+                };
+
+                let names = shader_reflection::struct_names(module);
+                if names.is_empty() {
+                    field::weak_label(ui, "This shader declares no structs.");
+                    return;
+                }
+
+                for name in names {
+                    let Some(members) = shader_reflection::reflect_struct(module, name) else {
+                        continue;
+                    };
+                    ui.horizontal(|ui| {
+                        ui.strong(name);
+                        let create = resource_icons::derive_text(ui, "Create Uniform");
+                        let mappable = members.iter().all(|member| member.kind.is_some());
+                        if ui
+                            .add_enabled(mappable, egui::Button::new(create).small())
+                            .on_disabled_hover_text(
+                                "Every member needs a uniform field type for the fields to \
+                                 follow the struct's layout.",
+                            )
+                            .clicked()
+                        {
+                            self.event_queue.add(StateEvent::DeriveUniformFromStruct {
+                                shader_id,
+                                struct_name: name.to_string(),
+                            });
+                        }
+                    });
+                    struct_members_ui(ui, ("shader_struct", name), &members);
+                    ui.add_space(4.0);
+                }
+            },
+        );
     }
 }
+
+/// A grid of struct members and the uniform field type each maps onto.
+pub(super) fn struct_members_ui(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    members: &[ReflectedMember],
+) {
+    ui.indent(&id_salt, |ui| {
+        field::field_grid(ui, &id_salt, |ui| {
+            for member in members {
+                ui.label(egui::RichText::new(&member.name).monospace());
+                match member.kind {
+                    Some(kind) => {
+                        ui.weak(kind.to_string());
+                    }
+                    None => {
+                        field::error_label(
+                            ui,
+                            format!("{} has no uniform field type", member.type_label),
+                        );
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    });
+}
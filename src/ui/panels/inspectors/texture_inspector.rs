@@ -4,8 +4,12 @@ use wgpu::TextureUsages;
 use crate::{
     project::{
         TextureId,
-        paths::FilePath,
-        resource::{dimension::Dimension, texture::TextureSource},
+        paths::{FilePath, is_image_file},
+        resource::{
+            dimension::Dimension,
+            presentation::PassStep,
+            texture::{SwapTrigger, TextureSource},
+        },
         storage::Storage,
     },
     ui::{
@@ -51,6 +55,25 @@ impl AsRichText for TextureSourceKind {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SwapTriggerKind {
+    EveryFrame,
+    AfterStep,
+}
+
+impl AsRichText for SwapTriggerKind {
+    fn as_rich_text(&self) -> egui::RichText {
+        match self {
+            Self::EveryFrame => "Every Frame",
+            Self::AfterStep => "After Step",
+        }
+        .into()
+    }
+}
+
+const SWAP_TRIGGER_KINDS: [SwapTriggerKind; 2] =
+    [SwapTriggerKind::EveryFrame, SwapTriggerKind::AfterStep];
+
 const TEXTURE_USAGES: &[(TextureUsages, &str)] = &[
     (TextureUsages::COPY_SRC, "Copy Source"),
     (TextureUsages::COPY_DST, "Copy Destination"),
@@ -75,6 +98,18 @@ impl AsRichText for TextureFormat {
 
 impl StateSnapshot<'_> {
     pub fn texture_inspector_ui(&mut self, ui: &mut egui::Ui, texture_id: TextureId) {
+        let steps: Vec<(PassStep, String)> = self
+            .project
+            .presentation
+            .steps()
+            .iter()
+            .enumerate()
+            .map(|(index, step)| {
+                let label = self.project.label(*step).unwrap_or("Unknown");
+                (*step, format!("Step {} · {label}", index + 1))
+            })
+            .collect();
+
         let Ok(texture) = self.project.textures.get_mut(texture_id) else {
             ui.label("Texture couldn't be found.");
             return;
@@ -89,6 +124,9 @@ impl StateSnapshot<'_> {
         let source_before = texture.source().clone();
         let mut source = source_before.clone();
 
+        let ping_pong_before = texture.ping_pong();
+        let mut ping_pong = ping_pong_before;
+
         inspector::section(ui, "Settings", |ui| {
             field::field_grid(ui, "texture_inspector_grid", |ui| {
                 inspector::combo_row_doc(
@@ -135,6 +173,8 @@ impl StateSnapshot<'_> {
                 &self.project.dimensions,
                 self.file_storage.files(),
             );
+
+            ui_ping_pong(ui, &mut ping_pong, &steps);
         });
 
         inspector::section(ui, "Texture View", |ui| {
@@ -162,9 +202,92 @@ impl StateSnapshot<'_> {
         if source != source_before {
             texture.set_source(source);
         }
+
+        if ping_pong != ping_pong_before {
+            texture.set_ping_pong(ping_pong);
+        }
     }
 }
 
+fn ui_ping_pong(
+    ui: &mut egui::Ui,
+    ping_pong: &mut Option<SwapTrigger>,
+    steps: &[(PassStep, String)],
+) {
+    field::field_grid(ui, "texture_ping_pong_grid", |ui| {
+        let mut double_buffered = ping_pong.is_some();
+        if inspector::checkbox_row_doc(
+            ui,
+            "Double Buffered",
+            field_doc!(
+                "Allocates a second texture of the same size and format, for effects that read \
+                the previous frame while writing the next one (simulations, feedback, temporal \
+                accumulation).\n\n\
+                Texture Views pick a half: passes read the **Front**, which holds the last \
+                finished result, and write the **Back**. When the texture swaps, the back \
+                becomes the front, and every view and bind group follows without being \
+                rebuilt.\n\n\
+                Swaps happen between frames, so within a frame each half stays put."
+            ),
+            &mut double_buffered,
+        ) {
+            *ping_pong = double_buffered.then_some(SwapTrigger::EveryFrame);
+        }
+
+        let Some(trigger) = ping_pong else {
+            return;
+        };
+
+        let mut kind = match trigger {
+            SwapTrigger::EveryFrame => SwapTriggerKind::EveryFrame,
+            SwapTrigger::AfterStep(_) => SwapTriggerKind::AfterStep,
+        };
+        if inspector::combo_row_doc(
+            ui,
+            "Swap",
+            field_doc!(
+                "When the front and back swap.\n\n\
+                - **Every Frame**: after every frame.\n\
+                - **After Step**: after every frame the chosen schedule step ran in, so a \
+                periodic simulation only advances when it actually stepped."
+            ),
+            "texture_swap_trigger",
+            SWAP_TRIGGER_KINDS,
+            &mut kind,
+        ) {
+            *trigger = match kind {
+                SwapTriggerKind::EveryFrame => SwapTrigger::EveryFrame,
+                SwapTriggerKind::AfterStep => SwapTrigger::AfterStep(None),
+            };
+        }
+
+        if let SwapTrigger::AfterStep(step) = trigger {
+            field::row_doc(
+                ui,
+                "Step",
+                field_doc!("The schedule step whose runs swap the texture."),
+                |ui| {
+                    let label = |step: &Option<PassStep>| {
+                        let label = step.and_then(|step| {
+                            steps
+                                .iter()
+                                .find(|(scheduled, _)| *scheduled == step)
+                                .map(|(_, label)| label.as_str())
+                        });
+                        match (step, label) {
+                            (_, Some(label)) => egui::RichText::new(label),
+                            (Some(_), None) => egui::RichText::new("Not Scheduled"),
+                            (None, None) => egui::RichText::new("Select..."),
+                        }
+                    };
+                    let options = steps.iter().map(|(step, _)| Some(*step));
+                    inspector::value_combo_with(ui, "texture_swap_step", options, label, step);
+                },
+            );
+        }
+    });
+}
+
 fn ui_texture_source(
     ui: &mut egui::Ui,
     source: &mut TextureSource,
@@ -298,9 +421,3 @@ fn ui_texture_source(
         });
     });
 }
-
-fn is_image_file(path: &FilePath) -> bool {
-    path.extension()
-        .and_then(image::ImageFormat::from_extension)
-        .is_some_and(|format| format.can_read() && format.reading_enabled())
-}
@@ -3,19 +3,72 @@ use egui::load::SizedTexture;
 use crate::{
     project::{
         TextureViewId,
-        resource::texture_view::{TextureViewFormat, previewable_formats_label},
+        resource::texture_view::{
+            BufferSide, TextureView, TextureViewFormat, is_previewable,
+            preview::{PreviewChannel, PreviewRange, PreviewRemap},
+            previewable_formats_label,
+        },
     },
     ui::{
         components::{
             field,
             field_docs::field_doc,
             inspector::{self, AsRichText},
+            pixel_probe,
         },
         pane::StateSnapshot,
     },
-    utils::wgpu_utils::TextureFormat,
+    utils::{
+        texture_stats::{
+            CONTINUOUS_INTERVAL_FRAMES, ChannelStatistics, SpecialTexels, TextureAnalysis,
+            TextureStatistics,
+        },
+        wgpu_utils::TextureFormat,
+    },
 };
 
+/// Height of each channel's histogram, in points.
+const HISTOGRAM_HEIGHT: f32 = 48.0;
+
+impl AsRichText for BufferSide {
+    fn as_rich_text(&self) -> egui::RichText {
+        match self {
+            BufferSide::Front => "Front",
+            BufferSide::Back => "Back",
+        }
+        .into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PreviewRangeKind {
+    Linear,
+    Depth,
+}
+
+impl AsRichText for PreviewRangeKind {
+    fn as_rich_text(&self) -> egui::RichText {
+        match self {
+            Self::Linear => "Linear",
+            Self::Depth => "Depth",
+        }
+        .into()
+    }
+}
+
+impl AsRichText for PreviewChannel {
+    fn as_rich_text(&self) -> egui::RichText {
+        match self {
+            PreviewChannel::All => "All",
+            PreviewChannel::R => "R",
+            PreviewChannel::G => "G",
+            PreviewChannel::B => "B",
+            PreviewChannel::A => "A",
+        }
+        .into()
+    }
+}
+
 impl StateSnapshot<'_> {
     pub fn texture_view_inspector_ui(&mut self, ui: &mut egui::Ui, texture_view_id: TextureViewId) {
         let resolved = self
@@ -121,9 +174,52 @@ impl StateSnapshot<'_> {
                 if dimension_changed {
                     texture_view.set_dimension(current_dimension);
                 }
+
+                let double_buffered = texture_id
+                    .and_then(|id| self.project.textures.get(id).ok())
+                    .is_some_and(|texture| texture.ping_pong().is_some());
+                let mut side = texture_view.side();
+                if (double_buffered || side == BufferSide::Back)
+                    && inspector::combo_row_doc(
+                        ui,
+                        "Side",
+                        field_doc!(
+                            "Which half of the double-buffered texture this view shows.\n\n\
+                            - **Front**: the last finished result. Read from it.\n\
+                            - **Back**: the half being written this frame. Write to it.\n\n\
+                            When the texture swaps, this view follows its side to the other \
+                            texture."
+                        ),
+                        "side",
+                        [BufferSide::Front, BufferSide::Back],
+                        &mut side,
+                    )
+                {
+                    texture_view.set_side(side);
+                }
             });
         });
 
+        let texture_format = texture_view
+            .texture_id()
+            .and_then(|id| self.project.textures.get(id).ok())
+            .map(|texture| texture.format());
+        let needs_remap = texture_format.is_some_and(|format| !is_previewable(format));
+        if needs_remap {
+            inspector::section_doc(
+                ui,
+                "Preview Remap",
+                field_doc!(
+                    "Depth and unfilterable float textures can't be shown as they are, so the \
+                    preview converts them into an RGBA8 copy each frame.\n\n\
+                    Nothing about the conversion is guessed from the data: pick the range of \
+                    values to show and how."
+                ),
+                |ui| preview_remap_ui(ui, texture_view),
+            );
+        }
+        let remapped = texture_view.preview_remap().is_some();
+
         inspector::section(ui, "Preview", |ui| {
             let texture_view = match self.runtime_project.texture_views.get_init(texture_view_id) {
                 Ok(Some(texture_view)) => texture_view,
@@ -138,17 +234,44 @@ impl StateSnapshot<'_> {
             };
 
             let Some(egui_id) = texture_view.egui_id() else {
-                ui.label(format!(
-                    "Only texture views with a filterable RGBA format ({}) can be previewed.",
-                    previewable_formats_label()
-                ));
+                match (needs_remap, remapped) {
+                    (true, true) => ui.label(
+                        "The texture needs the Texture Binding usage to be previewed through a \
+                        remap.",
+                    ),
+                    (true, false) => ui.label("Turn on the Preview Remap to preview this view."),
+                    (false, _) => ui.label(format!(
+                        "Only texture views with a filterable RGBA format ({}) can be previewed.",
+                        previewable_formats_label()
+                    )),
+                };
                 return;
             };
 
             let size = ui.available_height().min(ui.available_width()).min(500.0);
             let sized_texture = SizedTexture::new(egui_id, (size, size));
-            ui.add(egui::Image::new(sized_texture));
+            let response = ui.add(egui::Image::new(sized_texture).sense(egui::Sense::hover()));
+            pixel_probe::show(response, self.pixel_probe, texture_view_id, texture_view);
         });
+
+        inspector::section_doc(
+            ui,
+            "Statistics",
+            field_doc!(
+                "Per-channel **min**, **max**, **mean** and histogram of the view's first layer \
+                and mip level, and the texels holding NaN or Inf, computed on the GPU.\n\n\
+                NaN and Inf values are left out of the other statistics. With **Continuous**, \
+                the analysis reruns while the project runs and NaN texels are reported in the \
+                error panel."
+            ),
+            |ui| {
+                let analysis = self
+                    .runtime_project
+                    .texture_analyses
+                    .state_mut(texture_view_id);
+                statistics_ui(ui, analysis);
+            },
+        );
     }
 }
 
@@ -171,3 +294,215 @@ impl AsRichText for Option<wgpu::TextureViewDimension> {
         }
     }
 }
+
+fn preview_remap_ui(ui: &mut egui::Ui, texture_view: &mut TextureView) {
+    field::field_grid(ui, "preview_remap_grid", |ui| {
+        let mut enabled = texture_view.preview_remap().is_some();
+        if field::row(ui, "Enabled", |ui| ui.checkbox(&mut enabled, ()).changed()) {
+            texture_view.set_preview_remap(enabled.then(PreviewRemap::default));
+        }
+        let Some(mut remap) = texture_view.preview_remap() else {
+            return;
+        };
+
+        let mut kind = match remap.range {
+            PreviewRange::Linear { .. } => PreviewRangeKind::Linear,
+            PreviewRange::Depth { .. } => PreviewRangeKind::Depth,
+        };
+        if inspector::combo_row_doc(
+            ui,
+            "Range",
+            field_doc!(
+                "Which values show as black through white.\n\n\
+                - **Linear**: **Min** is black and **Max** is white.\n\
+                - **Depth**: for depth written with a perspective projection. Turns it back \
+                into the distance from the camera, so **Near** is black and **Far** is white. \
+                Use the camera's near and far planes."
+            ),
+            "preview_range",
+            [PreviewRangeKind::Linear, PreviewRangeKind::Depth],
+            &mut kind,
+        ) {
+            remap.range = match kind {
+                PreviewRangeKind::Linear => PreviewRange::Linear { min: 0.0, max: 1.0 },
+                PreviewRangeKind::Depth => PreviewRange::Depth {
+                    near: 0.1,
+                    far: 100.0,
+                },
+            };
+        }
+
+        let (low, high) = match &mut remap.range {
+            PreviewRange::Linear { min, max } => (("Min", min), ("Max", max)),
+            PreviewRange::Depth { near, far } => (("Near", near), ("Far", far)),
+        };
+        for (label, value) in [low, high] {
+            field::row(ui, label, |ui| {
+                ui.add(egui::DragValue::new(value).speed(0.01))
+            });
+        }
+
+        inspector::combo_row_doc(
+            ui,
+            "Channel",
+            field_doc!(
+                "Shows one channel as gray, or **All** to show red, green and blue as they are."
+            ),
+            "preview_channel",
+            [
+                PreviewChannel::All,
+                PreviewChannel::R,
+                PreviewChannel::G,
+                PreviewChannel::B,
+                PreviewChannel::A,
+            ],
+            &mut remap.channel,
+        );
+
+        field::row_doc(
+            ui,
+            "False Color",
+            field_doc!(
+                "Shows the channel with a color ramp (Turbo) instead of as gray, so small \
+                differences stand out. With **All**, the red channel is shown."
+            ),
+            |ui| ui.checkbox(&mut remap.false_color, ()),
+        );
+
+        texture_view.update_preview_remap(remap);
+    });
+}
+
+fn statistics_ui(ui: &mut egui::Ui, analysis: &mut TextureAnalysis) {
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(!analysis.is_running(), egui::Button::new("Analyze"))
+            .clicked()
+        {
+            analysis.request();
+        }
+        ui.checkbox(&mut analysis.continuous, "Continuous")
+            .on_hover_text(format!(
+                "Analyze again every {CONTINUOUS_INTERVAL_FRAMES} frames."
+            ));
+        if analysis.is_running() {
+            field::spinner(ui);
+        }
+    });
+
+    match analysis.result() {
+        Some(Ok(statistics)) => {
+            ui.add_space(4.0);
+            statistics_table(ui, statistics);
+        }
+        Some(Err(error)) => {
+            field::error_label(ui, error.to_string());
+        }
+        None => {
+            field::weak_label(ui, "Not analyzed yet.");
+        }
+    }
+}
+
+fn statistics_table(ui: &mut egui::Ui, statistics: &TextureStatistics) {
+    let [width, height] = statistics.size;
+    field::weak_label(ui, format!("{width} × {height} texels"));
+
+    egui::Grid::new("texture_statistics_grid")
+        .num_columns(4)
+        .striped(true)
+        .show(ui, |ui| {
+            for header in ["Channel", "Min", "Max", "Mean"] {
+                ui.strong(header);
+            }
+            ui.end_row();
+            for channel in &statistics.channels {
+                ui.label(channel.name);
+                for value in [channel.min, channel.max, channel.mean] {
+                    match value.is_nan() {
+                        true => field::weak_label(ui, "—"),
+                        false => ui.monospace(format!("{value:.6}")),
+                    };
+                }
+                ui.end_row();
+            }
+        });
+
+    for channel in &statistics.channels {
+        ui.add_space(4.0);
+        histogram(ui, channel);
+    }
+
+    ui.add_space(4.0);
+    field::field_grid(ui, "texture_statistics_special_grid", |ui| {
+        for (label, texels) in [("NaN", &statistics.nan), ("Inf", &statistics.inf)] {
+            field::row(ui, label, |ui| special_texels_ui(ui, texels));
+        }
+    });
+}
+
+/// Draws the channel's histogram as bars, scaled to its fullest bucket.
+fn histogram(ui: &mut egui::Ui, channel: &ChannelStatistics) {
+    let color = match channel.name {
+        "r" => egui::Color32::from_rgb(230, 90, 90),
+        "g" => egui::Color32::from_rgb(90, 200, 110),
+        "b" => egui::Color32::from_rgb(90, 140, 240),
+        _ => ui.visuals().strong_text_color(),
+    };
+    let size = egui::vec2(ui.available_width(), HISTOGRAM_HEIGHT);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let fullest = channel.histogram.iter().copied().max().unwrap_or(0).max(1);
+    let bar_width = rect.width() / channel.histogram.len() as f32;
+    for (index, count) in channel.histogram.iter().enumerate() {
+        let bar_height = rect.height() * *count as f32 / fullest as f32;
+        let min = egui::pos2(
+            rect.left() + index as f32 * bar_width,
+            rect.bottom() - bar_height,
+        );
+        let bar = egui::Rect::from_min_size(min, egui::vec2(bar_width, bar_height));
+        painter.rect_filled(bar.shrink2(egui::vec2(0.5, 0.0)), 0.0, color);
+    }
+    painter.text(
+        rect.left_top() + egui::vec2(4.0, 2.0),
+        egui::Align2::LEFT_TOP,
+        channel.name,
+        egui::FontId::monospace(11.0),
+        ui.visuals().text_color(),
+    );
+
+    if let Some(pointer) = response.hover_pos() {
+        let index = (((pointer.x - rect.left()) / bar_width) as usize)
+            .min(channel.histogram.len().saturating_sub(1));
+        let step = (channel.max - channel.min) / channel.histogram.len() as f32;
+        let low = channel.min + step * index as f32;
+        response.on_hover_text_at_pointer(format!(
+            "{:.6} to {:.6}: {} texels",
+            low,
+            low + step,
+            channel.histogram[index]
+        ));
+    }
+}
+
+fn special_texels_ui(ui: &mut egui::Ui, texels: &SpecialTexels) -> egui::Response {
+    if texels.count == 0 {
+        return field::weak_label(ui, "None");
+    }
+    let mut locations = texels
+        .locations
+        .iter()
+        .map(|[x, y]| format!("({x}, {y})"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if texels.count as usize > texels.locations.len() {
+        locations.push_str(", …");
+    }
+    ui.vertical(|ui| {
+        field::error_label(ui, format!("{} texels", texels.count));
+        ui.monospace(locations);
+    })
+    .response
+}
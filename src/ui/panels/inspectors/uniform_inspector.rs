@@ -1,15 +1,18 @@
-use egui::{Label, Sense, Ui};
+use egui::{Color32, Label, Sense, Ui};
+use egui_phosphor::regular;
 use strum::IntoEnumIterator;
 
 use crate::{
     project::{
-        UniformId,
+        ShaderId, UniformId,
         resource::{
             camera::Camera,
             dimension::Dimension,
             uniform::{
                 self, Transform, UniformField, UniformFieldData, UniformFieldDataKind,
-                UniformFieldSource, UniformRuntimeField, camera::CameraField,
+                UniformFieldSource, UniformRuntimeField,
+                animation::{self, Animation, Interpolation, Keyframe, LoopMode},
+                camera::CameraField,
             },
         },
         storage::Storage,
@@ -26,9 +29,10 @@ use crate::{
             resource_icons,
         },
         pane::StateSnapshot,
+        panels::inspectors::shader_inspector,
         rename::{RenameState, RenameTarget},
     },
-    utils::{event_queue::EventQueue, shader_preview::ShaderGenCtx},
+    utils::{event_queue::EventQueue, shader_preview::ShaderGenCtx, shader_reflection},
     workspace::StateEvent,
 };
 
@@ -40,6 +44,33 @@ pub enum UniformFieldSourceKind {
     Transform,
     Dimension,
     Time,
+    Animated,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, strum::EnumIter, strum::Display)]
+enum InterpolationKind {
+    Step,
+    Linear,
+    #[strum(to_string = "Cubic Bézier")]
+    CubicBezier,
+}
+
+impl InterpolationKind {
+    fn from_interpolation(interpolation: &Interpolation) -> Self {
+        match interpolation {
+            Interpolation::Step => Self::Step,
+            Interpolation::Linear => Self::Linear,
+            Interpolation::CubicBezier { .. } => Self::CubicBezier,
+        }
+    }
+
+    fn into_interpolation(self) -> Interpolation {
+        match self {
+            Self::Step => Interpolation::Step,
+            Self::Linear => Interpolation::Linear,
+            Self::CubicBezier => Interpolation::EASE_IN_OUT,
+        }
+    }
 }
 
 impl UniformFieldSourceKind {
@@ -50,6 +81,7 @@ impl UniformFieldSourceKind {
             UniformFieldSource::Transform(..) => Self::Transform,
             UniformFieldSource::Dimension { .. } => Self::Dimension,
             UniformFieldSource::Time => Self::Time,
+            UniformFieldSource::Animated(..) => Self::Animated,
         }
     }
 
@@ -62,6 +94,7 @@ impl UniformFieldSourceKind {
             Self::Transform => UniformFieldSource::new_transform(Transform::default()),
             Self::Dimension => UniformFieldSource::new_dimension(None),
             Self::Time => UniformFieldSource::new_time(),
+            Self::Animated => UniformFieldSource::new_animated(Animation::default()),
         }
     }
 }
@@ -193,11 +226,108 @@ impl StateSnapshot<'_> {
             });
         }
 
+        self.uniform_struct_section_ui(ui, uniform_id);
+
         if let Ok(uniform) = self.project.uniforms.get(uniform_id) {
             let ctx = ShaderGenCtx::from_project(self.project);
             shader_code_section(ui, uniform, &ctx);
         }
     }
+
+    fn uniform_struct_section_ui(&mut self, ui: &mut egui::Ui, uniform_id: UniformId) {
+        let Ok(uniform) = self.project.uniforms.get_mut(uniform_id) else {
+            return;
+        };
+
+        let state_id = ui.make_persistent_id(("uniform_struct_source", uniform_id));
+        let mut source: StructSource = ui.data(|data| data.get_temp(state_id)).unwrap_or_default();
+
+        inspector::section_doc(
+            ui,
+            "Shader Struct",
+            field_doc!(
+                "Match this uniform's fields to a struct declared in a shader.\n\n\
+                **Update Fields** rewrites the field list in the struct's member order. Fields \
+                whose name and WGSL type already match keep their source; the rest become User \
+                Defined. A member whose type has no uniform field equivalent stops the update: the \
+                button is disabled and its tooltip shows the error."
+            ),
+            |ui| {
+                let module = source
+                    .shader_id
+                    .and_then(|id| self.runtime_project.shaders.get_init(id).ok().flatten())
+                    .map(|runtime| runtime.module());
+
+                field::field_grid(ui, "uniform_struct_grid", |ui| {
+                    field::row(ui, "Shader", |ui| {
+                        inspector::storage_combo(
+                            ui,
+                            "uniform_struct_shader",
+                            &self.project.shaders,
+                            &mut source.shader_id,
+                        )
+                    });
+                    field::row(ui, "Struct", |ui| match module {
+                        Some(module) => {
+                            let names = shader_reflection::struct_names(module);
+                            inspector::value_combo_with(
+                                ui,
+                                "uniform_struct_name",
+                                names.into_iter().map(|name| Some(name.to_string())),
+                                |name| match name {
+                                    Some(name) => name.as_str().into(),
+                                    None => "Select...".into(),
+                                },
+                                &mut source.struct_name,
+                            );
+                        }
+                        None => {
+                            field::weak_label(ui, "Select a compiled shader first.");
+                        }
+                    });
+                });
+
+                let members = module
+                    .zip(source.struct_name.as_deref())
+                    .and_then(|(module, name)| shader_reflection::reflect_struct(module, name));
+                let Some(members) = members else {
+                    return;
+                };
+
+                shader_inspector::struct_members_ui(ui, "uniform_struct_members", &members);
+
+                let fields =
+                    shader_reflection::uniform_fields_from_members(uniform.fields(), &members);
+                let button = egui::Button::new("Update Fields");
+                match fields {
+                    Ok(fields) => {
+                        let up_to_date = fields == uniform.fields();
+                        if ui
+                            .add_enabled(!up_to_date, button)
+                            .on_disabled_hover_text("The fields already match the struct.")
+                            .clicked()
+                        {
+                            uniform.set_fields(fields);
+                        }
+                    }
+                    Err(error) => {
+                        ui.add_enabled(false, button)
+                            .on_disabled_hover_text(error.to_string());
+                    }
+                }
+            },
+        );
+
+        ui.data_mut(|data| data.insert_temp(state_id, source));
+    }
+}
+
+/// The shader struct picked in a uniform's "Shader Struct" section. Kept in egui's
+/// temporary memory, as it's only an editing aid.
+#[derive(Clone, Default)]
+struct StructSource {
+    shader_id: Option<ShaderId>,
+    struct_name: Option<String>,
 }
 
 struct UniformUiContext<'a> {
@@ -275,7 +405,8 @@ fn ui_field_entry(
             - **Camera**: pulled from a Camera (position, matrices, and so on).\n\
             - **Transform**: a model matrix built from position, rotation, and scale.\n\
             - **Dimension**: the width and height of a Dimension.\n\
-            - **Time**: the elapsed time in seconds, updated every frame."
+            - **Time**: the elapsed time in seconds, updated every frame.\n\
+            - **Animated**: keyframes interpolated over time, updated every frame."
         ),
         "source",
         UniformFieldSourceKind::iter(),
@@ -406,6 +537,11 @@ fn ui_field_entry(
                 .then_some(UniformFieldSource::new_dimension(dimension_id))
         }
         UniformFieldSource::Time => None,
+        UniformFieldSource::Animated(animation) => {
+            let mut animation = animation.clone();
+            edit_animation(ui, &mut animation)
+                .then_some(UniformFieldSource::new_animated(animation))
+        }
     };
 
     if let Some(new_source) = source_kind_changed
@@ -417,6 +553,307 @@ fn ui_field_entry(
     }
 }
 
+fn edit_animation(ui: &mut Ui, animation: &mut Animation) -> bool {
+    let mut changed = false;
+
+    let mut kind = animation.kind();
+    if inspector::combo_row_doc(
+        ui,
+        "Type",
+        field_doc!(
+            "The data type every keyframe holds. Changing it converts the existing keyframes, \
+            keeping as many components as fit."
+        ),
+        "animation_type",
+        UniformFieldDataKind::iter(),
+        &mut kind,
+    ) {
+        for keyframe in &mut animation.keyframes {
+            let components = animation::components(&keyframe.value);
+            keyframe.value = animation::from_components(kind, &components);
+        }
+        changed = true;
+    }
+
+    changed |= inspector::f32_drag_row_doc(
+        ui,
+        "Duration",
+        field_doc!("The length of one cycle of the animation, in seconds."),
+        &mut animation.duration,
+        0.01..=f32::MAX,
+        0.01,
+        2,
+    );
+
+    changed |= inspector::combo_row_doc(
+        ui,
+        "Loop",
+        field_doc!(
+            "What happens once the clock passes the duration:\n\n\
+            - **Once**: holds the value at the end of the cycle.\n\
+            - **Repeat**: starts over from the beginning.\n\
+            - **Ping Pong**: plays backwards, then forwards again."
+        ),
+        "animation_loop",
+        LoopMode::iter(),
+        &mut animation.loop_mode,
+    );
+
+    field::row_doc(
+        ui,
+        "Curve",
+        field_doc!(
+            "One cycle of the animation, one line per component. Drag a keyframe handle to \
+            move it in time and change that component's value."
+        ),
+        |ui| changed |= animation_curve_ui(ui, animation),
+    );
+
+    field::row_doc(
+        ui,
+        "Keyframes",
+        field_doc!(
+            "The values the animation passes through. Each keyframe's **interpolation** \
+            shapes the segment up to the next keyframe:\n\n\
+            - **Step**: holds the value until the next keyframe.\n\
+            - **Linear**: blends at a constant rate.\n\
+            - **Cubic Bézier**: eases along a curve with two control points, like CSS \
+            `cubic-bezier()`.\n\n\
+            Integer types are rounded to the nearest value."
+        ),
+        |ui| changed |= keyframes_ui(ui, animation),
+    );
+
+    if changed {
+        animation.sort_keyframes();
+    }
+    changed
+}
+
+const CURVE_COLORS: [Color32; 4] = [
+    Color32::from_rgb(230, 100, 100),
+    Color32::from_rgb(110, 200, 110),
+    Color32::from_rgb(100, 150, 235),
+    Color32::from_rgb(200, 200, 200),
+];
+
+/// The time bounds a keyframe can be moved within without passing its neighbours.
+fn keyframe_time_bounds(animation: &Animation, index: usize) -> (f32, f32) {
+    let keyframes = &animation.keyframes;
+    let low = match index.checked_sub(1) {
+        Some(previous) => keyframes[previous].time,
+        None => 0.0,
+    };
+    let high = match keyframes.get(index + 1) {
+        Some(next) => next.time,
+        None => animation.duration.max(keyframes[index].time),
+    };
+    (low, high)
+}
+
+fn animation_curve_ui(ui: &mut Ui, animation: &mut Animation) -> bool {
+    const HEIGHT: f32 = 90.0;
+    const SAMPLES: usize = 64;
+    const HANDLE_SIZE: f32 = 9.0;
+
+    let duration = animation.duration.max(f32::EPSILON);
+    let samples: Result<Vec<Vec<f32>>, _> = (0..=SAMPLES)
+        .map(|i| {
+            let time = duration * i as f32 / SAMPLES as f32;
+            animation
+                .evaluate_local(time)
+                .map(|value| animation::components(&value))
+        })
+        .collect();
+    let samples = match samples {
+        Ok(samples) => samples,
+        Err(error) => {
+            field::error_label(ui, error.to_string());
+            return false;
+        }
+    };
+
+    let keyframe_values: Vec<Vec<f32>> = animation
+        .keyframes
+        .iter()
+        .map(|keyframe| animation::components(&keyframe.value))
+        .collect();
+    let (mut min, mut max) = samples
+        .iter()
+        .chain(&keyframe_values)
+        .flatten()
+        .fold((f32::MAX, f32::MIN), |(min, max), v| {
+            (min.min(*v), max.max(*v))
+        });
+    if max - min < 1e-3 {
+        min -= 0.5;
+        max += 0.5;
+    }
+    let padding = (max - min) * 0.1;
+    let (min, max) = (min - padding, max + padding);
+
+    let width = ui.available_width().max(160.0);
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(width, HEIGHT), Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let to_screen = |time: f32, value: f32| {
+        egui::pos2(
+            rect.left() + time / duration * rect.width(),
+            rect.bottom() - (value - min) / (max - min) * rect.height(),
+        )
+    };
+
+    if min < 0.0 && max > 0.0 {
+        let zero = to_screen(0.0, 0.0).y;
+        painter.hline(
+            rect.x_range(),
+            zero,
+            egui::Stroke::new(1.0, ui.visuals().weak_text_color().gamma_multiply(0.4)),
+        );
+    }
+
+    let component_count = samples.first().map_or(0, Vec::len);
+    for component in 0..component_count {
+        let color = CURVE_COLORS[component % CURVE_COLORS.len()];
+        let points = samples
+            .iter()
+            .enumerate()
+            .map(|(i, values)| {
+                let time = duration * i as f32 / SAMPLES as f32;
+                to_screen(time, values[component])
+            })
+            .collect();
+        painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, color)));
+    }
+
+    let kind = animation.kind();
+    let mut changed = false;
+    for (index, values) in keyframe_values.iter().enumerate() {
+        let time = animation.keyframes[index].time;
+        for (component, value) in values.iter().enumerate() {
+            let center = to_screen(time, *value);
+            let handle = egui::Rect::from_center_size(center, egui::Vec2::splat(HANDLE_SIZE));
+            let response = ui
+                .interact(
+                    handle,
+                    ui.id().with(("keyframe", index, component)),
+                    Sense::drag(),
+                )
+                .on_hover_cursor(egui::CursorIcon::Grab);
+
+            if response.dragged() {
+                let delta = response.drag_delta();
+                let (low, high) = keyframe_time_bounds(animation, index);
+                let keyframe = &mut animation.keyframes[index];
+                keyframe.time = (time + delta.x / rect.width() * duration).clamp(low, high);
+
+                let mut components = values.clone();
+                components[component] -= delta.y / rect.height() * (max - min);
+                keyframe.value = animation::from_components(kind, &components);
+                changed = true;
+            }
+
+            let color = CURVE_COLORS[component % CURVE_COLORS.len()];
+            let radius = if response.hovered() || response.dragged() {
+                HANDLE_SIZE * 0.6
+            } else {
+                HANDLE_SIZE * 0.4
+            };
+            painter.circle_filled(center, radius, color);
+        }
+    }
+
+    changed
+}
+
+fn keyframes_ui(ui: &mut Ui, animation: &mut Animation) -> bool {
+    let mut changed = false;
+    let mut removed = None;
+    let count = animation.keyframes.len();
+
+    ui.vertical(|ui| {
+        for index in 0..count {
+            let (low, high) = keyframe_time_bounds(animation, index);
+            let keyframe = &mut animation.keyframes[index];
+
+            ui.push_id(index, |ui| {
+                ui.horizontal(|ui| {
+                    let time = egui::DragValue::new(&mut keyframe.time)
+                        .speed(0.01)
+                        .max_decimals(2)
+                        .range(low..=high)
+                        .suffix(" s");
+                    changed |= ui.add(time).changed();
+
+                    let mut kind = InterpolationKind::from_interpolation(&keyframe.interpolation);
+                    if inspector::value_combo(
+                        ui,
+                        "interpolation",
+                        InterpolationKind::iter(),
+                        &mut kind,
+                    ) {
+                        keyframe.interpolation = kind.into_interpolation();
+                        changed = true;
+                    }
+
+                    let remove = ui
+                        .add_enabled(count > 1, egui::Button::new(regular::TRASH).small())
+                        .on_hover_text("Remove keyframe");
+                    if remove.clicked() {
+                        removed = Some(index);
+                    }
+                });
+
+                changed |= edit_uniform_field_data(ui, &mut keyframe.value);
+
+                if let Interpolation::CubicBezier { p1, p2 } = &mut keyframe.interpolation {
+                    ui.horizontal(|ui| {
+                        for (label, point) in [("P1", p1), ("P2", p2)] {
+                            ui.weak(label);
+                            let x = egui::DragValue::new(&mut point[0])
+                                .speed(0.01)
+                                .max_decimals(2)
+                                .range(0.0..=1.0);
+                            changed |= ui.add(x).changed();
+                            let y = egui::DragValue::new(&mut point[1])
+                                .speed(0.01)
+                                .max_decimals(2);
+                            changed |= ui.add(y).changed();
+                        }
+                    });
+                }
+            });
+            ui.add_space(4.0);
+        }
+
+        if ui
+            .button(resource_icons::add_text(ui, "Add Keyframe"))
+            .clicked()
+        {
+            let keyframe = match animation.keyframes.last() {
+                Some(last) => {
+                    Keyframe::new(last.time + 0.5, last.value.clone(), last.interpolation)
+                }
+                None => Keyframe::new(
+                    0.0,
+                    UniformFieldData::from_kind(animation.kind()),
+                    Interpolation::Linear,
+                ),
+            };
+            animation.duration = animation.duration.max(keyframe.time);
+            animation.keyframes.push(keyframe);
+            changed = true;
+        }
+    });
+
+    if let Some(index) = removed {
+        animation.keyframes.remove(index);
+        changed = true;
+    }
+    changed
+}
+
 fn ui_uniform_type_label(ui: &mut Ui, kind: UniformFieldDataKind, padding: usize) {
     let (align, size) = kind.layout();
     egui::Popup::from_toggle_button_response(
@@ -552,6 +989,18 @@ impl AsRichText for UniformFieldDataKind {
     }
 }
 
+impl AsRichText for LoopMode {
+    fn as_rich_text(&self) -> egui::RichText {
+        self.to_string().into()
+    }
+}
+
+impl AsRichText for InterpolationKind {
+    fn as_rich_text(&self) -> egui::RichText {
+        self.to_string().into()
+    }
+}
+
 impl AsRichText for CameraField {
     fn as_rich_text(&self) -> egui::RichText {
         self.to_string().into()
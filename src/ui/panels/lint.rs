@@ -0,0 +1,117 @@
+//! Project lint: unused resources, dangling references and unused files,
+//! each with a button that applies its fix.
+
+use egui_phosphor::regular;
+
+use crate::{
+    project::{
+        Project, RuntimeProject,
+        lint::{self, Lint, LintFix},
+        paths::FilePath,
+    },
+    ui::{
+        components::{field, resource_icons},
+        pane::StateSnapshot,
+    },
+    utils::event_queue::EventQueue,
+    workspace::StateEvent,
+};
+
+pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui) {
+    let Some(files) = state.file_storage.files() else {
+        field::centered(ui, |ui| field::spinner(ui));
+        return;
+    };
+    let indirect_files = material_files(state.project, state.runtime_project);
+    let lints = lint::lint(state.project, files, &indirect_files);
+
+    if lints.is_empty() {
+        field::centered(ui, |ui| {
+            field::weak_label(
+                ui,
+                format!("{} Nothing unused or dangling", regular::CHECK_CIRCLE),
+            );
+        });
+        return;
+    }
+
+    egui::ScrollArea::vertical()
+        .auto_shrink(false)
+        .show(ui, |ui| {
+            egui::Grid::new("lint_grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for lint in &lints {
+                        lint_row(ui, state.project, state.event_queue, lint);
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+/// The textures loaded models read through their materials.
+fn material_files(project: &Project, runtime_project: &RuntimeProject) -> Vec<FilePath> {
+    project
+        .models
+        .list()
+        .filter_map(|(id, _)| runtime_project.models.get_init(id).ok().flatten())
+        .flat_map(|model| model.materials())
+        .flat_map(|material| material.texture_paths())
+        .map(|(_, path)| path.clone())
+        .collect()
+}
+
+fn lint_row(
+    ui: &mut egui::Ui,
+    project: &Project,
+    event_queue: &mut EventQueue<StateEvent>,
+    lint: &Lint,
+) {
+    match (lint.resource, &lint.fix) {
+        (Some(id), _) => {
+            let label = project.label(id).unwrap_or("Unknown");
+            let text = resource_icons::icon_text(ui, resource_icons::resource_id_icon(id), label);
+            if ui
+                .link(text)
+                .on_hover_text("Click to open inspector")
+                .clicked()
+            {
+                event_queue.inspect_resource(id);
+            }
+        }
+        (None, LintFix::DeleteFile(path)) => {
+            let icon = resource_icons::file_icon(path);
+            let text = resource_icons::icon_text(ui, icon, &path.to_string());
+            if ui.link(text).on_hover_text("Click to open file").clicked() {
+                event_queue.open_file(path.clone());
+            }
+        }
+        (None, _) => {
+            ui.label("");
+        }
+    }
+
+    field::weak_label(ui, lint.error.to_string());
+
+    let text = format!("{} {}", regular::WRENCH, lint.fix.label());
+    if ui.small_button(text).clicked() {
+        event_queue.add(fix_event(project, &lint.fix));
+    }
+}
+
+fn fix_event(project: &Project, fix: &LintFix) -> StateEvent {
+    match fix {
+        LintFix::Delete(id) => StateEvent::DeleteResource(*id),
+        LintFix::ClearReference { owner, target } => StateEvent::ClearReference {
+            owner: *owner,
+            target: *target,
+        },
+        LintFix::RemoveFromSchedule(step) => {
+            let mut steps = project.presentation.steps().to_vec();
+            steps.retain(|other| other != step);
+            StateEvent::SetPresentationSteps(steps)
+        }
+        LintFix::DeleteFile(path) => StateEvent::DeleteFile(path.clone()),
+    }
+}
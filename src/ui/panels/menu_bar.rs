@@ -9,6 +9,7 @@ use crate::{
 const CREATABLE_RESOURCES: &[(ResourceKind, &str)] = &[
     (ResourceKind::RenderPass, "Render Pass"),
     (ResourceKind::ComputePass, "Compute Pass"),
+    (ResourceKind::CopyPass, "Copy Pass"),
     (ResourceKind::RenderPipeline, "Render Pipeline"),
     (ResourceKind::Shader, "Shader"),
     (ResourceKind::BindGroup, "Bind Group"),
@@ -28,6 +29,8 @@ pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui) {
         ui.menu_button("Project", |ui| project_menu(state, ui));
         ui.menu_button("Create", |ui| create_menu(state, ui));
         ui.menu_button("View", |ui| view_menu(state, ui));
+        #[cfg(not(target_arch = "wasm32"))]
+        ui.menu_button("Record", |ui| record_menu(state, ui));
     });
 }
 
@@ -102,12 +105,79 @@ fn project_menu(state: &mut StateSnapshot, ui: &mut egui::Ui) {
             .add(StateEvent::ImportFile(FilePath::default()));
     }
 
+    ui.separator();
+    let presentation = &state.project.presentation;
+    let reset = ui
+        .add_enabled(
+            presentation.initializer_steps().next().is_some(),
+            egui::Button::new("Reset Simulation"),
+        )
+        .on_disabled_hover_text("Mark a step of the schedule as an initializer first.");
+    if reset.clicked() {
+        state.runtime_project.reset_simulation(presentation);
+    }
+
     ui.separator();
     if ui.button("Close Project").clicked() {
         state.app_event_queue.close_project();
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn record_menu(state: &mut StateSnapshot, ui: &mut egui::Ui) {
+    use crate::{ui::components::field, utils::recording::RecordingFormat};
+
+    let recording = &mut *state.recording;
+    if let Some(recorder) = recording.recorder() {
+        ui.label(format!("Recording frame {}", recorder.frames()));
+        field::weak_label(ui, recorder.output().display().to_string());
+        if ui.button("Stop Recording").clicked() {
+            recording.stop();
+            ui.close();
+        }
+        return;
+    }
+
+    ui.add_enabled_ui(!recording.is_starting(), |ui| {
+        field::field_grid(ui, "record_menu_grid", |ui| {
+            field::row(ui, "Format", |ui| {
+                for format in [RecordingFormat::Y4m, RecordingFormat::PngSequence] {
+                    ui.radio_value(&mut recording.format, format, format.label());
+                }
+            });
+            field::row(ui, "Frame Rate", |ui| {
+                ui.add(
+                    egui::DragValue::new(&mut recording.fps)
+                        .range(1.0..=240.0)
+                        .suffix(" fps"),
+                )
+            });
+            field::row(ui, "Length", |ui| {
+                let mut limited = recording.frame_limit.is_some();
+                ui.checkbox(&mut limited, "Stop after");
+                let mut frames = recording.frame_limit.unwrap_or(300);
+                ui.add_enabled(
+                    limited,
+                    egui::DragValue::new(&mut frames)
+                        .range(1..=u32::MAX)
+                        .suffix(" frames"),
+                );
+                recording.frame_limit = limited.then_some(frames);
+            });
+        });
+
+        let has_main_viewport = state.project.presentation.main_viewport().is_some();
+        let start = ui
+            .add_enabled(has_main_viewport, egui::Button::new("Start Recording…"))
+            .on_hover_text("The clock advances by one frame time per recorded frame.")
+            .on_disabled_hover_text("Set a main viewport to record first.");
+        if start.clicked() {
+            recording.start();
+            ui.close();
+        }
+    });
+}
+
 fn create_menu(state: &mut StateSnapshot, ui: &mut egui::Ui) {
     for &(kind, label) in CREATABLE_RESOURCES {
         let icon = resource_icons::resource_kind_icon(kind);
@@ -137,6 +207,18 @@ fn view_menu(state: &mut StateSnapshot, ui: &mut egui::Ui) {
     if ui.button("Inspect Presentation").clicked() {
         state.event_queue.inspect_resource(PresentationId);
     }
+    if ui.button("Frame Graph").clicked() {
+        state.event_queue.add(StateEvent::OpenFrameGraph);
+    }
+    if ui.button("Frame Capture").clicked() {
+        state.event_queue.add(StateEvent::OpenFrameCapture);
+    }
+    if ui.button("Image Compare").clicked() {
+        state.event_queue.add(StateEvent::OpenImageCompare);
+    }
+    if ui.button("Project Lint").clicked() {
+        state.event_queue.add(StateEvent::OpenLint);
+    }
 
     ui.separator();
 
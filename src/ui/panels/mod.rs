@@ -1,8 +1,13 @@
 pub mod error_panel;
 pub mod files_panel;
+pub mod frame_capture;
+pub mod frame_graph;
+pub mod image_compare;
 pub mod inspector_pane;
 pub mod inspectors;
+pub mod lint;
 pub mod menu_bar;
 pub mod project_tree_panel;
+pub mod readback;
 pub mod status_bar;
 pub mod viewport_pane;
@@ -5,7 +5,7 @@ use egui_phosphor::regular;
 use crate::{
     error::AppError,
     project::{
-        BindGroupId, CameraId, ComputePassId, DimensionId, ModelId, PresentationId,
+        BindGroupId, CameraId, ComputePassId, CopyPassId, DimensionId, ModelId, PresentationId,
         ProjectResource, RenderPassId, RenderPipelineId, ResourceId, ResourceKind, SamplerId,
         ShaderId, TextureId, TextureViewId, UniformId, ViewportId,
     },
@@ -17,6 +17,7 @@ use crate::{
         pane::StateSnapshot,
         rename::RenameTarget,
     },
+    utils::readback::ReadbackTarget,
     workspace::StateEvent,
 };
 
@@ -49,6 +50,8 @@ pub enum TreeNodeId {
     RenderPass(RenderPassId),
     ComputePassFolder,
     ComputePass(ComputePassId),
+    CopyPassFolder,
+    CopyPass(CopyPassId),
     Presentation(PresentationId),
 }
 
@@ -82,6 +85,7 @@ fn resource_icon(id: TreeNodeId) -> resource_icons::Icon {
         N::RenderPipelineFolder | N::RenderPipeline(_) => ResourceKind::RenderPipeline,
         N::RenderPassFolder | N::RenderPass(_) => ResourceKind::RenderPass,
         N::ComputePassFolder | N::ComputePass(_) => ResourceKind::ComputePass,
+        N::CopyPassFolder | N::CopyPass(_) => ResourceKind::CopyPass,
         N::Presentation(_) => ResourceKind::Presentation,
         N::PendingCreate(kind) => kind,
     };
@@ -104,6 +108,7 @@ fn node_resource_id(id: TreeNodeId) -> Option<ResourceId> {
         N::RenderPipeline(id) => id.into(),
         N::RenderPass(id) => id.into(),
         N::ComputePass(id) => id.into(),
+        N::CopyPass(id) => id.into(),
         N::Presentation(id) => id.into(),
         N::PendingCreate(_)
         | N::UniformFolder
@@ -118,7 +123,8 @@ fn node_resource_id(id: TreeNodeId) -> Option<ResourceId> {
         | N::ModelFolder
         | N::RenderPipelineFolder
         | N::RenderPassFolder
-        | N::ComputePassFolder => return None,
+        | N::ComputePassFolder
+        | N::CopyPassFolder => return None,
     })
 }
 
@@ -222,6 +228,29 @@ pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui) -> Response {
             }
             builder.close_dir();
 
+            resource_folder(TreeNodeId::CopyPassFolder, "Copy Passes")
+                .with_label_suffix(count_suffix(state.project.copy_passes.len()))
+                .with_event(
+                    "Create New Copy Pass",
+                    StateEvent::CreateResource(ResourceKind::CopyPass),
+                )
+                .build_to(builder, state.event_queue, state.rename_state);
+            pending_resource_node(state, builder, ResourceKind::CopyPass);
+            for (id, copy_pass) in state.project.copy_passes.list_sorted() {
+                let error = state.runtime_project.get_error(id);
+                resource_leaf(TreeNodeId::CopyPass(id), copy_pass.label(), error)
+                    .with_event("Inspect", StateEvent::InspectResource(id.into()))
+                    .with_rename_event("Rename", RenameTarget::CopyPass(id))
+                    .with_event("Delete", StateEvent::DeleteResource(id.into()))
+                    .with_separator()
+                    .with_event(
+                        "Create New Copy Pass",
+                        StateEvent::CreateResource(ResourceKind::CopyPass),
+                    )
+                    .build_to(builder, state.event_queue, state.rename_state);
+            }
+            builder.close_dir();
+
             resource_folder(TreeNodeId::RenderPipelineFolder, "Render Pipelines")
                 .with_label_suffix(count_suffix(state.project.render_pipelines.len()))
                 .with_event(
@@ -303,6 +332,10 @@ pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui) -> Response {
                 let error = state.runtime_project.get_error(id);
                 resource_leaf(TreeNodeId::Uniform(id), uniform.label(), error)
                     .with_event("Inspect", StateEvent::InspectResource(id.into()))
+                    .with_event(
+                        "Read Back Buffer",
+                        StateEvent::OpenReadback(ReadbackTarget::Uniform(id)),
+                    )
                     .with_rename_event("Rename", RenameTarget::Uniform(id))
                     .with_event("Delete", StateEvent::DeleteResource(id.into()))
                     .with_separator()
@@ -328,6 +361,10 @@ pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui) -> Response {
                     .with_event("Inspect", StateEvent::InspectResource(id.into()))
                     .with_event("Derive Texture View", StateEvent::DeriveTextureView(id))
                     .with_event("Save as Image", StateEvent::DownloadTextureImage(id))
+                    .with_event(
+                        "Read Back Texels",
+                        StateEvent::OpenReadback(ReadbackTarget::Texture(id)),
+                    )
                     .with_rename_event("Rename", RenameTarget::Texture(id))
                     .with_event("Delete", StateEvent::DeleteResource(id.into()))
                     .with_separator()
@@ -508,6 +545,7 @@ pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui) -> Response {
                     TreeNodeId::RenderPipeline(id) => StateEvent::InspectResource(id.into()),
                     TreeNodeId::RenderPass(id) => StateEvent::InspectResource(id.into()),
                     TreeNodeId::ComputePass(id) => StateEvent::InspectResource(id.into()),
+                    TreeNodeId::CopyPass(id) => StateEvent::InspectResource(id.into()),
                     TreeNodeId::Presentation(id) => StateEvent::InspectResource(id.into()),
                     TreeNodeId::UniformFolder
                     | TreeNodeId::BindGroupFolder
@@ -522,6 +560,7 @@ pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui) -> Response {
                     | TreeNodeId::RenderPipelineFolder
                     | TreeNodeId::RenderPassFolder
                     | TreeNodeId::ComputePassFolder
+                    | TreeNodeId::CopyPassFolder
                     | TreeNodeId::PendingCreate(_) => continue,
                 };
 
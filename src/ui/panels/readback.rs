@@ -0,0 +1,407 @@
+//! Readback tab: a texture's texels or a uniform's buffer, read back from the
+//! GPU and shown as a paged table.
+
+use egui::Widget;
+use egui_phosphor::regular;
+
+use crate::{
+    project::{
+        ShaderId,
+        resource::{shader::Shader, texture_view::BufferSide},
+        storage::{RuntimeStorage, Storage},
+    },
+    ui::{
+        components::{
+            field,
+            field_docs::field_doc,
+            inspector::{self, AsRichText},
+        },
+        pane::StateSnapshot,
+    },
+    utils::{
+        readback::{
+            self, BufferLayout, ELEMENT_PAGE_SIZE, ReadbackState, ReadbackTarget, Readout,
+            TexelRegion,
+        },
+        shader_reflection,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BufferLayoutKind {
+    UniformFields,
+    ShaderStruct,
+    WgslType,
+}
+
+impl BufferLayoutKind {
+    fn from_layout(layout: &BufferLayout) -> Self {
+        match layout {
+            BufferLayout::UniformFields => Self::UniformFields,
+            BufferLayout::ShaderStruct { .. } => Self::ShaderStruct,
+            BufferLayout::WgslType(_) => Self::WgslType,
+        }
+    }
+}
+
+impl AsRichText for BufferLayoutKind {
+    fn as_rich_text(&self) -> egui::RichText {
+        match self {
+            Self::UniformFields => "Uniform Fields",
+            Self::ShaderStruct => "Shader Struct",
+            Self::WgslType => "WGSL Type",
+        }
+        .into()
+    }
+}
+
+const BUFFER_LAYOUT_KINDS: [BufferLayoutKind; 3] = [
+    BufferLayoutKind::UniformFields,
+    BufferLayoutKind::ShaderStruct,
+    BufferLayoutKind::WgslType,
+];
+
+const DEFAULT_REFRESH_FRAMES: u32 = 10;
+
+pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui, target: ReadbackTarget) {
+    let readback = state.readbacks.state_mut(target);
+    readback.mark_viewed();
+
+    let texture = match target {
+        ReadbackTarget::Texture(id) => {
+            let double_buffered = state
+                .project
+                .textures
+                .get(id)
+                .is_ok_and(|texture| texture.ping_pong().is_some());
+            let size = state
+                .runtime_project
+                .textures
+                .get_init(id)
+                .ok()
+                .flatten()
+                .map(|runtime| (runtime.inner().width(), runtime.inner().height()));
+            Some((double_buffered, size))
+        }
+        ReadbackTarget::Uniform(_) => None,
+    };
+
+    inspector::section_doc(
+        ui,
+        "Readback",
+        field_doc!(
+            "Copies the resource back from the GPU and decodes it, to check what a pass \
+            actually wrote.\n\n\
+            Textures show a grid of texels, one page at a time, with each channel decoded \
+            the way a shader reads it. Buffers are split into elements by the chosen \
+            **Layout**.\n\n\
+            Only the page on screen is read, and only while this tab is visible. **Export \
+            CSV** reads the whole resource."
+        ),
+        |ui| {
+            let mut changed = false;
+            field::field_grid(ui, "readback_grid", |ui| {
+                if let Some((true, _)) = texture {
+                    changed |= inspector::combo_row_doc(
+                        ui,
+                        "Side",
+                        field_doc!(
+                            "Which half of the double-buffered texture to read. The **Front** \
+                            holds the last finished result."
+                        ),
+                        "readback_side",
+                        [BufferSide::Front, BufferSide::Back],
+                        &mut readback.side,
+                    );
+                }
+
+                if let ReadbackTarget::Uniform(_) = target {
+                    changed |= buffer_layout_rows(
+                        ui,
+                        &state.project.shaders,
+                        &state.runtime_project.shaders,
+                        &mut readback.layout,
+                    );
+                }
+
+                field::row_doc(
+                    ui,
+                    "Auto Refresh",
+                    field_doc!(
+                        "Reads the resource again every few frames. When off, it's only read \
+                        when the tab opens and on **Refresh**."
+                    ),
+                    |ui| refresh_ui(ui, &mut readback.refresh_every),
+                );
+            });
+
+            ui.horizontal(|ui| {
+                if ui.button("Refresh").clicked() {
+                    changed = true;
+                }
+                if ui.button("Export CSV…").clicked() {
+                    readback.request_export();
+                }
+                if readback.is_reading() {
+                    field::spinner(ui);
+                }
+            });
+
+            if changed {
+                readback.request();
+            }
+        },
+    );
+
+    inspector::section(ui, "Data", |ui| match texture {
+        Some((_, Some((width, height)))) => {
+            let [columns, rows] = readback::page_count(width, height);
+            let mut page = readback.page;
+            let changed = ui
+                .horizontal(|ui| {
+                    page_controls(ui, "Columns", &mut page[0], columns)
+                        | page_controls(ui, "Rows", &mut page[1], rows)
+                })
+                .inner;
+            if changed {
+                readback.page = page;
+                readback.request();
+            }
+            readout_ui(ui, readback, texel_grid);
+        }
+        Some((_, None)) => {
+            field::weak_label(ui, "The texture isn't created yet.");
+        }
+        None => {
+            let element_count = match readback.readout() {
+                Some(Ok(readout)) => readout.len(),
+                _ => 0,
+            };
+            let pages = element_count.div_ceil(ELEMENT_PAGE_SIZE).max(1) as u32;
+            page_controls(ui, "Elements", &mut readback.page[1], pages);
+            let page = readback.page[1] as usize;
+            readout_ui(ui, readback, |ui, readout| element_table(ui, readout, page));
+        }
+    });
+}
+
+fn buffer_layout_rows(
+    ui: &mut egui::Ui,
+    shaders: &Storage<Shader>,
+    runtime_shaders: &RuntimeStorage<Shader>,
+    layout: &mut BufferLayout,
+) -> bool {
+    let mut kind = BufferLayoutKind::from_layout(layout);
+    let mut changed = inspector::combo_row_doc(
+        ui,
+        "Layout",
+        field_doc!(
+            "How the buffer's bytes are split into elements.\n\n\
+            - **Uniform Fields**: the uniform's own fields, packed the way they're uploaded.\n\
+            - **Shader Struct**: a struct declared in a shader, e.g. to check that the \
+            shader's layout matches the uniform's.\n\
+            - **WGSL Type**: any type a shader could declare, such as `vec4<f32>` or \
+            `array<u32, 4>`.\n\n\
+            Elements repeat every **stride** bytes, the type's size rounded up to its \
+            alignment."
+        ),
+        "readback_layout",
+        BUFFER_LAYOUT_KINDS,
+        &mut kind,
+    );
+    if changed {
+        *layout = match kind {
+            BufferLayoutKind::UniformFields => BufferLayout::UniformFields,
+            BufferLayoutKind::ShaderStruct => BufferLayout::ShaderStruct {
+                shader_id: None,
+                name: None,
+            },
+            BufferLayoutKind::WgslType => BufferLayout::WgslType("vec4<f32>".to_string()),
+        };
+    }
+
+    match layout {
+        BufferLayout::UniformFields => {}
+        BufferLayout::ShaderStruct { shader_id, name } => {
+            changed |= field::row(ui, "Shader", |ui| {
+                inspector::storage_combo(ui, "readback_shader", shaders, shader_id)
+            });
+            changed |= field::row(ui, "Struct", |ui| {
+                struct_combo(ui, runtime_shaders, *shader_id, name)
+            });
+        }
+        BufferLayout::WgslType(ty) => {
+            changed |= field::row(ui, "Type", |ui| {
+                egui::TextEdit::singleline(ty)
+                    .code_editor()
+                    .desired_width(160.0)
+                    .ui(ui)
+                    .changed()
+            });
+        }
+    }
+    changed
+}
+
+fn struct_combo(
+    ui: &mut egui::Ui,
+    runtime_shaders: &RuntimeStorage<Shader>,
+    shader_id: Option<ShaderId>,
+    name: &mut Option<String>,
+) -> bool {
+    let module = shader_id
+        .and_then(|id| runtime_shaders.get_init(id).ok().flatten())
+        .map(|runtime| runtime.module());
+    let Some(module) = module else {
+        field::weak_label(ui, "Select a compiled shader first.");
+        return false;
+    };
+
+    let names = shader_reflection::struct_names(module);
+    inspector::value_combo_with(
+        ui,
+        "readback_struct",
+        names.into_iter().map(|name| Some(name.to_string())),
+        |name| match name {
+            Some(name) => name.as_str().into(),
+            None => "Select...".into(),
+        },
+        name,
+    )
+}
+
+fn refresh_ui(ui: &mut egui::Ui, refresh_every: &mut Option<u32>) {
+    ui.horizontal(|ui| {
+        let mut enabled = refresh_every.is_some();
+        let mut frames = refresh_every.unwrap_or(DEFAULT_REFRESH_FRAMES);
+        ui.checkbox(&mut enabled, ());
+        if enabled {
+            ui.label("every");
+            egui::DragValue::new(&mut frames)
+                .speed(1)
+                .range(1..=600)
+                .ui(ui);
+            ui.label("frames");
+        }
+        *refresh_every = enabled.then_some(frames);
+    });
+}
+
+/// Previous and next buttons around "`label` page of count". Returns whether
+/// the page changed.
+fn page_controls(ui: &mut egui::Ui, label: &str, page: &mut u32, count: u32) -> bool {
+    let before = *page;
+    *page = (*page).min(count.saturating_sub(1));
+    if ui
+        .add_enabled(*page > 0, egui::Button::new(regular::CARET_LEFT))
+        .clicked()
+    {
+        *page -= 1;
+    }
+    ui.label(format!("{label} {} of {count}", *page + 1));
+    if ui
+        .add_enabled(*page + 1 < count, egui::Button::new(regular::CARET_RIGHT))
+        .clicked()
+    {
+        *page += 1;
+    }
+    *page != before
+}
+
+fn readout_ui(
+    ui: &mut egui::Ui,
+    readback: &ReadbackState,
+    show: impl FnOnce(&mut egui::Ui, &Readout),
+) {
+    match readback.readout() {
+        Some(Ok(readout)) => show(ui, readout),
+        Some(Err(error)) => {
+            field::error_label(ui, error.to_string());
+        }
+        None => {
+            field::spinner(ui);
+        }
+    }
+}
+
+fn texel_grid(ui: &mut egui::Ui, readout: &Readout) {
+    let Some(TexelRegion {
+        x,
+        y,
+        width,
+        height,
+    }) = readout.region()
+    else {
+        return;
+    };
+    let names: Vec<&str> = readout
+        .layout()
+        .columns
+        .iter()
+        .map(|column| column.name.as_str())
+        .collect();
+
+    field::weak_label(
+        ui,
+        format!("Channels: {}. Rows are y, columns are x.", names.join(", ")),
+    );
+    egui::Grid::new("readback_texels")
+        .striped(true)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            ui.label("");
+            for column in x..x + width {
+                ui.strong(column.to_string());
+            }
+            ui.end_row();
+
+            for row in y..y + height {
+                ui.strong(row.to_string());
+                for column in x..x + width {
+                    let values = readout.texel(column, row).unwrap_or_default();
+                    let tooltip = names
+                        .iter()
+                        .zip(&values)
+                        .map(|(name, value)| format!("{name}: {value}"))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    ui.monospace(values.join(", ")).on_hover_text(tooltip);
+                }
+                ui.end_row();
+            }
+        });
+}
+
+fn element_table(ui: &mut egui::Ui, readout: &Readout, page: usize) {
+    let layout = readout.layout();
+    field::weak_label(
+        ui,
+        format!(
+            "{} elements of {} bytes, {} bytes in total.",
+            readout.len(),
+            layout.stride,
+            readout.byte_len()
+        ),
+    );
+
+    let start = page * ELEMENT_PAGE_SIZE;
+    let end = (start + ELEMENT_PAGE_SIZE).min(readout.len());
+    egui::Grid::new("readback_elements")
+        .striped(true)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            ui.strong("#");
+            for column in &layout.columns {
+                ui.strong(&column.name);
+            }
+            ui.end_row();
+
+            for index in start..end {
+                ui.weak(index.to_string());
+                for value in readout.values(index).unwrap_or_default() {
+                    ui.monospace(value);
+                }
+                ui.end_row();
+            }
+        });
+}
@@ -14,6 +14,9 @@ pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui, error_panel: &mut ErrorP
             field::spinner(ui).on_hover_text("Rebuilding resources...");
         }
 
+        #[cfg(not(target_arch = "wasm32"))]
+        recording_status_ui(ui, state);
+
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             renderer_status_ui(ui, state.backend);
             ui.separator();
@@ -26,6 +29,26 @@ pub fn ui(state: &mut StateSnapshot, ui: &mut egui::Ui, error_panel: &mut ErrorP
     });
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn recording_status_ui(ui: &mut egui::Ui, state: &mut StateSnapshot) {
+    let Some(recorder) = state.recording.recorder() else {
+        return;
+    };
+
+    let text = egui::RichText::new(format!("● REC {}", recorder.frames()))
+        .color(ui.visuals().error_fg_color);
+    let output = recorder.output().display().to_string();
+    let response = ui
+        .add(egui::Button::new(text).frame(false))
+        .on_hover_ui(|ui| {
+            ui.label(format!("Recording the main viewport to {output}."));
+            ui.label(egui::RichText::new("Click to stop recording.").weak());
+        });
+    if response.clicked() {
+        state.recording.stop();
+    }
+}
+
 fn frame_time_ui(ui: &mut egui::Ui, frame_time: &FrameTimeTracker) {
     let frame_time_ms = frame_time.displayed_ms();
     let fps = if frame_time_ms > 0.0 {
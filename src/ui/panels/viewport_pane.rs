@@ -5,7 +5,7 @@ use crate::{
     },
     ui::{
         components::{
-            field, inspector,
+            field, inspector, pixel_probe,
             resource_icons::{icon_tab_title, resource_kind_icon},
             tiles::Pane,
         },
@@ -86,20 +86,27 @@ impl Pane for ViewportPane {
                 self.viewport_id,
                 format!(
                     "This texture view can't be displayed.\n\
-                     Only filterable RGBA formats ({}) are supported in viewports.",
+                     Only filterable RGBA formats ({}) are shown as they are. Others need a \
+                     Preview Remap, set in the texture view's inspector.",
                     previewable_formats_label()
                 ),
             );
             return egui_tiles::UiResponse::None;
         };
 
-        let events = crate::ui::components::viewport::ui(
+        let (response, events) = crate::ui::components::viewport::ui(
             ui,
             self.viewport_id,
             egui_id,
             viewport.requested_ui_size(),
         );
         state.event_queue.add_all(events);
+        pixel_probe::show(
+            response,
+            state.pixel_probe,
+            texture_view_id,
+            runtime_texture_view,
+        );
 
         egui_tiles::UiResponse::None
     }
@@ -1,9 +1,9 @@
 use crate::{
     file::file_storage::FileStorage,
     project::{
-        BindGroupId, CameraId, ComputePassId, DimensionId, ModelId, Project, RenderPassId,
-        RenderPipelineId, ResourceId, ResourceKind, SamplerId, ShaderId, TextureId, TextureViewId,
-        UniformId, ViewportId, paths::FilePath,
+        BindGroupId, CameraId, ComputePassId, CopyPassId, DimensionId, ModelId, Project,
+        RenderPassId, RenderPipelineId, ResourceId, ResourceKind, SamplerId, ShaderId, TextureId,
+        TextureViewId, UniformId, ViewportId, paths::FilePath,
     },
 };
 
@@ -33,6 +33,7 @@ pub enum RenameTarget {
     RenderPass(RenderPassId),
     RenderPipeline(RenderPipelineId),
     ComputePass(ComputePassId),
+    CopyPass(CopyPassId),
 }
 
 impl RenameTarget {
@@ -56,6 +57,7 @@ impl RenameTarget {
             RenameTarget::RenderPipeline(id) => project.label(*id),
             RenameTarget::RenderPass(id) => project.label(*id),
             RenameTarget::ComputePass(id) => project.label(*id),
+            RenameTarget::CopyPass(id) => project.label(*id),
             RenameTarget::UniformField(id, index) => project
                 .uniforms
                 .get(*id)
@@ -187,6 +189,11 @@ impl RenameTarget {
                     compute_pass.set_label(new_name);
                 }
             }
+            RenameTarget::CopyPass(copy_pass_id) => {
+                if let Ok(copy_pass) = project.copy_passes.get_mut(copy_pass_id) {
+                    copy_pass.set_label(new_name);
+                }
+            }
         }
     }
 }
@@ -207,6 +214,7 @@ impl From<ResourceId> for Option<RenameTarget> {
             ResourceId::RenderPipeline(id) => Some(RenameTarget::RenderPipeline(id)),
             ResourceId::RenderPass(id) => Some(RenameTarget::RenderPass(id)),
             ResourceId::ComputePass(id) => Some(RenameTarget::ComputePass(id)),
+            ResourceId::CopyPass(id) => Some(RenameTarget::CopyPass(id)),
             ResourceId::Presentation(_) => None,
         }
     }
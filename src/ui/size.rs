@@ -1,3 +1,5 @@
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::{JsonSchema, Schema, SchemaGenerator};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -56,3 +58,14 @@ impl<'de> Deserialize<'de> for Size2d {
         Ok(Self::new(width, height))
     }
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+impl JsonSchema for Size2d {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Size2d".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        <[u32; 2]>::json_schema(generator)
+    }
+}
@@ -1,17 +1,19 @@
-//! Shared helpers for deriving textures and texture views from existing
-//! resources or image paths.
+//! Shared helpers for deriving resources (textures, texture views, uniforms)
+//! from existing resources, image paths or shader reflection.
 
 use crate::{
+    error::{AppError, AppResult},
     project::{
-        Project, ProjectResource, TextureId, TextureViewId,
+        Project, ProjectResource, TextureId, TextureViewId, UniformId,
         paths::FilePath,
         resource::{
             model::TextureType,
             texture::{Texture, TextureSource},
             texture_view::TextureView,
+            uniform::Uniform,
         },
     },
-    utils::wgpu_utils::TextureFormat,
+    utils::{shader_reflection, wgpu_utils::TextureFormat},
 };
 
 /// Registers a new texture sourced from the image at `path`, deduplicating
@@ -48,6 +50,22 @@ pub fn derive_texture_view(project: &mut Project, texture_id: TextureId) -> Text
         .register(TextureView::new(label, Some(texture_id), None, None))
 }
 
+/// Registers a new uniform whose fields mirror the shader struct `struct_name`.
+/// Fails if the module has no such struct, or if one of its members has no
+/// matching field type.
+pub fn derive_uniform_from_struct(
+    project: &mut Project,
+    module: &naga::Module,
+    struct_name: &str,
+) -> AppResult<UniformId> {
+    let members = shader_reflection::reflect_struct(module, struct_name)
+        .ok_or_else(|| AppError::StructNotFound(struct_name.to_string()))?;
+    let fields = shader_reflection::uniform_fields_from_members(&[], &members)?;
+
+    let label = project.uniforms.next_label(struct_name);
+    Ok(project.uniforms.register(Uniform::new(label, fields)))
+}
+
 /// Derives a texture from a material image `path`, choosing a default format
 /// and label for the given `texture_type`.
 pub fn derive_texture_from_material_path(
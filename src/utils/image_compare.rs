@@ -0,0 +1,335 @@
+//! Compares an image against a reference, for catching visual regressions:
+//! per-pixel differences, PSNR, SSIM and a heat map of where they differ.
+//!
+//! Channels are compared as stored, so an 8-bit image compares its sRGB-encoded
+//! values and a float image its linear ones. Compare images of the same kind.
+
+use std::task::Poll;
+
+use crate::{
+    error::{AppError, AppResult},
+    file::file_storage::FileStorage,
+    project::{Project, RuntimeProject, TextureViewId, paths::FilePath},
+    utils::{
+        async_job::AsyncJob,
+        background_task,
+        texture_capture::{TextureReadback, texture_image},
+    },
+};
+
+/// Side of the windows SSIM is averaged over.
+const SSIM_WINDOW: u32 = 8;
+/// Stabilizes SSIM's luminance term, for a dynamic range of 1.
+const SSIM_C1: f64 = 0.01 * 0.01;
+/// Stabilizes SSIM's contrast and structure term, for a dynamic range of 1.
+const SSIM_C2: f64 = 0.03 * 0.03;
+
+/// How far apart an image and its reference may be and still match.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    /// Largest difference a pixel's channels may have for it to count as equal.
+    pub pixel: f32,
+    /// Fraction of the pixels that may differ.
+    pub fraction: f32,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            pixel: 2.0 / 255.0,
+            fraction: 0.0,
+        }
+    }
+}
+
+pub struct Comparison {
+    pub size: [u32; 2],
+    /// Largest channel difference of each pixel, row by row.
+    differences: Vec<f32>,
+    pub max_difference: f32,
+    pub mean_difference: f32,
+    /// Peak signal-to-noise ratio of the color channels in decibels, for a
+    /// peak of 1. Infinite when they are identical.
+    pub psnr: f64,
+    /// Mean structural similarity of the luma, over [`SSIM_WINDOW`]-sized
+    /// windows. 1 when it is identical.
+    pub ssim: f64,
+}
+
+impl Comparison {
+    pub fn new(image: &image::DynamicImage, reference: &image::DynamicImage) -> AppResult<Self> {
+        let (image, reference) = (image.to_rgba32f(), reference.to_rgba32f());
+        if image.dimensions() != reference.dimensions() {
+            let size = |(width, height): (u32, u32)| format!("{width}×{height}");
+            return Err(AppError::CompareSizeMismatch {
+                image: size(image.dimensions()),
+                reference: size(reference.dimensions()),
+            });
+        }
+
+        let differences: Vec<f32> = image
+            .pixels()
+            .zip(reference.pixels())
+            .map(|(a, b)| {
+                a.0.iter()
+                    .zip(b.0)
+                    .map(|(a, b)| (a - b).abs())
+                    .fold(0.0, f32::max)
+            })
+            .collect();
+        let max_difference = differences.iter().copied().fold(0.0, f32::max);
+        let mean_difference = differences.iter().sum::<f32>() / differences.len().max(1) as f32;
+
+        let squared_error: f64 = image
+            .pixels()
+            .zip(reference.pixels())
+            .flat_map(|(a, b)| (0..3).map(move |channel| (a.0[channel] - b.0[channel]) as f64))
+            .map(|error| error * error)
+            .sum();
+        let mse = squared_error / (3 * differences.len().max(1)) as f64;
+        let psnr = match mse {
+            0.0 => f64::INFINITY,
+            mse => -10.0 * mse.log10(),
+        };
+
+        Ok(Self {
+            size: [image.width(), image.height()],
+            differences,
+            max_difference,
+            mean_difference,
+            psnr,
+            ssim: ssim(&image, &reference),
+        })
+    }
+
+    /// Pixels whose difference is above `tolerance`.
+    pub fn differing_pixels(&self, tolerance: f32) -> usize {
+        self.differences
+            .iter()
+            .filter(|&&difference| difference > tolerance)
+            .count()
+    }
+
+    pub fn pixel_count(&self) -> usize {
+        self.differences.len()
+    }
+
+    pub fn matches(&self, tolerance: Tolerance) -> bool {
+        let differing = self.differing_pixels(tolerance.pixel);
+        differing as f32 <= tolerance.fraction * self.pixel_count() as f32
+    }
+
+    /// The differences as colors, from black where the images are equal,
+    /// through red and yellow, to white at the largest difference.
+    pub fn heat_map(&self) -> image::RgbaImage {
+        let [width, height] = self.size;
+        let scale = match self.max_difference {
+            0.0 => 0.0,
+            max => 1.0 / max,
+        };
+        image::RgbaImage::from_fn(width, height, |x, y| {
+            let difference = self.differences[(y * width + x) as usize];
+            let [r, g, b] = heat_color(difference * scale);
+            image::Rgba([r, g, b, 255])
+        })
+    }
+}
+
+fn heat_color(t: f32) -> [u8; 3] {
+    let channel = |start: f32| ((t * 3.0 - start).clamp(0.0, 1.0) * 255.0).round() as u8;
+    [channel(0.0), channel(1.0), channel(2.0)]
+}
+
+fn luma(pixel: &image::Rgba<f32>) -> f64 {
+    let [r, g, b, _] = pixel.0.map(|channel| channel.clamp(0.0, 1.0) as f64);
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+fn ssim(image: &image::Rgba32FImage, reference: &image::Rgba32FImage) -> f64 {
+    let (width, height) = image.dimensions();
+    let mut total = 0.0;
+    let mut windows = 0;
+    for top in (0..height).step_by(SSIM_WINDOW as usize) {
+        for left in (0..width).step_by(SSIM_WINDOW as usize) {
+            let texels: Vec<(f64, f64)> = (top..(top + SSIM_WINDOW).min(height))
+                .flat_map(|y| (left..(left + SSIM_WINDOW).min(width)).map(move |x| (x, y)))
+                .map(|(x, y)| {
+                    let a = luma(image.get_pixel(x, y));
+                    let b = luma(reference.get_pixel(x, y));
+                    (a, b)
+                })
+                .collect();
+
+            let count = texels.len() as f64;
+            let mean_a = texels.iter().map(|(a, _)| a).sum::<f64>() / count;
+            let mean_b = texels.iter().map(|(_, b)| b).sum::<f64>() / count;
+            let (mut variance_a, mut variance_b, mut covariance) = (0.0, 0.0, 0.0);
+            for (a, b) in &texels {
+                variance_a += (a - mean_a) * (a - mean_a);
+                variance_b += (b - mean_b) * (b - mean_b);
+                covariance += (a - mean_a) * (b - mean_b);
+            }
+            let (variance_a, variance_b, covariance) =
+                (variance_a / count, variance_b / count, covariance / count);
+
+            total += ((2.0 * mean_a * mean_b + SSIM_C1) * (2.0 * covariance + SSIM_C2))
+                / ((mean_a * mean_a + mean_b * mean_b + SSIM_C1)
+                    * (variance_a + variance_b + SSIM_C2));
+            windows += 1;
+        }
+    }
+    if windows == 0 {
+        return 1.0;
+    }
+    total / windows as f64
+}
+
+/// The comparison shown in the image compare tab: a texture view, as the
+/// last frame rendered it, against a reference image from the project.
+#[derive(Default)]
+pub struct ImageCompare {
+    pub texture_view: Option<TextureViewId>,
+    pub reference: Option<FilePath>,
+    pub tolerance: Tolerance,
+    requested: bool,
+    task: Option<AsyncJob<AppResult<Comparison>>>,
+    result: Option<AppResult<Comparison>>,
+    /// The result's heat map, uploaded the first time it's shown.
+    heat_map: Option<egui::TextureHandle>,
+}
+
+impl ImageCompare {
+    /// Compares the texture view against the reference after the next frame.
+    pub fn request(&mut self) {
+        self.requested = true;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.requested || self.task.is_some()
+    }
+
+    pub fn result(&self) -> Option<&AppResult<Comparison>> {
+        self.result.as_ref()
+    }
+
+    /// The heat map of a successful comparison, as an egui texture.
+    pub fn heat_map_texture(&mut self, ctx: &egui::Context) -> Option<&egui::TextureHandle> {
+        let Some(Ok(comparison)) = &self.result else {
+            return None;
+        };
+        let texture = self.heat_map.get_or_insert_with(|| {
+            let heat_map = comparison.heat_map();
+            let [width, height] = comparison.size.map(|side| side as usize);
+            let image =
+                egui::ColorImage::from_rgba_unmultiplied([width, height], heat_map.as_raw());
+            ctx.load_texture(
+                "image-compare-heat-map",
+                image,
+                egui::TextureOptions::NEAREST,
+            )
+        });
+        Some(texture)
+    }
+
+    fn finish(&mut self, result: AppResult<Comparison>) {
+        self.result = Some(result);
+        self.heat_map = None;
+    }
+
+    /// Starts a requested comparison and collects the finished one. Call it
+    /// after the frame is submitted, so the texture view holds its results.
+    pub fn tick(
+        &mut self,
+        project: &Project,
+        runtime_project: &RuntimeProject,
+        file_storage: &FileStorage,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) {
+        if let Some(task) = &mut self.task
+            && let Poll::Ready(result) = task.try_resolve()
+        {
+            self.finish(result);
+            self.task = None;
+        }
+
+        if !std::mem::take(&mut self.requested) {
+            return;
+        }
+        match self.start(project, runtime_project, file_storage, device, queue) {
+            Ok(task) => self.task = Some(task),
+            Err(error) => self.finish(Err(error)),
+        }
+    }
+
+    fn start(
+        &self,
+        project: &Project,
+        runtime_project: &RuntimeProject,
+        file_storage: &FileStorage,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> AppResult<AsyncJob<AppResult<Comparison>>> {
+        let texture_view_id = self
+            .texture_view
+            .ok_or(AppError::uninit_field("Texture View"))?;
+        let path = self
+            .reference
+            .as_ref()
+            .ok_or(AppError::uninit_field("Reference"))?;
+        project.texture_views.get(texture_view_id)?;
+        let texture_view = runtime_project
+            .texture_views
+            .get_init(texture_view_id)?
+            .ok_or(AppError::CaptureError(
+                "the texture view is not ready yet".into(),
+            ))?;
+
+        let readback = TextureReadback::copy(device, queue, texture_view.inner().texture())?;
+        let reference = file_storage.read(path);
+        let device = device.clone();
+        Ok(background_task::spawn_future("image-compare", async move {
+            let [width, height] = readback.size();
+            let format = readback.format();
+            let image = texture_image(readback.read(&device).await?, width, height, format)?;
+            let reference = image::load_from_memory(&reference.await?)?;
+            Comparison::new(&image, &reference)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comparisons_measure_how_images_differ() {
+        let reference = image::RgbaImage::from_pixel(16, 16, image::Rgba([100, 150, 200, 255]));
+        let mut image = reference.clone();
+        image.put_pixel(3, 4, image::Rgba([110, 150, 200, 255]));
+        let (image, reference) = (image.into(), reference.into());
+
+        let identical = Comparison::new(&reference, &reference).unwrap();
+        assert_eq!(identical.psnr, f64::INFINITY);
+        assert!((identical.ssim - 1.0).abs() < 1e-9);
+        assert!(identical.matches(Tolerance::default()));
+
+        let comparison = Comparison::new(&image, &reference).unwrap();
+        assert!((comparison.max_difference - 10.0 / 255.0).abs() < 1e-6);
+        assert_eq!(comparison.differing_pixels(2.0 / 255.0), 1);
+        assert!(comparison.psnr.is_finite() && comparison.ssim < 1.0);
+        assert!(!comparison.matches(Tolerance::default()));
+        let lenient = Tolerance {
+            pixel: 2.0 / 255.0,
+            fraction: 0.01,
+        };
+        assert!(comparison.matches(lenient));
+        assert_eq!(
+            comparison.heat_map().get_pixel(3, 4).0,
+            [255, 255, 255, 255]
+        );
+
+        let smaller = image::RgbaImage::new(8, 8).into();
+        assert!(Comparison::new(&smaller, &reference).is_err());
+    }
+}
@@ -9,12 +9,19 @@ pub mod event_queue;
 pub mod fps;
 pub mod github;
 pub mod icon;
+pub mod image_compare;
 pub mod key;
 pub mod obj;
+pub mod pixel_probe;
 pub mod raw_scroll;
+pub mod readback;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod recording;
 pub mod resizable_buffer;
 pub mod shader_preview;
+pub mod shader_reflection;
 pub mod texture_capture;
+pub mod texture_stats;
 pub mod validate_bind_group_layouts;
 pub mod wgpu_error_scope;
 pub mod wgpu_utils;
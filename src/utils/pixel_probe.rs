@@ -0,0 +1,154 @@
+//! Reads back the texels under the cursor of a viewport or texture preview,
+//! for the pixel probe.
+//!
+//! Each read copies a small square around the cursor, which also feeds the
+//! magnifier. Reads go through the same async mapping as
+//! [`readback`](crate::utils::readback), one at a time and at most once per
+//! [`PROBE_INTERVAL`], so hovering never stalls the frame, natively or on
+//! WebGPU.
+
+use std::{task::Poll, time::Duration};
+
+use crate::{
+    error::AppResult,
+    project::{RuntimeProject, TextureViewId},
+    utils::{
+        async_job::AsyncJob,
+        readback::{self, Readout, TexelRegion},
+    },
+};
+
+/// The shortest time between two reads while the cursor rests on a texture.
+pub const PROBE_INTERVAL: Duration = Duration::from_millis(100);
+/// Texels across the magnifier. Odd, so the probed texel sits in the middle.
+pub const MAGNIFIER_TEXELS: u32 = 9;
+
+/// The latest texels read under the cursor, and the read in flight.
+#[derive(Default)]
+pub struct PixelProbe {
+    /// The texel hovered since the last tick.
+    hovered: Option<(TextureViewId, [u32; 2])>,
+    readout: Option<(TextureViewId, AppResult<Readout>)>,
+    task: Option<(TextureViewId, AsyncJob<AppResult<Readout>>)>,
+    last_read: Option<instant::Instant>,
+}
+
+impl PixelProbe {
+    /// Probes `texel` of the texture view on the next tick. Call it every
+    /// frame the probe is shown, or reading stops.
+    pub fn hover(&mut self, texture_view_id: TextureViewId, texel: [u32; 2]) {
+        self.hovered = Some((texture_view_id, texel));
+    }
+
+    /// The latest read of the texture view, or why it failed.
+    pub fn readout(&self, texture_view_id: TextureViewId) -> Option<&AppResult<Readout>> {
+        match &self.readout {
+            Some((id, readout)) if *id == texture_view_id => Some(readout),
+            _ => None,
+        }
+    }
+
+    /// Starts a read if one is due, and collects the finished one. Call it
+    /// after the frame is submitted, so reads see its results.
+    pub fn tick(
+        &mut self,
+        runtime_project: &RuntimeProject,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) {
+        if let Some((id, task)) = &mut self.task
+            && let Poll::Ready(result) = task.try_resolve()
+        {
+            self.readout = Some((*id, result));
+            self.task = None;
+        }
+
+        let Some((texture_view_id, texel)) = self.hovered.take() else {
+            return;
+        };
+        let now = instant::Instant::now();
+        if !self.read_due(now) {
+            return;
+        }
+
+        match start_probe(runtime_project, device, queue, texture_view_id, texel) {
+            Ok(Some(task)) => self.task = Some((texture_view_id, task)),
+            Ok(None) => return, // still being created: try again next frame
+            Err(error) => self.readout = Some((texture_view_id, Err(error))),
+        }
+        self.last_read = Some(now);
+    }
+
+    /// Whether a read can start at `now`: none is in flight, and the last one
+    /// started at least [`PROBE_INTERVAL`] ago.
+    fn read_due(&self, now: instant::Instant) -> bool {
+        self.task.is_none()
+            && self
+                .last_read
+                .is_none_or(|last_read| now.duration_since(last_read) >= PROBE_INTERVAL)
+    }
+}
+
+/// Copies the magnifier's square around `texel` of the view's texture, or
+/// returns `None` if the view isn't created yet.
+fn start_probe(
+    runtime_project: &RuntimeProject,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture_view_id: TextureViewId,
+    texel: [u32; 2],
+) -> AppResult<Option<AsyncJob<AppResult<Readout>>>> {
+    let Some(view) = runtime_project.texture_views.get_init(texture_view_id)? else {
+        return Ok(None);
+    };
+    let texture = view.inner().texture();
+    let extent = [texture.width(), texture.height()];
+    let region = TexelRegion::around(extent, texel, MAGNIFIER_TEXELS);
+    readback::read_texels(device, queue, texture, region).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_are_throttled_and_one_at_a_time() {
+        let start = instant::Instant::now();
+        let mut probe = PixelProbe::default();
+        assert!(probe.read_due(start));
+
+        probe.last_read = Some(start);
+        assert!(!probe.read_due(start));
+        assert!(!probe.read_due(start + PROBE_INTERVAL / 2));
+        assert!(probe.read_due(start + PROBE_INTERVAL));
+
+        let pending = AsyncJob::new(std::future::pending());
+        probe.task = Some((TextureViewId::default(), pending));
+        assert!(!probe.read_due(start + PROBE_INTERVAL * 2));
+    }
+
+    #[test]
+    fn magnifier_region_stays_inside_the_texture() {
+        let region = |extent, texel| TexelRegion::around(extent, texel, MAGNIFIER_TEXELS);
+        let square = |x, y, size| TexelRegion {
+            x,
+            y,
+            width: size,
+            height: size,
+        };
+
+        assert_eq!(region([64, 64], [32, 32]), square(28, 28, 9));
+        assert_eq!(region([64, 64], [0, 2]), square(0, 0, 9));
+        assert_eq!(region([64, 64], [63, 60]), square(55, 55, 9));
+        assert_eq!(
+            region([64, 4], [10, 3]),
+            TexelRegion {
+                x: 6,
+                y: 0,
+                width: 9,
+                height: 4,
+            }
+        );
+        assert_eq!(region([1, 1], [0, 0]), square(0, 0, 1));
+    }
+}
@@ -0,0 +1,1059 @@
+//! Reads textures and uniform buffers back from the GPU and decodes them into
+//! typed tables, for the readback tab.
+//!
+//! Like [`texture_capture`](crate::utils::texture_capture), the copy and submit
+//! happen on the render thread and the mapping runs as an [`AsyncJob`], so a
+//! readback never stalls the frame. Texture readbacks only copy the page of
+//! texels on screen; CSV exports read the whole resource.
+
+use std::{borrow::Cow, collections::HashMap, task::Poll};
+
+use crate::{
+    error::{AppError, AppResult, RequiredFieldExt},
+    project::{
+        Project, ResourceId, RuntimeProject, ShaderId, TextureId, UniformId,
+        resource::{
+            shader::Shader,
+            texture_view::BufferSide,
+            uniform::{UniformField, UniformFieldDataKind},
+        },
+        storage::RuntimeStorage,
+    },
+    utils::{
+        async_job::AsyncJob, background_task, texture_capture::read_buffer,
+        wgpu_utils::create_command_encoder,
+    },
+};
+
+/// Texels per page of a texture's grid, in columns and rows.
+pub const TEXEL_PAGE_SIZE: [u32; 2] = [8, 16];
+/// Elements per page of a buffer's table.
+pub const ELEMENT_PAGE_SIZE: usize = 64;
+/// The most values one element can be split into.
+pub const MAX_COLUMNS: usize = 256;
+
+/// A resource whose contents can be read back. Uniform buffers are the only
+/// buffers a project owns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReadbackTarget {
+    Texture(TextureId),
+    Uniform(UniformId),
+}
+
+impl From<ReadbackTarget> for ResourceId {
+    fn from(target: ReadbackTarget) -> Self {
+        match target {
+            ReadbackTarget::Texture(id) => id.into(),
+            ReadbackTarget::Uniform(id) => id.into(),
+        }
+    }
+}
+
+/// How the bytes of a buffer are split into elements.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum BufferLayout {
+    /// The uniform's own fields, packed the way they're uploaded.
+    #[default]
+    UniformFields,
+    /// A struct declared in a shader.
+    ShaderStruct {
+        shader_id: Option<ShaderId>,
+        name: Option<String>,
+    },
+    /// Any WGSL type, e.g. `vec4<f32>` or `array<u32, 4>`.
+    WgslType(String),
+}
+
+impl BufferLayout {
+    pub fn resolve(
+        &self,
+        fields: &[UniformField],
+        runtime_shaders: &RuntimeStorage<Shader>,
+    ) -> AppResult<ElementLayout> {
+        match self {
+            BufferLayout::UniformFields => Ok(ElementLayout::uniform(fields)),
+            BufferLayout::ShaderStruct { shader_id, name } => {
+                let shader_id = shader_id.ok_or_uninit_field("Shader")?;
+                let shader = runtime_shaders
+                    .get_init(shader_id)?
+                    .ok_or_uninit_field("Shader")?;
+                let name = name.as_deref().ok_or_uninit_field("Struct")?;
+                ElementLayout::shader_struct(shader.module(), name)
+            }
+            BufferLayout::WgslType(ty) => ElementLayout::wgsl_type(ty),
+        }
+    }
+}
+
+/// The type of one value in read-back data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scalar {
+    F32,
+    F16,
+    U32,
+    I32,
+    U16,
+    I16,
+    U8,
+    I8,
+    Unorm16,
+    Snorm16,
+    Unorm8,
+    Snorm8,
+}
+
+impl Scalar {
+    pub fn size(self) -> usize {
+        match self {
+            Scalar::F32 | Scalar::U32 | Scalar::I32 => 4,
+            Scalar::F16 | Scalar::U16 | Scalar::I16 | Scalar::Unorm16 | Scalar::Snorm16 => 2,
+            Scalar::U8 | Scalar::I8 | Scalar::Unorm8 | Scalar::Snorm8 => 1,
+        }
+    }
+
+    fn from_naga(scalar: naga::Scalar) -> Option<Self> {
+        match (scalar.kind, scalar.width) {
+            (naga::ScalarKind::Float, 4) => Some(Scalar::F32),
+            (naga::ScalarKind::Float, 2) => Some(Scalar::F16),
+            (naga::ScalarKind::Uint, 4) => Some(Scalar::U32),
+            (naga::ScalarKind::Sint, 4) => Some(Scalar::I32),
+            _ => None,
+        }
+    }
+
+    /// Decodes little-endian `bytes` the way a shader would see them, so
+    /// normalized integers come out as floats.
+    fn format(self, bytes: &[u8]) -> String {
+        let b2 = || [bytes[0], bytes[1]];
+        let b4 = || [bytes[0], bytes[1], bytes[2], bytes[3]];
+        match self {
+            Scalar::F32 => f32::from_le_bytes(b4()).to_string(),
+            Scalar::F16 => half::f16::from_le_bytes(b2()).to_string(),
+            Scalar::U32 => u32::from_le_bytes(b4()).to_string(),
+            Scalar::I32 => i32::from_le_bytes(b4()).to_string(),
+            Scalar::U16 => u16::from_le_bytes(b2()).to_string(),
+            Scalar::I16 => i16::from_le_bytes(b2()).to_string(),
+            Scalar::U8 => bytes[0].to_string(),
+            Scalar::I8 => (bytes[0] as i8).to_string(),
+            Scalar::Unorm16 => (u16::from_le_bytes(b2()) as f32 / 65535.0).to_string(),
+            Scalar::Snorm16 => (i16::from_le_bytes(b2()) as f32 / 32767.0)
+                .max(-1.0)
+                .to_string(),
+            Scalar::Unorm8 => (bytes[0] as f32 / 255.0).to_string(),
+            Scalar::Snorm8 => ((bytes[0] as i8) as f32 / 127.0).max(-1.0).to_string(),
+        }
+    }
+
+    /// Decodes `bytes` like [`Scalar::format`], as a number to draw with.
+    /// Integers above 2^24 lose precision.
+    fn to_f32(self, bytes: &[u8]) -> f32 {
+        let b2 = || [bytes[0], bytes[1]];
+        let b4 = || [bytes[0], bytes[1], bytes[2], bytes[3]];
+        match self {
+            Scalar::F32 => f32::from_le_bytes(b4()),
+            Scalar::F16 => half::f16::from_le_bytes(b2()).to_f32(),
+            Scalar::U32 => u32::from_le_bytes(b4()) as f32,
+            Scalar::I32 => i32::from_le_bytes(b4()) as f32,
+            Scalar::U16 => u16::from_le_bytes(b2()) as f32,
+            Scalar::I16 => i16::from_le_bytes(b2()) as f32,
+            Scalar::U8 => bytes[0] as f32,
+            Scalar::I8 => (bytes[0] as i8) as f32,
+            Scalar::Unorm16 => u16::from_le_bytes(b2()) as f32 / 65535.0,
+            Scalar::Snorm16 => (i16::from_le_bytes(b2()) as f32 / 32767.0).max(-1.0),
+            Scalar::Unorm8 => bytes[0] as f32 / 255.0,
+            Scalar::Snorm8 => ((bytes[0] as i8) as f32 / 127.0).max(-1.0),
+        }
+    }
+}
+
+/// One value of an element: its name and where it sits in the element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub offset: usize,
+    pub scalar: Scalar,
+}
+
+/// How one element (a texel, or a buffer entry) is laid out in memory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementLayout {
+    pub columns: Vec<Column>,
+    /// Bytes from one element to the next.
+    pub stride: usize,
+}
+
+const COMPONENTS: [&str; 4] = ["x", "y", "z", "w"];
+
+impl ElementLayout {
+    /// The channels of a texel in `format`, in memory order.
+    pub fn texel(format: wgpu::TextureFormat) -> AppResult<Self> {
+        use wgpu::TextureFormat as F;
+
+        const R: &[&str] = &["r"];
+        const RG: &[&str] = &["r", "g"];
+        const RGBA: &[&str] = &["r", "g", "b", "a"];
+
+        let (scalar, channels) = match format {
+            F::R8Unorm => (Scalar::Unorm8, R),
+            F::Rg8Unorm => (Scalar::Unorm8, RG),
+            F::Rgba8Unorm | F::Rgba8UnormSrgb => (Scalar::Unorm8, RGBA),
+            F::Bgra8Unorm | F::Bgra8UnormSrgb => (Scalar::Unorm8, &["b", "g", "r", "a"][..]),
+            F::R8Snorm => (Scalar::Snorm8, R),
+            F::Rg8Snorm => (Scalar::Snorm8, RG),
+            F::Rgba8Snorm => (Scalar::Snorm8, RGBA),
+            F::R8Uint => (Scalar::U8, R),
+            F::Rg8Uint => (Scalar::U8, RG),
+            F::Rgba8Uint => (Scalar::U8, RGBA),
+            F::R8Sint => (Scalar::I8, R),
+            F::Rg8Sint => (Scalar::I8, RG),
+            F::Rgba8Sint => (Scalar::I8, RGBA),
+            F::R16Unorm => (Scalar::Unorm16, R),
+            F::Rg16Unorm => (Scalar::Unorm16, RG),
+            F::Rgba16Unorm => (Scalar::Unorm16, RGBA),
+            F::R16Snorm => (Scalar::Snorm16, R),
+            F::Rg16Snorm => (Scalar::Snorm16, RG),
+            F::Rgba16Snorm => (Scalar::Snorm16, RGBA),
+            F::R16Uint => (Scalar::U16, R),
+            F::Rg16Uint => (Scalar::U16, RG),
+            F::Rgba16Uint => (Scalar::U16, RGBA),
+            F::R16Sint => (Scalar::I16, R),
+            F::Rg16Sint => (Scalar::I16, RG),
+            F::Rgba16Sint => (Scalar::I16, RGBA),
+            F::R16Float => (Scalar::F16, R),
+            F::Rg16Float => (Scalar::F16, RG),
+            F::Rgba16Float => (Scalar::F16, RGBA),
+            F::R32Uint => (Scalar::U32, R),
+            F::Rg32Uint => (Scalar::U32, RG),
+            F::Rgba32Uint => (Scalar::U32, RGBA),
+            F::R32Sint => (Scalar::I32, R),
+            F::Rg32Sint => (Scalar::I32, RG),
+            F::Rgba32Sint => (Scalar::I32, RGBA),
+            F::R32Float => (Scalar::F32, R),
+            F::Rg32Float => (Scalar::F32, RG),
+            F::Rgba32Float => (Scalar::F32, RGBA),
+            F::Stencil8 => (Scalar::U8, &["stencil"][..]),
+            F::Depth16Unorm => (Scalar::Unorm16, &["depth"][..]),
+            F::Depth32Float | F::Depth32FloatStencil8 => (Scalar::F32, &["depth"][..]),
+            other => {
+                return Err(AppError::ReadbackFormat {
+                    format: format!("{other:?}"),
+                });
+            }
+        };
+
+        let columns = channels
+            .iter()
+            .enumerate()
+            .map(|(index, name)| Column {
+                name: name.to_string(),
+                offset: index * scalar.size(),
+                scalar,
+            })
+            .collect();
+        Ok(Self {
+            columns,
+            stride: channels.len() * scalar.size(),
+        })
+    }
+
+    /// The fields of a uniform, packed the way they're uploaded.
+    pub fn uniform(fields: &[UniformField]) -> Self {
+        let mut columns = vec![];
+        let mut offset: usize = 0;
+        let mut struct_align = 1;
+        for field in fields {
+            let kind = field.kind();
+            let (align, size) = kind.layout();
+            struct_align = struct_align.max(align);
+            offset = offset.next_multiple_of(align);
+            push_uniform_field(&mut columns, field.label(), offset, kind);
+            offset += size;
+        }
+
+        Self {
+            columns,
+            stride: offset.next_multiple_of(struct_align),
+        }
+    }
+
+    /// The members of the struct named `name` in `module`.
+    pub fn shader_struct(module: &naga::Module, name: &str) -> AppResult<Self> {
+        let (ty, span) = find_struct(module, name).ok_or_uninit_field("Struct")?;
+        Self::flattened(module, ty, span)
+    }
+
+    /// A WGSL type, spelled the way a shader would declare it.
+    pub fn wgsl_type(ty: &str) -> AppResult<Self> {
+        let source = format!("struct ReadbackElement {{ value: {ty} }}");
+        let module = naga::front::wgsl::parse_str(&source)
+            .map_err(|err| AppError::ShaderParseError(err.emit_to_string(&source)))?;
+        let (element, span) = find_struct(&module, "ReadbackElement")
+            .ok_or_else(|| AppError::ReadbackType { ty: ty.to_string() })?;
+        let naga::TypeInner::Struct { members, .. } = &module.types[element].inner else {
+            unreachable!("find_struct only returns structs");
+        };
+        Self::flattened(&module, members[0].ty, span)
+    }
+
+    fn flattened(
+        module: &naga::Module,
+        ty: naga::Handle<naga::Type>,
+        stride: u32,
+    ) -> AppResult<Self> {
+        let mut columns = vec![];
+        flatten(module, ty, "", 0, &mut columns)?;
+        match columns.len() <= MAX_COLUMNS {
+            true => Ok(Self {
+                columns,
+                stride: stride as usize,
+            }),
+            false => Err(AppError::ReadbackColumnLimit { max: MAX_COLUMNS }),
+        }
+    }
+}
+
+fn find_struct(module: &naga::Module, name: &str) -> Option<(naga::Handle<naga::Type>, u32)> {
+    module.types.iter().find_map(|(handle, ty)| match ty.inner {
+        naga::TypeInner::Struct { span, .. } if ty.name.as_deref() == Some(name) => {
+            Some((handle, span))
+        }
+        _ => None,
+    })
+}
+
+/// Appends a column per scalar in `ty`, named after the path to it.
+fn flatten(
+    module: &naga::Module,
+    ty: naga::Handle<naga::Type>,
+    name: &str,
+    offset: usize,
+    columns: &mut Vec<Column>,
+) -> AppResult<()> {
+    use naga::TypeInner;
+
+    if columns.len() > MAX_COLUMNS {
+        return Err(AppError::ReadbackColumnLimit { max: MAX_COLUMNS });
+    }
+
+    let unsupported = || AppError::ReadbackType {
+        ty: crate::utils::shader_reflection::type_label(module, ty),
+    };
+    match module.types[ty].inner {
+        TypeInner::Scalar(scalar) | TypeInner::Atomic(scalar) => {
+            let scalar = Scalar::from_naga(scalar).ok_or_else(unsupported)?;
+            push_vector(columns, name, offset, scalar, 1);
+        }
+        TypeInner::Vector { size, scalar } => {
+            let scalar = Scalar::from_naga(scalar).ok_or_else(unsupported)?;
+            push_vector(columns, name, offset, scalar, size as usize);
+        }
+        TypeInner::Matrix {
+            columns: matrix_columns,
+            rows,
+            scalar,
+        } => {
+            let scalar = Scalar::from_naga(scalar).ok_or_else(unsupported)?;
+            push_matrix(
+                columns,
+                name,
+                offset,
+                scalar,
+                matrix_columns as usize,
+                rows as usize,
+            );
+        }
+        TypeInner::Array {
+            base,
+            size: naga::ArraySize::Constant(count),
+            stride,
+        } => {
+            for index in 0..count.get() as usize {
+                let element_offset = offset + index * stride as usize;
+                flatten(
+                    module,
+                    base,
+                    &format!("{name}[{index}]"),
+                    element_offset,
+                    columns,
+                )?;
+            }
+        }
+        TypeInner::Struct { ref members, .. } => {
+            for (index, member) in members.iter().enumerate() {
+                let member_name = match &member.name {
+                    Some(member_name) => join(name, member_name),
+                    None => join(name, &format!("member{index}")),
+                };
+                let member_offset = offset + member.offset as usize;
+                flatten(module, member.ty, &member_name, member_offset, columns)?;
+            }
+        }
+        _ => return Err(unsupported()),
+    }
+    Ok(())
+}
+
+fn push_uniform_field(
+    columns: &mut Vec<Column>,
+    name: &str,
+    offset: usize,
+    kind: UniformFieldDataKind,
+) {
+    use UniformFieldDataKind as K;
+
+    match kind {
+        K::UInt32 => push_vector(columns, name, offset, Scalar::U32, 1),
+        K::Float => push_vector(columns, name, offset, Scalar::F32, 1),
+        K::Vec2u => push_vector(columns, name, offset, Scalar::U32, 2),
+        K::Vec3u => push_vector(columns, name, offset, Scalar::U32, 3),
+        K::Vec4u => push_vector(columns, name, offset, Scalar::U32, 4),
+        K::Vec2f => push_vector(columns, name, offset, Scalar::F32, 2),
+        K::Vec3f | K::Rgb => push_vector(columns, name, offset, Scalar::F32, 3),
+        K::Vec4f | K::Rgba => push_vector(columns, name, offset, Scalar::F32, 4),
+        K::Mat4x4f => push_matrix(columns, name, offset, Scalar::F32, 4, 4),
+    }
+}
+
+/// Pushes the components of a vector, or a single scalar when `components`
+/// is 1.
+fn push_vector(
+    columns: &mut Vec<Column>,
+    name: &str,
+    offset: usize,
+    scalar: Scalar,
+    components: usize,
+) {
+    for (index, component) in COMPONENTS.iter().enumerate().take(components) {
+        let name = match (components, name.is_empty()) {
+            (1, true) => "value".to_string(),
+            (1, false) => name.to_string(),
+            _ => join(name, component),
+        };
+        columns.push(Column {
+            name,
+            offset: offset + index * scalar.size(),
+            scalar,
+        });
+    }
+}
+
+/// Pushes a matrix column by column. Each column is aligned like a vector of
+/// `rows` components.
+fn push_matrix(
+    columns: &mut Vec<Column>,
+    name: &str,
+    offset: usize,
+    scalar: Scalar,
+    matrix_columns: usize,
+    rows: usize,
+) {
+    let column_stride = rows.next_power_of_two() * scalar.size();
+    for column in 0..matrix_columns {
+        let column_offset = offset + column * column_stride;
+        push_vector(
+            columns,
+            &format!("{name}[{column}]"),
+            column_offset,
+            scalar,
+            rows,
+        );
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    match path.is_empty() {
+        true => name.to_string(),
+        false => format!("{path}.{name}"),
+    }
+}
+
+/// A rectangle of texels, in texture coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TexelRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl TexelRegion {
+    fn whole(texture: &wgpu::Texture) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width: texture.width(),
+            height: texture.height(),
+        }
+    }
+
+    /// The texels of `page`, in page columns and rows, clipped to the texture.
+    fn page(texture: &wgpu::Texture, page: [u32; 2]) -> Self {
+        let [columns, rows] = page_count(texture.width(), texture.height());
+        let x = page[0].min(columns - 1) * TEXEL_PAGE_SIZE[0];
+        let y = page[1].min(rows - 1) * TEXEL_PAGE_SIZE[1];
+        Self {
+            x,
+            y,
+            width: TEXEL_PAGE_SIZE[0].min(texture.width() - x),
+            height: TEXEL_PAGE_SIZE[1].min(texture.height() - y),
+        }
+    }
+
+    /// A `size` × `size` square centered on `texel`, shifted to stay inside
+    /// a texture of `extent` texels.
+    pub fn around(extent: [u32; 2], texel: [u32; 2], size: u32) -> Self {
+        let clamp = |center: u32, extent: u32| {
+            let start = center
+                .saturating_sub(size / 2)
+                .min(extent.saturating_sub(size));
+            (start, size.min(extent))
+        };
+        let (x, width) = clamp(texel[0], extent[0]);
+        let (y, height) = clamp(texel[1], extent[1]);
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// Texel pages of a `width` × `height` texture, in columns and rows.
+pub fn page_count(width: u32, height: u32) -> [u32; 2] {
+    [
+        width.div_ceil(TEXEL_PAGE_SIZE[0]).max(1),
+        height.div_ceil(TEXEL_PAGE_SIZE[1]).max(1),
+    ]
+}
+
+/// Data read back from the GPU, with the layout to decode it.
+#[derive(Debug)]
+pub struct Readout {
+    layout: ElementLayout,
+    bytes: Vec<u8>,
+    /// The texels the data covers, row by row. `None` for buffers.
+    region: Option<TexelRegion>,
+}
+
+impl Readout {
+    pub fn layout(&self) -> &ElementLayout {
+        &self.layout
+    }
+
+    pub fn region(&self) -> Option<TexelRegion> {
+        self.region
+    }
+
+    /// How many whole elements the data holds.
+    pub fn len(&self) -> usize {
+        self.bytes
+            .len()
+            .checked_div(self.layout.stride)
+            .unwrap_or(0)
+    }
+
+    pub fn byte_len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// The values of element `index`, one per column.
+    pub fn values(&self, index: usize) -> Option<Vec<String>> {
+        self.decode(index, Scalar::format, "—".to_string())
+    }
+
+    /// The values of the texel at `x`, `y`, if the data covers it.
+    pub fn texel(&self, x: u32, y: u32) -> Option<Vec<String>> {
+        self.values(self.texel_index(x, y)?)
+    }
+
+    /// Like [`Readout::texel`], but as numbers to draw the texel with.
+    pub fn texel_numbers(&self, x: u32, y: u32) -> Option<Vec<f32>> {
+        self.decode(self.texel_index(x, y)?, Scalar::to_f32, 0.0)
+    }
+
+    fn texel_index(&self, x: u32, y: u32) -> Option<usize> {
+        let region = self.region?;
+        let (column, row) = (x.checked_sub(region.x)?, y.checked_sub(region.y)?);
+        if column >= region.width || row >= region.height {
+            return None;
+        }
+        Some((row * region.width + column) as usize)
+    }
+
+    /// Decodes each column of element `index`, or `missing` for columns that
+    /// reach past its end.
+    fn decode<T: Clone>(
+        &self,
+        index: usize,
+        decode: impl Fn(Scalar, &[u8]) -> T,
+        missing: T,
+    ) -> Option<Vec<T>> {
+        let start = index * self.layout.stride;
+        let element = self.bytes.get(start..start + self.layout.stride)?;
+        let values = self.layout.columns.iter().map(|column| {
+            match element.get(column.offset..column.offset + column.scalar.size()) {
+                Some(bytes) => decode(column.scalar, bytes),
+                None => missing.clone(),
+            }
+        });
+        Some(values.collect())
+    }
+
+    /// One line per element, led by its index, or its coordinates for texels.
+    pub fn to_csv(&self) -> String {
+        let names = self
+            .layout
+            .columns
+            .iter()
+            .map(|column| csv_field(&column.name));
+        let header: Vec<Cow<str>> = match self.region {
+            Some(_) => ["x", "y"].map(Cow::from).into_iter().chain(names).collect(),
+            None => [Cow::from("index")].into_iter().chain(names).collect(),
+        };
+
+        let mut csv = header.join(",");
+        csv.push('\n');
+        for index in 0..self.len() {
+            let key = match self.region {
+                Some(region) => {
+                    let index = index as u32;
+                    let x = region.x + index % region.width;
+                    let y = region.y + index / region.width;
+                    format!("{x},{y}")
+                }
+                None => index.to_string(),
+            };
+            let values = self.values(index).unwrap_or_default();
+            csv.push_str(&key);
+            for value in values {
+                csv.push(',');
+                csv.push_str(&value);
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Quotes a CSV field that holds a separator, a quote or a line break, and
+/// doubles the quotes inside it, as RFC 4180 asks.
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\r', '\n']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// The settings and latest data of one readback tab.
+#[derive(Default)]
+pub struct ReadbackState {
+    /// Reads again every this many frames, or only on request.
+    pub refresh_every: Option<u32>,
+    /// The page on screen, in columns and rows. Buffers only page rows.
+    pub page: [u32; 2],
+    /// Which half of a double-buffered texture to read.
+    pub side: BufferSide,
+    /// How a buffer's bytes are split into elements.
+    pub layout: BufferLayout,
+    readout: Option<AppResult<Readout>>,
+    task: Option<AsyncJob<AppResult<Readout>>>,
+    frames_since_read: u32,
+    requested: bool,
+    export_requested: bool,
+    /// Whether the tab was drawn since the last tick. Hidden tabs don't read.
+    viewed: bool,
+}
+
+impl ReadbackState {
+    /// The latest readout, or why it failed.
+    pub fn readout(&self) -> Option<&AppResult<Readout>> {
+        self.readout.as_ref()
+    }
+
+    pub fn is_reading(&self) -> bool {
+        self.task.is_some()
+    }
+
+    /// Reads again on the next frame.
+    pub fn request(&mut self) {
+        self.requested = true;
+    }
+
+    /// Reads the whole resource on the next frame and saves it as CSV.
+    pub fn request_export(&mut self) {
+        self.export_requested = true;
+    }
+
+    /// Marks the tab as on screen, so it keeps reading.
+    pub fn mark_viewed(&mut self) {
+        self.viewed = true;
+    }
+}
+
+/// Owns every readback tab's state and in-flight CSV exports.
+#[derive(Default)]
+pub struct Readbacks {
+    states: HashMap<ReadbackTarget, ReadbackState>,
+    exports: Vec<AsyncJob<AppResult<()>>>,
+}
+
+impl Readbacks {
+    pub fn state_mut(&mut self, target: ReadbackTarget) -> &mut ReadbackState {
+        self.states.entry(target).or_default()
+    }
+
+    /// Starts the reads and exports that are due, and collects finished ones.
+    /// Call it after the frame is submitted, so reads see its results.
+    pub fn tick(
+        &mut self,
+        project: &Project,
+        runtime_project: &RuntimeProject,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        toasts: &mut egui_notify::Toasts,
+    ) {
+        self.states
+            .retain(|target, _| project.label(*target).is_some());
+
+        for (target, state) in &mut self.states {
+            if let Some(task) = &mut state.task
+                && let Poll::Ready(result) = task.try_resolve()
+            {
+                state.readout = Some(result);
+                state.task = None;
+            }
+
+            if std::mem::take(&mut state.export_requested) {
+                match start_export(project, runtime_project, device, queue, *target, state) {
+                    Ok(job) => self.exports.push(job),
+                    Err(error) => {
+                        toasts_log_error!(toasts, "Failed to export readback: {error}");
+                    }
+                }
+            }
+
+            if !std::mem::take(&mut state.viewed) || state.task.is_some() {
+                continue;
+            }
+
+            state.frames_since_read = state.frames_since_read.saturating_add(1);
+            let due = state.requested
+                || state.readout.is_none()
+                || state
+                    .refresh_every
+                    .is_some_and(|frames| state.frames_since_read >= frames);
+            if !due {
+                continue;
+            }
+
+            match start_readback(
+                project,
+                runtime_project,
+                device,
+                queue,
+                *target,
+                state,
+                false,
+            ) {
+                Ok(Some(task)) => state.task = Some(task),
+                Ok(None) => continue, // still being created: try again next frame
+                Err(error) => state.readout = Some(Err(error)),
+            }
+            state.requested = false;
+            state.frames_since_read = 0;
+        }
+
+        self.exports.retain_mut(|task| match task.try_resolve() {
+            Poll::Ready(Ok(())) => false,
+            Poll::Ready(Err(error)) => {
+                toasts_log_error!(toasts, "Failed to save readback: {error}");
+                false
+            }
+            Poll::Pending => true,
+        });
+    }
+}
+
+/// Copies `target` into a mappable buffer and returns the job that decodes
+/// it, or `None` if the resource isn't created yet. Textures only copy the
+/// page on screen unless `whole` is set.
+fn start_readback(
+    project: &Project,
+    runtime_project: &RuntimeProject,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    target: ReadbackTarget,
+    state: &ReadbackState,
+    whole: bool,
+) -> AppResult<Option<AsyncJob<AppResult<Readout>>>> {
+    match target {
+        ReadbackTarget::Texture(id) => {
+            let Some(runtime) = runtime_project.textures.get_init(id)? else {
+                return Ok(None);
+            };
+            let texture = runtime
+                .side(state.side)
+                .ok_or_else(|| AppError::NotDoubleBuffered {
+                    texture: project.label(id).unwrap_or_default().to_string(),
+                })?;
+            let region = match whole {
+                true => TexelRegion::whole(texture),
+                false => TexelRegion::page(texture, state.page),
+            };
+            read_texels(device, queue, texture, region).map(Some)
+        }
+        ReadbackTarget::Uniform(id) => {
+            let Some(runtime) = runtime_project.uniforms.get_init(id)? else {
+                return Ok(None);
+            };
+            let fields = project.uniforms.get(id)?.fields();
+            let layout = state.layout.resolve(fields, &runtime_project.shaders)?;
+            Ok(Some(read_buffer_elements(
+                device,
+                queue,
+                runtime.buffer().inner(),
+                layout,
+            )))
+        }
+    }
+}
+
+/// Copies `region` of `texture` into a mappable buffer and returns the job
+/// that decodes it.
+pub fn read_texels(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    region: TexelRegion,
+) -> AppResult<AsyncJob<AppResult<Readout>>> {
+    if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+        return Err(AppError::ReadbackTextureUsage);
+    }
+
+    let format = texture.format();
+    let layout = ElementLayout::texel(format)?;
+    // Combined depth-stencil textures copy one aspect at a time.
+    let aspect = match format.is_combined_depth_stencil_format() {
+        true => wgpu::TextureAspect::DepthOnly,
+        false => wgpu::TextureAspect::All,
+    };
+
+    let unpadded_bytes_per_row = region.width * layout.stride as u32;
+    let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+        * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Texture Readback Buffer"),
+        size: (padded_bytes_per_row as u64) * (region.height as u64),
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = create_command_encoder(device, "Texture Readback Encoder");
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d {
+                x: region.x,
+                y: region.y,
+                z: 0,
+            },
+            aspect,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(region.height),
+            },
+        },
+        wgpu::Extent3d {
+            width: region.width,
+            height: region.height,
+            depth_or_array_layers: 1,
+        },
+    );
+    queue.submit([encoder.finish()]);
+
+    let device = device.clone();
+    Ok(background_task::spawn_future(
+        "texture-readback",
+        async move {
+            let bytes = read_buffer(
+                &device,
+                &buffer,
+                region.height,
+                padded_bytes_per_row,
+                unpadded_bytes_per_row,
+            )
+            .await?;
+            Ok(Readout {
+                layout,
+                bytes,
+                region: Some(region),
+            })
+        },
+    ))
+}
+
+fn read_buffer_elements(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    source: &wgpu::Buffer,
+    layout: ElementLayout,
+) -> AsyncJob<AppResult<Readout>> {
+    let size = source.size();
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Buffer Readback Buffer"),
+        size,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = create_command_encoder(device, "Buffer Readback Encoder");
+    encoder.copy_buffer_to_buffer(source, 0, &buffer, 0, size);
+    queue.submit([encoder.finish()]);
+
+    let device = device.clone();
+    background_task::spawn_future("buffer-readback", async move {
+        let bytes = read_buffer(&device, &buffer, 1, size as u32, size as u32).await?;
+        Ok(Readout {
+            layout,
+            bytes,
+            region: None,
+        })
+    })
+}
+
+fn start_export(
+    project: &Project,
+    runtime_project: &RuntimeProject,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    target: ReadbackTarget,
+    state: &ReadbackState,
+) -> AppResult<AsyncJob<AppResult<()>>> {
+    let readback = start_readback(project, runtime_project, device, queue, target, state, true)?
+        .ok_or(AppError::CaptureError(
+            "the resource is not ready yet".into(),
+        ))?;
+    let csv = background_task::spawn_future("readback-csv", async move {
+        readback.await.map(|readout| readout.to_csv())
+    });
+
+    let label = project.label(target).unwrap_or("readback");
+    let file_name = format!("{label}.csv");
+    Ok(AsyncJob::new(async move {
+        let csv = csv.await?;
+        save_csv(file_name, csv.into_bytes()).await
+    }))
+}
+
+async fn save_csv(file_name: String, csv: Vec<u8>) -> AppResult<()> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let Some(handle) = rfd::AsyncFileDialog::new()
+            .set_title("Save Readback")
+            .set_file_name(&file_name)
+            .add_filter("CSV", &["csv"])
+            .save_file()
+            .await
+        else {
+            return Ok(());
+        };
+
+        handle.write(&csv).await?;
+        Ok(())
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        crate::utils::browser::download_file(&file_name, csv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wgsl_types_follow_wgsl_layout() {
+        let layout = ElementLayout::wgsl_type("vec3<f32>").unwrap();
+        let names: Vec<&str> = layout.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["x", "y", "z"]);
+        // vec3 is aligned to 16 bytes, so consecutive elements are 16 apart.
+        assert_eq!(layout.stride, 16);
+
+        let layout = ElementLayout::wgsl_type("mat3x3<f32>").unwrap();
+        assert_eq!(layout.columns[3].name, "[1].x");
+        assert_eq!(layout.columns[3].offset, 16);
+        assert_eq!(layout.stride, 48);
+
+        assert!(ElementLayout::wgsl_type("vec4<bool>").is_err());
+    }
+
+    #[test]
+    fn shader_structs_flatten_into_columns() {
+        let module = naga::front::wgsl::parse_str(
+            "struct Particle { position: vec2<f32>, alive: u32, weights: array<f32, 2> }",
+        )
+        .unwrap();
+        let layout = ElementLayout::shader_struct(&module, "Particle").unwrap();
+        let columns: Vec<(&str, usize)> = layout
+            .columns
+            .iter()
+            .map(|column| (column.name.as_str(), column.offset))
+            .collect();
+        assert_eq!(
+            columns,
+            [
+                ("position.x", 0),
+                ("position.y", 4),
+                ("alive", 8),
+                ("weights[0]", 12),
+                ("weights[1]", 16),
+            ]
+        );
+        // The struct is as aligned as its vec2, so 20 bytes round up to 24.
+        assert_eq!(layout.stride, 24);
+    }
+
+    #[test]
+    fn texels_export_with_their_coordinates() {
+        let readout = Readout {
+            layout: ElementLayout::texel(wgpu::TextureFormat::Rg8Uint).unwrap(),
+            bytes: vec![1, 2, 3, 4, 5, 6, 7, 8],
+            region: Some(TexelRegion {
+                x: 4,
+                y: 10,
+                width: 2,
+                height: 2,
+            }),
+        };
+        assert_eq!(readout.texel(5, 11).unwrap(), ["7", "8"]);
+        assert_eq!(readout.texel(6, 11), None);
+        assert_eq!(readout.texel_numbers(5, 11).unwrap(), [7.0, 8.0]);
+        assert_eq!(
+            readout.to_csv(),
+            "x,y,r,g\n4,10,1,2\n5,10,3,4\n4,11,5,6\n5,11,7,8\n"
+        );
+    }
+    #[test]
+    fn csv_header_quotes_names_with_separators() {
+        let mut layout = ElementLayout::texel(wgpu::TextureFormat::Rg8Uint).unwrap();
+        layout.columns[0].name = "a,b".to_string();
+        layout.columns[1].name = "say \"hi\"".to_string();
+        let readout = Readout {
+            layout,
+            bytes: vec![1, 2],
+            region: None,
+        };
+        assert_eq!(
+            readout.to_csv(),
+            "index,\"a,b\",\"say \"\"hi\"\"\"\n0,1,2\n"
+        );
+    }
+}
@@ -0,0 +1,352 @@
+//! Records the main viewport, one texture per frame, into an uncompressed
+//! YUV4MPEG2 stream or a numbered PNG sequence.
+//!
+//! Each frame is copied on the render thread and read back and converted on a
+//! background thread. Several frames stay in flight at once, so rendering only
+//! waits for a readback when it gets [`MAX_IN_FLIGHT`] frames ahead of them.
+
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    task::Poll,
+    time::Duration,
+};
+
+use crate::{
+    error::{AppError, AppResult},
+    utils::{
+        async_job::AsyncJob,
+        background_task,
+        texture_capture::{ImageFileFormat, TextureReadback},
+    },
+};
+
+/// Frames read back at once before recording waits for the oldest one.
+const MAX_IN_FLIGHT: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum RecordingFormat {
+    /// An uncompressed YUV4MPEG2 stream, which video tools read directly.
+    Y4m,
+    /// A folder of numbered PNG images.
+    PngSequence,
+}
+
+impl RecordingFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            RecordingFormat::Y4m => "Y4M Video",
+            RecordingFormat::PngSequence => "PNG Sequence",
+        }
+    }
+}
+
+/// Writes frames, in the order they were captured, to `output`: the stream
+/// file for Y4M, or the folder for a PNG sequence.
+pub struct Recorder {
+    format: RecordingFormat,
+    fps: f32,
+    output: PathBuf,
+    stream: Option<BufWriter<File>>,
+    /// Size of the first frame. A Y4M stream can't change size, so neither can
+    /// any recording.
+    size: Option<[u32; 2]>,
+    /// Frames being read back and converted, oldest first.
+    pending: VecDeque<AsyncJob<AppResult<Vec<u8>>>>,
+    written: u32,
+}
+
+impl Recorder {
+    pub fn create(format: RecordingFormat, fps: f32, output: PathBuf) -> AppResult<Self> {
+        let stream = match format {
+            RecordingFormat::Y4m => Some(BufWriter::new(File::create(&output)?)),
+            RecordingFormat::PngSequence => {
+                std::fs::create_dir_all(&output)?;
+                None
+            }
+        };
+
+        Ok(Self {
+            format,
+            fps,
+            output,
+            stream,
+            size: None,
+            pending: VecDeque::new(),
+            written: 0,
+        })
+    }
+
+    /// Time between two recorded frames.
+    pub fn frame_time(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.fps as f64)
+    }
+
+    pub fn output(&self) -> &PathBuf {
+        &self.output
+    }
+
+    /// Frames captured so far, written or not.
+    pub fn frames(&self) -> u32 {
+        self.written + self.pending.len() as u32
+    }
+
+    /// Copies `texture` as the next frame, and writes the frames whose
+    /// readback finished.
+    pub fn capture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture: &wgpu::Texture,
+    ) -> AppResult<()> {
+        let texture_format = texture.format();
+        if !matches!(
+            texture_format,
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb
+        ) {
+            return Err(AppError::CaptureError(format!(
+                "Can't record {texture_format:?} textures; only 8-bit RGBA ones can be recorded"
+            )));
+        }
+        let size = [texture.width(), texture.height()];
+        if *self.size.get_or_insert(size) != size {
+            return Err(AppError::CaptureError(
+                "the main viewport changed size while recording".into(),
+            ));
+        }
+
+        let readback = TextureReadback::copy(device, queue, texture)?;
+        let format = self.format;
+        let device = device.clone();
+        let job = background_task::spawn_future("recording-readback", async move {
+            let [width, height] = readback.size();
+            let pixels = readback.read(&device).await?;
+            match format {
+                RecordingFormat::Y4m => Ok(y4m_frame(&pixels, width, height)),
+                RecordingFormat::PngSequence => {
+                    ImageFileFormat::Png.encode(pixels, width, height, texture_format)
+                }
+            }
+        });
+        self.pending.push_back(job);
+
+        if self.pending.len() > MAX_IN_FLIGHT
+            && let Some(oldest) = self.pending.pop_front()
+        {
+            self.write(pollster::block_on(oldest)?)?;
+        }
+        self.write_ready()
+    }
+
+    /// Waits for the frames still in flight and writes them, returning how
+    /// many frames were recorded.
+    pub fn finish(mut self) -> AppResult<u32> {
+        while let Some(job) = self.pending.pop_front() {
+            self.write(pollster::block_on(job)?)?;
+        }
+        if let Some(stream) = &mut self.stream {
+            stream.flush()?;
+        }
+        Ok(self.written)
+    }
+
+    fn write_ready(&mut self) -> AppResult<()> {
+        while let Some(job) = self.pending.front_mut() {
+            let Poll::Ready(frame) = job.try_resolve() else {
+                break;
+            };
+            self.pending.pop_front();
+            self.write(frame?)?;
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, frame: Vec<u8>) -> AppResult<()> {
+        match &mut self.stream {
+            Some(stream) => {
+                if self.written == 0
+                    && let Some([width, height]) = self.size
+                {
+                    stream.write_all(y4m_header(width, height, self.fps).as_bytes())?;
+                }
+                stream.write_all(&frame)?;
+            }
+            None => {
+                let file_name = ImageFileFormat::Png.frame_file_name(self.written);
+                std::fs::write(self.output.join(file_name), frame)?;
+            }
+        }
+        self.written += 1;
+        Ok(())
+    }
+}
+
+/// The recording the UI controls. While it runs, the workspace advances its
+/// clock by the recording's frame time rather than the real one, so the clip
+/// plays back at the speed the project would run.
+pub struct Recording {
+    pub format: RecordingFormat,
+    pub fps: f32,
+    /// Stops on its own after this many frames, when set.
+    pub frame_limit: Option<u32>,
+    output_picker: Option<AsyncJob<Option<PathBuf>>>,
+    recorder: Option<Recorder>,
+    stop_requested: bool,
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        Self {
+            format: RecordingFormat::Y4m,
+            fps: 60.0,
+            frame_limit: None,
+            output_picker: None,
+            recorder: None,
+            stop_requested: false,
+        }
+    }
+}
+
+impl Recording {
+    /// Asks where to write the recording, and starts it on the next frame.
+    pub fn start(&mut self) {
+        let format = self.format;
+        self.output_picker = Some(AsyncJob::new(async move {
+            let dialog = rfd::AsyncFileDialog::new().set_title("Record Main Viewport");
+            let handle = match format {
+                RecordingFormat::Y4m => {
+                    dialog
+                        .set_file_name("recording.y4m")
+                        .add_filter("YUV4MPEG2 Video", &["y4m"])
+                        .save_file()
+                        .await
+                }
+                RecordingFormat::PngSequence => dialog.pick_folder().await,
+            };
+            handle.map(|handle| handle.path().to_path_buf())
+        }));
+    }
+
+    pub fn stop(&mut self) {
+        self.stop_requested = true;
+    }
+
+    pub fn is_starting(&self) -> bool {
+        self.output_picker.is_some()
+    }
+
+    pub fn recorder(&self) -> Option<&Recorder> {
+        self.recorder.as_ref()
+    }
+
+    /// The clock step the workspace should use, while recording.
+    pub fn frame_time(&self) -> Option<Duration> {
+        self.recorder.as_ref().map(Recorder::frame_time)
+    }
+
+    /// Records the frame that was just submitted, and starts or stops the
+    /// recording when asked to.
+    pub fn tick(
+        &mut self,
+        texture: AppResult<&wgpu::Texture>,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        toasts: &mut egui_notify::Toasts,
+    ) {
+        if let Some(recorder) = &mut self.recorder {
+            let result = texture.and_then(|texture| recorder.capture(device, queue, texture));
+            if let Err(error) = result {
+                toasts_log_error!(toasts, "Failed to record frame: {error}");
+                self.stop_requested = true;
+            }
+            if self
+                .frame_limit
+                .is_some_and(|limit| recorder.frames() >= limit)
+            {
+                self.stop_requested = true;
+            }
+        }
+
+        if std::mem::take(&mut self.stop_requested)
+            && let Some(recorder) = self.recorder.take()
+        {
+            let output = recorder.output().display().to_string();
+            match recorder.finish() {
+                Ok(frames) => {
+                    log::info!("Recorded {frames} frames to {output}");
+                    toasts.success(format!("Recorded {frames} frames to {output}"));
+                }
+                Err(error) => {
+                    toasts_log_error!(toasts, "Failed to finish recording: {error}");
+                }
+            }
+        }
+
+        if let Some(picker) = &mut self.output_picker
+            && let Poll::Ready(output) = picker.try_resolve()
+        {
+            self.output_picker = None;
+            if let Some(output) = output {
+                match Recorder::create(self.format, self.fps, output) {
+                    Ok(recorder) => self.recorder = Some(recorder),
+                    Err(error) => {
+                        toasts_log_error!(toasts, "Failed to start recording: {error}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The stream header, declaring full-resolution 4:4:4 chroma so no color
+/// is lost to subsampling.
+fn y4m_header(width: u32, height: u32, fps: f32) -> String {
+    let (numerator, denominator) = match fps.fract() == 0.0 {
+        true => (fps as u32, 1),
+        false => ((fps * 1000.0).round() as u32, 1000),
+    };
+    format!("YUV4MPEG2 W{width} H{height} F{numerator}:{denominator} Ip A1:1 C444\n")
+}
+
+/// One frame of the stream: RGBA texels converted to BT.601 limited-range
+/// Y, U and V planes. Alpha is dropped.
+fn y4m_frame(pixels: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let texels = (width * height) as usize;
+    let mut frame = Vec::with_capacity(6 + 3 * texels);
+    frame.extend_from_slice(b"FRAME\n");
+    frame.resize(6 + 3 * texels, 0);
+
+    let (y_plane, chroma) = frame[6..].split_at_mut(texels);
+    let (u_plane, v_plane) = chroma.split_at_mut(texels);
+    for (index, texel) in pixels.chunks_exact(4).enumerate() {
+        let [r, g, b] = [texel[0], texel[1], texel[2]].map(i32::from);
+        y_plane[index] = (((66 * r + 129 * g + 25 * b + 128) >> 8) + 16) as u8;
+        u_plane[index] = (((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128) as u8;
+        v_plane[index] = (((112 * r - 94 * g - 18 * b + 128) >> 8) + 128) as u8;
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn y4m_frames_hold_limited_range_planes() {
+        let pixels = [255, 255, 255, 255, 0, 0, 0, 255, 255, 0, 0, 255];
+        let frame = y4m_frame(&pixels, 3, 1);
+
+        assert_eq!(&frame[..6], b"FRAME\n");
+        let (y, u, v) = (&frame[6..9], &frame[9..12], &frame[12..15]);
+        assert_eq!(y, [235, 16, 82]);
+        assert_eq!(u, [128, 128, 90]);
+        assert_eq!(v, [128, 128, 240]);
+
+        assert_eq!(
+            y4m_header(3, 1, 29.97),
+            "YUV4MPEG2 W3 H1 F29970:1000 Ip A1:1 C444\n"
+        );
+    }
+}
@@ -8,7 +8,7 @@ use crate::project::{
     Project, SamplerId, TextureViewId,
     resource::{
         bindgroup::{BindGroup, BindGroupResource},
-        compute_pass::ComputePass,
+        compute_pass::{ComputePass, IterationCount},
         model::{Model, vertex_buffer::VertexBufferSpec},
         render_pipeline::{BindGroupTarget, RenderDrawStrategy, RenderPipeline},
         sampler::Sampler,
@@ -131,6 +131,9 @@ impl ShaderInterface for ComputePass {
                 Err(_) => module.comment(format!("group {group} is empty")),
             }
         }
+        if self.iterations().count != IterationCount::Fixed(1) {
+            module.comment("iteration index: var<immediate> iteration: u32;");
+        }
     }
 }
 
@@ -0,0 +1,860 @@
+//! Reflection helpers over validated naga modules, used to keep project
+//! resources in step with the shaders that consume them.
+
+use std::fmt;
+
+use crate::{
+    error::{AppError, AppResult},
+    project::resource::uniform::{
+        UniformField, UniformFieldData, UniformFieldDataKind, UniformFieldSource,
+    },
+};
+
+/// A member of a shader struct and the uniform field type it maps onto, if any.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReflectedMember {
+    pub name: String,
+    /// The member type as it would be spelled in WGSL.
+    pub type_label: String,
+    pub kind: Option<UniformFieldDataKind>,
+}
+
+/// Names of every named struct declared in the module, in declaration order.
+pub fn struct_names(module: &naga::Module) -> Vec<&str> {
+    module
+        .types
+        .iter()
+        .filter(|(_, ty)| matches!(ty.inner, naga::TypeInner::Struct { .. }))
+        .filter_map(|(_, ty)| ty.name.as_deref())
+        .collect()
+}
+
+/// Maps each member of the struct named `name` onto a uniform field type.
+/// Returns `None` if the module has no struct with that name.
+pub fn reflect_struct(module: &naga::Module, name: &str) -> Option<Vec<ReflectedMember>> {
+    let members = module.types.iter().find_map(|(_, ty)| match &ty.inner {
+        naga::TypeInner::Struct { members, .. } if ty.name.as_deref() == Some(name) => {
+            Some(members)
+        }
+        _ => None,
+    })?;
+
+    let reflected = members
+        .iter()
+        .enumerate()
+        .map(|(index, member)| ReflectedMember {
+            name: member
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("member{index}")),
+            type_label: type_label(module, member.ty),
+            kind: uniform_field_kind(&module.types[member.ty].inner),
+        })
+        .collect();
+    Some(reflected)
+}
+
+/// The uniform field type matching a shader type, if one exists.
+pub fn uniform_field_kind(inner: &naga::TypeInner) -> Option<UniformFieldDataKind> {
+    use UniformFieldDataKind as K;
+    use naga::{Scalar, TypeInner, VectorSize};
+
+    match *inner {
+        TypeInner::Scalar(Scalar::U32) => Some(K::UInt32),
+        TypeInner::Scalar(Scalar::F32) => Some(K::Float),
+        TypeInner::Vector { size, scalar } => match (size, scalar) {
+            (VectorSize::Bi, Scalar::U32) => Some(K::Vec2u),
+            (VectorSize::Tri, Scalar::U32) => Some(K::Vec3u),
+            (VectorSize::Quad, Scalar::U32) => Some(K::Vec4u),
+            (VectorSize::Bi, Scalar::F32) => Some(K::Vec2f),
+            (VectorSize::Tri, Scalar::F32) => Some(K::Vec3f),
+            (VectorSize::Quad, Scalar::F32) => Some(K::Vec4f),
+            _ => None,
+        },
+        TypeInner::Matrix {
+            columns: VectorSize::Quad,
+            rows: VectorSize::Quad,
+            scalar: Scalar::F32,
+        } => Some(K::Mat4x4f),
+        _ => None,
+    }
+}
+
+/// Spells a shader type the way WGSL would.
+pub fn type_label(module: &naga::Module, ty: naga::Handle<naga::Type>) -> String {
+    use naga::TypeInner;
+
+    let ty = &module.types[ty];
+    match &ty.inner {
+        TypeInner::Scalar(scalar) => scalar_label(*scalar),
+        TypeInner::Vector { size, scalar } => {
+            format!("vec{}<{}>", *size as u8, scalar_label(*scalar))
+        }
+        TypeInner::Matrix {
+            columns,
+            rows,
+            scalar,
+        } => format!(
+            "mat{}x{}<{}>",
+            *columns as u8,
+            *rows as u8,
+            scalar_label(*scalar)
+        ),
+        TypeInner::Atomic(scalar) => format!("atomic<{}>", scalar_label(*scalar)),
+        TypeInner::Array { base, size, .. } => match size {
+            naga::ArraySize::Constant(count) => {
+                format!("array<{}, {count}>", type_label(module, *base))
+            }
+            _ => format!("array<{}>", type_label(module, *base)),
+        },
+        TypeInner::Struct { .. } => ty.name.clone().unwrap_or_else(|| "struct".to_string()),
+        TypeInner::Image { .. } => "texture".to_string(),
+        TypeInner::Sampler { comparison: false } => "sampler".to_string(),
+        TypeInner::Sampler { comparison: true } => "sampler_comparison".to_string(),
+        _ => ty.name.clone().unwrap_or_else(|| "unknown".to_string()),
+    }
+}
+
+fn scalar_label(scalar: naga::Scalar) -> String {
+    use naga::ScalarKind;
+
+    match scalar.kind {
+        ScalarKind::Bool => "bool".to_string(),
+        ScalarKind::Sint => format!("i{}", scalar.width * 8),
+        ScalarKind::Uint => format!("u{}", scalar.width * 8),
+        ScalarKind::Float => format!("f{}", scalar.width * 8),
+        ScalarKind::AbstractInt => "abstract-int".to_string(),
+        ScalarKind::AbstractFloat => "abstract-float".to_string(),
+    }
+}
+
+/// Builds a uniform field list matching `members`, in order. Existing fields
+/// with the same name and a compatible WGSL type keep their source (so a
+/// camera-sourced `mat4x4<f32>` stays camera-sourced); everything else becomes a
+/// user defined field.
+///
+/// Fails on the first member without a matching field type: leaving it out
+/// would shift every later field away from its offset in the struct.
+pub fn uniform_fields_from_members(
+    existing: &[UniformField],
+    members: &[ReflectedMember],
+) -> AppResult<Vec<UniformField>> {
+    members
+        .iter()
+        .map(|member| {
+            let kind = member
+                .kind
+                .ok_or_else(|| AppError::UnmappableStructMember {
+                    member: member.name.clone(),
+                    type_label: member.type_label.clone(),
+                })?;
+            let reused = existing.iter().find(|field| {
+                field.label() == member.name
+                    && field.kind().wgsl_type_label() == kind.wgsl_type_label()
+            });
+            Ok(match reused {
+                Some(field) => field.clone(),
+                None => UniformField::new(
+                    member.name.clone(),
+                    UniformFieldSource::new_user_defined(UniformFieldData::from_kind(kind)),
+                ),
+            })
+        })
+        .collect()
+}
+
+/// The kind of resource a binding holds, in terms that can be read both from a
+/// shader global and from a bind group layout entry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BindingKind {
+    UniformBuffer,
+    StorageBuffer {
+        read_only: bool,
+    },
+    Texture {
+        /// Filterability isn't declared by shaders, so float sample types are
+        /// always normalized to `filterable: true`.
+        sample_type: wgpu::TextureSampleType,
+        view_dimension: wgpu::TextureViewDimension,
+        multisampled: bool,
+    },
+    StorageTexture {
+        access: wgpu::StorageTextureAccess,
+        view_dimension: wgpu::TextureViewDimension,
+        /// `None` when the shader format has no wgpu equivalent we know of.
+        format: Option<wgpu::TextureFormat>,
+    },
+    Sampler {
+        comparison: bool,
+    },
+    Other,
+}
+
+/// A resource binding used by a shader entry point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderBinding {
+    pub group: u32,
+    pub binding: u32,
+    pub kind: BindingKind,
+}
+
+impl BindingKind {
+    pub fn from_wgpu(ty: &wgpu::BindingType) -> Self {
+        match *ty {
+            wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                ..
+            } => BindingKind::UniformBuffer,
+            wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                ..
+            } => BindingKind::StorageBuffer { read_only },
+            wgpu::BindingType::Texture {
+                sample_type,
+                view_dimension,
+                multisampled,
+            } => BindingKind::Texture {
+                sample_type: normalize_sample_type(sample_type),
+                view_dimension,
+                multisampled,
+            },
+            wgpu::BindingType::StorageTexture {
+                access,
+                format,
+                view_dimension,
+            } => BindingKind::StorageTexture {
+                access,
+                view_dimension,
+                format: Some(format),
+            },
+            wgpu::BindingType::Sampler(ty) => BindingKind::Sampler {
+                comparison: ty == wgpu::SamplerBindingType::Comparison,
+            },
+            _ => BindingKind::Other,
+        }
+    }
+
+    fn from_global(module: &naga::Module, global: &naga::GlobalVariable) -> Self {
+        match global.space {
+            naga::AddressSpace::Uniform => return BindingKind::UniformBuffer,
+            naga::AddressSpace::Storage { access } => {
+                return BindingKind::StorageBuffer {
+                    read_only: !access.contains(naga::StorageAccess::STORE),
+                };
+            }
+            naga::AddressSpace::Handle => {}
+            _ => return BindingKind::Other,
+        }
+
+        match module.types[global.ty].inner {
+            naga::TypeInner::Image {
+                dim,
+                arrayed,
+                class,
+            } => {
+                let view_dimension = view_dimension(dim, arrayed);
+                match class {
+                    naga::ImageClass::Sampled { kind, multi } => BindingKind::Texture {
+                        sample_type: match kind {
+                            naga::ScalarKind::Sint => wgpu::TextureSampleType::Sint,
+                            naga::ScalarKind::Uint => wgpu::TextureSampleType::Uint,
+                            _ => wgpu::TextureSampleType::Float { filterable: true },
+                        },
+                        view_dimension,
+                        multisampled: multi,
+                    },
+                    naga::ImageClass::Depth { multi } => BindingKind::Texture {
+                        sample_type: wgpu::TextureSampleType::Depth,
+                        view_dimension,
+                        multisampled: multi,
+                    },
+                    naga::ImageClass::Storage { format, access } => BindingKind::StorageTexture {
+                        access: storage_texture_access(access),
+                        view_dimension,
+                        format: storage_format(format),
+                    },
+                    _ => BindingKind::Other,
+                }
+            }
+            naga::TypeInner::Sampler { comparison } => BindingKind::Sampler { comparison },
+            _ => BindingKind::Other,
+        }
+    }
+
+    /// Whether a layout entry of kind `actual` satisfies a shader binding of this
+    /// kind.
+    pub fn accepts(&self, actual: &BindingKind) -> bool {
+        match (self, actual) {
+            (
+                BindingKind::StorageTexture {
+                    access,
+                    view_dimension,
+                    format: None,
+                },
+                BindingKind::StorageTexture {
+                    access: actual_access,
+                    view_dimension: actual_view_dimension,
+                    ..
+                },
+            ) => access == actual_access && view_dimension == actual_view_dimension,
+            _ => self == actual,
+        }
+    }
+}
+
+impl fmt::Display for BindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindingKind::UniformBuffer => write!(f, "var<uniform>"),
+            BindingKind::StorageBuffer { read_only: true } => write!(f, "var<storage, read>"),
+            BindingKind::StorageBuffer { read_only: false } => {
+                write!(f, "var<storage, read_write>")
+            }
+            BindingKind::Texture {
+                sample_type,
+                view_dimension,
+                multisampled,
+            } => {
+                let multisampled = if *multisampled { "_multisampled" } else { "" };
+                let dimension = view_dimension_label(*view_dimension);
+                match sample_type {
+                    wgpu::TextureSampleType::Depth => {
+                        write!(f, "texture_depth{multisampled}_{dimension}")
+                    }
+                    wgpu::TextureSampleType::Sint => {
+                        write!(f, "texture{multisampled}_{dimension}<i32>")
+                    }
+                    wgpu::TextureSampleType::Uint => {
+                        write!(f, "texture{multisampled}_{dimension}<u32>")
+                    }
+                    wgpu::TextureSampleType::Float { .. } => {
+                        write!(f, "texture{multisampled}_{dimension}<f32>")
+                    }
+                }
+            }
+            BindingKind::StorageTexture {
+                access,
+                view_dimension,
+                format,
+            } => {
+                let dimension = view_dimension_label(*view_dimension);
+                let format = match format {
+                    Some(format) => format!("{format:?}").to_lowercase(),
+                    None => "_".to_string(),
+                };
+                let access = match access {
+                    wgpu::StorageTextureAccess::ReadOnly => "read",
+                    wgpu::StorageTextureAccess::WriteOnly => "write",
+                    wgpu::StorageTextureAccess::ReadWrite => "read_write",
+                    wgpu::StorageTextureAccess::Atomic => "atomic",
+                };
+                write!(f, "texture_storage_{dimension}<{format}, {access}>")
+            }
+            BindingKind::Sampler { comparison: false } => write!(f, "sampler"),
+            BindingKind::Sampler { comparison: true } => write!(f, "sampler_comparison"),
+            BindingKind::Other => write!(f, "an unsupported binding"),
+        }
+    }
+}
+
+fn normalize_sample_type(sample_type: wgpu::TextureSampleType) -> wgpu::TextureSampleType {
+    match sample_type {
+        wgpu::TextureSampleType::Float { .. } => {
+            wgpu::TextureSampleType::Float { filterable: true }
+        }
+        other => other,
+    }
+}
+
+fn view_dimension(dim: naga::ImageDimension, arrayed: bool) -> wgpu::TextureViewDimension {
+    match (dim, arrayed) {
+        (naga::ImageDimension::D1, _) => wgpu::TextureViewDimension::D1,
+        (naga::ImageDimension::D2, false) => wgpu::TextureViewDimension::D2,
+        (naga::ImageDimension::D2, true) => wgpu::TextureViewDimension::D2Array,
+        (naga::ImageDimension::D3, _) => wgpu::TextureViewDimension::D3,
+        (naga::ImageDimension::Cube, false) => wgpu::TextureViewDimension::Cube,
+        (naga::ImageDimension::Cube, true) => wgpu::TextureViewDimension::CubeArray,
+    }
+}
+
+fn view_dimension_label(dimension: wgpu::TextureViewDimension) -> &'static str {
+    match dimension {
+        wgpu::TextureViewDimension::D1 => "1d",
+        wgpu::TextureViewDimension::D2 => "2d",
+        wgpu::TextureViewDimension::D2Array => "2d_array",
+        wgpu::TextureViewDimension::Cube => "cube",
+        wgpu::TextureViewDimension::CubeArray => "cube_array",
+        wgpu::TextureViewDimension::D3 => "3d",
+    }
+}
+
+fn storage_texture_access(access: naga::StorageAccess) -> wgpu::StorageTextureAccess {
+    if access.contains(naga::StorageAccess::ATOMIC) {
+        wgpu::StorageTextureAccess::Atomic
+    } else if access.contains(naga::StorageAccess::LOAD | naga::StorageAccess::STORE) {
+        wgpu::StorageTextureAccess::ReadWrite
+    } else if access.contains(naga::StorageAccess::STORE) {
+        wgpu::StorageTextureAccess::WriteOnly
+    } else {
+        wgpu::StorageTextureAccess::ReadOnly
+    }
+}
+
+fn storage_format(format: naga::StorageFormat) -> Option<wgpu::TextureFormat> {
+    use naga::StorageFormat as S;
+    use wgpu::TextureFormat as T;
+
+    Some(match format {
+        S::R32Uint => T::R32Uint,
+        S::R32Sint => T::R32Sint,
+        S::R32Float => T::R32Float,
+        S::Rg32Uint => T::Rg32Uint,
+        S::Rg32Sint => T::Rg32Sint,
+        S::Rg32Float => T::Rg32Float,
+        S::Rgba8Unorm => T::Rgba8Unorm,
+        S::Rgba8Snorm => T::Rgba8Snorm,
+        S::Rgba8Uint => T::Rgba8Uint,
+        S::Rgba8Sint => T::Rgba8Sint,
+        S::Bgra8Unorm => T::Bgra8Unorm,
+        S::Rgba16Uint => T::Rgba16Uint,
+        S::Rgba16Sint => T::Rgba16Sint,
+        S::Rgba16Float => T::Rgba16Float,
+        S::Rgba32Uint => T::Rgba32Uint,
+        S::Rgba32Sint => T::Rgba32Sint,
+        S::Rgba32Float => T::Rgba32Float,
+        _ => return None,
+    })
+}
+
+fn stage_flag(stage: naga::ShaderStage) -> wgpu::ShaderStages {
+    match stage {
+        naga::ShaderStage::Vertex => wgpu::ShaderStages::VERTEX,
+        naga::ShaderStage::Fragment => wgpu::ShaderStages::FRAGMENT,
+        naga::ShaderStage::Compute => wgpu::ShaderStages::COMPUTE,
+        _ => wgpu::ShaderStages::empty(),
+    }
+}
+
+/// The resource bindings used by the module's `stage` entry points, sorted by
+/// group and binding. Globals that are declared but never used are left out,
+/// just like wgpu does.
+pub fn used_bindings(
+    module: &naga::Module,
+    info: &naga::valid::ModuleInfo,
+    stage: naga::ShaderStage,
+) -> Vec<ShaderBinding> {
+    let entry_points: Vec<_> = module
+        .entry_points
+        .iter()
+        .enumerate()
+        .filter(|(_, entry_point)| entry_point.stage == stage)
+        .map(|(index, _)| info.get_entry_point(index))
+        .collect();
+
+    let mut bindings: Vec<_> = module
+        .global_variables
+        .iter()
+        .filter(|(handle, _)| {
+            entry_points
+                .iter()
+                .any(|function| !function[*handle].is_empty())
+        })
+        .filter_map(|(_, global)| {
+            let binding = global.binding.as_ref()?;
+            Some(ShaderBinding {
+                group: binding.group,
+                binding: binding.binding,
+                kind: BindingKind::from_global(module, global),
+            })
+        })
+        .collect();
+    bindings.sort_by_key(|binding| (binding.group, binding.binding));
+    bindings
+}
+
+/// Checks a pipeline's bind group layouts (one per group slot, `None` for empty
+/// slots) against the bindings the shader's `stage` entry point uses. Catches
+/// mismatches before wgpu reports them as a generic validation error.
+pub fn validate_bindings(
+    module: &naga::Module,
+    info: &naga::valid::ModuleInfo,
+    stage: naga::ShaderStage,
+    layouts: &[Option<&[wgpu::BindGroupLayoutEntry]>],
+) -> AppResult<()> {
+    for ShaderBinding {
+        group,
+        binding,
+        kind,
+    } in used_bindings(module, info, stage)
+    {
+        let entry = layouts
+            .get(group as usize)
+            .copied()
+            .flatten()
+            .and_then(|entries| entries.iter().find(|entry| entry.binding == binding));
+        let Some(entry) = entry else {
+            return Err(AppError::MissingShaderBinding {
+                group,
+                binding,
+                expected: kind.to_string(),
+            });
+        };
+
+        let actual = BindingKind::from_wgpu(&entry.ty);
+        if !kind.accepts(&actual) {
+            return Err(AppError::ShaderBindingMismatch {
+                group,
+                binding,
+                expected: kind.to_string(),
+                actual: actual.to_string(),
+            });
+        }
+
+        if !entry.visibility.contains(stage_flag(stage)) {
+            return Err(AppError::ShaderBindingVisibility {
+                group,
+                binding,
+                stage: format!("{stage:?}").to_lowercase(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The `@workgroup_size` of the module's first compute entry point.
+pub fn workgroup_size(module: &naga::Module) -> Option<[u32; 3]> {
+    module
+        .entry_points
+        .iter()
+        .find(|entry_point| entry_point.stage == naga::ShaderStage::Compute)
+        .map(|entry_point| entry_point.workgroup_size)
+}
+
+/// The type of the module's `var<immediate>` global, if it declares one.
+pub fn immediate_type(module: &naga::Module) -> Option<naga::Handle<naga::Type>> {
+    module
+        .global_variables
+        .iter()
+        .find(|(_, global)| global.space == naga::AddressSpace::Immediate)
+        .map(|(_, global)| global.ty)
+}
+
+/// A `@location` input of a vertex entry point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VertexInput {
+    pub location: u32,
+    pub name: Option<String>,
+    /// `None` when the input's type has no vertex format equivalent.
+    pub format: Option<wgpu::VertexFormat>,
+    pub type_label: String,
+}
+
+/// The `@location` inputs of the module's first vertex entry point, sorted by
+/// location. Inputs declared as struct members are flattened.
+pub fn vertex_inputs(module: &naga::Module) -> Vec<VertexInput> {
+    let Some(entry_point) = module
+        .entry_points
+        .iter()
+        .find(|entry_point| entry_point.stage == naga::ShaderStage::Vertex)
+    else {
+        return vec![];
+    };
+
+    let mut inputs = vec![];
+    for argument in &entry_point.function.arguments {
+        match (&argument.binding, &module.types[argument.ty].inner) {
+            (Some(binding), _) => {
+                push_vertex_input(module, &mut inputs, binding, &argument.name, argument.ty)
+            }
+            (None, naga::TypeInner::Struct { members, .. }) => {
+                for member in members {
+                    if let Some(binding) = &member.binding {
+                        push_vertex_input(module, &mut inputs, binding, &member.name, member.ty);
+                    }
+                }
+            }
+            (None, _) => {}
+        }
+    }
+    inputs.sort_by_key(|input| input.location);
+    inputs
+}
+
+fn push_vertex_input(
+    module: &naga::Module,
+    inputs: &mut Vec<VertexInput>,
+    binding: &naga::Binding,
+    name: &Option<String>,
+    ty: naga::Handle<naga::Type>,
+) {
+    let naga::Binding::Location { location, .. } = *binding else {
+        return;
+    };
+    inputs.push(VertexInput {
+        location,
+        name: name.clone(),
+        format: vertex_format(&module.types[ty].inner),
+        type_label: type_label(module, ty),
+    });
+}
+
+fn vertex_format(inner: &naga::TypeInner) -> Option<wgpu::VertexFormat> {
+    use naga::{ScalarKind as K, VectorSize as V};
+    use wgpu::VertexFormat as F;
+
+    let (scalar, size) = match *inner {
+        naga::TypeInner::Scalar(scalar) => (scalar, None),
+        naga::TypeInner::Vector { size, scalar } => (scalar, Some(size)),
+        _ => return None,
+    };
+    if scalar.width != 4 {
+        return None;
+    }
+
+    Some(match (scalar.kind, size) {
+        (K::Float, None) => F::Float32,
+        (K::Float, Some(V::Bi)) => F::Float32x2,
+        (K::Float, Some(V::Tri)) => F::Float32x3,
+        (K::Float, Some(V::Quad)) => F::Float32x4,
+        (K::Sint, None) => F::Sint32,
+        (K::Sint, Some(V::Bi)) => F::Sint32x2,
+        (K::Sint, Some(V::Tri)) => F::Sint32x3,
+        (K::Sint, Some(V::Quad)) => F::Sint32x4,
+        (K::Uint, None) => F::Uint32,
+        (K::Uint, Some(V::Bi)) => F::Uint32x2,
+        (K::Uint, Some(V::Tri)) => F::Uint32x3,
+        (K::Uint, Some(V::Quad)) => F::Uint32x4,
+        _ => return None,
+    })
+}
+
+/// The WGSL type a vertex format is read as, e.g. `vec3<f32>` for `Float32x3`.
+pub fn vertex_format_label(format: wgpu::VertexFormat) -> String {
+    use wgpu::VertexFormat as F;
+
+    let (scalar, components) = match format {
+        F::Float32 => ("f32", 1),
+        F::Float32x2 => ("f32", 2),
+        F::Float32x3 => ("f32", 3),
+        F::Float32x4 => ("f32", 4),
+        F::Sint32 => ("i32", 1),
+        F::Sint32x2 => ("i32", 2),
+        F::Sint32x3 => ("i32", 3),
+        F::Sint32x4 => ("i32", 4),
+        F::Uint32 => ("u32", 1),
+        F::Uint32x2 => ("u32", 2),
+        F::Uint32x3 => ("u32", 3),
+        F::Uint32x4 => ("u32", 4),
+        other => return format!("{other:?}"),
+    };
+    match components {
+        1 => scalar.to_string(),
+        n => format!("vec{n}<{scalar}>"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+        struct Light {
+            position: vec3<f32>,
+            intensity: f32,
+            flags: i32,
+            transform: mat4x4<f32>,
+        }
+
+        @group(0) @binding(0) var<uniform> light: Light;
+    "#;
+
+    #[test]
+    fn reflects_struct_members_and_reports_unmappable_types() {
+        let module = naga::front::wgsl::parse_str(SOURCE).unwrap();
+        assert_eq!(struct_names(&module), vec!["Light"]);
+
+        let members = reflect_struct(&module, "Light").unwrap();
+        let kinds: Vec<_> = members.iter().map(|member| member.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Some(UniformFieldDataKind::Vec3f),
+                Some(UniformFieldDataKind::Float),
+                None,
+                Some(UniformFieldDataKind::Mat4x4f),
+            ]
+        );
+        assert_eq!(members[2].type_label, "i32");
+    }
+
+    #[test]
+    fn keeps_existing_fields_with_compatible_types() {
+        let source = SOURCE.replace("flags: i32,", "flags: u32,");
+        let module = naga::front::wgsl::parse_str(&source).unwrap();
+        let members = reflect_struct(&module, "Light").unwrap();
+
+        let color = UniformField::new(
+            "position",
+            UniformFieldSource::new_user_defined(UniformFieldData::Rgb([1.0, 0.5, 0.0])),
+        );
+        let fields = uniform_fields_from_members(&[color.clone()], &members).unwrap();
+
+        let labels: Vec<_> = fields.iter().map(UniformField::label).collect();
+        assert_eq!(labels, vec!["position", "intensity", "flags", "transform"]);
+        assert_eq!(fields[0], color);
+    }
+
+    #[test]
+    fn refuses_structs_with_unmappable_members() {
+        let module = naga::front::wgsl::parse_str(SOURCE).unwrap();
+        let members = reflect_struct(&module, "Light").unwrap();
+
+        let error = uniform_fields_from_members(&[], &members).unwrap_err();
+        assert!(matches!(
+            error,
+            AppError::UnmappableStructMember { ref member, ref type_label }
+                if member == "flags" && type_label == "i32"
+        ));
+    }
+
+    #[test]
+    fn reports_mismatched_and_missing_bindings() {
+        let source = r#"
+            @group(0) @binding(0) var<uniform> scale: f32;
+            @group(0) @binding(1) var color: texture_2d<f32>;
+            @group(1) @binding(0) var unused: sampler;
+
+            @compute @workgroup_size(1)
+            fn main() {
+                let size = textureDimensions(color);
+                _ = scale * f32(size.x);
+            }
+        "#;
+        let (module, info) = crate::utils::wgpu_utils::parse_shader(
+            source,
+            crate::utils::wgpu_utils::ShaderSourceKind::Wgsl,
+        )
+        .unwrap();
+
+        let entry = |binding, ty| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty,
+            count: None,
+        };
+        let uniform = entry(
+            0,
+            wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+        );
+        let texture = |sample_type| {
+            entry(
+                1,
+                wgpu::BindingType::Texture {
+                    sample_type,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+            )
+        };
+
+        let matching = [
+            uniform,
+            texture(wgpu::TextureSampleType::Float { filterable: false }),
+        ];
+        validate_bindings(
+            &module,
+            &info,
+            naga::ShaderStage::Compute,
+            &[Some(&matching)],
+        )
+        .unwrap();
+
+        let mismatched = [uniform, texture(wgpu::TextureSampleType::Uint)];
+        let error = validate_bindings(
+            &module,
+            &info,
+            naga::ShaderStage::Compute,
+            &[Some(&mismatched)],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            AppError::ShaderBindingMismatch { group: 0, binding: 1, ref expected, ref actual }
+                if expected == "texture_2d<f32>" && actual == "texture_2d<u32>"
+        ));
+
+        let error = validate_bindings(
+            &module,
+            &info,
+            naga::ShaderStage::Compute,
+            &[Some(&[uniform])],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            AppError::MissingShaderBinding {
+                group: 0,
+                binding: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn reorders_vertex_buffer_spec_to_match_vertex_inputs() {
+        use crate::project::{
+            ModelId,
+            resource::model::vertex_buffer::{VertexBufferField, VertexBufferSpec},
+        };
+
+        let module = naga::front::wgsl::parse_str(
+            r#"
+            struct VertexInput {
+                @location(0) position: vec3<f32>,
+                @location(1) normal: vec3<f32>,
+                @location(2) uv: vec2<f32>,
+            }
+
+            @vertex
+            fn vs_main(input: VertexInput) -> @builtin(position) vec4<f32> {
+                return vec4<f32>(input.position + input.normal, input.uv.x);
+            }
+            "#,
+        )
+        .unwrap();
+        let inputs = vertex_inputs(&module);
+        let locations: Vec<_> = inputs.iter().map(|input| input.location).collect();
+        assert_eq!(locations, vec![0, 1, 2]);
+
+        let spec = VertexBufferSpec::new();
+        let error = spec
+            .validate_vertex_inputs(ModelId::default(), &inputs)
+            .unwrap_err();
+        let AppError::VertexInputMismatch {
+            location,
+            suggested_spec: Some(suggested_spec),
+            ..
+        } = error
+        else {
+            panic!("expected a vertex input mismatch, got {error:?}");
+        };
+        assert_eq!(location, 1);
+        assert_eq!(
+            suggested_spec.fields,
+            vec![
+                VertexBufferField::Position,
+                VertexBufferField::Normal,
+                VertexBufferField::TextureCoordinates,
+                VertexBufferField::Tangent,
+                VertexBufferField::Bitangent,
+            ]
+        );
+        suggested_spec
+            .validate_vertex_inputs(ModelId::default(), &inputs)
+            .unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-//! Reads a rendered texture back from the GPU and saves it as a PNG.
+//! Reads a rendered texture back from the GPU and saves it as a PNG or EXR.
 //!
 //! The GPU copy and submit happen synchronously on the render thread (where the
 //! device and queue live), and the rest (mapping the buffer, encoding the PNG and
@@ -7,8 +7,6 @@
 
 use std::{io::Cursor, task::Poll};
 
-use image::ImageEncoder;
-
 use crate::{
     error::{AppError, AppResult},
     project::{Project, ProjectResource, RuntimeProject, TextureId},
@@ -85,84 +83,131 @@ pub fn download_texture_as_png(
     texture: &wgpu::Texture,
     file_name: String,
 ) -> AppResult<AsyncJob<AppResult<()>>> {
-    if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
-        return Err(AppError::CaptureError(
-            "the texture needs the 'Copy Source' usage to be saved as an image".into(),
-        ));
-    }
+    ImageFileFormat::Png.check(texture.format())?;
+    let readback = TextureReadback::copy(device, queue, texture)?;
 
-    let format = texture.format();
-    let block_size = format
-        .block_copy_size(None)
-        .ok_or(AppError::CaptureError(format!(
-            "texture format {format:?} cannot be copied"
-        )))?;
-
-    let color_type = png_color_type(format)?;
-
-    let width = texture.width();
-    let height = texture.height();
-    if width == 0 || height == 0 {
-        return Err(AppError::CaptureError(
-            "Texture height/width cannot be zero".into(),
-        ));
-    }
+    let device = device.clone();
+    let readback = background_task::spawn_future("texture-capture-readback", async move {
+        let [width, height] = readback.size();
+        let format = readback.format();
+        let pixels = readback.read(&device).await?;
+        ImageFileFormat::Png.encode(pixels, width, height, format)
+    });
 
-    let unpadded_bytes_per_row = width * block_size;
-    let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
-        * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    Ok(AsyncJob::new(async move {
+        let png = readback.await?;
+        save_png(file_name, png).await
+    }))
+}
 
-    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
-        label: Some("Texture Capture Readback Buffer"),
-        size: (padded_bytes_per_row as u64) * (height as u64),
-        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-        mapped_at_creation: false,
-    });
+/// A texture copied into a mappable buffer, waiting to be read.
+pub struct TextureReadback {
+    buffer: wgpu::Buffer,
+    width: u32,
+    height: u32,
+    format: wgpu::TextureFormat,
+    padded_bytes_per_row: u32,
+    unpadded_bytes_per_row: u32,
+}
 
-    let mut encoder = create_command_encoder(device, "Texture Capture Encoder");
-    encoder.copy_texture_to_buffer(
-        wgpu::TexelCopyTextureInfo {
-            texture,
-            mip_level: 0,
-            origin: wgpu::Origin3d::ZERO,
-            aspect: wgpu::TextureAspect::All,
-        },
-        wgpu::TexelCopyBufferInfo {
-            buffer: &buffer,
-            layout: wgpu::TexelCopyBufferLayout {
-                offset: 0,
-                bytes_per_row: Some(padded_bytes_per_row),
-                rows_per_image: Some(height),
+impl TextureReadback {
+    /// Encodes and submits a copy of `texture`'s first mip level.
+    pub fn copy(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture: &wgpu::Texture,
+    ) -> AppResult<Self> {
+        if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            return Err(AppError::CaptureError(
+                "the texture needs the 'Copy Source' usage to be saved as an image".into(),
+            ));
+        }
+
+        let format = texture.format();
+        let block_size = format
+            .block_copy_size(None)
+            .ok_or(AppError::CaptureError(format!(
+                "texture format {format:?} cannot be copied"
+            )))?;
+
+        let width = texture.width();
+        let height = texture.height();
+        if width == 0 || height == 0 {
+            return Err(AppError::CaptureError(
+                "Texture height/width cannot be zero".into(),
+            ));
+        }
+
+        let unpadded_bytes_per_row = width * block_size;
+        let padded_bytes_per_row = unpadded_bytes_per_row
+            .div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Texture Capture Readback Buffer"),
+            size: (padded_bytes_per_row as u64) * (height as u64),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = create_command_encoder(device, "Texture Capture Encoder");
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
             },
-        },
-        wgpu::Extent3d {
-            width,
-            height,
-            depth_or_array_layers: 1,
-        },
-    );
-    queue.submit([encoder.finish()]);
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit([encoder.finish()]);
 
-    let device = device.clone();
-    let readback = background_task::spawn_future("texture-capture-readback", async move {
-        let pixels = read_buffer(
-            &device,
-            &buffer,
+        Ok(Self {
+            buffer,
+            width,
             height,
+            format,
             padded_bytes_per_row,
             unpadded_bytes_per_row,
-        )
-        .await?;
-        encode_png(&pixels, width, height, color_type)
-    });
+        })
+    }
 
-    Ok(AsyncJob::new(async move {
-        let png = readback.await?;
-        save_png(file_name, png).await
-    }))
+    pub fn size(&self) -> [u32; 2] {
+        [self.width, self.height]
+    }
+
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// Waits for the copy and returns the texels, tightly packed row by row.
+    pub async fn read(self, device: &wgpu::Device) -> AppResult<Vec<u8>> {
+        read_buffer(
+            device,
+            &self.buffer,
+            self.height,
+            self.padded_bytes_per_row,
+            self.unpadded_bytes_per_row,
+        )
+        .await
+    }
 }
 
-async fn read_buffer(
+/// Maps `buffer` and returns its contents, `height` rows of
+/// `unpadded_bytes_per_row` bytes with the row padding stripped.
+pub async fn read_buffer(
     device: &wgpu::Device,
     buffer: &wgpu::Buffer,
     height: u32,
@@ -201,27 +246,111 @@ async fn read_buffer(
     Ok(pixels)
 }
 
-fn png_color_type(format: wgpu::TextureFormat) -> AppResult<image::ExtendedColorType> {
-    match format {
-        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => {
-            Ok(image::ExtendedColorType::Rgba8)
+/// A file format texture images can be saved as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(clap::ValueEnum))]
+pub enum ImageFileFormat {
+    Png,
+    Exr,
+}
+
+impl ImageFileFormat {
+    /// EXR for float textures, whose values PNG would clamp, and PNG otherwise.
+    pub fn for_texture(format: wgpu::TextureFormat) -> Self {
+        match format {
+            wgpu::TextureFormat::Rgba16Float | wgpu::TextureFormat::Rgba32Float => Self::Exr,
+            _ => Self::Png,
         }
-        other => Err(AppError::CaptureError(format!(
-            "Texture format {other:?} cannot be saved as a PNG image"
-        ))),
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Exr => "exr",
+        }
+    }
+
+    /// The name of the `frame`th image of a numbered sequence.
+    pub fn frame_file_name(self, frame: u32) -> String {
+        format!("frame_{frame:05}.{}", self.extension())
+    }
+
+    /// Fails if texels of `format` can't be saved in this file format.
+    pub fn check(self, format: wgpu::TextureFormat) -> AppResult<()> {
+        let supported = match self {
+            Self::Png => matches!(
+                format,
+                wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb
+            ),
+            Self::Exr => matches!(
+                format,
+                wgpu::TextureFormat::Rgba16Float | wgpu::TextureFormat::Rgba32Float
+            ),
+        };
+        if !supported {
+            return Err(AppError::CaptureError(format!(
+                "Texture format {format:?} cannot be saved as a {} image",
+                self.extension().to_uppercase()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Encodes `pixels`, tightly packed texels of `format`, into an image file.
+    pub fn encode(
+        self,
+        pixels: Vec<u8>,
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+    ) -> AppResult<Vec<u8>> {
+        self.check(format)?;
+        let image = texture_image(pixels, width, height, format)?;
+        let mut file = Cursor::new(Vec::new());
+        let image_format = match self {
+            Self::Png => image::ImageFormat::Png,
+            Self::Exr => image::ImageFormat::OpenExr,
+        };
+        image.write_to(&mut file, image_format)?;
+        Ok(file.into_inner())
     }
 }
 
-fn encode_png(
-    pixels: &[u8],
+/// Wraps `pixels`, tightly packed texels of `format`, in an image: 8-bit
+/// RGBA as it is, and float RGBA as 32-bit floats.
+pub fn texture_image(
+    pixels: Vec<u8>,
     width: u32,
     height: u32,
-    color_type: image::ExtendedColorType,
-) -> AppResult<Vec<u8>> {
-    let mut png = Vec::new();
-    let encoder = image::codecs::png::PngEncoder::new(Cursor::new(&mut png));
-    encoder.write_image(pixels, width, height, color_type)?;
-    Ok(png)
+    format: wgpu::TextureFormat,
+) -> AppResult<image::DynamicImage> {
+    let image = match format {
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => {
+            image::RgbaImage::from_raw(width, height, pixels).map(image::DynamicImage::from)
+        }
+        wgpu::TextureFormat::Rgba16Float => {
+            let texels = pixels
+                .chunks_exact(2)
+                .map(|bytes| half::f16::from_le_bytes([bytes[0], bytes[1]]).to_f32())
+                .collect();
+            image::Rgba32FImage::from_raw(width, height, texels).map(image::DynamicImage::from)
+        }
+        wgpu::TextureFormat::Rgba32Float => {
+            let texels = pixels
+                .chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .collect();
+            image::Rgba32FImage::from_raw(width, height, texels).map(image::DynamicImage::from)
+        }
+        other => {
+            return Err(AppError::CaptureError(format!(
+                "Texture format {other:?} cannot be turned into an image"
+            )));
+        }
+    };
+    image.ok_or(AppError::CaptureError(
+        "the texels don't fill the texture".into(),
+    ))
 }
 
 async fn save_png(file_name: String, png: Vec<u8>) -> AppResult<()> {
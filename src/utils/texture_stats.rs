@@ -0,0 +1,671 @@
+//! Texture statistics: per-channel min, max, mean and histogram, and the NaN
+//! and Inf texels of a texture view, computed by a reduction on the GPU.
+//!
+//! Three compute dispatches do the work: each workgroup reduces its texels
+//! into a partial, one workgroup reduces the partials into a summary, and a
+//! last dispatch bins every texel into a histogram between the summary's min
+//! and max. Only the summary and the histogram are read back, through the
+//! same async mapping as [`readback`](crate::utils::readback).
+
+use std::{collections::HashMap, task::Poll};
+
+use crate::{
+    error::{AppError, AppResult},
+    project::{
+        ResourceId, TextureViewId, resource::texture_view::TextureView, storage::RuntimeStorage,
+    },
+    utils::{
+        async_job::AsyncJob, background_task, texture_capture::read_buffer,
+        wgpu_utils::create_command_encoder,
+    },
+};
+
+/// Buckets in each channel's histogram.
+pub const HISTOGRAM_BINS: usize = 64;
+/// How many NaN or Inf texels are located. The rest are only counted.
+pub const MAX_LOCATIONS: usize = 16;
+/// Frames between two analyses in continuous mode.
+pub const CONTINUOUS_INTERVAL_FRAMES: u32 = 30;
+
+const WORKGROUP_SIZE: u32 = 16;
+/// Bytes of the shader's `Partial` struct.
+const PARTIAL_SIZE: u64 = 64;
+/// Bytes of the shader's `Summary` struct, padded to its 16-byte alignment.
+const SUMMARY_SIZE: u64 = (72 + 2 * 8 * MAX_LOCATIONS as u64).next_multiple_of(16);
+const HISTOGRAM_SIZE: u64 = 4 * 4 * HISTOGRAM_BINS as u64;
+
+/// The statistics of one channel, over its finite values. Min, max and mean
+/// are NaN if it has none.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelStatistics {
+    pub name: &'static str,
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    /// Texels whose value in this channel is neither NaN nor Inf.
+    pub finite: u32,
+    /// Texel counts in [`HISTOGRAM_BINS`] equal buckets from `min` to `max`.
+    pub histogram: Vec<u32>,
+}
+
+/// Texels with a NaN, or an Inf, in any channel.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpecialTexels {
+    pub count: u32,
+    /// Up to [`MAX_LOCATIONS`] of them, in row order.
+    pub locations: Vec<[u32; 2]>,
+}
+
+/// The result of analyzing a texture view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextureStatistics {
+    pub size: [u32; 2],
+    pub channels: Vec<ChannelStatistics>,
+    pub nan: SpecialTexels,
+    pub inf: SpecialTexels,
+}
+
+impl TextureStatistics {
+    /// Decodes the shader's `Summary` struct followed by its histogram.
+    fn parse(bytes: &[u8], size: [u32; 2], names: &[&'static str]) -> Self {
+        let word = |index: usize| {
+            let start = index * 4;
+            u32::from_le_bytes([
+                bytes[start],
+                bytes[start + 1],
+                bytes[start + 2],
+                bytes[start + 3],
+            ])
+        };
+        let float = |index: usize| f32::from_bits(word(index));
+        let locations = |start: usize, count: u32| {
+            let mut locations: Vec<[u32; 2]> = (0..(count as usize).min(MAX_LOCATIONS))
+                .map(|index| [word(start + index * 2), word(start + index * 2 + 1)])
+                .collect();
+            // Invocations record them in whatever order they run.
+            locations.sort_by_key(|[x, y]| (*y, *x));
+            locations
+        };
+
+        let channels = names
+            .iter()
+            .enumerate()
+            .map(|(channel, name)| {
+                let finite = word(12 + channel);
+                let histogram_start = SUMMARY_SIZE as usize / 4 + channel * HISTOGRAM_BINS;
+                // Without a finite value, the shader's starting bounds are left over.
+                let finite_or_nan = |value: f32| if finite == 0 { f32::NAN } else { value };
+                ChannelStatistics {
+                    name,
+                    min: finite_or_nan(float(channel)),
+                    max: finite_or_nan(float(4 + channel)),
+                    mean: finite_or_nan(float(8 + channel) / finite as f32),
+                    finite,
+                    histogram: (histogram_start..histogram_start + HISTOGRAM_BINS)
+                        .map(word)
+                        .collect(),
+                }
+            })
+            .collect();
+
+        let (nan_count, inf_count) = (word(16), word(17));
+        Self {
+            size,
+            channels,
+            nan: SpecialTexels {
+                count: nan_count,
+                locations: locations(18, nan_count),
+            },
+            inf: SpecialTexels {
+                count: inf_count,
+                locations: locations(18 + 2 * MAX_LOCATIONS, inf_count),
+            },
+        }
+    }
+}
+
+/// The analysis settings and latest result of one texture view.
+#[derive(Default)]
+pub struct TextureAnalysis {
+    /// Analyzes again every [`CONTINUOUS_INTERVAL_FRAMES`] frames, and reports
+    /// NaN texels in the error panel.
+    pub continuous: bool,
+    result: Option<AppResult<TextureStatistics>>,
+    task: Option<AsyncJob<AppResult<TextureStatistics>>>,
+    requested: bool,
+    frames_since_run: u32,
+    /// The NaN report of the last continuous analysis.
+    warning: Option<AppError>,
+}
+
+impl TextureAnalysis {
+    /// The latest result, or why the analysis failed.
+    pub fn result(&self) -> Option<&AppResult<TextureStatistics>> {
+        self.result.as_ref()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task.is_some()
+    }
+
+    /// Analyzes the view on the next frame.
+    pub fn request(&mut self) {
+        self.requested = true;
+    }
+}
+
+/// Owns every texture view's analysis, and the pipelines they share.
+#[derive(Default)]
+pub struct TextureAnalyses {
+    states: HashMap<TextureViewId, TextureAnalysis>,
+    /// Built on first use, one for float and one for depth textures.
+    pipelines: HashMap<bool, AnalysisPipelines>,
+}
+
+impl TextureAnalyses {
+    pub fn state_mut(&mut self, id: TextureViewId) -> &mut TextureAnalysis {
+        self.states.entry(id).or_default()
+    }
+
+    pub fn remove(&mut self, id: TextureViewId) {
+        self.states.remove(&id);
+    }
+
+    /// NaN texels found by continuous analyses.
+    pub fn diagnostics(&self) -> impl Iterator<Item = (ResourceId, &AppError)> {
+        self.states
+            .iter()
+            .filter_map(|(id, state)| Some(((*id).into(), state.warning.as_ref()?)))
+    }
+
+    /// Starts the analyses that are due, and collects finished ones. Call it
+    /// after the frame is submitted, so analyses see its results.
+    pub fn tick(
+        &mut self,
+        runtime_texture_views: &RuntimeStorage<TextureView>,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) {
+        for (id, state) in &mut self.states {
+            if let Some(task) = &mut state.task
+                && let Poll::Ready(result) = task.try_resolve()
+            {
+                state.warning = match &result {
+                    Ok(statistics) if state.continuous => nan_warning(statistics),
+                    _ => None,
+                };
+                state.result = Some(result);
+                state.task = None;
+            }
+            if !state.continuous {
+                state.warning = None;
+            }
+
+            if state.task.is_some() {
+                continue;
+            }
+            state.frames_since_run = state.frames_since_run.saturating_add(1);
+            let due = state.requested
+                || (state.continuous && state.frames_since_run >= CONTINUOUS_INTERVAL_FRAMES);
+            if !due {
+                continue;
+            }
+
+            let started = runtime_texture_views.get_init(*id).and_then(|runtime| {
+                let Some(runtime) = runtime else {
+                    return Ok(None);
+                };
+                start_analysis(
+                    &mut self.pipelines,
+                    device,
+                    queue,
+                    runtime.inner(),
+                    runtime.format(),
+                )
+                .map(Some)
+            });
+            match started {
+                Ok(Some(task)) => state.task = Some(task),
+                Ok(None) => continue, // still being created: try again next frame
+                Err(error) => state.result = Some(Err(error)),
+            }
+            state.requested = false;
+            state.frames_since_run = 0;
+        }
+    }
+}
+
+fn nan_warning(statistics: &TextureStatistics) -> Option<AppError> {
+    (statistics.nan.count > 0).then_some(AppError::TextureNan)
+}
+
+/// The channel names of a texel as the shader loads it.
+fn channel_names(format: wgpu::TextureFormat) -> &'static [&'static str] {
+    match (format.is_depth_stencil_format(), format.components()) {
+        (true, _) => &["depth"],
+        (false, 1) => &["r"],
+        (false, 2) => &["r", "g"],
+        (false, 3) => &["r", "g", "b"],
+        (false, _) => &["r", "g", "b", "a"],
+    }
+}
+
+fn start_analysis(
+    pipelines: &mut HashMap<bool, AnalysisPipelines>,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    view: &wgpu::TextureView,
+    format: wgpu::TextureFormat,
+) -> AppResult<AsyncJob<AppResult<TextureStatistics>>> {
+    let texture = view.texture();
+    if !texture
+        .usage()
+        .contains(wgpu::TextureUsages::TEXTURE_BINDING)
+    {
+        return Err(AppError::AnalysisTextureUsage);
+    }
+    // Combined depth-stencil formats have no sample type without an aspect.
+    let aspect = format
+        .has_depth_aspect()
+        .then_some(wgpu::TextureAspect::DepthOnly);
+    let depth = match format.sample_type(aspect, None) {
+        Some(wgpu::TextureSampleType::Float { .. }) => false,
+        Some(wgpu::TextureSampleType::Depth) => true,
+        _ => {
+            return Err(AppError::AnalysisFormat {
+                format: format!("{format:?}"),
+            });
+        }
+    };
+    let pipelines = pipelines
+        .entry(depth)
+        .or_insert_with(|| AnalysisPipelines::new(device, depth));
+
+    // The first layer and mip level, in the view's format.
+    let source = texture.create_view(&wgpu::TextureViewDescriptor {
+        label: Some("Texture Analysis Source"),
+        format: Some(format),
+        dimension: Some(wgpu::TextureViewDimension::D2),
+        aspect: match depth {
+            true => wgpu::TextureAspect::DepthOnly,
+            false => wgpu::TextureAspect::All,
+        },
+        mip_level_count: Some(1),
+        array_layer_count: Some(1),
+        ..Default::default()
+    });
+
+    let size = [texture.width(), texture.height()];
+    let groups = size.map(|extent| extent.div_ceil(WORKGROUP_SIZE));
+    let storage = |label: &str, size: u64| {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        })
+    };
+    let partials = storage(
+        "Texture Analysis Partials",
+        (groups[0] * groups[1]) as u64 * PARTIAL_SIZE,
+    );
+    let summary = storage("Texture Analysis Summary", SUMMARY_SIZE);
+    let histogram = storage("Texture Analysis Histogram", HISTOGRAM_SIZE);
+    let readback = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Texture Analysis Readback Buffer"),
+        size: SUMMARY_SIZE + HISTOGRAM_SIZE,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Texture Analysis Bind Group"),
+        layout: &pipelines.bind_group_layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&source),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: partials.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: summary.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: histogram.as_entire_binding(),
+            },
+        ],
+    });
+
+    let mut encoder = create_command_encoder(device, "Texture Analysis Encoder");
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Texture Analysis"),
+            timestamp_writes: None,
+        });
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.set_pipeline(&pipelines.reduce_texels);
+        pass.dispatch_workgroups(groups[0], groups[1], 1);
+        pass.set_pipeline(&pipelines.reduce_partials);
+        pass.dispatch_workgroups(1, 1, 1);
+        pass.set_pipeline(&pipelines.bin_texels);
+        pass.dispatch_workgroups(groups[0], groups[1], 1);
+    }
+    encoder.copy_buffer_to_buffer(&summary, 0, &readback, 0, SUMMARY_SIZE);
+    encoder.copy_buffer_to_buffer(&histogram, 0, &readback, SUMMARY_SIZE, HISTOGRAM_SIZE);
+    queue.submit([encoder.finish()]);
+
+    let names = channel_names(format);
+    let device = device.clone();
+    let length = (SUMMARY_SIZE + HISTOGRAM_SIZE) as u32;
+    Ok(background_task::spawn_future(
+        "texture-analysis-readback",
+        async move {
+            let bytes = read_buffer(&device, &readback, 1, length, length).await?;
+            Ok(TextureStatistics::parse(&bytes, size, names))
+        },
+    ))
+}
+
+/// The three reduction pipelines, sharing one bind group layout.
+struct AnalysisPipelines {
+    bind_group_layout: wgpu::BindGroupLayout,
+    reduce_texels: wgpu::ComputePipeline,
+    reduce_partials: wgpu::ComputePipeline,
+    bin_texels: wgpu::ComputePipeline,
+}
+
+impl AnalysisPipelines {
+    fn new(device: &wgpu::Device, depth: bool) -> Self {
+        let storage = |binding: u32| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Texture Analysis Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: match depth {
+                            true => wgpu::TextureSampleType::Depth,
+                            false => wgpu::TextureSampleType::Float { filterable: false },
+                        },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                storage(1),
+                storage(2),
+                storage(3),
+            ],
+        });
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Texture Analysis Shader"),
+            source: wgpu::ShaderSource::Wgsl(shader_source(depth).into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Texture Analysis Pipeline Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+        let pipeline = |entry_point: &str| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(&format!("Texture Analysis ({entry_point})")),
+                layout: Some(&layout),
+                module: &module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+
+        Self {
+            reduce_texels: pipeline("reduce_texels"),
+            reduce_partials: pipeline("reduce_partials"),
+            bin_texels: pipeline("bin_texels"),
+            bind_group_layout,
+        }
+    }
+}
+
+fn shader_source(depth: bool) -> String {
+    let source = match depth {
+        true => DEPTH_SOURCE,
+        false => COLOR_SOURCE,
+    };
+    [source, ANALYSIS_SHADER]
+        .concat()
+        .replace("HISTOGRAM_BINS", &format!("{HISTOGRAM_BINS}u"))
+        .replace("MAX_LOCATIONS", &format!("{MAX_LOCATIONS}u"))
+}
+
+const COLOR_SOURCE: &str = r#"
+@group(0) @binding(0) var source: texture_2d<f32>;
+
+fn load(coords: vec2<u32>) -> vec4<f32> {
+    return textureLoad(source, coords, 0);
+}
+"#;
+
+const DEPTH_SOURCE: &str = r#"
+@group(0) @binding(0) var source: texture_depth_2d;
+
+fn load(coords: vec2<u32>) -> vec4<f32> {
+    return vec4<f32>(textureLoad(source, coords, 0), 0.0, 0.0, 1.0);
+}
+"#;
+
+const ANALYSIS_SHADER: &str = r#"
+struct Partial {
+    low: vec4<f32>,
+    high: vec4<f32>,
+    sum: vec4<f32>,
+    finite: vec4<u32>,
+}
+
+struct Summary {
+    low: vec4<f32>,
+    high: vec4<f32>,
+    sum: vec4<f32>,
+    finite: vec4<u32>,
+    nan_count: atomic<u32>,
+    inf_count: atomic<u32>,
+    nan_locations: array<vec2<u32>, MAX_LOCATIONS>,
+    inf_locations: array<vec2<u32>, MAX_LOCATIONS>,
+}
+
+@group(0) @binding(1) var<storage, read_write> partials: array<Partial>;
+@group(0) @binding(2) var<storage, read_write> summary: Summary;
+@group(0) @binding(3) var<storage, read_write> histogram: array<atomic<u32>>;
+
+// WGSL has no infinity literal, and shaders may assume floats are finite, so
+// NaN and Inf are told apart by their bits.
+const LARGEST: f32 = 3.40282347e38;
+const EXPONENT: u32 = 0x7f800000u;
+const MANTISSA: u32 = 0x007fffffu;
+
+var<workgroup> shared_low: array<vec4<f32>, 256>;
+var<workgroup> shared_high: array<vec4<f32>, 256>;
+var<workgroup> shared_sum: array<vec4<f32>, 256>;
+var<workgroup> shared_finite: array<vec4<u32>, 256>;
+
+fn is_finite(texel: vec4<f32>) -> vec4<bool> {
+    return (bitcast<vec4<u32>>(texel) & vec4<u32>(EXPONENT)) != vec4<u32>(EXPONENT);
+}
+
+fn is_nan(texel: vec4<f32>) -> vec4<bool> {
+    let bits = bitcast<vec4<u32>>(texel);
+    return !is_finite(texel) & ((bits & vec4<u32>(MANTISSA)) != vec4<u32>(0u));
+}
+
+// Folds the shared partials of the workgroup into slot 0.
+fn reduce_shared(local: u32) {
+    for (var stride = 128u; stride > 0u; stride = stride >> 1u) {
+        workgroupBarrier();
+        if local < stride {
+            shared_low[local] = min(shared_low[local], shared_low[local + stride]);
+            shared_high[local] = max(shared_high[local], shared_high[local + stride]);
+            shared_sum[local] = shared_sum[local] + shared_sum[local + stride];
+            shared_finite[local] = shared_finite[local] + shared_finite[local + stride];
+        }
+    }
+    workgroupBarrier();
+}
+
+fn record(coords: vec2<u32>, texel: vec4<f32>) {
+    let finite = is_finite(texel);
+    if all(finite) {
+        return;
+    }
+    if any(is_nan(texel)) {
+        let slot = atomicAdd(&summary.nan_count, 1u);
+        if slot < MAX_LOCATIONS {
+            summary.nan_locations[slot] = coords;
+        }
+    } else {
+        let slot = atomicAdd(&summary.inf_count, 1u);
+        if slot < MAX_LOCATIONS {
+            summary.inf_locations[slot] = coords;
+        }
+    }
+}
+
+@compute @workgroup_size(16, 16)
+fn reduce_texels(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(local_invocation_index) local: u32,
+    @builtin(workgroup_id) group: vec3<u32>,
+    @builtin(num_workgroups) groups: vec3<u32>,
+) {
+    var low = vec4<f32>(LARGEST);
+    var high = vec4<f32>(-LARGEST);
+    var sum = vec4<f32>(0.0);
+    var finite = vec4<u32>(0u);
+    if all(id.xy < textureDimensions(source)) {
+        let texel = load(id.xy);
+        let mask = is_finite(texel);
+        record(id.xy, texel);
+        low = select(low, texel, mask);
+        high = select(high, texel, mask);
+        sum = select(sum, texel, mask);
+        finite = select(finite, vec4<u32>(1u), mask);
+    }
+    shared_low[local] = low;
+    shared_high[local] = high;
+    shared_sum[local] = sum;
+    shared_finite[local] = finite;
+
+    reduce_shared(local);
+    if local == 0u {
+        partials[group.y * groups.x + group.x] = Partial(
+            shared_low[0],
+            shared_high[0],
+            shared_sum[0],
+            shared_finite[0],
+        );
+    }
+}
+
+@compute @workgroup_size(256)
+fn reduce_partials(@builtin(local_invocation_index) local: u32) {
+    var low = vec4<f32>(LARGEST);
+    var high = vec4<f32>(-LARGEST);
+    var sum = vec4<f32>(0.0);
+    var finite = vec4<u32>(0u);
+    for (var index = local; index < arrayLength(&partials); index += 256u) {
+        let partial = partials[index];
+        low = min(low, partial.low);
+        high = max(high, partial.high);
+        sum += partial.sum;
+        finite += partial.finite;
+    }
+    shared_low[local] = low;
+    shared_high[local] = high;
+    shared_sum[local] = sum;
+    shared_finite[local] = finite;
+
+    reduce_shared(local);
+    if local == 0u {
+        summary.low = shared_low[0];
+        summary.high = shared_high[0];
+        summary.sum = shared_sum[0];
+        summary.finite = shared_finite[0];
+    }
+}
+
+@compute @workgroup_size(16, 16)
+fn bin_texels(@builtin(global_invocation_id) id: vec3<u32>) {
+    if any(id.xy >= textureDimensions(source)) {
+        return;
+    }
+    let texel = load(id.xy);
+    let finite = is_finite(texel);
+    let range = summary.high - summary.low;
+    for (var channel = 0u; channel < 4u; channel++) {
+        if !finite[channel] {
+            continue;
+        }
+        var bin = 0u;
+        if range[channel] > 0.0 {
+            let t = (texel[channel] - summary.low[channel]) / range[channel];
+            bin = min(u32(t * f32(HISTOGRAM_BINS)), HISTOGRAM_BINS - 1u);
+        }
+        atomicAdd(&histogram[channel * HISTOGRAM_BINS + bin], 1u);
+    }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analysis_shaders_validate() {
+        for depth in [false, true] {
+            let source = shader_source(depth);
+            let module = naga::front::wgsl::parse_str(&source).unwrap();
+            naga::valid::Validator::new(
+                naga::valid::ValidationFlags::all(),
+                naga::valid::Capabilities::empty(),
+            )
+            .validate(&module)
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn summaries_parse_into_channels() {
+        let mut words = vec![0u32; (SUMMARY_SIZE + HISTOGRAM_SIZE) as usize / 4];
+        words[0] = (-1.0f32).to_bits(); // low.r
+        words[4] = 3.0f32.to_bits(); // high.r
+        words[8] = 4.0f32.to_bits(); // sum.r
+        words[12] = 4; // finite.r
+        words[16] = 2; // nan_count
+        words[18..22].copy_from_slice(&[7, 9, 3, 1]);
+        let histogram_start = SUMMARY_SIZE as usize / 4;
+        words[histogram_start] = 1;
+        words[histogram_start + HISTOGRAM_BINS - 1] = 3;
+
+        let statistics = TextureStatistics::parse(bytemuck::cast_slice(&words), [4, 4], &["r"]);
+        let r = &statistics.channels[0];
+        assert_eq!((r.min, r.max, r.mean, r.finite), (-1.0, 3.0, 1.0, 4));
+        assert_eq!(r.histogram.iter().sum::<u32>(), 4);
+        assert_eq!(statistics.nan.count, 2);
+        assert_eq!(statistics.nan.locations, [[3, 1], [7, 9]]);
+        assert_eq!(statistics.inf, SpecialTexels::default());
+    }
+}
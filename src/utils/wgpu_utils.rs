@@ -1,7 +1,11 @@
+#[cfg(not(target_arch = "wasm32"))]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
 use crate::error::{AppError, AppResult};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::project::schema;
 
 /// The sampler address modes the application supports.
 ///
@@ -10,6 +14,7 @@ use crate::error::{AppError, AppResult};
 /// (and needs a border color), which the app never enables. Owning the enum
 /// keeps that unsupported mode out of the project entirely.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, EnumIter)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum AddressMode {
     #[default]
@@ -47,12 +52,33 @@ impl AddressMode {
 /// The app never enables those features, so they could only ever be left at
 /// their defaults. Owning the struct keeps them out of the project and the UI.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PrimitiveState {
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        schemars(schema_with = "schema::primitive_topology")
+    )]
     pub topology: wgpu::PrimitiveTopology,
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        schemars(schema_with = "schema::optional_index_format")
+    )]
     pub strip_index_format: Option<wgpu::IndexFormat>,
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        schemars(schema_with = "schema::front_face")
+    )]
     pub front_face: wgpu::FrontFace,
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        schemars(schema_with = "schema::optional_face")
+    )]
     pub cull_mode: Option<wgpu::Face>,
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        schemars(schema_with = "schema::polygon_mode")
+    )]
     pub polygon_mode: wgpu::PolygonMode,
 }
 
@@ -97,6 +123,7 @@ impl PrimitiveState {
 /// passing the full wgpu format list around) lets us match on it exhaustively
 /// and keeps unsupported formats out of the project.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum TextureFormat {
     Rgba8UnormSrgb,
@@ -174,12 +201,12 @@ impl ShaderSourceKind {
     }
 }
 
-pub fn compile_shader(
-    device: &wgpu::Device,
-    label: &str,
+/// Parses and validates shader source into a naga module, without touching the
+/// GPU. The validation info is returned alongside for reflection.
+pub fn parse_shader(
     source: &str,
     kind: ShaderSourceKind,
-) -> AppResult<wgpu::ShaderModule> {
+) -> AppResult<(naga::Module, naga::valid::ModuleInfo)> {
     let module = match kind {
         ShaderSourceKind::Wgsl => naga::front::wgsl::parse_str(source)
             .map_err(|err| AppError::ShaderParseError(err.emit_to_string(source)))?,
@@ -188,7 +215,7 @@ pub fn compile_shader(
             .map_err(|errors| AppError::ShaderParseError(errors.emit_to_string(source)))?,
     };
 
-    let _module_info: naga::valid::ModuleInfo = naga::valid::Validator::new(
+    let module_info = naga::valid::Validator::new(
         naga::valid::ValidationFlags::all(),
         naga::valid::Capabilities::all(),
     )
@@ -197,10 +224,18 @@ pub fn compile_shader(
     .validate(&module)
     .map_err(|err| AppError::ShaderCompilationError(err.emit_to_string(source)))?;
 
-    Ok(device.create_shader_module(wgpu::ShaderModuleDescriptor {
+    Ok((module, module_info))
+}
+
+pub fn compile_shader(
+    device: &wgpu::Device,
+    label: &str,
+    module: naga::Module,
+) -> wgpu::ShaderModule {
+    device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: Some(label),
         source: wgpu::ShaderSource::Naga(std::borrow::Cow::Owned(module)),
-    }))
+    })
 }
 
 pub fn create_command_encoder(device: &wgpu::Device, label: &str) -> wgpu::CommandEncoder {
@@ -1,5 +1,8 @@
 use slotmap::SecondaryMap;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{error::AppError, utils::recording::Recording};
+
 use crate::{
     app::AppEvent,
     error::AppResult,
@@ -9,15 +12,18 @@ use crate::{
         identifier::ProjectSource,
     },
     project::{
-        DimensionId, ModelId, Project, ResourceId, ResourceKind, RuntimeProject, TextureId,
-        ViewportId,
+        DimensionId, ModelId, Project, ResourceId, ResourceKind, RuntimeProject, ShaderId,
+        TextureId, ViewportId,
+        frame_capture::{CaptureRecorder, FrameCapture},
+        lint,
         paths::FilePath,
         render::{self, PresentationRender},
         resource::{
             bindgroup::BindGroupCreationContext,
             camera::CameraCreationContext,
-            compute_pass,
-            model::{ModelCreationContext, TextureType},
+            compute_pass, copy_pass,
+            model::{ModelCreationContext, TextureType, vertex_buffer::VertexBufferSpec},
+            presentation::PassStep,
             render_pass, render_pipeline,
             shader::ShaderCreationContext,
             texture::TextureCreationContext,
@@ -38,12 +44,16 @@ use crate::{
     },
     utils::{
         async_job::AsyncJob,
-        derive::{derive_texture_from_material_path, derive_texture_view},
+        derive::{
+            derive_texture_from_material_path, derive_texture_view, derive_uniform_from_struct,
+        },
         event_queue::EventQueue,
         fps::FrameTimeTracker,
+        image_compare::ImageCompare,
         key::KeyboardState,
+        pixel_probe::PixelProbe,
+        readback::{ReadbackTarget, Readbacks},
         texture_capture::TextureCaptures,
-        wgpu_utils::create_command_encoder,
     },
 };
 
@@ -53,6 +63,9 @@ pub struct Workspace {
     tracker: SyncTracker,
     file_storage: FileStorage,
     project_save_state: ProjectSaveState,
+    /// Whether project changes are written back to `project.json`. Headless
+    /// renders override sizes they must not persist.
+    autosave: bool,
     rename_state: Option<ui::rename::RenameState>,
     event_queue: EventQueue<StateEvent>,
     inspector_tree_pane: TreePane<InspectorPane>,
@@ -66,6 +79,12 @@ pub struct Workspace {
     mtl_dependencies: SecondaryMap<ModelId, Vec<FilePath>>,
     elapsed: instant::Duration,
     texture_captures: TextureCaptures,
+    readbacks: Readbacks,
+    pixel_probe: PixelProbe,
+    frame_capture: FrameCapture,
+    image_compare: ImageCompare,
+    #[cfg(not(target_arch = "wasm32"))]
+    recording: Recording,
     toasts: egui_notify::Toasts,
     material_bind_groups_modal: Option<MaterialBindGroupsModal>,
 }
@@ -93,11 +112,22 @@ pub enum StateEvent {
     OpenFile(FilePath),
     InspectResource(ResourceId),
     OpenViewport(ViewportId),
+    OpenFrameGraph,
+    OpenFrameCapture,
+    OpenImageCompare,
+    OpenLint,
+    /// Open the readback tab of a texture or uniform.
+    OpenReadback(ReadbackTarget),
     CreateResource(ResourceKind),
     StartRename(RenameTarget),
     CancelRename,
     ApplyRename(RenameTarget, String),
     DeleteResource(ResourceId),
+    /// Unset, or remove from its list, every reference `owner` has to `target`.
+    ClearReference {
+        owner: ResourceId,
+        target: ResourceId,
+    },
     CreateFile(FilePath),
     CreateFolder(FilePath),
     DeleteFile(FilePath),
@@ -120,6 +150,18 @@ pub enum StateEvent {
         path: FilePath,
         texture_type: TextureType,
     },
+    /// Create a new uniform whose fields mirror a struct declared in the shader.
+    DeriveUniformFromStruct {
+        shader_id: ShaderId,
+        struct_name: String,
+    },
+    /// Replace the presentation schedule, e.g. with the order its dependencies suggest.
+    SetPresentationSteps(Vec<PassStep>),
+    /// Replace a model's vertex buffer layout, e.g. with one that matches a shader.
+    SetVertexBufferSpec {
+        model_id: ModelId,
+        spec: VertexBufferSpec,
+    },
 }
 
 impl Workspace {
@@ -148,9 +190,21 @@ impl Workspace {
         Ok(workspace)
     }
 
+    /// Opens a project for rendering without a window. The project isn't
+    /// remembered as recent, and changes to it are never saved.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn open_headless(app_fs: &AppFileSystem, source: ProjectSource) -> AppResult<Self> {
+        let (file_system, file_watcher) = app_fs.mount_project(source.clone()).await?;
+        let file_storage = FileStorage::new(source, file_system, file_watcher);
+
+        let mut workspace = Self::open_project(file_storage).await?;
+        workspace.autosave = false;
+        Ok(workspace)
+    }
+
     async fn open_project(file_storage: FileStorage) -> AppResult<Self> {
         let project_bytes = file_storage.read(&FilePath::project_json()).await?;
-        let project: Project = serde_json::from_slice(&project_bytes)?;
+        let project = Project::deserialize(&project_bytes)?;
 
         let inspector_tree_pane = TreePane::new("inspector");
         let mut viewport_tree_pane = TreePane::new("viewport");
@@ -182,20 +236,34 @@ impl Workspace {
             tracker: SyncTracker::default(),
             file_storage,
             project_save_state,
+            autosave: true,
             dimension_owners: Default::default(),
             mtl_dependencies: SecondaryMap::default(),
             elapsed: instant::Duration::ZERO,
             texture_captures: TextureCaptures::default(),
+            readbacks: Readbacks::default(),
+            pixel_probe: PixelProbe::default(),
+            frame_capture: FrameCapture::default(),
+            image_compare: ImageCompare::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            recording: Recording::default(),
             toasts: egui_notify::Toasts::default(),
             material_bind_groups_modal: None,
         })
     }
 
     pub fn render(&mut self, ctx: &mut AppContext) {
+        // A recording advances the clock by its own frame time, whatever the real one.
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(frame_time) = self.recording.frame_time() {
+            ctx.dt = frame_time;
+        }
         self.elapsed += ctx.dt;
         self.handle_events();
-        self.project_save_state
-            .tick(&self.project, &mut self.file_storage);
+        if self.autosave {
+            self.project_save_state
+                .tick(&self.project, &mut self.file_storage);
+        }
 
         self.file_storage.tick(&mut self.tracker);
 
@@ -212,67 +280,190 @@ impl Workspace {
         }
 
         let resources_changed = self.tick_objects(ctx);
+        self.runtime_project.render_graph.refresh(&self.project);
+        self.runtime_project
+            .drop_stale_requests(&self.project.presentation);
 
-        // Compute dispatches get their own encoder, which is *always* submitted. The viewport
-        // render below uses a separate, droppable encoder: when a render pass bails out on a
-        // still-rebuilding or errored resource, that whole encoder is discarded without being
-        // finished. If the compute dispatches shared it, a dropped viewport frame would silently
-        // drop them too, and an `OnChange` pass, which only dispatches on the frame its inputs
-        // change, would miss that one dispatch and never re-run, leaving its output stuck.
-        let mut compute_encoder = create_command_encoder(ctx.device, "Compute Encoder");
+        let snapshot = self.project.snapshot();
+        let should_render = self
+            .runtime_project
+            .poll_presentation_errors(snapshot, resources_changed);
+
+        // Every step gets its own command buffer, and the frame is *always* submitted. Render
+        // steps are resolved before anything is encoded, and skipped together when one of them
+        // isn't ready, so a still-rebuilding resource never drops the compute steps with it: an
+        // `OnChange` pass, which only dispatches on the frame its inputs change, would otherwise
+        // miss that one dispatch and never re-run, leaving its output stuck. Separate buffers
+        // also keep a validation error in one step from invalidating the others.
+        let mut frame = render::FrameEncoder::new(ctx.device);
         let mut compute_ctx = render::ComputeDispatchContext {
             compute_passes: &self.project.compute_passes,
             runtime_compute_passes: &mut self.runtime_project.compute_passes,
             runtime_bind_groups: &self.runtime_project.bind_groups,
+            runtime_uniforms: &self.runtime_project.uniforms,
             dimensions: &self.project.dimensions,
             compute_accumulators: &mut self.runtime_project.compute_accumulators,
+            requested_compute_passes: &mut self.runtime_project.requested_compute_passes,
             tracker: &self.tracker,
             dt: ctx.dt,
         };
-        self.project
-            .presentation
-            .dispatch_computes(&mut compute_encoder, &mut compute_ctx);
-
-        ctx.queue.submit(std::iter::once(compute_encoder.finish()));
-
-        // Now that the compute dispatch has consumed this frame's change set, it can be cleared.
-        self.tracker.clear_changes();
-
-        let snapshot = self.project.snapshot();
-        if !self
-            .runtime_project
-            .poll_presentation_errors(snapshot, resources_changed)
-        {
-            return;
-        }
-
-        let render_ctx = render::RenderContext {
+        let mut written_textures = vec![];
+        let mut copy_ctx = render::CopyDispatchContext {
+            copy_passes: &self.project.copy_passes,
+            runtime_copy_passes: &mut self.runtime_project.copy_passes,
+            texture_views: &self.project.texture_views,
+            runtime_textures: &self.runtime_project.textures,
+            runtime_texture_views: &self.runtime_project.texture_views,
+            runtime_uniforms: &self.runtime_project.uniforms,
+            copy_accumulators: &mut self.runtime_project.copy_accumulators,
+            requested_copy_passes: &mut self.runtime_project.requested_copy_passes,
+            written_textures: &mut written_textures,
+            tracker: &self.tracker,
+            dt: ctx.dt,
+        };
+        let mut render_ctx = render::RenderContext {
             render_passes: &self.project.render_passes,
+            render_pipelines: &self.project.render_pipelines,
             runtime_render_passes: &self.runtime_project.render_passes,
             runtime_texture_views: &self.runtime_project.texture_views,
+            render_accumulators: &mut self.runtime_project.render_accumulators,
+            owed_render_passes: &mut self.runtime_project.owed_render_passes,
+            graph: self.runtime_project.render_graph.graph(),
+            should_render,
+            tracker: &self.tracker,
+            dt: ctx.dt,
         };
 
-        // The viewport render uses a separate, droppable encoder: if a render pass bails out
-        // because a resource is still rebuilding, we drop the whole encoder without finishing it.
-        //
-        // This avoids the flicker that would otherwise occur because of the pass LoadOp::Clear
-        // because of the `begin_render_pass` call.
-        let mut viewport_encoder = create_command_encoder(ctx.device, "Viewport Render Encoder");
-
-        let presentation = &self.project.presentation;
-        match presentation.render(&mut viewport_encoder, &render_ctx) {
-            Ok(true) => {
-                ctx.queue.submit([viewport_encoder.finish()]);
-            }
-            Ok(false) => {} // A resource is still pending, drop the encoder without submitting
-            Err(error) => {
-                let snapshot = self.project.snapshot();
-                let error = PresentationRender::Errored { error, snapshot };
-                self.runtime_project.presentation_render = error;
+        let mut capture = self.frame_capture.take_request().then(|| {
+            CaptureRecorder::new(
+                &self.project,
+                &self.runtime_project.textures,
+                &self.runtime_project.uniforms,
+                ctx.device,
+                ctx.queue,
+            )
+        });
+
+        let mut dispatched = vec![];
+        let result = self.project.presentation.execute(
+            &mut frame,
+            &mut compute_ctx,
+            &mut copy_ctx,
+            &mut render_ctx,
+            capture.as_mut(),
+            &mut dispatched,
+        );
+        if let Some(capture) = capture {
+            let frame = capture.finish(self.elapsed);
+            self.frame_capture
+                .store(frame, ctx.device, ctx.egui_renderer);
+        }
+
+        // Preview proxies convert what this frame's passes wrote.
+        frame.encode("Preview Encoder", |encoder| {
+            for (id, texture_view) in self.project.texture_views.list() {
+                if let Some(remap) = texture_view.preview_remap()
+                    && let Ok(Some(runtime)) = self.runtime_project.texture_views.get_init(id)
+                {
+                    runtime.encode_preview(ctx.queue, encoder, &remap);
+                }
             }
+        });
+        frame.submit(ctx.queue);
+
+        // Double-buffered textures swap between frames, never within one.
+        let swapped = self
+            .runtime_project
+            .swap_textures(&self.project, &dispatched);
+
+        // Read back once the frame is submitted and swapped, so readbacks see its results.
+        self.readbacks.tick(
+            &self.project,
+            &self.runtime_project,
+            ctx.device,
+            ctx.queue,
+            &mut self.toasts,
+        );
+        self.pixel_probe
+            .tick(&self.runtime_project, ctx.device, ctx.queue);
+        self.runtime_project.texture_analyses.tick(
+            &self.runtime_project.texture_views,
+            ctx.device,
+            ctx.queue,
+        );
+        self.image_compare.tick(
+            &self.project,
+            &self.runtime_project,
+            &self.file_storage,
+            ctx.device,
+            ctx.queue,
+        );
+        #[cfg(not(target_arch = "wasm32"))]
+        self.recording.tick(
+            main_viewport_texture(&self.project, &self.runtime_project),
+            ctx.device,
+            ctx.queue,
+            &mut self.toasts,
+        );
+
+        // Now that the compute dispatch has consumed this frame's change set, it can be cleared.
+        self.tracker.clear_changes();
+        // Copies and swaps change textures behind the tracker's back; report them for
+        // the next frame so `OnChange` passes reading that data rerun.
+        self.tracker
+            .push_data_changes(written_textures.into_iter().chain(swapped).map(Into::into));
+
+        if let Err(error) = result {
+            let snapshot = self.project.snapshot();
+            let error = PresentationRender::Errored { error, snapshot };
+            self.runtime_project.presentation_render = error;
         }
     }
 
+    /// Builds the project's resources without running the schedule, so a
+    /// headless render can wait for them before its first frame.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn build_resources(&mut self, ctx: &mut AppContext) {
+        self.handle_events();
+        self.file_storage.tick(&mut self.tracker);
+        self.tick_objects(ctx);
+        self.runtime_project.render_graph.refresh(&self.project);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn is_rebuilding(&self) -> bool {
+        self.runtime_project.is_rebuilding()
+    }
+
+    /// Every resource error, with the label of the resource it belongs to.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn errors(&self) -> impl Iterator<Item = (&str, &AppError)> {
+        self.runtime_project
+            .iter_errors()
+            .map(|(id, error)| (self.project.label(id).unwrap_or("Unknown"), error))
+    }
+
+    /// Resizes the main viewport as if its pane had been resized to `size`.
+    /// Takes effect on the next frame.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn resize_main_viewport(&mut self, size: Size2d) -> AppResult<()> {
+        let viewport_id = self
+            .project
+            .presentation
+            .main_viewport()
+            .ok_or(AppError::MissingMainViewport)?;
+        let event = ViewportEvent::Resize { size };
+        self.event_queue
+            .add(StateEvent::ViewportEvent(viewport_id, event));
+        Ok(())
+    }
+
+    /// The texture the main viewport shows.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn main_viewport_texture(&self) -> AppResult<&wgpu::Texture> {
+        main_viewport_texture(&self.project, &self.runtime_project)
+    }
+
     pub fn project_name(&self) -> &str {
         self.file_storage.project_source().project_name()
     }
@@ -300,6 +491,12 @@ impl Workspace {
             runtime_project: &mut self.runtime_project,
             rename_state: &mut self.rename_state,
             file_storage: &mut self.file_storage,
+            readbacks: &mut self.readbacks,
+            pixel_probe: &mut self.pixel_probe,
+            frame_capture: &mut self.frame_capture,
+            image_compare: &mut self.image_compare,
+            #[cfg(not(target_arch = "wasm32"))]
+            recording: &mut self.recording,
             backend,
             present_mode,
             frame_time,
@@ -352,6 +549,7 @@ impl Workspace {
                         ResourceId::RenderPass(id) => InspectorPane::RenderPass(id),
                         ResourceId::Presentation(id) => InspectorPane::Presentation(id),
                         ResourceId::ComputePass(id) => InspectorPane::ComputePass(id),
+                        ResourceId::CopyPass(id) => InspectorPane::CopyPass(id),
                     };
 
                     self.inspector_tree_pane.add_pane(pane);
@@ -371,6 +569,9 @@ impl Workspace {
                     self.runtime_project.unregister(id);
                     self.tracker.push_resource_change(id);
                 }
+                StateEvent::ClearReference { owner, target } => {
+                    lint::clear_reference(&mut self.project, owner, target);
+                }
                 StateEvent::CreateFile(parent_path) => {
                     let rename_target = RenameTarget::CreateFile(parent_path);
                     if let Some(label) = rename_target.get_rename_label(&self.project) {
@@ -415,6 +616,24 @@ impl Workspace {
                     self.viewport_tree_pane
                         .add_pane(ViewportPane { viewport_id });
                 }
+                StateEvent::OpenFrameGraph => {
+                    self.inspector_tree_pane.add_pane(InspectorPane::FrameGraph);
+                }
+                StateEvent::OpenFrameCapture => {
+                    self.inspector_tree_pane
+                        .add_pane(InspectorPane::FrameCapture);
+                }
+                StateEvent::OpenImageCompare => {
+                    self.inspector_tree_pane
+                        .add_pane(InspectorPane::ImageCompare);
+                }
+                StateEvent::OpenLint => {
+                    self.inspector_tree_pane.add_pane(InspectorPane::Lint);
+                }
+                StateEvent::OpenReadback(target) => {
+                    self.inspector_tree_pane
+                        .add_pane(InspectorPane::Readback(target));
+                }
                 StateEvent::StartRename(rename_target) => {
                     if let Some(current_name) = rename_target.get_rename_label(&self.project) {
                         let current_label = current_name.to_string();
@@ -528,6 +747,44 @@ impl Workspace {
                     self.inspector_tree_pane
                         .add_pane(InspectorPane::Texture(texture_id));
                 }
+                StateEvent::DeriveUniformFromStruct {
+                    shader_id,
+                    struct_name,
+                } => {
+                    let Ok(Some(shader_runtime)) = self.runtime_project.shaders.get_init(shader_id)
+                    else {
+                        toasts_log_error!(
+                            self.toasts,
+                            "Can't create a uniform from “{struct_name}”: the shader isn't compiled."
+                        );
+                        continue;
+                    };
+                    let uniform_id = match derive_uniform_from_struct(
+                        &mut self.project,
+                        shader_runtime.module(),
+                        &struct_name,
+                    ) {
+                        Ok(uniform_id) => uniform_id,
+                        Err(error) => {
+                            toasts_log_error!(
+                                self.toasts,
+                                "Can't create a uniform from “{struct_name}”: {error}"
+                            );
+                            continue;
+                        }
+                    };
+                    self.inspector_tree_pane
+                        .add_pane(InspectorPane::Uniform(uniform_id));
+                }
+                StateEvent::SetPresentationSteps(steps) => {
+                    self.project.presentation.set_steps(steps);
+                }
+                StateEvent::SetVertexBufferSpec { model_id, spec } => {
+                    let Ok(model) = self.project.models.get_mut(model_id) else {
+                        continue;
+                    };
+                    model.set_vertex_buffer_spec(spec);
+                }
             }
         }
     }
@@ -661,6 +918,7 @@ impl Workspace {
 
         let view = &mut compute_pass::Context {
             device: ctx.device,
+            uniforms: &self.project.uniforms,
             runtime_shaders: &mut self.runtime_project.shaders,
             runtime_bind_groups: &mut self.runtime_project.bind_groups,
         };
@@ -670,9 +928,41 @@ impl Workspace {
             view,
         );
 
-        // Note: the tracker is *not* cleared here. `dispatch_computes` runs after
+        let view = &mut copy_pass::Context {
+            device: ctx.device,
+            runtime_textures: &self.runtime_project.textures,
+            runtime_texture_views: &self.runtime_project.texture_views,
+            runtime_uniforms: &self.runtime_project.uniforms,
+        };
+        self.tracker.sync_storage(
+            &mut self.project.copy_passes,
+            &mut self.runtime_project.copy_passes,
+            view,
+        );
+
+        // Note: the tracker is *not* cleared here. `Presentation::execute` runs after
         // `tick_objects` returns and still needs this frame's change set to decide
         // which `OnChange` passes to dispatch; the caller clears it afterwards.
         self.tracker.has_resource_changes()
     }
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main_viewport_texture<'a>(
+    project: &Project,
+    runtime_project: &'a RuntimeProject,
+) -> AppResult<&'a wgpu::Texture> {
+    let viewport_id = project
+        .presentation
+        .main_viewport()
+        .ok_or(AppError::MissingMainViewport)?;
+    let viewport = project.viewports.get(viewport_id)?;
+    let texture_view_id = viewport
+        .texture_view_id()
+        .ok_or(AppError::uninit_field("Texture View"))?;
+    let texture_view = runtime_project
+        .texture_views
+        .get_init(texture_view_id)?
+        .ok_or(AppError::MainViewportNotReady)?;
+    Ok(texture_view.inner().texture())
+}